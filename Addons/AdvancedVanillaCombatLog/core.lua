@@ -836,6 +836,11 @@ function RPLL:grab_unit_information(unit)
             end
         end
 
+        -- Max health, units outside of the group only report a percentage
+        if UnitHealthMax(unit) ~= nil and UnitHealthMax(unit) > 100 then
+            info["max_health"] = UnitHealthMax(unit)
+        end
+
         -- Hero Class, race, sex
         if UnitClass(unit) ~= nil then
             local _, english_class = UnitClass(unit)
@@ -946,7 +951,7 @@ function RPLL:rotate_combat_log_global_string()
                 gear_str = gear_str .. "&" .. prep_value(character["gear"][i])
             end
 
-            result = result .. prep_value(character["name"]) .. "&" .. prep_value(character["hero_class"]) .. "&" .. prep_value(character["race"]) .. "&" .. prep_value(character["sex"]) .. "&" .. prep_value(character["pet"]) .. "&" .. prep_value(character["guild_name"]) .. "&" .. prep_value(character["guild_rank_name"]) .. "&" .. prep_value(character["guild_rank_index"]) .. "&" .. gear_str .. "&" .. prep_value(character["talents"])
+            result = result .. prep_value(character["name"]) .. "&" .. prep_value(character["hero_class"]) .. "&" .. prep_value(character["race"]) .. "&" .. prep_value(character["sex"]) .. "&" .. prep_value(character["pet"]) .. "&" .. prep_value(character["guild_name"]) .. "&" .. prep_value(character["guild_rank_name"]) .. "&" .. prep_value(character["guild_rank_index"]) .. "&" .. gear_str .. "&" .. prep_value(character["talents"]) .. "&" .. prep_value(character["max_health"])
             SPELLFAILCASTSELF = result
             SPELLFAILPERFORMSELF = result
         end
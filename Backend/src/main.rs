@@ -28,6 +28,7 @@ extern crate rocket_contrib;
 extern crate rocket_multipart_form_data;
 #[macro_use]
 extern crate rocket_okapi;
+extern crate rocket_prometheus;
 extern crate rust_lapper;
 #[macro_use]
 extern crate serde_derive;
@@ -41,8 +42,10 @@ use dotenv::dotenv;
 pub use rocket_contrib::databases::mysql;
 use rocket_contrib::databases::mysql::Opts;
 use rocket_okapi::swagger_ui::{make_swagger_ui, SwaggerUIConfig, UrlObject};
+use rocket_prometheus::PrometheusMetrics;
 
 use crate::modules::{account, armory, data, instance, live_data_processor, tooltip, utility};
+use crate::modules::data::tools::RetrieveEncounterRule;
 
 #[cfg(test)]
 mod tests;
@@ -90,11 +93,33 @@ fn main() {
 
     let account = account::Account::default().init(&mut conn);
     let data = data::Data::default().init(&mut conn);
+    if std::env::args().any(|arg| arg == "--validate-encounter-rules") {
+        let issues = data.validate_encounter_rules();
+        issues.iter().for_each(|issue| println!("{}", issue));
+        println!("Found {} issues in the encounter rules", issues.len());
+        std::process::exit(if issues.is_empty() { 0 } else { 1 });
+    }
     let armory = armory::Armory::default().init(&mut conn);
+    if std::env::args().any(|arg| arg == "--backfill-rankings") {
+        instance::tools::backfill_ranking_results(&mut conn, &armory, &data);
+        println!("Backfilled the ranking results");
+    }
+    if std::env::args().any(|arg| arg == "--flag-ranking-anomalies") {
+        let flagged_attempts = instance::tools::flag_all_attempt_anomalies(&mut conn);
+        println!("Flagged {} attempts for moderation", flagged_attempts);
+    }
+    if std::env::args().any(|arg| arg == "--check-rankings") {
+        let issues = instance::tools::check_ranking_results(&mut conn, &data);
+        issues.iter().for_each(|issue| println!("{}", issue));
+        println!("Found {} issues in the ranking results", issues.len());
+        std::process::exit(if issues.is_empty() { 0 } else { 1 });
+    }
     let tooltip = tooltip::Tooltip::default();
-    let live_data_processor = live_data_processor::LiveDataProcessor::default().init(&mut conn);
-    let instance = instance::Instance::default().init(instance_conn);
+    let live_data_processor = live_data_processor::LiveDataProcessor::default().init(&mut conn).start_upload_workers(&data, &armory);
+    let instance = instance::Instance::default().init(instance_conn, &data, &armory);
     let utility = utility::Utility::default().init(&mut conn);
+    let prometheus = PrometheusMetrics::new();
+    util::metrics::register_metrics(prometheus.registry());
 
     let swagger_ui_config = SwaggerUIConfig {
         url: "/openapi.json".to_string(),
@@ -141,6 +166,8 @@ fn main() {
         .manage(utility)
         .attach(MainDb::fairing())
         .attach(Logger) // Attach the Logger fairing here
+        .attach(prometheus.clone())
+        .mount("/API/metrics", prometheus)
         .mount("/API/", make_swagger_ui(&swagger_ui_config))
         .mount(
             "/API/account/",
@@ -165,6 +192,18 @@ fn main() {
                 account::transfer::update::nickname,
                 account::transfer::update::update_patreons,
                 account::transfer::update::update_patreons_post,
+                account::transfer::group::create_group,
+                account::transfer::group::get_groups,
+                account::transfer::group::delete_group,
+                account::transfer::group::invite_group_member,
+                account::transfer::group::accept_group_invite,
+                account::transfer::group::remove_group_member,
+                account::transfer::group::update_group_member_role,
+                account::transfer::permission::get_member_roles,
+                account::transfer::permission::grant_role,
+                account::transfer::permission::revoke_role,
+                account::transfer::permission::get_role_audit_log,
+                account::transfer::permission::get_member_role_audit_log,
             ],
         )
         .mount(
@@ -185,6 +224,11 @@ fn main() {
                 data::transfer::server::get_server,
                 data::transfer::server::reload_server,
                 data::transfer::server::get_all_servers,
+                data::transfer::reload::reload_data,
+                data::transfer::localized_name::import_localized_names,
+                data::transfer::season::get_season,
+                data::transfer::season::get_all_seasons,
+                data::transfer::season::get_seasons_by_server,
                 data::transfer::hero_class::get_hero_class,
                 data::transfer::hero_class::get_all_hero_classes,
                 data::transfer::hero_class::get_hero_class_localized,
@@ -245,6 +289,8 @@ fn main() {
                 data::transfer::encounter::get_all_encounters_localized,
                 data::transfer::encounter_npc::get_encounter_npc,
                 data::transfer::encounter_npc::get_all_encounter_npcs,
+                data::transfer::encounter_rule::get_encounter_rule,
+                data::transfer::encounter_rule::get_all_encounter_rules,
                 data::transfer::addon::get_addon,
                 data::transfer::addon::get_all_addons,
             ],
@@ -269,6 +315,10 @@ fn main() {
                 armory::transfer::character_history::set_character_history,
                 armory::transfer::character_history::get_character_history,
                 armory::transfer::character_history::delete_character_history,
+                armory::transfer::character_identity::get_merge_suggestions,
+                armory::transfer::character_identity::dismiss_merge_suggestion,
+                armory::transfer::character_identity::get_merge_audit_log,
+                armory::transfer::character_identity::merge_characters,
                 armory::transfer::character_search::get_character_search_result,
                 armory::transfer::character_viewer::get_character_viewer,
                 armory::transfer::character_viewer::get_character_viewer_by_history,
@@ -296,15 +346,24 @@ fn main() {
                 live_data_processor::transfer::instance_reset::set_instance_resets,
                 live_data_processor::transfer::upload::upload_log,
                 live_data_processor::transfer::upload::get_upload_progress,
+                live_data_processor::transfer::upload::get_upload_jobs,
+                live_data_processor::transfer::upload::get_upload_job,
+                live_data_processor::transfer::upload::cancel_upload_job,
+                live_data_processor::transfer::upload::retry_upload_job,
+                live_data_processor::transfer::upload::get_upload_report,
             ],
         )
         .mount(
             "/API/instance",
             routes_with_openapi![
                 instance::transfer::export::get_instance_event_type,
+                instance::transfer::export::get_instance_event_slice,
                 instance::transfer::export::get_instance_meta,
                 instance::transfer::export::get_instance_participants,
                 instance::transfer::export::get_instance_attempts,
+                instance::transfer::meter::get_instance_meter,
+                instance::transfer::meter::get_attempt_meter,
+                instance::transfer::meter::get_filtered_meter,
                 instance::transfer::meta::update_privacy,
                 instance::transfer::meta::export_raids,
                 instance::transfer::meta::export_rated_arenas,
@@ -322,15 +381,27 @@ fn main() {
                 instance::transfer::ranking::get_instance_ranking_hps,
                 instance::transfer::ranking::get_instance_ranking_hps_by_season,
                 instance::transfer::ranking::get_instance_ranking_hps_by_server_and_season,
-                // instance::transfer::ranking::get_instance_ranking_tps,
-                // instance::transfer::ranking::get_instance_ranking_tps_by_season,
-                // instance::transfer::ranking::get_instance_ranking_tps_by_server_and_season,
+                instance::transfer::ranking::get_instance_ranking_tps,
+                instance::transfer::ranking::get_instance_ranking_tps_by_season,
+                instance::transfer::ranking::get_instance_ranking_tps_by_server_and_season,
+                instance::transfer::ranking::get_character_parses_dps,
+                instance::transfer::ranking::get_instance_parses_dps,
+                instance::transfer::ranking::get_character_parses_hps,
+                instance::transfer::ranking::get_instance_parses_hps,
+                instance::transfer::ranking::get_character_parses_tps,
+                instance::transfer::ranking::get_instance_parses_tps,
                 instance::transfer::ranking::unrank_attempt,
+                instance::transfer::moderation::get_moderation_queue,
+                instance::transfer::moderation::get_moderation_audit_log,
+                instance::transfer::moderation::get_attempt_moderation_audit_log,
+                instance::transfer::moderation::moderate_attempt,
                 instance::transfer::delete::delete_instance,
-                // instance::transfer::speed_run::get_speed_runs,
-                // instance::transfer::speed_run::get_speed_runs_by_season,
-                // instance::transfer::speed_kill::get_speed_kills,
-                // instance::transfer::speed_kill::get_speed_kills_by_season,
+                instance::transfer::speed_run::get_speed_runs,
+                instance::transfer::speed_run::get_speed_runs_by_season,
+                instance::transfer::speed_run::search_speed_runs,
+                instance::transfer::speed_kill::get_speed_kills,
+                instance::transfer::speed_kill::get_speed_kills_by_season,
+                instance::transfer::speed_kill::search_speed_kills,
             ],
         )
         .mount("/API/utility", routes_with_openapi![
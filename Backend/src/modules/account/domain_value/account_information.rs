@@ -1,11 +1,15 @@
 use schemars::JsonSchema;
 
+use crate::modules::account::domain_value::MemberRole;
+
 #[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct AccountInformation {
     pub id: u32,
     pub mail: String,
     pub nickname: String,
     pub mail_confirmed: bool,
+    // Derived from the roles for compatibility: 1 = Moderate, 2 = Supporter, 4 = AdjustLogPrivacy
     pub access_rights: u32,
+    pub roles: Vec<MemberRole>,
     pub default_privacy_type: u8
 }
@@ -0,0 +1,30 @@
+use schemars::JsonSchema;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GroupRole {
+    Member,
+    Officer,
+    Owner,
+}
+
+impl GroupRole {
+    pub fn new(role: u8) -> Self {
+        match role {
+            2 => Self::Owner,
+            1 => Self::Officer,
+            _ => Self::Member,
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::Member => 0,
+            Self::Officer => 1,
+            Self::Owner => 2,
+        }
+    }
+
+    pub fn can_manage_members(&self) -> bool {
+        *self >= Self::Officer
+    }
+}
@@ -0,0 +1,10 @@
+use schemars::JsonSchema;
+
+use crate::modules::account::domain_value::Role;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct MemberRole {
+    pub role: Role,
+    // Server or guild id for scoped roles, 0 otherwise
+    pub scope_id: u32,
+}
@@ -1,3 +1,15 @@
 pub use self::account_information::AccountInformation;
+pub use self::group_information::{GroupInformation, GroupMemberInformation};
+pub use self::group_role::GroupRole;
+pub use self::member_role::MemberRole;
+pub use self::permission::Permission;
+pub use self::role::Role;
+pub use self::role_audit_entry::RoleAuditEntry;
 
 mod account_information;
+mod group_information;
+mod group_role;
+mod member_role;
+mod permission;
+mod role;
+mod role_audit_entry;
@@ -0,0 +1,19 @@
+use schemars::JsonSchema;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    Moderate,
+    AdjustLogPrivacy,
+    ManagePermissions,
+    // Scoped to the servers the member owns
+    ManageServer,
+}
+
+impl Permission {
+    pub fn is_scoped(&self) -> bool {
+        match self {
+            Permission::ManageServer => true,
+            Permission::Moderate | Permission::AdjustLogPrivacy | Permission::ManagePermissions => false,
+        }
+    }
+}
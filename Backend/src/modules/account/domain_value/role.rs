@@ -0,0 +1,51 @@
+use schemars::JsonSchema;
+
+use crate::modules::account::domain_value::Permission;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    Administrator,
+    Moderator,
+    LogPrivacyAdmin,
+    ServerOwner,
+    Supporter,
+}
+
+impl Role {
+    // 4 was the guild officer, it is not granted anymore
+    pub fn new(role: u8) -> Option<Self> {
+        match role {
+            0 => Some(Self::Administrator),
+            1 => Some(Self::Moderator),
+            2 => Some(Self::LogPrivacyAdmin),
+            3 => Some(Self::ServerOwner),
+            5 => Some(Self::Supporter),
+            _ => None,
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::Administrator => 0,
+            Self::Moderator => 1,
+            Self::LogPrivacyAdmin => 2,
+            Self::ServerOwner => 3,
+            Self::Supporter => 5,
+        }
+    }
+
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Self::Administrator => &[Permission::Moderate, Permission::AdjustLogPrivacy, Permission::ManagePermissions],
+            Self::Moderator => &[Permission::Moderate],
+            Self::LogPrivacyAdmin => &[Permission::AdjustLogPrivacy],
+            Self::ServerOwner => &[Permission::ManageServer],
+            Self::Supporter => &[],
+        }
+    }
+
+    /// Scoped roles are granted for a specific server, all others apply globally.
+    pub fn is_scoped(&self) -> bool {
+        self.permissions().iter().any(Permission::is_scoped)
+    }
+}
@@ -0,0 +1,15 @@
+use schemars::JsonSchema;
+
+use crate::modules::account::domain_value::Role;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct RoleAuditEntry {
+    pub id: u32,
+    // None if the change was not issued by a member, e.g. by the Patreon synchronization
+    pub actor_member_id: Option<u32>,
+    pub member_id: u32,
+    pub role: Role,
+    pub scope_id: u32,
+    pub granted: bool,
+    pub timestamp: u64,
+}
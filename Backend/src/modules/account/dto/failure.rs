@@ -22,6 +22,15 @@ pub enum Failure {
     TooManyDays,
     DateInThePast,
     TokenPurposeLength,
+    InvalidGroupName,
+    UnknownGroup,
+    NoGroupPermission,
+    AlreadyGroupMember,
+    UnknownGroupMember,
+    InvalidRoleScope,
+    RoleAlreadyGranted,
+    RoleNotGranted,
+    UnknownMember,
     Unknown,
 }
 
@@ -47,6 +56,15 @@ impl<'r> Responder<'r> for Failure {
             Failure::DateInThePast => Status::new(532, "DateInThePast"),
             Failure::TokenPurposeLength => Status::new(533, "TokenPurposeLength"),
             Failure::InvalidPasswordCharacters => Status::new(535, "InvalidPasswordCharacters"),
+            Failure::InvalidGroupName => Status::new(536, "InvalidGroupName"),
+            Failure::UnknownGroup => Status::new(537, "UnknownGroup"),
+            Failure::NoGroupPermission => Status::new(538, "NoGroupPermission"),
+            Failure::AlreadyGroupMember => Status::new(539, "AlreadyGroupMember"),
+            Failure::UnknownGroupMember => Status::new(540, "UnknownGroupMember"),
+            Failure::InvalidRoleScope => Status::new(541, "InvalidRoleScope"),
+            Failure::RoleAlreadyGranted => Status::new(542, "RoleAlreadyGranted"),
+            Failure::RoleNotGranted => Status::new(543, "RoleNotGranted"),
+            Failure::UnknownMember => Status::new(544, "UnknownMember"),
             Failure::Unknown => Status::new(599, "Unknown"),
         };
         Response::build().status(status).sized_body(Cursor::new(body)).ok()
@@ -72,6 +90,15 @@ impl<'r> OpenApiResponder<'r> for Failure {
         add_schema_response(&mut responses, 532, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 533, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 535, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 536, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 537, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 538, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 539, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 540, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 541, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 542, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 543, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 544, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 599, "text/plain", schema)?;
         Ok(responses)
     }
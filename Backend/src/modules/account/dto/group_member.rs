@@ -0,0 +1,7 @@
+use schemars::JsonSchema;
+
+#[derive(Deserialize, Serialize, Debug, JsonSchema)]
+pub struct GroupMember {
+    pub group_id: u32,
+    pub member_id: u32,
+}
@@ -1,9 +1,16 @@
-pub use self::{create_member::CreateMember, create_token::CreateToken, credentials::Credentials, failure::Failure, prolong_token::ProlongToken};
+pub use self::{
+    create_member::CreateMember, create_token::CreateToken, credentials::Credentials, failure::Failure, group_member::GroupMember, invite_group_member::InviteGroupMember, prolong_token::ProlongToken,
+    update_group_member_role::UpdateGroupMemberRole, update_member_role::UpdateMemberRole,
+};
 pub use self::patreon_response::*;
 
 mod create_member;
 mod create_token;
 mod credentials;
 mod failure;
+mod group_member;
+mod invite_group_member;
 mod prolong_token;
-mod patreon_response;
\ No newline at end of file
+mod patreon_response;
+mod update_group_member_role;
+mod update_member_role;
@@ -0,0 +1,10 @@
+use schemars::JsonSchema;
+
+use crate::modules::account::domain_value::GroupRole;
+
+#[derive(Deserialize, Serialize, Debug, JsonSchema)]
+pub struct UpdateGroupMemberRole {
+    pub group_id: u32,
+    pub member_id: u32,
+    pub role: GroupRole,
+}
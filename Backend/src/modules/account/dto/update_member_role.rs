@@ -0,0 +1,10 @@
+use schemars::JsonSchema;
+
+use crate::modules::account::domain_value::Role;
+
+#[derive(Deserialize, Serialize, Debug, JsonSchema)]
+pub struct UpdateMemberRole {
+    pub member_id: u32,
+    pub role: Role,
+    pub scope_id: u32,
+}
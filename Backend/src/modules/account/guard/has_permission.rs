@@ -0,0 +1,94 @@
+use std::marker::PhantomData;
+
+use okapi::openapi3::Responses;
+use rocket::{
+    http::Status,
+    outcome::Outcome::*,
+    request::{self, FromRequest, Request, State},
+    response::Responder,
+    Response,
+};
+use rocket_okapi::{gen::OpenApiGenerator, response::OpenApiResponder, util::add_schema_response};
+
+use crate::modules::account::{domain_value::Permission, guard::Authenticate, tools::Permissions, Account};
+
+/// Marks the permission a transfer requires, e.g. `HasPermission<Moderate>`.
+pub trait RequiredPermission {
+    const PERMISSION: Permission;
+}
+
+pub struct Moderate;
+pub struct AdjustLogPrivacy;
+pub struct ManagePermissions;
+pub struct ManageServer;
+
+impl RequiredPermission for Moderate {
+    const PERMISSION: Permission = Permission::Moderate;
+}
+
+impl RequiredPermission for AdjustLogPrivacy {
+    const PERMISSION: Permission = Permission::AdjustLogPrivacy;
+}
+
+impl RequiredPermission for ManagePermissions {
+    const PERMISSION: Permission = Permission::ManagePermissions;
+}
+
+impl RequiredPermission for ManageServer {
+    const PERMISSION: Permission = Permission::ManageServer;
+}
+
+pub struct HasPermission<P: RequiredPermission> {
+    pub member_id: u32,
+    // The lowest server id of scoped permissions, 0 otherwise
+    pub scope_id: u32,
+    permission: PhantomData<P>,
+}
+
+impl<'a, 'r, P: RequiredPermission> FromRequest<'a, 'r> for HasPermission<P> {
+    type Error = ();
+
+    fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        Authenticate::from_request(req).and_then(|authenticate| {
+            let account = req.guard::<State<'_, Account>>();
+            if account.is_failure() {
+                return Failure((Status::Unauthorized, ()));
+            }
+
+            let account = account.unwrap();
+            let scope_id = if P::PERMISSION.is_scoped() {
+                match account.get_permission_scopes(authenticate.0, P::PERMISSION).first() {
+                    Some(scope_id) => *scope_id,
+                    None => return Failure((Status::Unauthorized, ())),
+                }
+            } else if account.has_permission(authenticate.0, P::PERMISSION) {
+                0
+            } else {
+                return Failure((Status::Unauthorized, ()));
+            };
+
+            Success(HasPermission {
+                member_id: authenticate.0,
+                scope_id,
+                permission: PhantomData,
+            })
+        })
+    }
+}
+
+// This implementation is required from OpenAPI, it does nothing here
+// and is not supposed to be used!
+impl<P: RequiredPermission> Responder<'static> for HasPermission<P> {
+    fn respond_to(self, _: &Request) -> Result<Response<'static>, Status> {
+        Response::build().status(Status::Unauthorized).ok()
+    }
+}
+
+impl<P: RequiredPermission> OpenApiResponder<'static> for HasPermission<P> {
+    fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        let mut responses = Responses::default();
+        let schema = gen.json_schema::<String>();
+        add_schema_response(&mut responses, 401, "text/plain", schema)?;
+        Ok(responses)
+    }
+}
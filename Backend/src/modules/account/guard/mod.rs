@@ -1,7 +1,9 @@
-pub use self::{authenticate::Authenticate, can_adjust_log_privacy::CanAdjustLogPrivacy, current_user::CurrentUser, is_moderator::IsModerator, server_owner::ServerOwner};
+pub use self::{
+    authenticate::Authenticate,
+    current_user::CurrentUser,
+    has_permission::{AdjustLogPrivacy, HasPermission, ManagePermissions, ManageServer, Moderate, RequiredPermission},
+};
 
 mod authenticate;
 mod current_user;
-mod server_owner;
-mod is_moderator;
-mod can_adjust_log_privacy;
+mod has_permission;
@@ -6,7 +6,8 @@ use str_util::sha3;
 
 use crate::modules::account::{
     language::init::Init,
-    material::{APIToken, Member},
+    domain_value::{GroupRole, MemberRole, Role},
+    material::{APIToken, Group, GroupMember, Member},
 };
 
 #[derive(Debug)]
@@ -16,6 +17,8 @@ pub struct Account {
     pub api_token_to_member_id: RwLock<HashMap<String, u32>>,
     pub api_tokens: RwLock<HashMap<u32, Vec<APIToken>>>,
     pub requires_mail_confirmation: RwLock<HashMap<String, u32>>,
+    pub groups: RwLock<HashMap<u32, Group>>,
+    pub roles: RwLock<HashMap<u32, Vec<MemberRole>>>,
 }
 
 // Important: Always lock resources bottom to too, in order to prevent running into a deadlock
@@ -30,6 +33,8 @@ impl Default for Account {
             api_tokens: RwLock::new(HashMap::new()),
             api_token_to_member_id: RwLock::new(HashMap::new()),
             requires_mail_confirmation: RwLock::new(HashMap::new()),
+            groups: RwLock::new(HashMap::new()),
+            roles: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -47,7 +52,7 @@ impl Account {
 
             // We are a little wasteful here because we do not insert it directly but rather create a vector first and then copy it over
             for entry in db_main.select(
-                "SELECT id, nickname, mail, password, salt, mail_confirmed, forgot_password, delete_account, new_mail, access_rights, default_privacy_type FROM account_member",
+                "SELECT id, nickname, mail, password, salt, mail_confirmed, forgot_password, delete_account, new_mail, default_privacy_type FROM account_member",
                 |mut row| Member {
                     id: row.take(0).unwrap(),
                     nickname: row.take(1).unwrap(),
@@ -58,8 +63,7 @@ impl Account {
                     forgot_password: row.take(6).unwrap(),
                     delete_account: row.take(7).unwrap(),
                     new_mail: row.take(8).unwrap(),
-                    access_rights: row.take(9).unwrap(),
-                    default_privacy_type: row.take(10).unwrap()
+                    default_privacy_type: row.take(9).unwrap()
                 },
             ) {
                 // Prepping api_token map
@@ -97,6 +101,39 @@ impl Account {
             }
         }
 
+        {
+            let mut groups = self.groups.write().unwrap();
+            for (id, name) in db_main.select("SELECT id, name FROM account_group", |mut row| (row.take::<u32, usize>(0).unwrap(), row.take::<String, usize>(1).unwrap())) {
+                groups.insert(id, Group { id, name, members: HashMap::new() });
+            }
+
+            for (group_id, group_member) in db_main.select("SELECT group_id, member_id, role, accepted FROM account_group_member", |mut row| {
+                (
+                    row.take::<u32, usize>(0).unwrap(),
+                    GroupMember {
+                        member_id: row.take(1).unwrap(),
+                        role: GroupRole::new(row.take(2).unwrap()),
+                        accepted: row.take(3).unwrap(),
+                    },
+                )
+            }) {
+                if let Some(group) = groups.get_mut(&group_id) {
+                    group.members.insert(group_member.member_id, group_member);
+                }
+            }
+        }
+
+        {
+            let mut roles = self.roles.write().unwrap();
+            for (member_id, role, scope_id) in db_main.select("SELECT member_id, role, scope_id FROM account_member_role", |mut row| {
+                (row.take::<u32, usize>(0).unwrap(), row.take::<u8, usize>(1).unwrap(), row.take::<u32, usize>(2).unwrap())
+            }) {
+                if let Some(role) = Role::new(role) {
+                    roles.entry(member_id).or_insert_with(Vec::new).push(MemberRole { role, scope_id });
+                }
+            }
+        }
+
         self
     }
 }
@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+
+use crate::modules::account::domain_value::GroupRole;
+
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub id: u32,
+    pub name: String,
+    pub members: HashMap<u32, GroupMember>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupMember {
+    pub member_id: u32,
+    pub role: GroupRole,
+    // Invited members need to accept the invitation before they gain access
+    pub accepted: bool,
+}
+
+impl Group {
+    pub fn is_member(&self, member_id: u32) -> bool {
+        self.members.get(&member_id).map_or(false, |group_member| group_member.accepted)
+    }
+
+    pub fn get_role(&self, member_id: u32) -> Option<GroupRole> {
+        self.members.get(&member_id).filter(|group_member| group_member.accepted).map(|group_member| group_member.role)
+    }
+}
@@ -3,11 +3,11 @@ pub use self::material::Account;
 #[cfg(test)]
 mod tests;
 
-mod domain_value;
 mod language;
-mod material;
-mod tools;
 
+pub(crate) mod domain_value;
+pub(crate) mod material;
+pub(crate) mod tools;
 pub mod dto;
 pub mod guard;
 pub mod transfer;
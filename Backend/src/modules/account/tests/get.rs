@@ -26,7 +26,7 @@ fn get_exists() {
                 forgot_password: false,
                 delete_account: false,
                 new_mail: "".to_string(),
-                access_rights: 0,
+                default_privacy_type: 0,
             },
         );
     }
@@ -0,0 +1,115 @@
+use crate::modules::account::{
+    domain_value::GroupRole,
+    dto::Failure,
+    material::Account,
+    tools::{Create, Groups},
+};
+
+use crate::modules::account::tests::helper::get_create_member;
+use crate::tests::TestContainer;
+
+fn create_member(account: &Account, conn: &mut crate::mysql::Conn, nickname: &str) -> u32 {
+    let post_obj = get_create_member(nickname, &format!("{}@abc.de", nickname), "Password123456Password123456Password123456");
+    account.create(conn, &post_obj.credentials.mail, &post_obj.nickname, &post_obj.credentials.password).unwrap().member_id
+}
+
+#[test]
+fn create_group() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let owner = create_member(&account, &mut conn, "owner");
+    assert!(matches!(account.create_group(&mut conn, " ", owner), Err(Failure::InvalidGroupName)));
+
+    let group_id = account.create_group(&mut conn, " Raid ", owner).unwrap();
+    let groups = account.get_groups(owner);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].id, group_id);
+    assert_eq!(groups[0].name, "Raid");
+    assert_eq!(groups[0].role, GroupRole::Owner);
+    assert!(account.is_group_member(group_id, owner));
+}
+
+#[test]
+fn invite_and_accept() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let owner = create_member(&account, &mut conn, "owner");
+    let invited = create_member(&account, &mut conn, "invited");
+    let group_id = account.create_group(&mut conn, "Raid", owner).unwrap();
+
+    assert!(matches!(account.invite_group_member(&mut conn, group_id, "unknown", owner), Err(Failure::InvalidNickname)));
+    assert!(matches!(account.invite_group_member(&mut conn, group_id + 1, "invited", owner), Err(Failure::UnknownGroup)));
+    assert!(matches!(account.invite_group_member(&mut conn, group_id, "owner", invited), Err(Failure::NoGroupPermission)));
+    assert!(account.invite_group_member(&mut conn, group_id, "invited", owner).is_ok());
+    assert!(matches!(account.invite_group_member(&mut conn, group_id, "invited", owner), Err(Failure::AlreadyGroupMember)));
+
+    // Pending invites neither grant access nor reveal the members
+    assert!(!account.is_group_member(group_id, invited));
+    let groups = account.get_groups(invited);
+    assert_eq!(groups.len(), 1);
+    assert!(!groups[0].accepted);
+    assert!(groups[0].members.is_empty());
+
+    assert!(account.accept_group_invite(&mut conn, group_id, invited).is_ok());
+    assert!(account.is_group_member(group_id, invited));
+    assert_eq!(account.get_groups(invited)[0].members.len(), 2);
+    assert!(matches!(account.accept_group_invite(&mut conn, group_id, invited), Err(Failure::UnknownGroup)));
+
+    // Memberships are persisted
+    let reloaded_account = Account::default().init(&mut conn);
+    assert!(reloaded_account.is_group_member(group_id, invited));
+}
+
+#[test]
+fn remove_group_member() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let owner = create_member(&account, &mut conn, "owner");
+    let officer = create_member(&account, &mut conn, "officer");
+    let member = create_member(&account, &mut conn, "member");
+    let group_id = account.create_group(&mut conn, "Raid", owner).unwrap();
+    for (nickname, member_id) in [("officer", officer), ("member", member)].iter() {
+        account.invite_group_member(&mut conn, group_id, nickname, owner).unwrap();
+        account.accept_group_invite(&mut conn, group_id, *member_id).unwrap();
+    }
+    account.update_group_member_role(&mut conn, group_id, officer, GroupRole::Officer, owner).unwrap();
+
+    assert!(matches!(account.remove_group_member(&mut conn, group_id, owner, owner), Err(Failure::NoGroupPermission)));
+    assert!(matches!(account.remove_group_member(&mut conn, group_id, officer, member), Err(Failure::NoGroupPermission)));
+    assert!(matches!(account.remove_group_member(&mut conn, group_id, owner, officer), Err(Failure::NoGroupPermission)));
+    assert!(account.remove_group_member(&mut conn, group_id, member, officer).is_ok());
+    assert!(!account.is_group_member(group_id, member));
+    assert!(matches!(account.remove_group_member(&mut conn, group_id, member, owner), Err(Failure::UnknownGroupMember)));
+    assert!(account.remove_group_member(&mut conn, group_id, officer, officer).is_ok());
+    assert!(!account.is_group_member(group_id, officer));
+}
+
+#[test]
+fn pass_on_ownership_and_delete_group() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let owner = create_member(&account, &mut conn, "owner");
+    let member = create_member(&account, &mut conn, "member");
+    let group_id = account.create_group(&mut conn, "Raid", owner).unwrap();
+    account.invite_group_member(&mut conn, group_id, "member", owner).unwrap();
+
+    assert!(matches!(account.update_group_member_role(&mut conn, group_id, member, GroupRole::Owner, owner), Err(Failure::UnknownGroupMember)));
+    account.accept_group_invite(&mut conn, group_id, member).unwrap();
+    assert!(matches!(account.update_group_member_role(&mut conn, group_id, owner, GroupRole::Member, member), Err(Failure::NoGroupPermission)));
+    assert!(account.update_group_member_role(&mut conn, group_id, member, GroupRole::Owner, owner).is_ok());
+    assert_eq!(account.get_groups(owner)[0].role, GroupRole::Officer);
+    assert_eq!(account.get_groups(member)[0].role, GroupRole::Owner);
+
+    assert!(matches!(account.delete_group(&mut conn, group_id, owner), Err(Failure::NoGroupPermission)));
+    assert!(account.delete_group(&mut conn, group_id, member).is_ok());
+    assert!(account.get_groups(owner).is_empty());
+    assert!(!account.is_group_member(group_id, owner));
+}
@@ -3,7 +3,9 @@ mod create_transfer;
 mod delete;
 mod forgot;
 mod get;
+mod group;
 mod login;
+mod permission;
 mod token;
 mod update;
 
@@ -0,0 +1,96 @@
+use crate::modules::account::{
+    domain_value::{MemberRole, Permission, Role},
+    dto::Failure,
+    material::Account,
+    tools::{Create, GetAccountInformation, Permissions},
+};
+
+use crate::modules::account::tests::helper::get_create_member;
+use crate::tests::TestContainer;
+
+fn create_member(account: &Account, conn: &mut crate::mysql::Conn, nickname: &str) -> u32 {
+    let post_obj = get_create_member(nickname, &format!("{}@abc.de", nickname), "Password123456Password123456Password123456");
+    account.create(conn, &post_obj.credentials.mail, &post_obj.nickname, &post_obj.credentials.password).unwrap().member_id
+}
+
+#[test]
+fn role_permissions() {
+    assert!(Role::Administrator.permissions().contains(&Permission::ManagePermissions));
+    assert!(!Role::Administrator.permissions().contains(&Permission::ManageServer));
+    assert!(Role::ServerOwner.is_scoped());
+    assert!(!Role::Moderator.is_scoped());
+    for role in [Role::Administrator, Role::Moderator, Role::LogPrivacyAdmin, Role::ServerOwner, Role::Supporter].iter() {
+        assert_eq!(Role::new(role.to_u8()), Some(*role));
+    }
+    assert_eq!(Role::new(4), None);
+    assert_eq!(Role::new(6), None);
+}
+
+#[test]
+fn grant_and_revoke_role() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let admin = create_member(&account, &mut conn, "admin");
+    let member = create_member(&account, &mut conn, "member");
+
+    assert!(matches!(account.grant_role(&mut conn, Some(admin), member, Role::Moderator, 5), Err(Failure::InvalidRoleScope)));
+    assert!(matches!(account.grant_role(&mut conn, Some(admin), member, Role::ServerOwner, 0), Err(Failure::InvalidRoleScope)));
+    assert!(matches!(account.grant_role(&mut conn, Some(admin), member + 10, Role::Moderator, 0), Err(Failure::UnknownMember)));
+
+    assert!(account.grant_role(&mut conn, Some(admin), member, Role::Moderator, 0).is_ok());
+    assert!(matches!(account.grant_role(&mut conn, Some(admin), member, Role::Moderator, 0), Err(Failure::RoleAlreadyGranted)));
+    assert!(account.has_permission(member, Permission::Moderate));
+    assert!(!account.has_permission(member, Permission::ManagePermissions));
+    assert_eq!(account.get(member).unwrap().access_rights, 1);
+
+    // Roles are persisted
+    let reloaded_account = Account::default().init(&mut conn);
+    assert_eq!(reloaded_account.get_roles(member), vec![MemberRole { role: Role::Moderator, scope_id: 0 }]);
+
+    assert!(account.revoke_role(&mut conn, Some(admin), member, Role::Moderator, 0).is_ok());
+    assert!(matches!(account.revoke_role(&mut conn, Some(admin), member, Role::Moderator, 0), Err(Failure::RoleNotGranted)));
+    assert!(!account.has_permission(member, Permission::Moderate));
+    assert_eq!(account.get(member).unwrap().access_rights, 0);
+}
+
+#[test]
+fn scoped_permissions() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let owner = create_member(&account, &mut conn, "owner");
+    account.grant_role(&mut conn, None, owner, Role::ServerOwner, 7).unwrap();
+    account.grant_role(&mut conn, None, owner, Role::ServerOwner, 3).unwrap();
+
+    assert!(account.has_permission(owner, Permission::ManageServer));
+    assert_eq!(account.get_permission_scopes(owner, Permission::ManageServer), vec![3, 7]);
+    assert!(account.get_permission_scopes(owner, Permission::Moderate).is_empty());
+}
+
+#[test]
+fn role_audit_log() {
+    let container = TestContainer::new(false);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let admin = create_member(&account, &mut conn, "admin");
+    let member = create_member(&account, &mut conn, "member");
+    account.grant_role(&mut conn, None, admin, Role::Administrator, 0).unwrap();
+    account.grant_role(&mut conn, Some(admin), member, Role::LogPrivacyAdmin, 0).unwrap();
+    account.revoke_role(&mut conn, Some(admin), member, Role::LogPrivacyAdmin, 0).unwrap();
+
+    let audit_log = account.get_role_audit_log(&mut conn, None);
+    assert_eq!(audit_log.len(), 3);
+
+    // The most recent changes come first
+    let member_audit_log = account.get_role_audit_log(&mut conn, Some(member));
+    assert_eq!(member_audit_log.len(), 2);
+    assert!(!member_audit_log[0].granted);
+    assert!(member_audit_log[1].granted);
+    assert_eq!(member_audit_log[1].actor_member_id, Some(admin));
+    assert_eq!(member_audit_log[1].role, Role::LogPrivacyAdmin);
+    assert_eq!(audit_log[2].actor_member_id, None);
+}
@@ -83,7 +83,6 @@ impl Create for Account {
                         forgot_password: false,
                         delete_account: false,
                         new_mail: String::new(),
-                        access_rights: 0,
                         default_privacy_type: 0
                     },
                 );
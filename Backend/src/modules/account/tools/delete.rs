@@ -3,7 +3,7 @@ use crate::util::database::*;
 use language::{domain_value::Language, tools::Get};
 use str_util::{sha3, strformat};
 
-use crate::modules::account::{dto::Failure, material::Account};
+use crate::modules::account::{domain_value::GroupRole, dto::Failure, material::Account};
 
 pub trait Delete {
     fn issue_delete(&self, db_main: &mut impl Execute, member_id: u32) -> Result<(), Failure>;
@@ -38,6 +38,8 @@ impl Delete for Account {
     }
 
     fn confirm_delete(&self, db_main: &mut impl Execute, delete_id: &str) -> Result<(), Failure> {
+        let mut roles = self.roles.write().unwrap();
+        let mut groups = self.groups.write().unwrap();
         let mut requires_mail_confirmation = self.requires_mail_confirmation.write().unwrap();
         let mut api_token_to_member_id = self.api_token_to_member_id.write().unwrap();
         let mut api_token = self.api_tokens.write().unwrap();
@@ -50,6 +52,19 @@ impl Delete for Account {
 
         // Due to foreign key constraints, other tables depending on the member_id will also be deleted
         let member_id = *delete_confirmation_res.unwrap();
+
+        // Groups cannot exist without an owner
+        if !db_main.execute_wparams(
+            "DELETE ag FROM account_group ag JOIN account_group_member agm ON ag.id = agm.group_id WHERE agm.member_id = :member_id AND agm.role = :role",
+            params!(
+              "member_id" => member_id,
+              "role" => GroupRole::Owner.to_u8()
+            ),
+        ) {
+            return Err(Failure::Unknown);
+        }
+        groups.retain(|_, group| group.get_role(member_id) != Some(GroupRole::Owner));
+
         if db_main.execute_wparams(
             "DELETE FROM account_member WHERE id = :id",
             params!(
@@ -81,6 +96,12 @@ impl Delete for Account {
                 }
                 api_token.get_mut(&member_id).unwrap().clear();
                 api_token.remove(&member_id);
+
+                // Roles and group memberships are removed by the foreign key constraint
+                roles.remove(&member_id);
+                for group in groups.values_mut() {
+                    group.members.remove(&member_id);
+                }
             }
 
             member.remove(&member_id);
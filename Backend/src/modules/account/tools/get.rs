@@ -1,4 +1,9 @@
-use crate::modules::account::{domain_value::AccountInformation, dto::Failure, material::Account};
+use crate::modules::account::{
+    domain_value::{AccountInformation, Permission, Role},
+    dto::Failure,
+    material::Account,
+    tools::Permissions,
+};
 
 pub trait GetAccountInformation {
     fn get(&self, id: u32) -> Result<AccountInformation, Failure>;
@@ -6,6 +11,19 @@ pub trait GetAccountInformation {
 
 impl GetAccountInformation for Account {
     fn get(&self, id: u32) -> Result<AccountInformation, Failure> {
+        // Roles are locked before the members, see lock order
+        let roles = self.get_roles(id);
+        let mut access_rights = 0;
+        if self.has_permission(id, Permission::Moderate) {
+            access_rights |= 1;
+        }
+        if self.has_role(id, Role::Supporter) {
+            access_rights |= 2;
+        }
+        if self.has_permission(id, Permission::AdjustLogPrivacy) {
+            access_rights |= 4;
+        }
+
         let member = self.member.read().unwrap();
 
         // Although this should never happen;
@@ -21,7 +39,8 @@ impl GetAccountInformation for Account {
             mail: entry.mail.clone(),
             nickname: entry.nickname.clone(),
             mail_confirmed: entry.mail_confirmed,
-            access_rights: entry.access_rights,
+            access_rights,
+            roles,
             default_privacy_type: entry.default_privacy_type
         })
     }
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use crate::params;
+use crate::util::database::*;
+
+use crate::modules::account::{
+    domain_value::{GroupInformation, GroupMemberInformation, GroupRole},
+    dto::Failure,
+    material::{Account, Group, GroupMember},
+};
+
+pub trait Groups {
+    fn create_group(&self, db_main: &mut (impl Execute + Select), name: &str, member_id: u32) -> Result<u32, Failure>;
+    fn delete_group(&self, db_main: &mut impl Execute, group_id: u32, member_id: u32) -> Result<(), Failure>;
+    fn invite_group_member(&self, db_main: &mut impl Execute, group_id: u32, nickname: &str, member_id: u32) -> Result<(), Failure>;
+    fn accept_group_invite(&self, db_main: &mut impl Execute, group_id: u32, member_id: u32) -> Result<(), Failure>;
+    fn remove_group_member(&self, db_main: &mut impl Execute, group_id: u32, removed_member_id: u32, member_id: u32) -> Result<(), Failure>;
+    fn update_group_member_role(&self, db_main: &mut impl Execute, group_id: u32, updated_member_id: u32, role: GroupRole, member_id: u32) -> Result<(), Failure>;
+    fn get_groups(&self, member_id: u32) -> Vec<GroupInformation>;
+    fn is_group_member(&self, group_id: u32, member_id: u32) -> bool;
+}
+
+impl Groups for Account {
+    fn create_group(&self, db_main: &mut (impl Execute + Select), name: &str, member_id: u32) -> Result<u32, Failure> {
+        let name = name.trim();
+        let name_len = name.chars().count();
+        if name_len < 2 || name_len > 64 {
+            return Err(Failure::InvalidGroupName);
+        }
+
+        let mut groups = self.groups.write().unwrap();
+        if !db_main.execute_wparams("INSERT INTO account_group (`name`) VALUES (:name)", params!("name" => name)) {
+            return Err(Failure::Unknown);
+        }
+        let group_id: u32 = db_main.select_value("SELECT MAX(id) FROM account_group", |mut row| row.take(0).unwrap()).unwrap();
+        if !db_main.execute_wparams(
+            "INSERT INTO account_group_member (`group_id`, `member_id`, `role`, `accepted`) VALUES (:group_id, :member_id, :role, 1)",
+            params!("group_id" => group_id, "member_id" => member_id, "role" => GroupRole::Owner.to_u8()),
+        ) {
+            db_main.execute_wparams("DELETE FROM account_group WHERE id=:id", params!("id" => group_id));
+            return Err(Failure::Unknown);
+        }
+
+        let mut members = HashMap::new();
+        members.insert(
+            member_id,
+            GroupMember {
+                member_id,
+                role: GroupRole::Owner,
+                accepted: true,
+            },
+        );
+        groups.insert(group_id, Group { id: group_id, name: name.to_string(), members });
+        Ok(group_id)
+    }
+
+    fn delete_group(&self, db_main: &mut impl Execute, group_id: u32, member_id: u32) -> Result<(), Failure> {
+        let mut groups = self.groups.write().unwrap();
+        let group = groups.get(&group_id).ok_or(Failure::UnknownGroup)?;
+        if group.get_role(member_id) != Some(GroupRole::Owner) {
+            return Err(Failure::NoGroupPermission);
+        }
+
+        // Memberships are deleted by the foreign key constraint
+        if !db_main.execute_wparams("DELETE FROM account_group WHERE id=:id", params!("id" => group_id)) {
+            return Err(Failure::Unknown);
+        }
+        groups.remove(&group_id);
+        Ok(())
+    }
+
+    fn invite_group_member(&self, db_main: &mut impl Execute, group_id: u32, nickname: &str, member_id: u32) -> Result<(), Failure> {
+        let mut groups = self.groups.write().unwrap();
+        let invited_member_id = {
+            let member = self.member.read().unwrap();
+            member.values().find(|entry| entry.nickname.to_lowercase() == nickname.to_lowercase()).map(|entry| entry.id).ok_or(Failure::InvalidNickname)?
+        };
+
+        let group = groups.get_mut(&group_id).ok_or(Failure::UnknownGroup)?;
+        if !group.get_role(member_id).map_or(false, |role| role.can_manage_members()) {
+            return Err(Failure::NoGroupPermission);
+        }
+        if group.members.contains_key(&invited_member_id) {
+            return Err(Failure::AlreadyGroupMember);
+        }
+
+        if !db_main.execute_wparams(
+            "INSERT INTO account_group_member (`group_id`, `member_id`, `role`, `accepted`) VALUES (:group_id, :member_id, :role, 0)",
+            params!("group_id" => group_id, "member_id" => invited_member_id, "role" => GroupRole::Member.to_u8()),
+        ) {
+            return Err(Failure::Unknown);
+        }
+        group.members.insert(
+            invited_member_id,
+            GroupMember {
+                member_id: invited_member_id,
+                role: GroupRole::Member,
+                accepted: false,
+            },
+        );
+        Ok(())
+    }
+
+    fn accept_group_invite(&self, db_main: &mut impl Execute, group_id: u32, member_id: u32) -> Result<(), Failure> {
+        let mut groups = self.groups.write().unwrap();
+        let group_member = groups
+            .get_mut(&group_id)
+            .and_then(|group| group.members.get_mut(&member_id))
+            .filter(|group_member| !group_member.accepted)
+            .ok_or(Failure::UnknownGroup)?;
+
+        if !db_main.execute_wparams(
+            "UPDATE account_group_member SET `accepted`=1 WHERE group_id=:group_id AND member_id=:member_id",
+            params!("group_id" => group_id, "member_id" => member_id),
+        ) {
+            return Err(Failure::Unknown);
+        }
+        group_member.accepted = true;
+        Ok(())
+    }
+
+    /// Members may leave a group on their own, except for the owner, who has to pass on the ownership first.
+    /// Officers may remove members and pending invites, the owner may remove everyone else.
+    fn remove_group_member(&self, db_main: &mut impl Execute, group_id: u32, removed_member_id: u32, member_id: u32) -> Result<(), Failure> {
+        let mut groups = self.groups.write().unwrap();
+        let group = groups.get_mut(&group_id).ok_or(Failure::UnknownGroup)?;
+        let removed_member = group.members.get(&removed_member_id).ok_or(Failure::UnknownGroupMember)?;
+
+        let is_permitted = if removed_member_id == member_id {
+            removed_member.role != GroupRole::Owner
+        } else {
+            match group.get_role(member_id) {
+                Some(GroupRole::Owner) => true,
+                Some(GroupRole::Officer) => removed_member.role == GroupRole::Member,
+                _ => false,
+            }
+        };
+        if !is_permitted {
+            return Err(Failure::NoGroupPermission);
+        }
+
+        if !db_main.execute_wparams(
+            "DELETE FROM account_group_member WHERE group_id=:group_id AND member_id=:member_id",
+            params!("group_id" => group_id, "member_id" => removed_member_id),
+        ) {
+            return Err(Failure::Unknown);
+        }
+        group.members.remove(&removed_member_id);
+        Ok(())
+    }
+
+    /// Only the owner may change roles. Passing on the ownership demotes the current owner to an officer.
+    fn update_group_member_role(&self, db_main: &mut impl Execute, group_id: u32, updated_member_id: u32, role: GroupRole, member_id: u32) -> Result<(), Failure> {
+        let mut groups = self.groups.write().unwrap();
+        let group = groups.get_mut(&group_id).ok_or(Failure::UnknownGroup)?;
+        if group.get_role(member_id) != Some(GroupRole::Owner) {
+            return Err(Failure::NoGroupPermission);
+        }
+        if updated_member_id == member_id {
+            return Err(Failure::NoGroupPermission);
+        }
+        if !group.is_member(updated_member_id) {
+            return Err(Failure::UnknownGroupMember);
+        }
+
+        let query = "UPDATE account_group_member SET `role`=:role WHERE group_id=:group_id AND member_id=:member_id";
+        if !db_main.execute_wparams(query, params!("group_id" => group_id, "member_id" => updated_member_id, "role" => role.to_u8())) {
+            return Err(Failure::Unknown);
+        }
+        group.members.get_mut(&updated_member_id).unwrap().role = role;
+
+        if role == GroupRole::Owner {
+            if !db_main.execute_wparams(query, params!("group_id" => group_id, "member_id" => member_id, "role" => GroupRole::Officer.to_u8())) {
+                return Err(Failure::Unknown);
+            }
+            group.members.get_mut(&member_id).unwrap().role = GroupRole::Officer;
+        }
+        Ok(())
+    }
+
+    fn get_groups(&self, member_id: u32) -> Vec<GroupInformation> {
+        let groups = self.groups.read().unwrap();
+        let member = self.member.read().unwrap();
+        groups
+            .values()
+            .filter_map(|group| {
+                let own_membership = group.members.get(&member_id)?;
+                // Pending invites only reveal the name of the group
+                let mut members: Vec<GroupMemberInformation> = group
+                    .members
+                    .values()
+                    .filter(|_| own_membership.accepted)
+                    .filter_map(|group_member| {
+                        member.get(&group_member.member_id).map(|entry| GroupMemberInformation {
+                            member_id: group_member.member_id,
+                            nickname: entry.nickname.clone(),
+                            role: group_member.role,
+                            accepted: group_member.accepted,
+                        })
+                    })
+                    .collect();
+                members.sort_by(|left, right| right.role.cmp(&left.role).then_with(|| left.nickname.cmp(&right.nickname)));
+                Some(GroupInformation {
+                    id: group.id,
+                    name: group.name.clone(),
+                    role: own_membership.role,
+                    accepted: own_membership.accepted,
+                    members,
+                })
+            })
+            .collect()
+    }
+
+    fn is_group_member(&self, group_id: u32, member_id: u32) -> bool {
+        let groups = self.groups.read().unwrap();
+        groups.get(&group_id).map_or(false, |group| group.is_member(member_id))
+    }
+}
@@ -1,9 +1,11 @@
-pub use self::{create::Create, delete::Delete, forgot::Forgot, get::GetAccountInformation, login::Login, token::Token, update::Update};
+pub use self::{create::Create, delete::Delete, forgot::Forgot, get::GetAccountInformation, group::Groups, login::Login, permission::Permissions, token::Token, update::Update};
 
 mod create;
 mod delete;
 mod forgot;
 mod get;
+mod group;
 mod login;
+mod permission;
 mod token;
 mod update;
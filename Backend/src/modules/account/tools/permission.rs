@@ -0,0 +1,127 @@
+use crate::params;
+use crate::util::database::*;
+
+use crate::modules::account::{
+    domain_value::{MemberRole, Permission, Role, RoleAuditEntry},
+    dto::Failure,
+    material::Account,
+};
+
+pub trait Permissions {
+    fn get_roles(&self, member_id: u32) -> Vec<MemberRole>;
+    fn has_role(&self, member_id: u32, role: Role) -> bool;
+    fn has_permission(&self, member_id: u32, permission: Permission) -> bool;
+    fn get_permission_scopes(&self, member_id: u32, permission: Permission) -> Vec<u32>;
+    fn grant_role(&self, db_main: &mut impl Execute, actor_member_id: Option<u32>, member_id: u32, role: Role, scope_id: u32) -> Result<(), Failure>;
+    fn revoke_role(&self, db_main: &mut impl Execute, actor_member_id: Option<u32>, member_id: u32, role: Role, scope_id: u32) -> Result<(), Failure>;
+    fn get_role_audit_log(&self, db_main: &mut impl Select, member_id: Option<u32>) -> Vec<RoleAuditEntry>;
+}
+
+impl Permissions for Account {
+    fn get_roles(&self, member_id: u32) -> Vec<MemberRole> {
+        let roles = self.roles.read().unwrap();
+        roles.get(&member_id).cloned().unwrap_or_default()
+    }
+
+    fn has_role(&self, member_id: u32, role: Role) -> bool {
+        let roles = self.roles.read().unwrap();
+        roles.get(&member_id).map_or(false, |member_roles| member_roles.iter().any(|member_role| member_role.role == role))
+    }
+
+    fn has_permission(&self, member_id: u32, permission: Permission) -> bool {
+        let roles = self.roles.read().unwrap();
+        roles.get(&member_id).map_or(false, |member_roles| member_roles.iter().any(|member_role| member_role.role.permissions().contains(&permission)))
+    }
+
+    /// Returns the ids of the servers a scoped permission was granted for, in ascending order.
+    fn get_permission_scopes(&self, member_id: u32, permission: Permission) -> Vec<u32> {
+        let roles = self.roles.read().unwrap();
+        let mut scope_ids: Vec<u32> = roles
+            .get(&member_id)
+            .map(|member_roles| member_roles.iter().filter(|member_role| member_role.role.permissions().contains(&permission)).map(|member_role| member_role.scope_id).collect())
+            .unwrap_or_default();
+        scope_ids.sort_unstable();
+        scope_ids.dedup();
+        scope_ids
+    }
+
+    fn grant_role(&self, db_main: &mut impl Execute, actor_member_id: Option<u32>, member_id: u32, role: Role, scope_id: u32) -> Result<(), Failure> {
+        if role.is_scoped() == (scope_id == 0) {
+            return Err(Failure::InvalidRoleScope);
+        }
+
+        let mut roles = self.roles.write().unwrap();
+        if !self.member.read().unwrap().contains_key(&member_id) {
+            return Err(Failure::UnknownMember);
+        }
+        let member_role = MemberRole { role, scope_id };
+        if roles.get(&member_id).map_or(false, |member_roles| member_roles.contains(&member_role)) {
+            return Err(Failure::RoleAlreadyGranted);
+        }
+
+        if !db_main.execute_wparams(
+            "INSERT INTO account_member_role (`member_id`, `role`, `scope_id`) VALUES (:member_id, :role, :scope_id)",
+            params!("member_id" => member_id, "role" => role.to_u8(), "scope_id" => scope_id),
+        ) {
+            return Err(Failure::Unknown);
+        }
+        insert_audit_entry(db_main, actor_member_id, member_id, role, scope_id, true);
+        roles.entry(member_id).or_insert_with(Vec::new).push(member_role);
+        Ok(())
+    }
+
+    fn revoke_role(&self, db_main: &mut impl Execute, actor_member_id: Option<u32>, member_id: u32, role: Role, scope_id: u32) -> Result<(), Failure> {
+        let mut roles = self.roles.write().unwrap();
+        let member_role = MemberRole { role, scope_id };
+        let member_roles = roles.get_mut(&member_id).filter(|member_roles| member_roles.contains(&member_role)).ok_or(Failure::RoleNotGranted)?;
+
+        if !db_main.execute_wparams(
+            "DELETE FROM account_member_role WHERE member_id=:member_id AND role=:role AND scope_id=:scope_id",
+            params!("member_id" => member_id, "role" => role.to_u8(), "scope_id" => scope_id),
+        ) {
+            return Err(Failure::Unknown);
+        }
+        insert_audit_entry(db_main, actor_member_id, member_id, role, scope_id, false);
+        member_roles.retain(|entry| entry != &member_role);
+        Ok(())
+    }
+
+    fn get_role_audit_log(&self, db_main: &mut impl Select, member_id: Option<u32>) -> Vec<RoleAuditEntry> {
+        db_main
+            .select_wparams(
+                "SELECT id, actor_member_id, member_id, `role`, scope_id, granted, `timestamp` FROM account_role_audit WHERE :member_id IS NULL OR member_id=:member_id ORDER BY id DESC",
+                |mut row| {
+                    (
+                        row.take::<u32, usize>(0).unwrap(),
+                        row.take_opt::<u32, usize>(1).unwrap().ok(),
+                        row.take::<u32, usize>(2).unwrap(),
+                        row.take::<u8, usize>(3).unwrap(),
+                        row.take::<u32, usize>(4).unwrap(),
+                        row.take::<bool, usize>(5).unwrap(),
+                        row.take::<u64, usize>(6).unwrap(),
+                    )
+                },
+                params!("member_id" => member_id),
+            )
+            .into_iter()
+            .filter_map(|(id, actor_member_id, member_id, role, scope_id, granted, timestamp)| {
+                Role::new(role).map(|role| RoleAuditEntry {
+                    id,
+                    actor_member_id,
+                    member_id,
+                    role,
+                    scope_id,
+                    granted,
+                    timestamp,
+                })
+            })
+            .collect()
+    }
+}
+
+fn insert_audit_entry(db_main: &mut impl Execute, actor_member_id: Option<u32>, member_id: u32, role: Role, scope_id: u32, granted: bool) {
+    db_main.execute_wparams(
+        "INSERT INTO account_role_audit (`actor_member_id`, `member_id`, `role`, `scope_id`, `granted`, `timestamp`) VALUES (:actor_member_id, :member_id, :role, :scope_id, :granted, :timestamp)",
+        params!("actor_member_id" => actor_member_id, "member_id" => member_id, "role" => role.to_u8(), "scope_id" => scope_id, "granted" => granted, "timestamp" => time_util::now()),
+    );
+}
@@ -8,10 +8,10 @@ use validator::{
 };
 
 use crate::modules::account::{
-    domain_value::AccountInformation,
+    domain_value::{AccountInformation, Role},
     dto::Failure,
     material::{Account, APIToken},
-    tools::{GetAccountInformation, Token},
+    tools::{GetAccountInformation, Permissions, Token},
 };
 use crate::modules::account::dto::PatreonResponse;
 use crate::params;
@@ -184,19 +184,26 @@ impl Update for Account {
             .header("authorization", HeaderValue::from_str(&("Bearer ".to_owned() + &api_token)).unwrap())
             .send().and_then(|resp| resp.json::<PatreonResponse>()).map_err(|_| Failure::Unknown)?;
 
-        let mut members = self.member.write().unwrap();
-        for patreon_member in resp.data {
-            let found_member = members.iter_mut().find(|(_, member)| member.mail.to_lowercase() == patreon_member.attributes.email.to_lowercase());
-            if let Some(member) = found_member {
-                for tier in patreon_member.relationships.currently_entitled_tiers.data {
-                    if tier.id.contains("7262039") || tier.id.contains("7262085") {
-                        member.1.access_rights |= 2;
-                        member.1.access_rights |= 4;
+        let mut supporters = Vec::new();
+        {
+            let members = self.member.read().unwrap();
+            for patreon_member in resp.data {
+                let found_member = members.values().find(|member| member.mail.to_lowercase() == patreon_member.attributes.email.to_lowercase());
+                if let Some(member) = found_member {
+                    if patreon_member.relationships.currently_entitled_tiers.data.iter().any(|tier| tier.id.contains("7262039") || tier.id.contains("7262085")) {
+                        supporters.push(member.id);
                     }
+                } else {
+                    println!("Could not find Patreon: {}", patreon_member.attributes.email);
+                }
+            }
+        }
+
+        for member_id in supporters {
+            for role in [Role::Supporter, Role::LogPrivacyAdmin].iter() {
+                if !self.has_role(member_id, *role) {
+                    self.grant_role(db_main, None, member_id, *role, 0)?;
                 }
-                db_main.execute_wparams("UPDATE `account_member` SET access_rights=:access_rights WHERE id=:member_id", params!("access_rights" => member.1.access_rights, "member_id" => member.0));
-            } else {
-                println!("Could not find Patreon: {}", patreon_member.attributes.email);
             }
         }
 
@@ -0,0 +1,53 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::account::{
+    domain_value::GroupInformation,
+    dto::{Failure, GroupMember, InviteGroupMember, UpdateGroupMemberRole},
+    guard::Authenticate,
+    material::Account,
+    tools::Groups,
+};
+use crate::MainDb;
+
+#[openapi]
+#[post("/group", format = "application/json", data = "<name>")]
+pub fn create_group(mut db_main: MainDb, me: State<Account>, auth: Authenticate, name: Json<String>) -> Result<Json<u32>, Failure> {
+    me.create_group(&mut *db_main, &name, auth.0).map(Json)
+}
+
+#[openapi]
+#[get("/group")]
+pub fn get_groups(me: State<Account>, auth: Authenticate) -> Json<Vec<GroupInformation>> {
+    Json(me.get_groups(auth.0))
+}
+
+#[openapi]
+#[delete("/group", format = "application/json", data = "<group_id>")]
+pub fn delete_group(mut db_main: MainDb, me: State<Account>, auth: Authenticate, group_id: Json<u32>) -> Result<(), Failure> {
+    me.delete_group(&mut *db_main, group_id.0, auth.0)
+}
+
+#[openapi]
+#[post("/group/invite", format = "application/json", data = "<params>")]
+pub fn invite_group_member(mut db_main: MainDb, me: State<Account>, auth: Authenticate, params: Json<InviteGroupMember>) -> Result<(), Failure> {
+    me.invite_group_member(&mut *db_main, params.group_id, &params.nickname, auth.0)
+}
+
+#[openapi]
+#[post("/group/accept", format = "application/json", data = "<group_id>")]
+pub fn accept_group_invite(mut db_main: MainDb, me: State<Account>, auth: Authenticate, group_id: Json<u32>) -> Result<(), Failure> {
+    me.accept_group_invite(&mut *db_main, group_id.0, auth.0)
+}
+
+#[openapi]
+#[delete("/group/member", format = "application/json", data = "<params>")]
+pub fn remove_group_member(mut db_main: MainDb, me: State<Account>, auth: Authenticate, params: Json<GroupMember>) -> Result<(), Failure> {
+    me.remove_group_member(&mut *db_main, params.group_id, params.member_id, auth.0)
+}
+
+#[openapi]
+#[post("/group/role", format = "application/json", data = "<params>")]
+pub fn update_group_member_role(mut db_main: MainDb, me: State<Account>, auth: Authenticate, params: Json<UpdateGroupMemberRole>) -> Result<(), Failure> {
+    me.update_group_member_role(&mut *db_main, params.group_id, params.member_id, params.role, auth.0)
+}
@@ -2,6 +2,8 @@ pub mod create;
 pub mod delete;
 pub mod forgot;
 pub mod get;
+pub mod group;
 pub mod login;
+pub mod permission;
 pub mod token;
 pub mod update;
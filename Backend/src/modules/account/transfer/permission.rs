@@ -0,0 +1,41 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::account::{
+    domain_value::{MemberRole, RoleAuditEntry},
+    dto::{Failure, UpdateMemberRole},
+    guard::{HasPermission, ManagePermissions},
+    material::Account,
+    tools::Permissions,
+};
+use crate::MainDb;
+
+#[openapi]
+#[get("/permission/roles/<member_id>")]
+pub fn get_member_roles(me: State<Account>, _auth: HasPermission<ManagePermissions>, member_id: u32) -> Json<Vec<MemberRole>> {
+    Json(me.get_roles(member_id))
+}
+
+#[openapi]
+#[post("/permission/grant", format = "application/json", data = "<params>")]
+pub fn grant_role(mut db_main: MainDb, me: State<Account>, auth: HasPermission<ManagePermissions>, params: Json<UpdateMemberRole>) -> Result<(), Failure> {
+    me.grant_role(&mut *db_main, Some(auth.member_id), params.member_id, params.role, params.scope_id)
+}
+
+#[openapi]
+#[post("/permission/revoke", format = "application/json", data = "<params>")]
+pub fn revoke_role(mut db_main: MainDb, me: State<Account>, auth: HasPermission<ManagePermissions>, params: Json<UpdateMemberRole>) -> Result<(), Failure> {
+    me.revoke_role(&mut *db_main, Some(auth.member_id), params.member_id, params.role, params.scope_id)
+}
+
+#[openapi]
+#[get("/permission/audit")]
+pub fn get_role_audit_log(mut db_main: MainDb, me: State<Account>, _auth: HasPermission<ManagePermissions>) -> Json<Vec<RoleAuditEntry>> {
+    Json(me.get_role_audit_log(&mut *db_main, None))
+}
+
+#[openapi]
+#[get("/permission/audit/<member_id>")]
+pub fn get_member_role_audit_log(mut db_main: MainDb, me: State<Account>, _auth: HasPermission<ManagePermissions>, member_id: u32) -> Json<Vec<RoleAuditEntry>> {
+    Json(me.get_role_audit_log(&mut *db_main, Some(member_id)))
+}
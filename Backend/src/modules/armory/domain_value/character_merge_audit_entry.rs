@@ -0,0 +1,10 @@
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct CharacterMergeAuditEntry {
+    pub id: u32,
+    pub member_id: u32,
+    // The merged character no longer exists, hence its last name is kept
+    pub source_character_id: u32,
+    pub source_character_name: String,
+    pub target_character_id: u32,
+    pub timestamp: u64,
+}
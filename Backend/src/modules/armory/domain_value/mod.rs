@@ -1,5 +1,6 @@
 pub use self::arena_team::ArenaTeam;
 pub use self::arena_team_size_type::ArenaTeamSizeType;
+pub use self::character_merge_audit_entry::CharacterMergeAuditEntry;
 pub use self::inventory_type::InventoryType;
 pub use self::{character_facial::CharacterFacial, character_gear::*, character_guild::CharacterGuild, character_info::CharacterInfo, character_item::CharacterItem, guild_rank::GuildRank, history_moment::HistoryMoment};
 
@@ -10,6 +11,7 @@ mod character_gear;
 mod character_guild;
 mod character_info;
 mod character_item;
+mod character_merge_audit_entry;
 mod guild_rank;
 mod history_moment;
 mod inventory_type;
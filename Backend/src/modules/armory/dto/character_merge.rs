@@ -0,0 +1,5 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CharacterMergeDto {
+    pub source_character_id: u32,
+    pub target_character_id: u32,
+}
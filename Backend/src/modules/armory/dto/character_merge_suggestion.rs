@@ -0,0 +1,9 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct CharacterMergeSuggestion {
+    pub id: u32,
+    pub character_id: u32,
+    pub candidate_character_id: u32,
+    pub score: u8,
+    pub reasons: Vec<String>,
+    pub suggested_ts: u64,
+}
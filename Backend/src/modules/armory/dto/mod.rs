@@ -1,5 +1,7 @@
 pub use self::arena_team::ArenaTeamDto;
 pub use self::basic_character::BasicCharacter;
+pub use self::character_merge::CharacterMergeDto;
+pub use self::character_merge_suggestion::CharacterMergeSuggestion;
 pub use self::search_guild::SearchGuildDto;
 pub use self::{
     armory_failure::ArmoryFailure, character::CharacterDto, character_facial::CharacterFacialDto, character_gear::CharacterGearDto, character_guild::CharacterGuildDto, character_history::CharacterHistoryDto, character_info::CharacterInfoDto,
@@ -14,6 +16,8 @@ mod character_guild;
 mod character_history;
 mod character_info;
 mod character_item;
+mod character_merge;
+mod character_merge_suggestion;
 mod guild;
 
 mod character_search;
@@ -1,29 +1,34 @@
-use std::{collections::HashMap, sync::RwLock};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 
 use crate::modules::armory::domain_value::{ArenaTeam, ArenaTeamSizeType};
 use crate::modules::armory::{
     domain_value::{CharacterFacial, CharacterGear, CharacterGuild, CharacterInfo, CharacterItem, GuildRank, HistoryMoment},
-    material::{Character, CharacterHistory, Guild},
+    material::{Character, CharacterAlias, CharacterHistory, Guild, NameCacheEntry, NameCachePolicy},
 };
 use crate::params;
 use crate::util::database::*;
+use crate::util::metrics::{ARMORY_NAME_CACHE_ENTRIES, ARMORY_NAME_CACHE_EVICTED_ENTRIES};
 
 #[derive(Debug)]
 pub struct Armory {
-    pub characters: RwLock<HashMap<u32, Character>>,
-    pub guilds: RwLock<HashMap<u32, Guild>>,
+    pub characters: Arc<RwLock<HashMap<u32, Character>>>,
+    pub guilds: Arc<RwLock<HashMap<u32, Guild>>>,
+    pub character_aliases: Arc<RwLock<HashMap<u32, CharacterAlias>>>,
 
     // Caches
-    // TODO: Evict them at some point!
-    pub cache_char_name_to_id: RwLock<HashMap<String, Vec<u32>>>,
+    pub cache_char_name_to_id: Arc<RwLock<HashMap<String, NameCacheEntry>>>,
 }
 
 impl Default for Armory {
     fn default() -> Self {
         Armory {
-            characters: RwLock::new(HashMap::new()),
-            guilds: RwLock::new(HashMap::new()),
-            cache_char_name_to_id: RwLock::new(HashMap::new()),
+            characters: Arc::new(RwLock::new(HashMap::new())),
+            guilds: Arc::new(RwLock::new(HashMap::new())),
+            character_aliases: Arc::new(RwLock::new(HashMap::new())),
+            cache_char_name_to_id: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 }
@@ -32,11 +37,12 @@ impl Armory {
     pub fn init(self, db_main: &mut impl Select) -> Self {
         self.characters.write().unwrap().init(db_main);
         self.guilds.write().unwrap().init(db_main);
+        self.character_aliases.write().unwrap().init(db_main);
         {
             let chars = self.characters.read().unwrap();
             let mut cache = self.cache_char_name_to_id.write().unwrap();
             for (char_id, character) in chars.iter().filter(|(_, character)| character.last_update.is_some()) {
-                let vec = cache.entry(character.last_update.as_ref().unwrap().character_name.clone().to_lowercase()).or_insert_with(Vec::new);
+                let vec = &mut cache.entry(character.last_update.as_ref().unwrap().character_name.clone().to_lowercase()).or_default().character_ids;
                 if !vec.contains(char_id) {
                     vec.push(*char_id);
                 }
@@ -46,6 +52,16 @@ impl Armory {
         self
     }
 
+    /// Another handle on the same collections, e.g. for background workers, such that updates and merges reach them too.
+    pub fn share(&self) -> Self {
+        Armory {
+            characters: Arc::clone(&self.characters),
+            guilds: Arc::clone(&self.guilds),
+            character_aliases: Arc::clone(&self.character_aliases),
+            cache_char_name_to_id: Arc::clone(&self.cache_char_name_to_id),
+        }
+    }
+
     pub fn get_character_count(&self) -> usize {
         self.characters.read().unwrap().len()
     }
@@ -53,6 +69,38 @@ impl Armory {
     pub fn update(&self, db_main: &mut impl Select) {
         self.characters.write().unwrap().init(db_main);
         self.guilds.write().unwrap().init(db_main);
+        self.character_aliases.write().unwrap().init(db_main);
+    }
+
+    /// Removes names that no longer resolve to a character carrying that name, e.g. after renames or deletions,
+    /// names that were not looked up for longer than the policy allows and the least recently looked up names above its size limit.
+    /// Evicted names are looked up in the characters again. Returns the amount of evicted entries.
+    pub fn evict_name_cache(&self, policy: &NameCachePolicy, now: u64) -> usize {
+        let characters = self.characters.read().unwrap();
+        let mut cache = self.cache_char_name_to_id.write().unwrap();
+        let entries_before = cache.len();
+        cache.retain(|character_name, entry| {
+            entry.character_ids.retain(|character_id| {
+                characters
+                    .get(character_id)
+                    .and_then(|character| character.last_update.as_ref())
+                    .map_or(false, |last_update| last_update.character_name.to_lowercase() == *character_name)
+            });
+            !entry.character_ids.is_empty() && entry.get_last_access().saturating_add(policy.max_age) >= now
+        });
+        if cache.len() > policy.max_entries {
+            let mut by_age = cache.iter().map(|(character_name, entry)| (entry.get_last_access(), character_name.clone())).collect::<Vec<(u64, String)>>();
+            by_age.sort();
+            let evicted_amount = cache.len() - policy.max_entries;
+            for (_, character_name) in by_age.into_iter().take(evicted_amount) {
+                cache.remove(&character_name);
+            }
+        }
+
+        let evicted_entries = entries_before - cache.len();
+        ARMORY_NAME_CACHE_ENTRIES.set(cache.len() as i64);
+        ARMORY_NAME_CACHE_EVICTED_ENTRIES.inc_by(evicted_entries as u64);
+        evicted_entries
     }
 }
 
@@ -75,7 +123,7 @@ impl Init for HashMap<u32, Character> {
 
         // Loading the character itself
         db.select_wparams(
-            "SELECT * FROM armory_character A WHERE A.id > :character_id",
+            "SELECT A.id, A.server_id, A.server_uid FROM armory_character A WHERE A.id > :character_id AND A.merged_into_character_id IS NULL",
             |mut row| Character {
                 id: row.take(0).unwrap(),
                 server_id: row.take(1).unwrap(),
@@ -235,6 +283,29 @@ impl Init for HashMap<u32, Character> {
     }
 }
 
+impl Init for HashMap<u32, CharacterAlias> {
+    fn init(&mut self, db: &mut impl Select) {
+        db.select(
+            "SELECT A.id, A.server_id, A.server_uid, A.merged_into_character_id FROM armory_character A WHERE A.merged_into_character_id IS NOT NULL",
+            |mut row| {
+                let id: u32 = row.take(0).unwrap();
+                (
+                    id,
+                    CharacterAlias {
+                        server_id: row.take(1).unwrap(),
+                        server_uid: row.take(2).unwrap(),
+                        character_id: row.take(3).unwrap(),
+                    },
+                )
+            },
+        )
+        .into_iter()
+        .for_each(|(id, alias)| {
+            self.insert(id, alias);
+        });
+    }
+}
+
 impl Init for HashMap<u32, Guild> {
     fn init(&mut self, db: &mut impl Select) {
         let max_guild_id = self.iter().max_by(|left, right| left.0.cmp(&right.0)).map(|(id, _)| *id).unwrap_or(0);
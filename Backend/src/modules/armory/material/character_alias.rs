@@ -0,0 +1,7 @@
+/// A character that was merged into another one, its id and unit id resolve to the character it was merged into.
+#[derive(Debug, Clone)]
+pub struct CharacterAlias {
+    pub server_id: u32,
+    pub server_uid: u64,
+    pub character_id: u32,
+}
@@ -1,6 +1,15 @@
-pub use self::{armory::Armory, character::Character, character_history::CharacterHistory, guild::Guild};
+pub use self::{
+    armory::Armory,
+    character::Character,
+    character_alias::CharacterAlias,
+    character_history::CharacterHistory,
+    guild::Guild,
+    name_cache::{NameCacheEntry, NameCachePolicy},
+};
 
 mod armory;
 mod character;
+mod character_alias;
 mod character_history;
 mod guild;
+mod name_cache;
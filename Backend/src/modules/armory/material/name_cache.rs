@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Debug)]
+pub struct NameCacheEntry {
+    pub character_ids: Vec<u32>,
+    // Wall clock seconds, lookups update it while only holding the read lock
+    last_access: AtomicU64,
+}
+
+impl Default for NameCacheEntry {
+    fn default() -> Self {
+        NameCacheEntry {
+            character_ids: Vec::new(),
+            last_access: AtomicU64::new(time_util::now()),
+        }
+    }
+}
+
+impl NameCacheEntry {
+    pub fn touch(&self, now: u64) {
+        self.last_access.store(now, Ordering::Relaxed);
+    }
+
+    pub fn get_last_access(&self) -> u64 {
+        self.last_access.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NameCachePolicy {
+    // Names that were not looked up for longer than this many seconds are evicted
+    pub max_age: u64,
+    pub max_entries: usize,
+}
+
+impl Default for NameCachePolicy {
+    fn default() -> Self {
+        NameCachePolicy { max_age: 24 * 60 * 60, max_entries: 200000 }
+    }
+}
+
+impl NameCachePolicy {
+    pub fn from_env() -> Self {
+        let default = NameCachePolicy::default();
+        NameCachePolicy {
+            max_age: read_env("ARMORY_NAME_CACHE_MAX_AGE_SECS").unwrap_or(default.max_age),
+            max_entries: read_env("ARMORY_NAME_CACHE_MAX_ENTRIES").map(|amount| amount as usize).unwrap_or(default.max_entries),
+        }
+    }
+}
+
+fn read_env(key: &str) -> Option<u64> {
+    std::env::var(key).ok().and_then(|value| value.parse::<u64>().ok())
+}
@@ -0,0 +1,81 @@
+use super::helper::get_character;
+use crate::modules::armory::{
+    tools::{CharacterIdentity, CreateCharacter, DeleteCharacter, GetCharacter, SetCharacter},
+    Armory,
+};
+use crate::tests::TestContainer;
+
+#[test]
+fn merge_renamed_character() {
+    let container = TestContainer::new(true);
+    let (mut conn, _dns, _node) = container.run();
+
+    let armory = Armory::default();
+    let timestamp = time_util::now() * 1000;
+    let old_character = armory.set_character(&mut conn, 3, get_character(), timestamp).unwrap();
+
+    // Same class and gear, but a new name and hence a new unit id
+    let mut renamed_character_dto = get_character();
+    renamed_character_dto.server_uid += 1;
+    renamed_character_dto.character_history.as_mut().unwrap().character_name = "Pansipaul".to_string();
+    let renamed_character = armory.set_character(&mut conn, 3, renamed_character_dto, timestamp).unwrap();
+
+    let suggestions = armory.get_merge_suggestions(&mut conn);
+    let suggestion = suggestions
+        .iter()
+        .find(|suggestion| suggestion.character_id == renamed_character.id && suggestion.candidate_character_id == old_character.id)
+        .unwrap();
+    assert_eq!(suggestion.score, 100);
+    assert!(suggestion.reasons.contains(&"Same class".to_string()));
+    assert!(suggestion.reasons.contains(&"Same guild: GuildSample".to_string()));
+
+    assert!(armory.merge_characters(&mut conn, 1, old_character.id, old_character.id).is_err());
+    let merged_character = armory.merge_characters(&mut conn, 1, old_character.id, renamed_character.id).unwrap();
+    assert_eq!(merged_character.id, renamed_character.id);
+    assert_eq!(merged_character.history_moments.len(), old_character.history_moments.len() + renamed_character.history_moments.len());
+    assert_eq!(merged_character.last_update.as_ref().unwrap().character_name, "Pansipaul");
+    assert!(armory.get_characters_by_name("Pansipeter".to_string()).is_empty());
+
+    // The old character is kept as an alias, such that stored events and later uploads of the old unit id resolve to the merged character
+    assert_eq!(armory.get_character(old_character.id).unwrap().id, renamed_character.id);
+    assert_eq!(armory.get_character_id_by_uid(3, old_character.server_uid), Some(renamed_character.id));
+    assert_eq!(armory.create_character(&mut conn, 3, old_character.server_uid).unwrap(), renamed_character.id);
+    assert!(Armory::default().init(&mut conn).get_character(old_character.id).map_or(false, |character| character.id == renamed_character.id));
+
+    let suggestions = armory.get_merge_suggestions(&mut conn);
+    assert!(!suggestions.iter().any(|suggestion| suggestion.character_id == renamed_character.id && suggestion.candidate_character_id == old_character.id));
+
+    let audit_log = armory.get_merge_audit_log(&mut conn);
+    assert!(audit_log
+        .iter()
+        .any(|entry| entry.source_character_id == old_character.id && entry.target_character_id == renamed_character.id && entry.source_character_name == "Pansipeter" && entry.member_id == 1));
+
+    assert!(armory.delete_character(&mut conn, renamed_character.id).is_ok());
+    assert!(armory.get_character(old_character.id).is_none());
+}
+
+#[test]
+fn dismiss_merge_suggestion() {
+    let container = TestContainer::new(true);
+    let (mut conn, _dns, _node) = container.run();
+
+    let armory = Armory::default();
+    let timestamp = time_util::now() * 1000;
+    let character = armory.set_character(&mut conn, 3, get_character(), timestamp).unwrap();
+
+    let mut other_character_dto = get_character();
+    other_character_dto.server_uid += 2;
+    other_character_dto.character_history.as_mut().unwrap().character_name = "Pansipiet".to_string();
+    let other_character = armory.set_character(&mut conn, 3, other_character_dto, timestamp).unwrap();
+
+    let suggestion = armory.get_merge_suggestions(&mut conn).into_iter().find(|suggestion| suggestion.character_id == other_character.id).unwrap();
+    assert!(armory.dismiss_merge_suggestion(&mut conn, suggestion.id).is_ok());
+    assert!(armory.dismiss_merge_suggestion(&mut conn, suggestion.id).is_err());
+    assert!(!armory.get_merge_suggestions(&mut conn).iter().any(|pending| pending.id == suggestion.id));
+
+    // Repeatedly seeing the character does not suggest it again
+    assert!(armory.suggest_character_merges(&mut conn, other_character.id).is_empty());
+
+    assert!(armory.delete_character(&mut conn, character.id).is_ok());
+    assert!(armory.delete_character(&mut conn, other_character.id).is_ok());
+}
@@ -2,10 +2,12 @@ mod character;
 mod character_facial;
 mod character_gear;
 mod character_history;
+mod character_identity;
 mod character_info;
 mod character_item;
 mod character_search;
 mod character_viewer;
 mod guild;
+mod name_cache;
 
 mod helper;
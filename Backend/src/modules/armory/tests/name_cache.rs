@@ -0,0 +1,39 @@
+use super::helper::get_character;
+use crate::modules::armory::material::NameCachePolicy;
+use crate::modules::armory::{
+    tools::{DeleteCharacter, GetCharacter, SetCharacter},
+    Armory,
+};
+use crate::tests::TestContainer;
+
+#[test]
+fn evict_name_cache() {
+    let container = TestContainer::new(true);
+    let (mut conn, _dns, _node) = container.run();
+
+    let armory = Armory::default();
+    let timestamp = time_util::now() * 1000;
+    let character = armory.set_character(&mut conn, 3, get_character(), timestamp).unwrap();
+    let mut other_character_dto = get_character();
+    other_character_dto.server_uid += 1;
+    other_character_dto.character_history.as_mut().unwrap().character_name = "Pansipaul".to_string();
+    let other_character = armory.set_character(&mut conn, 3, other_character_dto, timestamp).unwrap();
+    assert_eq!(armory.cache_char_name_to_id.read().unwrap().len(), 2);
+
+    // Names that were looked up recently are kept, the least recently looked up above the limit are evicted
+    let now = time_util::now();
+    armory.cache_char_name_to_id.read().unwrap().get("pansipeter").unwrap().touch(now + 10);
+    assert_eq!(armory.evict_name_cache(&NameCachePolicy { max_age: 60, max_entries: 1 }, now), 1);
+    assert!(armory.cache_char_name_to_id.read().unwrap().contains_key("pansipeter"));
+
+    // Outdated names are evicted, but still found in the characters
+    assert_eq!(armory.evict_name_cache(&NameCachePolicy { max_age: 60, max_entries: 10 }, now + 100), 1);
+    assert!(armory.cache_char_name_to_id.read().unwrap().is_empty());
+    assert_eq!(armory.get_characters_by_name("Pansipaul".to_string()).len(), 1);
+    assert!(armory.cache_char_name_to_id.read().unwrap().contains_key("pansipaul"));
+    assert!(armory.get_characters_by_name("Unknown".to_string()).is_empty());
+    assert!(!armory.cache_char_name_to_id.read().unwrap().contains_key("unknown"));
+
+    assert!(armory.delete_character(&mut conn, character.id).is_ok());
+    assert!(armory.delete_character(&mut conn, other_character.id).is_ok());
+}
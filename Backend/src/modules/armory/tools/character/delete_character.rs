@@ -21,12 +21,14 @@ impl DeleteCharacter for Armory {
                 let current_character = characters.get(&id).unwrap();
                 if let Some(history) = &current_character.last_update {
                     let mut cache = self.cache_char_name_to_id.write().unwrap();
-                    let vec = cache.get_mut(&history.character_name.to_lowercase()).unwrap();
-                    if let Some(index) = vec.iter().position(|char_id| *char_id == id) {
-                        vec.remove(index);
+                    // The name may have been evicted from the cache already
+                    if let Some(entry) = cache.get_mut(&history.character_name.to_lowercase()) {
+                        entry.character_ids.retain(|char_id| *char_id != id);
                     }
                 }
             }
+            // The aliases are removed by the database as well
+            self.character_aliases.write().unwrap().retain(|_, alias| alias.character_id != id);
             return characters.remove(&id).ok_or(ArmoryFailure::InvalidInput).map(|_| ());
         }
         Err(ArmoryFailure::Database("delete_character".to_owned()))
@@ -20,7 +20,10 @@ pub trait GetCharacter {
 impl GetCharacter for Armory {
     fn get_character_id_by_uid(&self, server_id: u32, uid: u64) -> Option<u32> {
         let characters = self.characters.read().unwrap();
-        characters.iter().find(|(_, character)| character.server_id == server_id && character.server_uid == uid).map(|(id, _)| *id)
+        characters.iter().find(|(_, character)| character.server_id == server_id && character.server_uid == uid).map(|(id, _)| *id).or_else(|| {
+            let character_aliases = self.character_aliases.read().unwrap();
+            character_aliases.values().find(|alias| alias.server_id == server_id && alias.server_uid == uid).map(|alias| alias.character_id)
+        })
     }
 
     fn get_character_by_uid(&self, server_id: u32, uid: u64) -> Option<Character> {
@@ -29,14 +32,18 @@ impl GetCharacter for Armory {
 
     fn get_character(&self, character_id: u32) -> Option<Character> {
         let characters = self.characters.read().unwrap();
-        characters.get(&character_id).cloned()
+        // Merged characters are still referred to by stored events
+        characters
+            .get(&character_id)
+            .or_else(|| self.character_aliases.read().unwrap().get(&character_id).and_then(|alias| characters.get(&alias.character_id)))
+            .cloned()
     }
 
     fn get_basic_character(&self, db_main: &mut impl Select, character_id: u32, timestamp: u64) -> Option<BasicCharacter> {
         let character = self.get_character(character_id)?;
-        let char_history = self.get_character_moment(db_main, character_id, timestamp)?;
+        let char_history = self.get_character_moment(db_main, character.id, timestamp)?;
         Some(BasicCharacter {
-            id: character_id,
+            id: character.id,
             server_id: character.server_id,
             hero_class_id: Some(char_history.character_info.hero_class_id),
             race_id: Some(char_history.character_info.race_id),
@@ -46,20 +53,32 @@ impl GetCharacter for Armory {
     }
 
     fn get_characters_by_name(&self, character_name: String) -> Vec<Character> {
+        let character_name = character_name.to_lowercase();
         let characters = self.characters.read().unwrap();
-        let cache = self.cache_char_name_to_id.read().unwrap();
-        cache.get(&character_name.to_lowercase())
-            .map(|char_ids| {
-                char_ids.iter().fold(Vec::new(), |mut acc, id| {
-                    if let Some(character) = characters.get(id) {
-                        if character.last_update.is_some() {
-                            acc.push(character.clone());
-                        }
-                    }
-                    acc
-                })
-            })
-            .unwrap_or_else(Vec::new)
+        {
+            let cache = self.cache_char_name_to_id.read().unwrap();
+            if let Some(entry) = cache.get(&character_name) {
+                entry.touch(time_util::now());
+                return entry.character_ids.iter().filter_map(|id| characters.get(id)).filter(|character| character.last_update.is_some()).cloned().collect();
+            }
+        }
+
+        // Names are evicted from the cache, hence a miss is looked up in the characters and cached again
+        let found_characters = characters
+            .values()
+            .filter(|character| character.last_update.as_ref().map_or(false, |last_update| last_update.character_name.to_lowercase() == character_name))
+            .cloned()
+            .collect::<Vec<Character>>();
+        if !found_characters.is_empty() {
+            let mut cache = self.cache_char_name_to_id.write().unwrap();
+            let vec = &mut cache.entry(character_name).or_default().character_ids;
+            for character in found_characters.iter() {
+                if !vec.contains(&character.id) {
+                    vec.push(character.id);
+                }
+            }
+        }
+        found_characters
     }
 
     fn get_character_by_name(&self, server_id: u32, character_name: String) -> Option<Character> {
@@ -96,7 +115,7 @@ impl GetCharacter for Armory {
        AND t1.timestamp BETWEEN (:timestamp - :max_timestamp) AND (:timestamp + :max_timestamp) order by diff LIMIT 1;",
             |mut row| row.take(0).unwrap(),
             params! {
-        "character_id" => character_id,
+        "character_id" => character.id,
         "timestamp" => timestamp,
         "max_timestamp" => 21600
     },
@@ -3,7 +3,7 @@ use crate::{
     modules::armory::{
         dto::{ArmoryFailure, CharacterDto},
         material::Character,
-        tools::{CreateCharacter, GetCharacter, SetCharacterHistory},
+        tools::{CharacterIdentity, CreateCharacter, GetCharacter, SetCharacterHistory},
         Armory,
     },
 };
@@ -30,20 +30,27 @@ impl SetCharacter for Armory {
             }
             let char_history = character_history_res.unwrap();
 
+            let is_new_character = current_character.last_update.is_none();
             let mut cache = self.cache_char_name_to_id.write().unwrap();
             // Invalidate old entry
             if let Some(history) = current_character.last_update {
-                let vec = cache.entry(history.character_name.to_lowercase()).or_insert_with(Vec::new);
+                let vec = &mut cache.entry(history.character_name.to_lowercase()).or_default().character_ids;
                 if let Some(index) = vec.iter().position(|char_id| *char_id == character_id) {
                     vec.remove(index);
                 }
             }
 
             // Add new entry
-            let vec = cache.entry(char_history.character_name.to_lowercase()).or_insert_with(Vec::new);
+            let vec = &mut cache.entry(char_history.character_name.to_lowercase()).or_default().character_ids;
             if !vec.contains(&char_history.character_id) {
                 vec.push(char_history.character_id);
             }
+            drop(cache);
+
+            // A renamed or transferred character shows up as a new character
+            if is_new_character {
+                self.suggest_character_merges(db_main, character_id);
+            }
         }
 
         self.get_character(character_id).ok_or_else(|| ArmoryFailure::Database("get_character".to_owned()))
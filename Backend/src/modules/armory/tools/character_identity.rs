@@ -0,0 +1,286 @@
+use crate::modules::armory::domain_value::{CharacterGear, CharacterMergeAuditEntry};
+use crate::modules::armory::dto::{ArmoryFailure, CharacterMergeSuggestion};
+use crate::modules::armory::material::{Character, CharacterAlias, CharacterHistory};
+use crate::modules::armory::Armory;
+use crate::params;
+use crate::util::database::{Execute, Select, Transaction};
+
+/// Minimum number of identical equipped items, shirt and tabard aside, to suggest two characters for a merge
+pub const MERGE_MIN_MATCHING_ITEMS: usize = 8;
+
+// Every table that refers to a character, except for armory_character_history, which is handled separately.
+// Tables with the character in their primary key are updated with IGNORE, the remaining rows of the source are duplicates and removed.
+const MERGE_TABLES: [(&str, bool); 9] = [
+    ("instance_participants", true),
+    ("instance_upload_spec", true),
+    ("instance_loot", false),
+    ("instance_ranking_damage", false),
+    ("instance_ranking_heal", false),
+    ("instance_ranking_threat", false),
+    ("ranking_results_damage", false),
+    ("ranking_results_heal", false),
+    ("ranking_results_threat", false),
+];
+
+pub trait CharacterIdentity {
+    fn suggest_character_merges(&self, db_main: &mut (impl Execute + Select), character_id: u32) -> Vec<CharacterMergeSuggestion>;
+    fn get_merge_suggestions(&self, db_main: &mut impl Select) -> Vec<CharacterMergeSuggestion>;
+    fn dismiss_merge_suggestion(&self, db_main: &mut (impl Execute + Select), suggestion_id: u32) -> Result<(), ArmoryFailure>;
+    fn merge_characters(&self, db_main: &mut (impl Execute + Select + Transaction), member_id: u32, source_character_id: u32, target_character_id: u32) -> Result<Character, ArmoryFailure>;
+    fn get_merge_audit_log(&self, db_main: &mut impl Select) -> Vec<CharacterMergeAuditEntry>;
+}
+
+impl CharacterIdentity for Armory {
+    fn suggest_character_merges(&self, db_main: &mut (impl Execute + Select), character_id: u32) -> Vec<CharacterMergeSuggestion> {
+        let candidates = {
+            let characters = self.characters.read().unwrap();
+            let guilds = self.guilds.read().unwrap();
+            let guild_name = |history: &CharacterHistory| history.character_guild.as_ref().and_then(|character_guild| guilds.get(&character_guild.guild_id)).map(|guild| guild.name.clone());
+            let history = match characters.get(&character_id).and_then(|character| character.last_update.as_ref()) {
+                Some(history) => history,
+                None => return Vec::new(),
+            };
+            let history_guild_name = guild_name(history);
+
+            characters
+                .values()
+                .filter(|candidate| candidate.id != character_id)
+                .filter_map(|candidate| candidate.last_update.as_ref())
+                .filter_map(|candidate_history| compare_character_identity(history, candidate_history, history_guild_name.as_deref(), guild_name(candidate_history).as_deref()).map(|(score, reasons)| (candidate_history.character_id, score, reasons)))
+                .collect::<Vec<(u32, u8, Vec<String>)>>()
+        };
+
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        let now = time_util::now();
+        db_main.execute_batch_wparams(
+            "INSERT IGNORE INTO armory_character_merge_suggestion (`character_id`, `candidate_character_id`, `score`, `reasons`, `suggested_ts`) VALUES (:character_id, :candidate_character_id, :score, :reasons, :suggested_ts)",
+            candidates,
+            move |(candidate_character_id, score, reasons)| {
+                params!(
+                    "character_id" => character_id,
+                    "candidate_character_id" => candidate_character_id,
+                    "score" => score,
+                    "reasons" => reasons.join("\n"),
+                    "suggested_ts" => now
+                )
+            },
+        );
+
+        self.get_merge_suggestions(db_main).into_iter().filter(|suggestion| suggestion.character_id == character_id).collect()
+    }
+
+    fn get_merge_suggestions(&self, db_main: &mut impl Select) -> Vec<CharacterMergeSuggestion> {
+        db_main.select(
+            "SELECT id, character_id, candidate_character_id, score, reasons, suggested_ts FROM armory_character_merge_suggestion WHERE status = 0 ORDER BY score DESC, id",
+            |mut row| CharacterMergeSuggestion {
+                id: row.take(0).unwrap(),
+                character_id: row.take(1).unwrap(),
+                candidate_character_id: row.take(2).unwrap(),
+                score: row.take(3).unwrap(),
+                reasons: split_reasons(&row.take::<String, usize>(4).unwrap()),
+                suggested_ts: row.take(5).unwrap(),
+            },
+        )
+    }
+
+    fn dismiss_merge_suggestion(&self, db_main: &mut (impl Execute + Select), suggestion_id: u32) -> Result<(), ArmoryFailure> {
+        db_main
+            .select_wparams_value(
+                "SELECT id FROM armory_character_merge_suggestion WHERE id = :id AND status = 0",
+                |mut row| row.take::<u32, usize>(0).unwrap(),
+                params!("id" => suggestion_id),
+            )
+            .ok_or(ArmoryFailure::InvalidInput)?;
+        if db_main.execute_wparams("UPDATE armory_character_merge_suggestion SET status = 1 WHERE id = :id", params!("id" => suggestion_id)) {
+            return Ok(());
+        }
+        Err(ArmoryFailure::Database("dismiss_merge_suggestion".to_owned()))
+    }
+
+    fn merge_characters(&self, db_main: &mut (impl Execute + Select + Transaction), member_id: u32, source_character_id: u32, target_character_id: u32) -> Result<Character, ArmoryFailure> {
+        if source_character_id == target_character_id {
+            return Err(ArmoryFailure::InvalidInput);
+        }
+
+        let mut characters = self.characters.write().unwrap();
+        if !characters.contains_key(&source_character_id) || !characters.contains_key(&target_character_id) {
+            return Err(ArmoryFailure::InvalidInput);
+        }
+
+        let source_character_name = characters[&source_character_id].last_update.as_ref().map(|history| history.character_name.clone()).unwrap_or_default();
+        let merge_params = params!("source_character_id" => source_character_id, "target_character_id" => target_character_id);
+        // A failure halfway would leave the history and rankings split between both characters
+        if !db_main.start_transaction() {
+            return Err(ArmoryFailure::Database("merge_characters".to_owned()));
+        }
+        let mut success = db_main.execute_wparams("UPDATE armory_character_history SET character_id = :target_character_id WHERE character_id = :source_character_id", merge_params.clone());
+        for (table, is_keyed) in MERGE_TABLES.iter() {
+            success = success
+                && db_main.execute_wparams(
+                    &format!("UPDATE {}{} SET character_id = :target_character_id WHERE character_id = :source_character_id", if *is_keyed { "IGNORE " } else { "" }, table),
+                    merge_params.clone(),
+                )
+                && (!*is_keyed || db_main.execute_wparams(&format!("DELETE FROM {} WHERE character_id = :source_character_id", table), merge_params.clone()));
+        }
+        // Suggestions between the source and the target are resolved by the merge
+        success = success
+            && db_main.execute_wparams(
+                "DELETE FROM armory_character_merge_suggestion WHERE (character_id = :source_character_id AND candidate_character_id = :target_character_id) OR (character_id = :target_character_id AND candidate_character_id = \
+                 :source_character_id)",
+                merge_params.clone(),
+            )
+            && db_main.execute_wparams(
+                "UPDATE IGNORE armory_character_merge_suggestion SET character_id = :target_character_id WHERE character_id = :source_character_id",
+                merge_params.clone(),
+            )
+            && db_main.execute_wparams(
+                "UPDATE IGNORE armory_character_merge_suggestion SET candidate_character_id = :target_character_id WHERE candidate_character_id = :source_character_id",
+                merge_params.clone(),
+            )
+            && db_main.execute_wparams(
+                "DELETE FROM armory_character_merge_suggestion WHERE character_id = :source_character_id OR candidate_character_id = :source_character_id",
+                merge_params.clone(),
+            )
+            // The source is kept as an alias, as stored events and later uploads still refer to its id and unit id.
+            // Aliases of the source are redirected as well, such that aliases never chain.
+            && db_main.execute_wparams(
+                "UPDATE armory_character SET merged_into_character_id = :target_character_id WHERE id = :source_character_id OR merged_into_character_id = :source_character_id",
+                merge_params,
+            )
+            && db_main.execute_wparams(
+                "INSERT INTO armory_character_merge_audit (`member_id`, `source_character_id`, `source_character_name`, `target_character_id`, `timestamp`) VALUES (:member_id, :source_character_id, :source_character_name, :target_character_id, \
+                 :timestamp)",
+                params!(
+                    "member_id" => member_id,
+                    "source_character_id" => source_character_id,
+                    "source_character_name" => source_character_name.clone(),
+                    "target_character_id" => target_character_id,
+                    "timestamp" => time_util::now()
+                ),
+            );
+        if !success || !db_main.commit() {
+            db_main.rollback();
+            return Err(ArmoryFailure::Database("merge_characters".to_owned()));
+        }
+
+        let source_character = characters.remove(&source_character_id).unwrap();
+        {
+            let mut character_aliases = self.character_aliases.write().unwrap();
+            character_aliases.values_mut().filter(|alias| alias.character_id == source_character_id).for_each(|alias| alias.character_id = target_character_id);
+            character_aliases.insert(
+                source_character_id,
+                CharacterAlias {
+                    server_id: source_character.server_id,
+                    server_uid: source_character.server_uid,
+                    character_id: target_character_id,
+                },
+            );
+        }
+        let target_character = characters.get_mut(&target_character_id).unwrap();
+        let previous_name = target_character.last_update.as_ref().map(|history| history.character_name.clone());
+        target_character.history_moments.extend(source_character.history_moments);
+        target_character.history_moments.sort_by(|left, right| left.timestamp.cmp(&right.timestamp).then(left.id.cmp(&right.id)));
+        // The newest history is the one with the highest id, as in the initial load
+        if let Some(mut source_history) = source_character.last_update {
+            if target_character.last_update.as_ref().map_or(true, |history| history.id < source_history.id) {
+                source_history.character_id = target_character_id;
+                target_character.last_update = Some(source_history);
+            }
+        }
+
+        let mut cache = self.cache_char_name_to_id.write().unwrap();
+        if let Some(entry) = cache.get_mut(&source_character_name.to_lowercase()) {
+            entry.character_ids.retain(|character_id| *character_id != source_character_id);
+        }
+        if let Some(history) = &target_character.last_update {
+            if previous_name.as_ref() != Some(&history.character_name) {
+                if let Some(previous_name) = previous_name {
+                    if let Some(entry) = cache.get_mut(&previous_name.to_lowercase()) {
+                        entry.character_ids.retain(|character_id| *character_id != target_character_id);
+                    }
+                }
+                let vec = &mut cache.entry(history.character_name.to_lowercase()).or_default().character_ids;
+                if !vec.contains(&target_character_id) {
+                    vec.push(target_character_id);
+                }
+            }
+        }
+
+        Ok(target_character.clone())
+    }
+
+    fn get_merge_audit_log(&self, db_main: &mut impl Select) -> Vec<CharacterMergeAuditEntry> {
+        db_main.select(
+            "SELECT id, member_id, source_character_id, source_character_name, target_character_id, timestamp FROM armory_character_merge_audit ORDER BY id DESC",
+            |mut row| CharacterMergeAuditEntry {
+                id: row.take(0).unwrap(),
+                member_id: row.take(1).unwrap(),
+                source_character_id: row.take(2).unwrap(),
+                source_character_name: row.take(3).unwrap(),
+                target_character_id: row.take(4).unwrap(),
+                timestamp: row.take(5).unwrap(),
+            },
+        )
+    }
+}
+
+/// Compares the newest history of a character with the one of a candidate that was last seen before it,
+/// as a renamed or transferred character appears as a new character from then on.
+/// Returns the share of matching equipped items in percent and the reasons if both are likely the same character.
+pub fn compare_character_identity(history: &CharacterHistory, candidate: &CharacterHistory, guild_name: Option<&str>, candidate_guild_name: Option<&str>) -> Option<(u8, Vec<String>)> {
+    if history.character_info.hero_class_id != candidate.character_info.hero_class_id || candidate.timestamp > history.timestamp {
+        return None;
+    }
+
+    let item_ids = equipped_item_ids(&history.character_info.gear);
+    let candidate_item_ids = equipped_item_ids(&candidate.character_info.gear);
+    let equipped_items = item_ids.iter().filter(|item_id| item_id.is_some()).count();
+    let matching_items = item_ids.iter().zip(candidate_item_ids.iter()).filter(|(item_id, candidate_item_id)| item_id.is_some() && item_id == candidate_item_id).count();
+    if matching_items < MERGE_MIN_MATCHING_ITEMS {
+        return None;
+    }
+
+    let mut reasons = vec!["Same class".to_string(), format!("{} of {} equipped items match", matching_items, equipped_items)];
+    if history.character_info.race_id == candidate.character_info.race_id {
+        reasons.push("Same race".to_string());
+    }
+    if let (Some(guild_name), Some(candidate_guild_name)) = (guild_name, candidate_guild_name) {
+        if guild_name.to_lowercase() == candidate_guild_name.to_lowercase() {
+            reasons.push(format!("Same guild: {}", guild_name));
+        }
+    }
+    Some(((matching_items * 100 / equipped_items) as u8, reasons))
+}
+
+// Shirt and tabard are commonly shared cosmetics
+fn equipped_item_ids(gear: &CharacterGear) -> Vec<Option<u32>> {
+    vec![
+        &gear.head,
+        &gear.neck,
+        &gear.shoulder,
+        &gear.back,
+        &gear.chest,
+        &gear.wrist,
+        &gear.main_hand,
+        &gear.off_hand,
+        &gear.ternary_hand,
+        &gear.glove,
+        &gear.belt,
+        &gear.leg,
+        &gear.boot,
+        &gear.ring1,
+        &gear.ring2,
+        &gear.trinket1,
+        &gear.trinket2,
+    ]
+    .into_iter()
+    .map(|item| item.as_ref().map(|item| item.item_id))
+    .collect()
+}
+
+fn split_reasons(reasons: &str) -> Vec<String> {
+    reasons.lines().filter(|reason| !reason.is_empty()).map(|reason| reason.to_string()).collect()
+}
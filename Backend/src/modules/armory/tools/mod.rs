@@ -4,12 +4,14 @@ pub use self::{
 };
 
 pub use self::character_arena_team::*;
+pub use self::character_identity::{compare_character_identity, CharacterIdentity, MERGE_MIN_MATCHING_ITEMS};
 
 mod character;
 mod character_arena_team;
 mod character_facial;
 mod character_gear;
 mod character_history;
+mod character_identity;
 mod character_info;
 mod character_item;
 mod character_search;
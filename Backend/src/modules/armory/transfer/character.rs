@@ -3,7 +3,7 @@ use rocket_contrib::json::Json;
 
 use crate::modules::armory::dto::BasicCharacter;
 use crate::modules::{
-    account::guard::ServerOwner,
+    account::guard::{HasPermission, ManageServer},
     armory::{
         dto::{ArmoryFailure, CharacterDto},
         material::Character,
@@ -15,8 +15,8 @@ use crate::MainDb;
 
 #[openapi]
 #[post("/character", format = "application/json", data = "<character>")]
-pub fn set_character(mut db_main: MainDb, me: State<Armory>, owner: ServerOwner, character: Json<CharacterDto>) -> Result<(), ArmoryFailure> {
-    me.set_character(&mut *db_main, owner.0, character.into_inner(), time_util::now() * 1000).map(|_| ())
+pub fn set_character(mut db_main: MainDb, me: State<Armory>, owner: HasPermission<ManageServer>, character: Json<CharacterDto>) -> Result<(), ArmoryFailure> {
+    me.set_character(&mut *db_main, owner.scope_id, character.into_inner(), time_util::now() * 1000).map(|_| ())
 }
 
 #[openapi]
@@ -53,18 +53,18 @@ pub fn get_characters_by_name(me: State<Armory>, name: String) -> Json<Vec<Chara
 
 #[openapi]
 #[get("/character/by_uid/<uid>")]
-pub fn get_character_by_uid(me: State<Armory>, owner: ServerOwner, uid: u64) -> Result<Json<Character>, ArmoryFailure> {
-    me.get_character_by_uid(owner.0, uid).map(Json).ok_or(ArmoryFailure::InvalidInput)
+pub fn get_character_by_uid(me: State<Armory>, owner: HasPermission<ManageServer>, uid: u64) -> Result<Json<Character>, ArmoryFailure> {
+    me.get_character_by_uid(owner.scope_id, uid).map(Json).ok_or(ArmoryFailure::InvalidInput)
 }
 
 #[openapi]
 #[delete("/character/<id>")]
-pub fn delete_character(mut db_main: MainDb, me: State<Armory>, _owner: ServerOwner, id: u32) -> Result<(), ArmoryFailure> {
+pub fn delete_character(mut db_main: MainDb, me: State<Armory>, _owner: HasPermission<ManageServer>, id: u32) -> Result<(), ArmoryFailure> {
     me.delete_character(&mut *db_main, id)
 }
 
 #[openapi]
 #[delete("/character/by_uid/<uid>")]
-pub fn delete_character_by_uid(mut db_main: MainDb, me: State<Armory>, owner: ServerOwner, uid: u64) -> Result<(), ArmoryFailure> {
-    me.delete_character_by_uid(&mut *db_main, owner.0, uid)
+pub fn delete_character_by_uid(mut db_main: MainDb, me: State<Armory>, owner: HasPermission<ManageServer>, uid: u64) -> Result<(), ArmoryFailure> {
+    me.delete_character_by_uid(&mut *db_main, owner.scope_id, uid)
 }
@@ -2,7 +2,7 @@ use rocket::State;
 use rocket_contrib::json::Json;
 
 use crate::modules::{
-    account::guard::ServerOwner,
+    account::guard::{HasPermission, ManageServer},
     armory::{
         dto::{ArmoryFailure, CharacterHistoryDto},
         material::CharacterHistory,
@@ -14,8 +14,8 @@ use crate::MainDb;
 
 #[openapi]
 #[post("/character_history/<character_uid>", format = "application/json", data = "<character_history>")]
-pub fn set_character_history(mut db_main: MainDb, me: State<Armory>, owner: ServerOwner, character_history: Json<CharacterHistoryDto>, character_uid: u64) -> Result<(), ArmoryFailure> {
-    me.set_character_history(&mut *db_main, owner.0, character_history.into_inner(), character_uid, time_util::now() * 1000).map(|_| ())
+pub fn set_character_history(mut db_main: MainDb, me: State<Armory>, owner: HasPermission<ManageServer>, character_history: Json<CharacterHistoryDto>, character_uid: u64) -> Result<(), ArmoryFailure> {
+    me.set_character_history(&mut *db_main, owner.scope_id, character_history.into_inner(), character_uid, time_util::now() * 1000).map(|_| ())
 }
 
 #[openapi]
@@ -0,0 +1,38 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::account::guard::{HasPermission, Moderate};
+use crate::modules::armory::domain_value::CharacterMergeAuditEntry;
+use crate::modules::armory::dto::{ArmoryFailure, CharacterMergeDto, CharacterMergeSuggestion};
+use crate::modules::armory::material::Character;
+use crate::modules::armory::tools::CharacterIdentity;
+use crate::modules::armory::Armory;
+use crate::modules::instance::Instance;
+use crate::MainDb;
+
+#[openapi]
+#[get("/character/merge/suggestions")]
+pub fn get_merge_suggestions(mut db_main: MainDb, me: State<Armory>, _auth: HasPermission<Moderate>) -> Json<Vec<CharacterMergeSuggestion>> {
+    Json(me.get_merge_suggestions(&mut *db_main))
+}
+
+#[openapi]
+#[delete("/character/merge/suggestions/<suggestion_id>")]
+pub fn dismiss_merge_suggestion(mut db_main: MainDb, me: State<Armory>, suggestion_id: u32, _auth: HasPermission<Moderate>) -> Result<(), ArmoryFailure> {
+    me.dismiss_merge_suggestion(&mut *db_main, suggestion_id)
+}
+
+#[openapi]
+#[get("/character/merge/audit")]
+pub fn get_merge_audit_log(mut db_main: MainDb, me: State<Armory>, _auth: HasPermission<Moderate>) -> Json<Vec<CharacterMergeAuditEntry>> {
+    Json(me.get_merge_audit_log(&mut *db_main))
+}
+
+#[openapi]
+#[post("/character/merge", format = "application/json", data = "<merge>")]
+pub fn merge_characters(mut db_main: MainDb, me: State<Armory>, instance: State<Instance>, merge: Json<CharacterMergeDto>, auth: HasPermission<Moderate>) -> Result<Json<Character>, ArmoryFailure> {
+    let merge = merge.into_inner();
+    let character = me.merge_characters(&mut *db_main, auth.member_id, merge.source_character_id, merge.target_character_id)?;
+    instance.merge_participants(merge.source_character_id, merge.target_character_id);
+    Ok(Json(character))
+}
@@ -1,5 +1,6 @@
 pub mod character;
 pub mod character_history;
+pub mod character_identity;
 pub mod character_search;
 pub mod character_viewer;
 pub mod guild;
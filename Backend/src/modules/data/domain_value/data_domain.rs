@@ -0,0 +1,17 @@
+/// A group of tables that is reloaded together, e.g. after a custom NPC or encounter was added to the database.
+/// Static tables like races or item qualities are only loaded once at startup.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+pub enum DataDomain {
+    Servers,
+    Spells,
+    Npcs,
+    Items,
+    Encounters,
+    Seasons,
+    SpecRules,
+    Addons,
+}
+
+impl DataDomain {
+    pub const ALL: [DataDomain; 8] = [DataDomain::Servers, DataDomain::Spells, DataDomain::Npcs, DataDomain::Items, DataDomain::Encounters, DataDomain::Seasons, DataDomain::SpecRules, DataDomain::Addons];
+}
@@ -0,0 +1,48 @@
+/// Rules that refine the attempt detection of an encounter beyond its encounter NPCs.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct EncounterRule {
+    pub encounter_id: u32,
+    pub duration_variants: Vec<EncounterDurationVariant>,
+    pub hard_modes: Vec<EncounterHardMode>,
+    pub hard_mode_aura_spell_ids: Vec<u32>,
+    pub hard_mode_npc_ids: Vec<u32>,
+    pub add_phase: Option<EncounterAddPhase>,
+}
+
+/// Attempts whose duration in ms lies within the (inclusive) bounds are attributed to the variant encounter.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct EncounterDurationVariant {
+    pub variant_encounter_id: u32,
+    pub min_duration: Option<u64>,
+    pub max_duration: Option<u64>,
+}
+
+/// Attempts are ranked as the hard mode if the number of found triggers, i.e. hard mode auras gained by players
+/// and hard mode NPCs that died, lies within the (inclusive) bounds and the attempt did not last longer than max_duration.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct EncounterHardMode {
+    pub hard_mode_encounter_id: u32,
+    pub min_triggers: u8,
+    pub max_triggers: Option<u8>,
+    pub max_duration: Option<u64>,
+}
+
+/// Encounters that start with adds before the boss engages. The required deaths of the encounter are expected
+/// from the start and players leaving combat within the grace period after the last creature death do not end the attempt.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct EncounterAddPhase {
+    pub grace_period: Option<u64>,
+}
+
+impl EncounterRule {
+    pub fn new(encounter_id: u32) -> Self {
+        EncounterRule {
+            encounter_id,
+            duration_variants: Vec::new(),
+            hard_modes: Vec::new(),
+            hard_mode_aura_spell_ids: Vec::new(),
+            hard_mode_npc_ids: Vec::new(),
+            add_phase: None,
+        }
+    }
+}
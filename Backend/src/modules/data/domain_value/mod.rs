@@ -1,15 +1,17 @@
 pub use self::{
-    difficulty::Difficulty, dispel_type::DispelType, enchant::Enchant, encounter::Encounter, encounter_npc::EncounterNpc, expansion::Expansion, gem::Gem, hero_class::HeroClass, hero_class_talent::HeroClassTalent, icon::Icon, item::Item,
+    data_domain::DataDomain, difficulty::Difficulty, dispel_type::DispelType, enchant::Enchant, encounter::Encounter, encounter_npc::EncounterNpc, encounter_rule::{EncounterAddPhase, EncounterDurationVariant, EncounterHardMode, EncounterRule}, expansion::Expansion, gem::Gem, hero_class::HeroClass, hero_class_talent::HeroClassTalent, icon::Icon, item::Item,
     item_bonding::ItemBonding, item_class::ItemClass, item_damage::ItemDamage, item_damage_type::ItemDamageType, item_effect::ItemEffect, item_inventory_type::ItemInventoryType, item_quality::ItemQuality, item_random_property::ItemRandomProperty,
     item_random_property_points::ItemRandomPropertyPoints, item_sheath::ItemSheath, item_socket::ItemSocket, item_stat::ItemStat, itemset_effect::ItemsetEffect, itemset_name::ItemsetName, language::Language, localization::Localization,
-    localized::Localized, map::Map, npc::NPC, power_type::PowerType, profession::Profession, race::Race, server::Server, spell::Spell, spell_effect::SpellEffect, stat::Stat, stat_type::StatType, title::Title, addon::Addon
+    localized::Localized, map::Map, npc::NPC, npc_name::NpcName, power_type::PowerType, profession::Profession, race::Race, season::Season, server::Server, spec_rule::{SpecRule, SpecRuleSource}, spell::Spell, spell_effect::SpellEffect, spell_name::SpellName, stat::Stat, stat_type::StatType, title::Title, addon::Addon
 };
 
+mod data_domain;
 mod difficulty;
 mod dispel_type;
 mod enchant;
 mod encounter;
 mod encounter_npc;
+mod encounter_rule;
 mod expansion;
 mod gem;
 mod hero_class;
@@ -35,12 +37,16 @@ mod localization;
 mod localized;
 mod map;
 mod npc;
+mod npc_name;
 mod power_type;
 mod profession;
 mod race;
+mod season;
 mod server;
+mod spec_rule;
 mod spell;
 mod spell_effect;
+mod spell_name;
 mod stat;
 mod stat_type;
 mod title;
@@ -7,4 +7,5 @@ pub struct NPC {
     pub friend: u8,
     pub family: u8,
     pub map_id: Option<u16>,
+    pub max_health: Option<u32>,
 }
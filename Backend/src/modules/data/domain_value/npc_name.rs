@@ -0,0 +1,8 @@
+/// The name of an NPC as it is shown by a client of another language than English.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct NpcName {
+    pub expansion_id: u8,
+    pub npc_id: u32,
+    pub language_id: u8,
+    pub name: String,
+}
@@ -0,0 +1,17 @@
+/// A ranking season of a server, e.g. a content phase. Seasons with server_id 0 apply to all servers.
+/// The season covers all maps if map_ids is empty, otherwise only the listed maps.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct Season {
+    pub id: u32,
+    pub server_id: u32,
+    pub name: String,
+    pub start_ts: u64,
+    pub end_ts: Option<u64>,
+    pub map_ids: Vec<u16>,
+}
+
+impl Season {
+    pub fn contains(&self, server_id: u32, map_id: u16, ts: u64) -> bool {
+        (self.server_id == 0 || self.server_id == server_id) && self.start_ts <= ts && self.end_ts.map(|end_ts| ts <= end_ts).unwrap_or(true) && (self.map_ids.is_empty() || self.map_ids.contains(&map_id))
+    }
+}
@@ -0,0 +1,36 @@
+/// The combat log event in which the spell of a spec rule is observed.
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema, PartialEq, Eq)]
+pub enum SpecRuleSource {
+    AuraGain = 1,
+    Heal = 2,
+    Cast = 3,
+}
+
+impl SpecRuleSource {
+    pub fn new(source: u8) -> Option<Self> {
+        match source {
+            1 => Some(SpecRuleSource::AuraGain),
+            2 => Some(SpecRuleSource::Heal),
+            3 => Some(SpecRuleSource::Cast),
+            _ => None,
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// A spell whose occurrence is evidence that a character of the hero class is specced into the talent tree (0-2).
+/// The weight expresses how reliable the evidence is, e.g. talents that are commonly picked up by other specs weigh less.
+/// The role (1 = Tank, 2 = Healer, 3 = Dps) overrides the role that would be guessed from the talent tree.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+pub struct SpecRule {
+    pub id: u32,
+    pub source: SpecRuleSource,
+    pub spell_name: String,
+    pub hero_class_id: u8,
+    pub talent_tree: u8,
+    pub role: Option<u8>,
+    pub weight: u32,
+}
@@ -0,0 +1,8 @@
+/// The name of a spell as it is shown by a client of another language than English.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct SpellName {
+    pub expansion_id: u8,
+    pub spell_id: u32,
+    pub language_id: u8,
+    pub name: String,
+}
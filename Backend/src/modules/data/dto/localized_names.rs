@@ -0,0 +1,8 @@
+use crate::modules::data::domain_value::{NpcName, SpellName};
+
+/// Spell and NPC names of another language than English, e.g. as they are extracted from the client of that language.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct LocalizedNames {
+    pub spell_names: Vec<SpellName>,
+    pub npc_names: Vec<NpcName>,
+}
@@ -1,7 +1,9 @@
 pub use self::available_server::AvailableServer;
 pub use self::basic_item::BasicItem;
 pub use self::basic_spell::BasicSpell;
+pub use self::localized_names::LocalizedNames;
 
 mod available_server;
 mod basic_item;
 mod basic_spell;
+mod localized_names;
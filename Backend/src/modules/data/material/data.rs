@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, RwLock};
 
 use language::material::Dictionary;
 
@@ -10,7 +11,7 @@ use crate::modules::data::{
     },
     language::init::Init as DictionaryInit,
 };
-use crate::modules::data::domain_value::{Addon, Difficulty, Encounter, EncounterNpc, Map};
+use crate::modules::data::domain_value::{Addon, DataDomain, Difficulty, Encounter, EncounterAddPhase, EncounterDurationVariant, EncounterHardMode, EncounterNpc, EncounterRule, Map, NpcName, Season, SpecRule, SpecRuleSource, SpellName};
 use crate::util::database::*;
 
 #[derive(Debug)]
@@ -21,38 +22,48 @@ pub struct Data {
     pub localization: Vec<HashMap<u32, Localization>>,
     pub races: HashMap<u8, Race>,
     pub professions: HashMap<u16, Profession>,
-    pub servers: RwLock<HashMap<u32, Server>>,
+    pub servers: Arc<RwLock<HashMap<u32, Server>>>,
     pub hero_classes: HashMap<u8, HeroClass>,
-    pub spells: Vec<HashMap<u32, Spell>>,
+    pub spells: Arc<RwLock<Vec<HashMap<u32, Spell>>>>,
+    // Spell names of other languages than English by language and name
+    pub spell_names: Arc<RwLock<HashMap<u8, HashMap<String, Vec<SpellName>>>>>,
     pub dispel_types: HashMap<u8, DispelType>,
     pub power_types: HashMap<u8, PowerType>,
     pub stat_types: HashMap<u8, StatType>,
-    pub spell_effects: Vec<HashMap<u32, Vec<SpellEffect>>>,
-    pub npcs: Vec<HashMap<u32, NPC>>,
+    pub spell_effects: Arc<RwLock<Vec<HashMap<u32, Vec<SpellEffect>>>>>,
+    pub npcs: Arc<RwLock<Vec<HashMap<u32, NPC>>>>,
+    // NPC names of other languages than English by language and name
+    pub npc_names: Arc<RwLock<HashMap<u8, HashMap<String, Vec<NpcName>>>>>,
     pub icons: HashMap<u16, Icon>,
-    pub items: Vec<HashMap<u32, Item>>,
-    pub gems: Vec<HashMap<u32, Gem>>,
-    pub enchants: Vec<HashMap<u32, Enchant>>,
+    pub items: Arc<RwLock<Vec<HashMap<u32, Item>>>>,
+    pub gems: Arc<RwLock<Vec<HashMap<u32, Gem>>>>,
+    pub enchants: Arc<RwLock<Vec<HashMap<u32, Enchant>>>>,
     pub item_bondings: HashMap<u8, ItemBonding>,
     pub item_classes: HashMap<u8, ItemClass>,
-    pub item_damages: Vec<HashMap<u32, Vec<ItemDamage>>>,
+    pub item_damages: Arc<RwLock<Vec<HashMap<u32, Vec<ItemDamage>>>>>,
     pub item_damage_types: HashMap<u8, ItemDamageType>,
-    pub item_effects: Vec<HashMap<u32, Vec<ItemEffect>>>,
+    pub item_effects: Arc<RwLock<Vec<HashMap<u32, Vec<ItemEffect>>>>>,
     pub item_inventory_types: HashMap<u8, ItemInventoryType>,
     pub item_qualities: HashMap<u8, ItemQuality>,
-    pub item_random_properties: Vec<HashMap<i16, ItemRandomProperty>>,
+    pub item_random_properties: Arc<RwLock<Vec<HashMap<i16, ItemRandomProperty>>>>,
     pub item_sheaths: HashMap<u8, ItemSheath>,
-    pub item_sockets: Vec<HashMap<u32, ItemSocket>>,
-    pub item_stats: Vec<HashMap<u32, Vec<ItemStat>>>,
-    pub itemset_names: Vec<HashMap<u16, ItemsetName>>,
-    pub itemset_effects: Vec<HashMap<u16, Vec<ItemsetEffect>>>,
+    pub item_sockets: Arc<RwLock<Vec<HashMap<u32, ItemSocket>>>>,
+    pub item_stats: Arc<RwLock<Vec<HashMap<u32, Vec<ItemStat>>>>>,
+    pub itemset_names: Arc<RwLock<Vec<HashMap<u16, ItemsetName>>>>,
+    pub itemset_effects: Arc<RwLock<Vec<HashMap<u16, Vec<ItemsetEffect>>>>>,
     pub titles: HashMap<u16, Title>,
     pub item_random_property_points: HashMap<u8, Vec<ItemRandomPropertyPoints>>,
-    pub maps: HashMap<u16, Map>,
-    pub difficulties: HashMap<u8, Difficulty>,
-    pub encounters: HashMap<u32, Encounter>,
-    pub encounter_npcs: HashMap<u32, EncounterNpc>,
-    pub addons: HashMap<u32, Addon>,
+    pub maps: Arc<RwLock<HashMap<u16, Map>>>,
+    pub difficulties: Arc<RwLock<HashMap<u8, Difficulty>>>,
+    pub encounters: Arc<RwLock<HashMap<u32, Encounter>>>,
+    pub encounter_npcs: Arc<RwLock<HashMap<u32, EncounterNpc>>>,
+    pub encounter_rules: Arc<RwLock<HashMap<u32, EncounterRule>>>,
+    pub instance_encounters: Arc<RwLock<HashMap<u16, Vec<u32>>>>,
+    pub seasons: Arc<RwLock<HashMap<u32, Season>>>,
+    pub spec_rules: Arc<RwLock<HashMap<String, Vec<SpecRule>>>>,
+    pub addons: Arc<RwLock<HashMap<u32, Addon>>>,
+    // Increased with every reload, such that dependent caches can tell that they are outdated
+    pub generation: Arc<AtomicU64>,
 }
 
 impl Default for Data {
@@ -66,38 +77,45 @@ impl Default for Data {
             localization: Vec::new(),
             races: HashMap::new(),
             professions: HashMap::new(),
-            servers: RwLock::new(HashMap::new()),
+            servers: Arc::new(RwLock::new(HashMap::new())),
             hero_classes: HashMap::new(),
-            spells: Vec::new(),
+            spells: Arc::new(RwLock::new(Vec::new())),
+            spell_names: Arc::new(RwLock::new(HashMap::new())),
             dispel_types: HashMap::new(),
             power_types: HashMap::new(),
             stat_types: HashMap::new(),
-            spell_effects: Vec::new(),
-            npcs: Vec::new(),
+            spell_effects: Arc::new(RwLock::new(Vec::new())),
+            npcs: Arc::new(RwLock::new(Vec::new())),
+            npc_names: Arc::new(RwLock::new(HashMap::new())),
             icons: HashMap::new(),
-            items: Vec::new(),
-            gems: Vec::new(),
-            enchants: Vec::new(),
+            items: Arc::new(RwLock::new(Vec::new())),
+            gems: Arc::new(RwLock::new(Vec::new())),
+            enchants: Arc::new(RwLock::new(Vec::new())),
             item_bondings: HashMap::new(),
             item_classes: HashMap::new(),
-            item_damages: Vec::new(),
+            item_damages: Arc::new(RwLock::new(Vec::new())),
             item_damage_types: HashMap::new(),
-            item_effects: Vec::new(),
+            item_effects: Arc::new(RwLock::new(Vec::new())),
             item_inventory_types: HashMap::new(),
             item_qualities: HashMap::new(),
-            item_random_properties: Vec::new(),
+            item_random_properties: Arc::new(RwLock::new(Vec::new())),
             item_sheaths: HashMap::new(),
-            item_sockets: Vec::new(),
-            item_stats: Vec::new(),
-            itemset_names: Vec::new(),
-            itemset_effects: Vec::new(),
+            item_sockets: Arc::new(RwLock::new(Vec::new())),
+            item_stats: Arc::new(RwLock::new(Vec::new())),
+            itemset_names: Arc::new(RwLock::new(Vec::new())),
+            itemset_effects: Arc::new(RwLock::new(Vec::new())),
             titles: HashMap::new(),
             item_random_property_points: HashMap::new(),
-            maps: HashMap::new(),
-            difficulties: HashMap::new(),
-            encounters: HashMap::new(),
-            encounter_npcs: HashMap::new(),
-            addons: HashMap::new(),
+            maps: Arc::new(RwLock::new(HashMap::new())),
+            difficulties: Arc::new(RwLock::new(HashMap::new())),
+            encounters: Arc::new(RwLock::new(HashMap::new())),
+            encounter_npcs: Arc::new(RwLock::new(HashMap::new())),
+            encounter_rules: Arc::new(RwLock::new(HashMap::new())),
+            instance_encounters: Arc::new(RwLock::new(HashMap::new())),
+            seasons: Arc::new(RwLock::new(HashMap::new())),
+            spec_rules: Arc::new(RwLock::new(HashMap::new())),
+            addons: Arc::new(RwLock::new(HashMap::new())),
+            generation: Arc::new(AtomicU64::new(0)),
         }
     }
 }
@@ -109,43 +127,141 @@ impl Data {
         self.localization.init(db_main);
         self.races.init(db_main);
         self.professions.init(db_main);
-        {
-            let mut servers = self.servers.write().unwrap();
-            (*servers).init(db_main);
-        }
         self.hero_classes.init(db_main);
-        self.spells.init(db_main);
         self.dispel_types.init(db_main);
         self.power_types.init(db_main);
         self.stat_types.init(db_main);
-        self.spell_effects.init(db_main);
-        self.npcs.init(db_main);
         self.icons.init(db_main);
-        self.items.init(db_main);
-        self.gems.init(db_main);
-        self.enchants.init(db_main);
         self.item_bondings.init(db_main);
         self.item_classes.init(db_main);
-        self.item_damages.init(db_main);
         self.item_damage_types.init(db_main);
-        self.item_effects.init(db_main);
         self.item_inventory_types.init(db_main);
         self.item_qualities.init(db_main);
-        self.item_random_properties.init(db_main);
         self.item_sheaths.init(db_main);
-        self.item_sockets.init(db_main);
-        self.item_stats.init(db_main);
-        self.itemset_names.init(db_main);
-        self.itemset_effects.init(db_main);
         self.titles.init(db_main);
         self.item_random_property_points.init(db_main);
-        self.maps.init(db_main);
-        self.difficulties.init(db_main);
-        self.encounters.init(db_main);
-        self.encounter_npcs.init(db_main);
-        self.addons.init(db_main);
+        DataDomain::ALL.iter().for_each(|domain| self.load_domain(*domain, db_main));
         self
     }
+
+    pub fn load_domain(&self, domain: DataDomain, db_main: &mut impl Select) {
+        match domain {
+            DataDomain::Servers => swap(&self.servers, db_main),
+            DataDomain::Spells => {
+                let (spells, spell_names, spell_effects) = (load(db_main), load(db_main), load(db_main));
+                let (mut spells_lock, mut spell_names_lock, mut spell_effects_lock) = (self.spells.write().unwrap(), self.spell_names.write().unwrap(), self.spell_effects.write().unwrap());
+                let _previous_collections = (
+                    std::mem::replace(&mut *spells_lock, spells),
+                    std::mem::replace(&mut *spell_names_lock, spell_names),
+                    std::mem::replace(&mut *spell_effects_lock, spell_effects),
+                );
+            },
+            DataDomain::Npcs => {
+                let (npcs, npc_names) = (load(db_main), load(db_main));
+                let (mut npcs_lock, mut npc_names_lock) = (self.npcs.write().unwrap(), self.npc_names.write().unwrap());
+                let _previous_collections = (std::mem::replace(&mut *npcs_lock, npcs), std::mem::replace(&mut *npc_names_lock, npc_names));
+            },
+            DataDomain::Items => {
+                swap(&self.items, db_main);
+                swap(&self.gems, db_main);
+                swap(&self.enchants, db_main);
+                swap(&self.item_damages, db_main);
+                swap(&self.item_effects, db_main);
+                swap(&self.item_random_properties, db_main);
+                swap(&self.item_sockets, db_main);
+                swap(&self.item_stats, db_main);
+                swap(&self.itemset_names, db_main);
+                swap(&self.itemset_effects, db_main);
+            },
+            DataDomain::Encounters => {
+                // Encounters reference maps, difficulties and npcs of each other, hence readers must never see a partially reloaded domain
+                let (maps, difficulties, encounters, encounter_npcs, encounter_rules, instance_encounters) = (load(db_main), load(db_main), load(db_main), load(db_main), load(db_main), load(db_main));
+                let mut maps_lock = self.maps.write().unwrap();
+                let mut difficulties_lock = self.difficulties.write().unwrap();
+                let mut encounters_lock = self.encounters.write().unwrap();
+                let mut encounter_npcs_lock = self.encounter_npcs.write().unwrap();
+                let mut encounter_rules_lock = self.encounter_rules.write().unwrap();
+                let mut instance_encounters_lock = self.instance_encounters.write().unwrap();
+                let _previous_collections = (
+                    std::mem::replace(&mut *maps_lock, maps),
+                    std::mem::replace(&mut *difficulties_lock, difficulties),
+                    std::mem::replace(&mut *encounters_lock, encounters),
+                    std::mem::replace(&mut *encounter_npcs_lock, encounter_npcs),
+                    std::mem::replace(&mut *encounter_rules_lock, encounter_rules),
+                    std::mem::replace(&mut *instance_encounters_lock, instance_encounters),
+                );
+            },
+            DataDomain::Seasons => swap(&self.seasons, db_main),
+            DataDomain::SpecRules => swap(&self.spec_rules, db_main),
+            DataDomain::Addons => swap(&self.addons, db_main),
+        }
+    }
+
+    /// Another handle on the same reloadable collections, e.g. for background workers, such that reloads reach them too.
+    /// The static collections are copied.
+    pub fn share(&self) -> Self {
+        let dictionary = Dictionary::default();
+        Dictionary::init(&dictionary);
+        Data {
+            dictionary,
+            expansions: self.expansions.clone(),
+            languages: self.languages.clone(),
+            localization: self.localization.clone(),
+            races: self.races.clone(),
+            professions: self.professions.clone(),
+            servers: Arc::clone(&self.servers),
+            hero_classes: self.hero_classes.clone(),
+            spells: Arc::clone(&self.spells),
+            spell_names: Arc::clone(&self.spell_names),
+            dispel_types: self.dispel_types.clone(),
+            power_types: self.power_types.clone(),
+            stat_types: self.stat_types.clone(),
+            spell_effects: Arc::clone(&self.spell_effects),
+            npcs: Arc::clone(&self.npcs),
+            npc_names: Arc::clone(&self.npc_names),
+            icons: self.icons.clone(),
+            items: Arc::clone(&self.items),
+            gems: Arc::clone(&self.gems),
+            enchants: Arc::clone(&self.enchants),
+            item_bondings: self.item_bondings.clone(),
+            item_classes: self.item_classes.clone(),
+            item_damages: Arc::clone(&self.item_damages),
+            item_damage_types: self.item_damage_types.clone(),
+            item_effects: Arc::clone(&self.item_effects),
+            item_inventory_types: self.item_inventory_types.clone(),
+            item_qualities: self.item_qualities.clone(),
+            item_random_properties: Arc::clone(&self.item_random_properties),
+            item_sheaths: self.item_sheaths.clone(),
+            item_sockets: Arc::clone(&self.item_sockets),
+            item_stats: Arc::clone(&self.item_stats),
+            itemset_names: Arc::clone(&self.itemset_names),
+            itemset_effects: Arc::clone(&self.itemset_effects),
+            titles: self.titles.clone(),
+            item_random_property_points: self.item_random_property_points.clone(),
+            maps: Arc::clone(&self.maps),
+            difficulties: Arc::clone(&self.difficulties),
+            encounters: Arc::clone(&self.encounters),
+            encounter_npcs: Arc::clone(&self.encounter_npcs),
+            encounter_rules: Arc::clone(&self.encounter_rules),
+            instance_encounters: Arc::clone(&self.instance_encounters),
+            seasons: Arc::clone(&self.seasons),
+            spec_rules: Arc::clone(&self.spec_rules),
+            addons: Arc::clone(&self.addons),
+            generation: Arc::clone(&self.generation),
+        }
+    }
+}
+
+// The collection is loaded aside, hence readers are only blocked by the swap. The previous collection is dropped after the lock was released.
+fn swap<T: Init + Default>(collection: &RwLock<T>, db_main: &mut impl Select) {
+    let loaded_collection = load(db_main);
+    let _previous_collection = std::mem::replace(&mut *collection.write().unwrap(), loaded_collection);
+}
+
+fn load<T: Init + Default>(db_main: &mut impl Select) -> T {
+    let mut loaded_collection = T::default();
+    loaded_collection.init(db_main);
+    loaded_collection
 }
 
 // Initializer for the collections
@@ -201,7 +317,6 @@ impl Init for HashMap<u8, Language> {
 
 impl Init for Vec<HashMap<u32, Localization>> {
     fn init(&mut self, db_main: &mut impl Select) {
-        let mut last_language_id = 0;
         db_main
             .select("SELECT * FROM data_localization ORDER BY language_id, id", |mut row| Localization {
                 language_id: row.take(0).unwrap(),
@@ -210,9 +325,9 @@ impl Init for Vec<HashMap<u32, Localization>> {
             })
             .into_iter()
             .for_each(|result| {
-                if result.language_id != last_language_id {
+                // Languages without any localization still occupy their index
+                while self.len() < result.language_id as usize {
                     self.push(HashMap::new());
-                    last_language_id = result.language_id;
                 }
                 let localizations = self.get_mut(result.language_id as usize - 1).unwrap();
                 localizations.insert(result.id, result);
@@ -336,6 +451,38 @@ impl Init for Vec<HashMap<u32, Spell>> {
     }
 }
 
+impl Init for HashMap<u8, HashMap<String, Vec<SpellName>>> {
+    fn init(&mut self, db_main: &mut impl Select) {
+        db_main
+            .select("SELECT expansion_id, spell_id, language_id, name FROM data_spell_name", |mut row| SpellName {
+                expansion_id: row.take(0).unwrap(),
+                spell_id: row.take(1).unwrap(),
+                language_id: row.take(2).unwrap(),
+                name: row.take(3).unwrap(),
+            })
+            .into_iter()
+            .for_each(|result| {
+                self.entry(result.language_id).or_insert_with(HashMap::new).entry(result.name.clone()).or_insert_with(Vec::new).push(result);
+            });
+    }
+}
+
+impl Init for HashMap<u8, HashMap<String, Vec<NpcName>>> {
+    fn init(&mut self, db_main: &mut impl Select) {
+        db_main
+            .select("SELECT expansion_id, npc_id, language_id, name FROM data_npc_name", |mut row| NpcName {
+                expansion_id: row.take(0).unwrap(),
+                npc_id: row.take(1).unwrap(),
+                language_id: row.take(2).unwrap(),
+                name: row.take(3).unwrap(),
+            })
+            .into_iter()
+            .for_each(|result| {
+                self.entry(result.language_id).or_insert_with(HashMap::new).entry(result.name.clone()).or_insert_with(Vec::new).push(result);
+            });
+    }
+}
+
 impl Init for HashMap<u8, DispelType> {
     fn init(&mut self, db_main: &mut impl Select) {
         db_main
@@ -423,6 +570,7 @@ impl Init for Vec<HashMap<u32, NPC>> {
                 friend: row.take(4).unwrap(),
                 family: row.take(5).unwrap(),
                 map_id: row.take_opt(6).unwrap().ok(),
+                max_health: row.take_opt(7).unwrap().ok(),
             })
             .into_iter()
             .for_each(|result| {
@@ -951,3 +1099,134 @@ impl Init for HashMap<u32, EncounterNpc> {
             });
     }
 }
+
+impl Init for HashMap<u32, EncounterRule> {
+    fn init(&mut self, db_main: &mut impl Select) {
+        db_main
+            .select(
+                "SELECT encounter_id, variant_encounter_id, min_duration, max_duration FROM data_encounter_duration_variant ORDER BY encounter_id, variant_encounter_id",
+                |mut row| {
+                    (
+                        row.take::<u32, usize>(0).unwrap(),
+                        EncounterDurationVariant {
+                            variant_encounter_id: row.take(1).unwrap(),
+                            min_duration: row.take_opt(2).unwrap().ok(),
+                            max_duration: row.take_opt(3).unwrap().ok(),
+                        },
+                    )
+                },
+            )
+            .into_iter()
+            .for_each(|(encounter_id, duration_variant)| {
+                self.entry(encounter_id).or_insert_with(|| EncounterRule::new(encounter_id)).duration_variants.push(duration_variant);
+            });
+
+        db_main
+            .select(
+                "SELECT encounter_id, hard_mode_encounter_id, min_triggers, max_triggers, max_duration FROM data_encounter_hard_mode ORDER BY encounter_id, hard_mode_encounter_id",
+                |mut row| {
+                    (
+                        row.take::<u32, usize>(0).unwrap(),
+                        EncounterHardMode {
+                            hard_mode_encounter_id: row.take(1).unwrap(),
+                            min_triggers: row.take(2).unwrap(),
+                            max_triggers: row.take_opt(3).unwrap().ok(),
+                            max_duration: row.take_opt(4).unwrap().ok(),
+                        },
+                    )
+                },
+            )
+            .into_iter()
+            .for_each(|(encounter_id, hard_mode)| {
+                self.entry(encounter_id).or_insert_with(|| EncounterRule::new(encounter_id)).hard_modes.push(hard_mode);
+            });
+
+        // Trigger type 0 is an aura gained by a player, 1 the death of a NPC
+        db_main
+            .select("SELECT encounter_id, trigger_type, trigger_id FROM data_encounter_hard_mode_trigger", |mut row| {
+                (row.take::<u32, usize>(0).unwrap(), row.take::<u8, usize>(1).unwrap(), row.take::<u32, usize>(2).unwrap())
+            })
+            .into_iter()
+            .for_each(|(encounter_id, trigger_type, trigger_id)| {
+                let encounter_rule = self.entry(encounter_id).or_insert_with(|| EncounterRule::new(encounter_id));
+                if trigger_type == 0 {
+                    encounter_rule.hard_mode_aura_spell_ids.push(trigger_id);
+                } else {
+                    encounter_rule.hard_mode_npc_ids.push(trigger_id);
+                }
+            });
+
+        db_main
+            .select("SELECT encounter_id, grace_period FROM data_encounter_add_phase", |mut row| {
+                (row.take::<u32, usize>(0).unwrap(), EncounterAddPhase { grace_period: row.take_opt(1).unwrap().ok() })
+            })
+            .into_iter()
+            .for_each(|(encounter_id, add_phase)| {
+                self.entry(encounter_id).or_insert_with(|| EncounterRule::new(encounter_id)).add_phase = Some(add_phase);
+            });
+    }
+}
+
+impl Init for HashMap<u16, Vec<u32>> {
+    fn init(&mut self, db_main: &mut impl Select) {
+        db_main
+            .select("SELECT map_id, encounter_id FROM data_instance_encounter ORDER BY map_id, encounter_id", |mut row| {
+                (row.take::<u16, usize>(0).unwrap(), row.take::<u32, usize>(1).unwrap())
+            })
+            .into_iter()
+            .for_each(|(map_id, encounter_id)| {
+                self.entry(map_id).or_insert_with(Vec::new).push(encounter_id);
+            });
+    }
+}
+
+impl Init for HashMap<u32, Season> {
+    fn init(&mut self, db_main: &mut impl Select) {
+        db_main
+            .select("SELECT id, server_id, name, start_ts, end_ts FROM data_season", |mut row| Season {
+                id: row.take(0).unwrap(),
+                server_id: row.take(1).unwrap(),
+                name: row.take(2).unwrap(),
+                start_ts: row.take(3).unwrap(),
+                end_ts: row.take_opt(4).unwrap().ok(),
+                map_ids: Vec::new(),
+            })
+            .into_iter()
+            .for_each(|result| {
+                self.insert(result.id, result);
+            });
+
+        db_main
+            .select("SELECT season_id, map_id FROM data_season_map ORDER BY season_id, map_id", |mut row| {
+                (row.take::<u32, usize>(0).unwrap(), row.take::<u16, usize>(1).unwrap())
+            })
+            .into_iter()
+            .for_each(|(season_id, map_id)| {
+                if let Some(season) = self.get_mut(&season_id) {
+                    season.map_ids.push(map_id);
+                }
+            });
+    }
+}
+
+impl Init for HashMap<String, Vec<SpecRule>> {
+    fn init(&mut self, db_main: &mut impl Select) {
+        db_main
+            .select("SELECT id, source, spell_name, hero_class_id, talent_tree, role, weight FROM data_spec_rule", |mut row| {
+                SpecRuleSource::new(row.take(1).unwrap()).map(|source| SpecRule {
+                    id: row.take(0).unwrap(),
+                    source,
+                    spell_name: row.take(2).unwrap(),
+                    hero_class_id: row.take(3).unwrap(),
+                    talent_tree: row.take(4).unwrap(),
+                    role: row.take_opt(5).unwrap().ok(),
+                    weight: row.take(6).unwrap(),
+                })
+            })
+            .into_iter()
+            .flatten()
+            .for_each(|result| {
+                self.entry(result.spell_name.clone()).or_insert_with(Vec::new).push(result);
+            });
+    }
+}
@@ -3,14 +3,14 @@ use crate::modules::data::{tools::RetrieveDifficulty, Data};
 
 #[test]
 fn get_difficulty() {
-    let mut data = Data::default();
+    let data = Data::default();
     let difficulty_id = 1;
     let difficulty = Difficulty {
         id: difficulty_id,
         localization_id: 3242,
         icon: "".to_string(),
     };
-    data.difficulties.insert(difficulty_id, difficulty.clone());
+    data.difficulties.write().unwrap().insert(difficulty_id, difficulty.clone());
 
     let difficulty_res = data.get_difficulty(difficulty_id);
     assert!(difficulty_res.is_some());
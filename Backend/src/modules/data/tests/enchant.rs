@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_enchant() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let enchant_id = 1;
     let enchant = Enchant {
@@ -15,7 +15,7 @@ fn get_enchant() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(enchant_id, enchant.clone());
-    data.enchants.push(hashmap);
+    data.enchants.write().unwrap().push(hashmap);
 
     let enchant_res = data.get_enchant(expansion_id, enchant_id);
     assert!(enchant_res.is_some());
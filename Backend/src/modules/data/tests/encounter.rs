@@ -3,7 +3,7 @@ use crate::modules::data::{tools::RetrieveEncounter, Data};
 
 #[test]
 fn get_encounter() {
-    let mut data = Data::default();
+    let data = Data::default();
     let encounter_id = 1;
     let encounter = Encounter {
         id: encounter_id,
@@ -11,7 +11,7 @@ fn get_encounter() {
         map_id: 32,
         retail_id: None,
     };
-    data.encounters.insert(encounter_id, encounter.clone());
+    data.encounters.write().unwrap().insert(encounter_id, encounter.clone());
 
     let encounter_res = data.get_encounter(encounter_id);
     assert!(encounter_res.is_some());
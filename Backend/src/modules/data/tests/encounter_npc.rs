@@ -3,7 +3,7 @@ use crate::modules::data::{tools::RetrieveEncounterNpc, Data};
 
 #[test]
 fn get_encounter_npc() {
-    let mut data = Data::default();
+    let data = Data::default();
     let encounter_npc_id = 1;
     let encounter_npc = EncounterNpc {
         encounter_id: 42,
@@ -13,7 +13,7 @@ fn get_encounter_npc() {
         is_pivot: true,
         health_treshold: Some(2),
     };
-    data.encounter_npcs.insert(encounter_npc_id, encounter_npc.clone());
+    data.encounter_npcs.write().unwrap().insert(encounter_npc_id, encounter_npc.clone());
 
     let encounter_npc_res = data.get_encounter_npc(encounter_npc_id);
     assert!(encounter_npc_res.is_some());
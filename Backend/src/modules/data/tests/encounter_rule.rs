@@ -0,0 +1,206 @@
+use crate::modules::data::domain_value::{Encounter, EncounterAddPhase, EncounterDurationVariant, EncounterHardMode, EncounterRule};
+use crate::modules::data::{tools::RetrieveEncounterRule, Data};
+use crate::modules::live_data_processor::material::Attempt;
+use crate::modules::live_data_processor::tools::server::server_post_processing::apply_encounter_rules;
+
+fn duration_variant(variant_encounter_id: u32, min_duration: Option<u64>, max_duration: Option<u64>) -> EncounterDurationVariant {
+    EncounterDurationVariant {
+        variant_encounter_id,
+        min_duration,
+        max_duration,
+    }
+}
+
+fn hard_mode(hard_mode_encounter_id: u32, min_triggers: u8, max_triggers: Option<u8>, max_duration: Option<u64>) -> EncounterHardMode {
+    EncounterHardMode {
+        hard_mode_encounter_id,
+        min_triggers,
+        max_triggers,
+        max_duration,
+    }
+}
+
+// Mirrors the rules of the migration that introduced them
+fn data_with_existing_rules() -> Data {
+    let data = Data::default();
+    let add_rule = |encounter_rule: EncounterRule| {
+        data.encounter_rules.write().unwrap().insert(encounter_rule.encounter_id, encounter_rule);
+    };
+
+    for (encounter_id, duration_variants) in [
+        (42, vec![duration_variant(60, None, Some(119999))]),
+        (54, vec![duration_variant(61, None, Some(179999))]),
+        (57, vec![duration_variant(62, None, Some(209999)), duration_variant(63, Some(360001), None)]),
+        (29, vec![duration_variant(64, None, Some(179999))]),
+    ]
+    .iter()
+    {
+        let mut encounter_rule = EncounterRule::new(*encounter_id);
+        encounter_rule.duration_variants = duration_variants.clone();
+        encounter_rule.add_phase = Some(EncounterAddPhase { grace_period: Some(30000) });
+        add_rule(encounter_rule);
+    }
+    for encounter_id in [22, 17, 46].iter() {
+        let mut encounter_rule = EncounterRule::new(*encounter_id);
+        encounter_rule.add_phase = Some(EncounterAddPhase { grace_period: Some(30000) });
+        add_rule(encounter_rule);
+    }
+    let mut encounter_rule = EncounterRule::new(207);
+    encounter_rule.add_phase = Some(EncounterAddPhase { grace_period: None });
+    add_rule(encounter_rule);
+
+    for (encounter_id, hard_modes) in [
+        (114, vec![hard_mode(146, 1, Some(1), None), hard_mode(147, 2, Some(2), None), hard_mode(148, 3, Some(3), None), hard_mode(149, 4, Some(4), None)]),
+        (122, vec![hard_mode(152, 1, Some(1), None), hard_mode(153, 2, Some(2), None), hard_mode(154, 3, Some(3), None)]),
+        (121, vec![hard_mode(156, 1, None, None)]),
+        (123, vec![hard_mode(155, 1, None, None)]),
+        (124, vec![hard_mode(162, 0, None, Some(122000))]),
+        (125, vec![hard_mode(157, 1, None, None)]),
+        (126, vec![hard_mode(158, 3, Some(3), None), hard_mode(159, 2, Some(2), None), hard_mode(160, 1, Some(1), None), hard_mode(161, 0, Some(0), None)]),
+    ]
+    .iter()
+    {
+        let mut encounter_rule = EncounterRule::new(*encounter_id);
+        encounter_rule.hard_modes = hard_modes.clone();
+        if *encounter_id == 126 {
+            encounter_rule.hard_mode_aura_spell_ids = vec![62670, 62650, 62671, 62702];
+        }
+        add_rule(encounter_rule);
+    }
+
+    for encounter_id in [17, 22, 29, 42, 46, 54, 57, 60, 61, 62, 63, 64, 114, 121, 122, 123, 124, 125, 126, 146, 147, 148, 149, 152, 153, 154, 155, 156, 157, 158, 159, 160, 161, 162, 207].iter() {
+        data.encounters.write().unwrap().insert(
+            *encounter_id,
+            Encounter {
+                id: *encounter_id,
+                localization_id: 0,
+                map_id: 0,
+                retail_id: None,
+            },
+        );
+    }
+    data
+}
+
+fn attempt(encounter_id: u32, duration: u64, found_buffs: Vec<u32>) -> Attempt {
+    let mut attempt = Attempt::new(encounter_id, 1000, false);
+    attempt.end_ts = 1000 + duration;
+    attempt.hard_mode_found_buffs = found_buffs.into_iter().collect();
+    attempt
+}
+
+#[test]
+fn get_encounter_rule() {
+    let data = data_with_existing_rules();
+    let encounter_rule = data.get_encounter_rule(57);
+    assert!(encounter_rule.is_some());
+    assert_eq!(encounter_rule.unwrap().duration_variants.len(), 2);
+    assert!(data.get_encounter_rule(0).is_none());
+}
+
+#[test]
+fn get_all_encounter_rules() {
+    let data = Data::default();
+    let encounter_rules = data.get_all_encounter_rules();
+    assert!(encounter_rules.is_empty());
+}
+
+#[test]
+fn existing_rules_are_valid() {
+    let data = data_with_existing_rules();
+    assert!(data.validate_encounter_rules().is_empty());
+}
+
+#[test]
+fn validate_encounter_rules_reports_ambiguous_rules() {
+    let data = data_with_existing_rules();
+    let mut encounter_rule = EncounterRule::new(42);
+    encounter_rule.duration_variants = vec![duration_variant(60, None, Some(119999)), duration_variant(63, Some(100000), None), duration_variant(64, None, None)];
+    encounter_rule.hard_modes = vec![hard_mode(146, 1, None, None), hard_mode(147, 2, Some(2), None), hard_mode(1000, 3, Some(2), None)];
+    data.encounter_rules.write().unwrap().insert(42, encounter_rule);
+
+    let issues = data.validate_encounter_rules();
+    assert_eq!(
+        issues,
+        vec![
+            "Encounter 42: Variants 60 and 63 overlap".to_string(),
+            "Encounter 42: Variants 60 and 64 overlap".to_string(),
+            "Encounter 42: Variants 63 and 64 overlap".to_string(),
+            "Encounter 42: Variant 64 has no duration bounds".to_string(),
+            "Encounter 42: Hard modes 146 and 147 overlap".to_string(),
+            "Encounter 42: Unknown hard mode encounter 1000".to_string(),
+            "Encounter 42: Hard mode 1000 can never apply".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn duration_variants() {
+    let data = data_with_existing_rules();
+    assert_eq!(data.get_encounter_duration_variant(42, 119999), Some(60));
+    assert_eq!(data.get_encounter_duration_variant(42, 120000), None);
+    assert_eq!(data.get_encounter_duration_variant(54, 179999), Some(61));
+    assert_eq!(data.get_encounter_duration_variant(57, 209999), Some(62));
+    assert_eq!(data.get_encounter_duration_variant(57, 300000), None);
+    assert_eq!(data.get_encounter_duration_variant(57, 360000), None);
+    assert_eq!(data.get_encounter_duration_variant(57, 360001), Some(63));
+    assert_eq!(data.get_encounter_duration_variant(29, 100000), Some(64));
+    assert_eq!(data.get_encounter_duration_variant(1, 100000), None);
+}
+
+#[test]
+fn hard_modes() {
+    let data = data_with_existing_rules();
+    assert_eq!(data.get_hard_mode_encounter_id(114, 0, 600000), None);
+    assert_eq!(data.get_hard_mode_encounter_id(114, 4, 600000), Some(149));
+    assert_eq!(data.get_hard_mode_encounter_id(122, 2, 600000), Some(153));
+    assert_eq!(data.get_hard_mode_encounter_id(122, 4, 600000), None);
+    assert_eq!(data.get_hard_mode_encounter_id(121, 3, 600000), Some(156));
+    assert_eq!(data.get_hard_mode_encounter_id(123, 0, 600000), None);
+    assert_eq!(data.get_hard_mode_encounter_id(124, 0, 122000), Some(162));
+    assert_eq!(data.get_hard_mode_encounter_id(124, 0, 122001), None);
+    assert_eq!(data.get_hard_mode_encounter_id(125, 1, 600000), Some(157));
+    assert_eq!(data.get_hard_mode_encounter_id(126, 0, 600000), Some(161));
+    assert_eq!(data.get_hard_mode_encounter_id(126, 3, 600000), Some(158));
+    assert_eq!(data.get_hard_mode_encounter_id(126, 4, 600000), None);
+}
+
+#[test]
+fn hard_mode_triggers_and_add_phases() {
+    let data = data_with_existing_rules();
+    assert!(data.is_hard_mode_aura(126, 62670));
+    assert!(!data.is_hard_mode_aura(125, 62670));
+    assert!(!data.is_hard_mode_npc(126, 62670));
+
+    for encounter_id in [42, 29, 22, 17, 57, 54, 46].iter() {
+        assert!(data.encounter_has_add_phase(*encounter_id));
+        assert_eq!(data.get_add_phase_grace_period(*encounter_id), Some(30000));
+    }
+    assert!(data.encounter_has_add_phase(207));
+    assert_eq!(data.get_add_phase_grace_period(207), None);
+    assert!(!data.encounter_has_add_phase(126));
+}
+
+#[test]
+fn apply_existing_encounter_rules() {
+    let data = data_with_existing_rules();
+
+    let mut kel_thuzad = attempt(57, 400000, vec![]);
+    apply_encounter_rules(&data, &mut kel_thuzad);
+    assert_eq!(kel_thuzad.encounter_id, 63);
+    assert_eq!(kel_thuzad.hard_mode_encounter_id, None);
+
+    let mut yogg_saron = attempt(126, 600000, vec![62670, 62650]);
+    apply_encounter_rules(&data, &mut yogg_saron);
+    assert_eq!(yogg_saron.encounter_id, 126);
+    assert_eq!(yogg_saron.hard_mode_encounter_id, Some(159));
+
+    let mut freya = attempt(122, 600000, vec![]);
+    freya.hard_mode_npcs_died.insert(32915);
+    apply_encounter_rules(&data, &mut freya);
+    assert_eq!(freya.hard_mode_encounter_id, Some(152));
+
+    let mut hodir = attempt(124, 121000, vec![]);
+    apply_encounter_rules(&data, &mut hodir);
+    assert_eq!(hodir.hard_mode_encounter_id, Some(162));
+}
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_gem() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 2;
     let item_id = 22459;
     let gem = Gem {
@@ -15,7 +15,7 @@ fn get_gem() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(item_id, gem.clone());
-    data.gems.push(hashmap);
+    data.gems.write().unwrap().push(hashmap);
 
     let gem_res = data.get_gem(expansion_id, item_id);
     assert!(gem_res.is_some());
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_item() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let item_id = 25;
     let item = Item {
@@ -26,7 +26,7 @@ fn get_item() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(item_id, item.clone());
-    data.items.push(hashmap);
+    data.items.write().unwrap().push(hashmap);
 
     let item_res = data.get_item(expansion_id, item_id);
     assert!(item_res.is_some());
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_item_damage() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let item_id = 25;
     let item_damage = ItemDamage {
@@ -17,7 +17,7 @@ fn get_item_damage() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(item_id, vec![item_damage.clone()]);
-    data.item_damages.push(hashmap);
+    data.item_damages.write().unwrap().push(hashmap);
 
     let item_damage_res_vec = data.get_item_damage(expansion_id, item_id);
     assert!(item_damage_res_vec.is_some());
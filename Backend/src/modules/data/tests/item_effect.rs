@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_item_effect() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let item_id = 117;
     let item_effect = ItemEffect {
@@ -15,7 +15,7 @@ fn get_item_effect() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(item_id, vec![item_effect.clone()]);
-    data.item_effects.push(hashmap);
+    data.item_effects.write().unwrap().push(hashmap);
 
     let item_effect_res = data.get_item_effect(expansion_id, item_id);
     assert!(item_effect_res.is_some());
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_item_random_property() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let item_random_property_id = 5;
     let item_random_property = ItemRandomProperty {
@@ -16,7 +16,7 @@ fn get_item_random_property() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(item_random_property_id, item_random_property.clone());
-    data.item_random_properties.push(hashmap);
+    data.item_random_properties.write().unwrap().push(hashmap);
 
     let item_random_property_res = data.get_item_random_property(expansion_id, item_random_property_id);
     assert!(item_random_property_res.is_some());
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_item_socket() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 2;
     let item_id = 21846;
     let item_socket = ItemSocket {
@@ -15,7 +15,7 @@ fn get_item_socket() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(item_id, item_socket.clone());
-    data.item_sockets.push(hashmap);
+    data.item_sockets.write().unwrap().push(hashmap);
 
     let item_socket_res = data.get_item_socket(expansion_id, item_id);
     assert!(item_socket_res.is_some());
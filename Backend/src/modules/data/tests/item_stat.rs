@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_item_stats() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let item_id = 940;
     let item_stat = ItemStat {
@@ -15,7 +15,7 @@ fn get_item_stats() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(item_id, vec![item_stat.clone()]);
-    data.item_stats.push(hashmap);
+    data.item_stats.write().unwrap().push(hashmap);
 
     let item_stats = data.get_item_stats(expansion_id, item_id);
     assert!(item_stats.is_some());
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_itemset_effects() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let itemset_id = 1;
     let itemset_effect = ItemsetEffect {
@@ -16,7 +16,7 @@ fn get_itemset_effects() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(itemset_id, vec![itemset_effect.clone()]);
-    data.itemset_effects.push(hashmap);
+    data.itemset_effects.write().unwrap().push(hashmap);
 
     let itemset_effects = data.get_itemset_effects(expansion_id, itemset_id);
     assert!(itemset_effects.is_some());
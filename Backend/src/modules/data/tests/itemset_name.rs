@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_itemset_name() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let itemset_id = 1;
     let itemset_name = ItemsetName {
@@ -14,7 +14,7 @@ fn get_itemset_name() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(itemset_id, itemset_name.clone());
-    data.itemset_names.push(hashmap);
+    data.itemset_names.write().unwrap().push(hashmap);
 
     let itemset_name_res = data.get_itemset_name(expansion_id, itemset_id);
     assert!(itemset_name_res.is_some());
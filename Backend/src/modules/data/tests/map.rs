@@ -3,7 +3,7 @@ use crate::modules::data::{tools::RetrieveMap, Data};
 
 #[test]
 fn get_map() {
-    let mut data = Data::default();
+    let data = Data::default();
     let map_id = 1;
     let map = Map {
         id: map_id,
@@ -11,7 +11,7 @@ fn get_map() {
         map_type: 1,
         icon: "".to_string(),
     };
-    data.maps.insert(map_id, map.clone());
+    data.maps.write().unwrap().insert(map_id, map.clone());
 
     let map_res = data.get_map(map_id);
     assert!(map_res.is_some());
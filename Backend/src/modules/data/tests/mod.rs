@@ -3,6 +3,7 @@ mod dispel_type;
 mod enchant;
 mod encounter;
 mod encounter_npc;
+mod encounter_rule;
 mod expansion;
 mod gem;
 mod hero_class;
@@ -29,9 +30,13 @@ mod npc;
 mod power_type;
 mod profession;
 mod race;
+mod reload;
+mod season;
 mod server;
+mod spec_rule;
 mod spell;
 mod spell_description;
 mod spell_effect;
+mod spell_name;
 mod stat_type;
 mod title;
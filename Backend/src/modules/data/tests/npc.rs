@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_npc() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let npc_id = 1;
     let npc = NPC {
@@ -15,10 +15,11 @@ fn get_npc() {
         friend: 1,
         family: 2,
         map_id: None,
+        max_health: None,
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(npc_id, npc.clone());
-    data.npcs.push(hashmap);
+    data.npcs.write().unwrap().push(hashmap);
 
     let npc_res = data.get_npc(expansion_id, npc_id);
     assert!(npc_res.is_some());
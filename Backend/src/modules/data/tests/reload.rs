@@ -0,0 +1,53 @@
+use crate::modules::data::domain_value::{Addon, DataDomain, Encounter};
+use crate::modules::data::tools::{ReloadData, RetrieveAddon, RetrieveEncounter};
+use crate::modules::data::Data;
+use crate::tests::TestContainer;
+
+fn addon(id: u32) -> Addon {
+    Addon {
+        id,
+        expansion_id: 1,
+        addon_name: "Custom".to_string(),
+        addon_desc: "Custom addon".to_string(),
+        url_name: "custom".to_string(),
+    }
+}
+
+fn encounter(id: u32) -> Encounter {
+    Encounter {
+        id,
+        localization_id: 0,
+        map_id: 0,
+        retail_id: None,
+    }
+}
+
+#[test]
+fn share_data() {
+    let data = Data::default();
+    let shared_data = data.share();
+    data.addons.write().unwrap().insert(4000000, addon(4000000));
+
+    assert_eq!(shared_data.get_addon(4000000), Some(addon(4000000)));
+    assert_eq!(shared_data.get_generation(), data.get_generation());
+}
+
+#[test]
+fn reload_data() {
+    let container = TestContainer::new(true);
+    let (mut conn, _dns, _node) = container.run();
+
+    let data = Data::default().init(&mut conn);
+    let shared_data = data.share();
+    data.addons.write().unwrap().insert(4000000, addon(4000000));
+    data.encounters.write().unwrap().insert(4000000, encounter(4000000));
+
+    assert_eq!(data.reload_data(&mut conn, &[DataDomain::Addons, DataDomain::Addons]), vec![DataDomain::Addons]);
+    assert!(shared_data.get_addon(4000000).is_none());
+    assert_eq!(shared_data.get_encounter(4000000), Some(encounter(4000000)));
+    assert_eq!(shared_data.get_generation(), 1);
+
+    assert_eq!(data.reload_data(&mut conn, &[]), DataDomain::ALL.to_vec());
+    assert!(shared_data.get_encounter(4000000).is_none());
+    assert_eq!(shared_data.get_generation(), 2);
+}
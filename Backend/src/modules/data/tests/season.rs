@@ -0,0 +1,63 @@
+use crate::modules::data::domain_value::Season;
+use crate::modules::data::{tools::RetrieveSeason, Data};
+
+fn season(id: u32, server_id: u32, start_ts: u64, end_ts: Option<u64>, map_ids: Vec<u16>) -> Season {
+    Season {
+        id,
+        server_id,
+        name: format!("Season {}", id),
+        start_ts,
+        end_ts,
+        map_ids,
+    }
+}
+
+#[test]
+fn get_season() {
+    let data = Data::default();
+    let season = season(1, 0, 1000, Some(1999), Vec::new());
+    data.seasons.write().unwrap().insert(season.id, season.clone());
+
+    let season_res = data.get_season(1);
+    assert!(season_res.is_some());
+    assert_eq!(season_res.unwrap(), season);
+    let no_season = data.get_season(0);
+    assert!(no_season.is_none());
+}
+
+#[test]
+fn get_seasons_by_server() {
+    let data = Data::default();
+    data.seasons.write().unwrap().insert(3, season(3, 2, 3000, None, Vec::new()));
+    data.seasons.write().unwrap().insert(2, season(2, 1, 2000, None, Vec::new()));
+    data.seasons.write().unwrap().insert(1, season(1, 0, 1000, Some(1999), Vec::new()));
+
+    let seasons = data.get_all_seasons();
+    assert_eq!(seasons.iter().map(|season| season.id).collect::<Vec<u32>>(), vec![1, 2, 3]);
+    let seasons = data.get_seasons_by_server(1);
+    assert_eq!(seasons.iter().map(|season| season.id).collect::<Vec<u32>>(), vec![1, 2]);
+}
+
+#[test]
+fn find_season() {
+    let data = Data::default();
+    assert!(data.find_season(1, 409, 1500).is_none());
+
+    data.seasons.write().unwrap().insert(1, season(1, 0, 1000, Some(1999), Vec::new()));
+    data.seasons.write().unwrap().insert(2, season(2, 1, 1000, None, vec![409]));
+    data.seasons.write().unwrap().insert(3, season(3, 1, 1500, Some(2999), vec![409]));
+
+    assert_eq!(data.find_season(2, 409, 1000), Some(1));
+    assert_eq!(data.find_season(2, 409, 1999), Some(1));
+    assert!(data.find_season(2, 409, 2000).is_none());
+    assert!(data.find_season(2, 409, 999).is_none());
+
+    // Seasons of the server precede seasons of all servers, later seasons precede earlier ones
+    assert_eq!(data.find_season(1, 409, 1200), Some(2));
+    assert_eq!(data.find_season(1, 409, 1500), Some(3));
+    assert_eq!(data.find_season(1, 409, 3000), Some(2));
+
+    // Maps that are not included in the season
+    assert_eq!(data.find_season(1, 249, 1500), Some(1));
+    assert!(data.find_season(1, 249, 2500).is_none());
+}
@@ -0,0 +1,40 @@
+use crate::modules::data::domain_value::{SpecRule, SpecRuleSource};
+use crate::modules::data::{tools::RetrieveSpecRule, Data};
+
+fn spec_rule(id: u32, source: SpecRuleSource, spell_name: &str, hero_class_id: u8) -> SpecRule {
+    SpecRule {
+        id,
+        source,
+        spell_name: spell_name.to_string(),
+        hero_class_id,
+        talent_tree: 1,
+        role: None,
+        weight: 10,
+    }
+}
+
+#[test]
+fn get_spec_rules() {
+    let data = Data::default();
+    for spec_rule in vec![
+        spec_rule(3, SpecRuleSource::Cast, "Berserk", 1),
+        spec_rule(2, SpecRuleSource::AuraGain, "Berserk", 11),
+        spec_rule(1, SpecRuleSource::Cast, "Bloodthirst", 1),
+    ] {
+        data.spec_rules.write().unwrap().entry(spec_rule.spell_name.clone()).or_insert_with(Vec::new).push(spec_rule);
+    }
+
+    assert_eq!(data.get_spec_rules(SpecRuleSource::AuraGain, "Berserk"), vec![spec_rule(2, SpecRuleSource::AuraGain, "Berserk", 11)]);
+    assert_eq!(data.get_spec_rules(SpecRuleSource::Cast, "Berserk"), vec![spec_rule(3, SpecRuleSource::Cast, "Berserk", 1)]);
+    assert!(data.get_spec_rules(SpecRuleSource::Heal, "Berserk").is_empty());
+    assert!(data.get_spec_rules(SpecRuleSource::Cast, "Mortal Strike").is_empty());
+    assert_eq!(data.get_all_spec_rules().iter().map(|spec_rule| spec_rule.id).collect::<Vec<u32>>(), vec![1, 2, 3]);
+}
+
+#[test]
+fn spec_rule_source() {
+    for source in [SpecRuleSource::AuraGain, SpecRuleSource::Heal, SpecRuleSource::Cast].iter() {
+        assert_eq!(SpecRuleSource::new(source.to_u8()), Some(*source));
+    }
+    assert_eq!(SpecRuleSource::new(0), None);
+}
@@ -4,16 +4,18 @@ use std::collections::HashMap;
 
 #[test]
 fn get_spell() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let spell_id = 1;
     let spell = Spell {
         id: spell_id,
         expansion_id,
         name: "test".parse().unwrap(),
+        subtext: String::new(),
         description: "test".parse().unwrap(),
+        aura: String::new(),
         cost: 0,
-        cost_in_percent: false,
+        cost_in_percent: 0,
         power_type: 0,
         cast_time: 0,
         school_mask: 0,
@@ -25,7 +27,7 @@ fn get_spell() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(spell_id, spell.clone());
-    data.spells.push(hashmap);
+    data.spells.write().unwrap().push(hashmap);
 
     let spell_res = data.get_spell(expansion_id, spell_id);
     assert!(spell_res.is_some());
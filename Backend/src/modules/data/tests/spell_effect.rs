@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 #[test]
 fn get_spell_effects() {
-    let mut data = Data::default();
+    let data = Data::default();
     let expansion_id = 1;
     let spell_id = 1;
     let spell_effect = SpellEffect {
@@ -18,7 +18,7 @@ fn get_spell_effects() {
     };
     let mut hashmap = HashMap::new();
     hashmap.insert(spell_id, vec![spell_effect.clone()]);
-    data.spell_effects.push(hashmap);
+    data.spell_effects.write().unwrap().push(hashmap);
 
     let spell_effects = data.get_spell_effects(expansion_id, spell_id);
     assert!(spell_effects.is_some());
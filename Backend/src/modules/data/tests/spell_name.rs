@@ -0,0 +1,108 @@
+use crate::modules::data::domain_value::{Localization, NpcName, Spell, SpellName, NPC};
+use crate::modules::data::tools::{RetrieveNPC, RetrieveSpell};
+use crate::modules::data::Data;
+use std::collections::HashMap;
+
+fn spell(id: u32, name: &str) -> Spell {
+    Spell {
+        id,
+        expansion_id: 1,
+        name: name.to_string(),
+        subtext: String::new(),
+        cost: 0,
+        cost_in_percent: 0,
+        power_type: 0,
+        cast_time: 0,
+        school_mask: 4,
+        dispel_type: 0,
+        range_max: 0,
+        cooldown: 0,
+        duration: 0,
+        icon: 0,
+        description: String::new(),
+        aura: String::new(),
+    }
+}
+
+#[test]
+fn get_spell_by_localized_name() {
+    let data = Data::default();
+    let mut spells = HashMap::new();
+    spells.insert(133, spell(133, "Fireball"));
+    spells.insert(116, spell(116, "Frostbolt"));
+    data.spells.write().unwrap().push(spells);
+    let spell_name = SpellName {
+        expansion_id: 1,
+        spell_id: 133,
+        language_id: 2,
+        name: "Feuerball".to_string(),
+    };
+    data.spell_names.write().unwrap().entry(2).or_insert_with(HashMap::new).insert(spell_name.name.clone(), vec![spell_name]);
+
+    assert_eq!(data.get_spell_by_localized_name(1, 2, &"Feuerball".to_string()).map(|spell| spell.id), Some(133));
+    // Falls back to the English name
+    assert_eq!(data.get_spell_by_localized_name(1, 2, &"Frostbolt".to_string()).map(|spell| spell.id), Some(116));
+    assert_eq!(data.get_spell_by_localized_name(1, 1, &"Feuerball".to_string()), None);
+}
+
+#[test]
+fn get_npc_by_localized_name() {
+    let mut data = Data::default();
+    let npc = NPC {
+        expansion_id: 1,
+        id: 11502,
+        localization_id: 1,
+        is_boss: true,
+        friend: 0,
+        family: 0,
+        map_id: Some(409),
+        max_health: None,
+    };
+    let mut npcs = HashMap::new();
+    npcs.insert(npc.id, npc.clone());
+    data.npcs.write().unwrap().push(npcs);
+    for (language_id, content) in [(1, "Ragnaros"), (2, "Ragnaros der Feuerfürst")].iter() {
+        let mut localizations = HashMap::new();
+        localizations.insert(
+            1,
+            Localization {
+                id: 1,
+                language_id: *language_id,
+                content: content.to_string(),
+            },
+        );
+        data.localization.push(localizations);
+    }
+
+    assert_eq!(data.get_npc_by_localized_name(1, 2, &"Ragnaros der Feuerfürst".to_string()), Some(npc.clone()));
+    assert_eq!(data.get_npc_by_name(1, &"Ragnaros".to_string()), Some(npc));
+    assert_eq!(data.get_npc_by_localized_name(1, 1, &"Ragnaros der Feuerfürst".to_string()), None);
+}
+
+#[test]
+fn get_npc_by_imported_localized_name() {
+    let data = Data::default();
+    let npc = NPC {
+        expansion_id: 1,
+        id: 11988,
+        localization_id: 1,
+        is_boss: true,
+        friend: 0,
+        family: 0,
+        map_id: Some(409),
+        max_health: None,
+    };
+    let mut npcs = HashMap::new();
+    npcs.insert(npc.id, npc.clone());
+    data.npcs.write().unwrap().push(npcs);
+    let npc_name = NpcName {
+        expansion_id: 1,
+        npc_id: 11988,
+        language_id: 2,
+        name: "Golemagg der Verbrenner".to_string(),
+    };
+    data.npc_names.write().unwrap().entry(2).or_insert_with(HashMap::new).insert(npc_name.name.clone(), vec![npc_name]);
+
+    assert_eq!(data.get_npc_by_localized_name(1, 2, &"Golemagg der Verbrenner".to_string()), Some(npc));
+    assert_eq!(data.get_npc_by_localized_name(1, 3, &"Golemagg der Verbrenner".to_string()), None);
+}
@@ -8,10 +8,10 @@ pub trait RetrieveAddon {
 
 impl RetrieveAddon for Data {
     fn get_addon(&self, id: u32) -> Option<Addon> {
-        self.addons.get(&id).cloned()
+        self.addons.read().unwrap().get(&id).cloned()
     }
 
     fn get_all_addons(&self) -> Vec<Addon> {
-        self.addons.iter().map(|(_, addon)| addon.clone()).collect()
+        self.addons.read().unwrap().iter().map(|(_, addon)| addon.clone()).collect()
     }
 }
@@ -8,10 +8,10 @@ pub trait RetrieveDifficulty {
 
 impl RetrieveDifficulty for Data {
     fn get_difficulty(&self, id: u8) -> Option<Difficulty> {
-        self.difficulties.get(&id).cloned()
+        self.difficulties.read().unwrap().get(&id).cloned()
     }
 
     fn get_all_difficulties(&self) -> Vec<Difficulty> {
-        self.difficulties.iter().map(|(_, difficulty)| difficulty.clone()).collect()
+        self.difficulties.read().unwrap().iter().map(|(_, difficulty)| difficulty.clone()).collect()
     }
 }
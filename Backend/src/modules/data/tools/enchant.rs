@@ -10,6 +10,6 @@ impl RetrieveEnchant for Data {
             return None;
         }
 
-        self.enchants.get(expansion_id as usize - 1).and_then(|map| map.get(&enchant_id).cloned())
+        self.enchants.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&enchant_id).cloned())
     }
 }
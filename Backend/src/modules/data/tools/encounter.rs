@@ -8,14 +8,14 @@ pub trait RetrieveEncounter {
 
 impl RetrieveEncounter for Data {
     fn get_encounter(&self, id: u32) -> Option<Encounter> {
-        self.encounters.get(&id).cloned()
+        self.encounters.read().unwrap().get(&id).cloned()
     }
 
     fn get_encounter_by_retail_id(&self, retail_id: u32) -> Option<Encounter> {
-        self.encounters.iter().find(|(_, encounter)| encounter.retail_id.contains(&retail_id)).map(|(_, encounter)| encounter.clone())
+        self.encounters.read().unwrap().iter().find(|(_, encounter)| encounter.retail_id.contains(&retail_id)).map(|(_, encounter)| encounter.clone())
     }
 
     fn get_all_encounters(&self) -> Vec<Encounter> {
-        self.encounters.iter().map(|(_, encounter)| encounter.clone()).collect()
+        self.encounters.read().unwrap().iter().map(|(_, encounter)| encounter.clone()).collect()
     }
 }
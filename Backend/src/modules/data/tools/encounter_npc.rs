@@ -9,15 +9,17 @@ pub trait RetrieveEncounterNpc {
 
 impl RetrieveEncounterNpc for Data {
     fn get_encounter_npc(&self, id: u32) -> Option<EncounterNpc> {
-        self.encounter_npcs.get(&id).cloned()
+        self.encounter_npcs.read().unwrap().get(&id).cloned()
     }
 
     fn get_all_encounter_npcs(&self) -> Vec<EncounterNpc> {
-        self.encounter_npcs.iter().map(|(_, encounter_npc)| encounter_npc.clone()).collect()
+        self.encounter_npcs.read().unwrap().iter().map(|(_, encounter_npc)| encounter_npc.clone()).collect()
     }
     
     fn get_required_death_creature_ids(&self, encounter_id: u32) -> Vec<u64> {
         self.encounter_npcs
+            .read()
+            .unwrap()
             .iter()
             .filter_map(|(_id, encounter_npc)| {
                 if encounter_npc.encounter_id == encounter_id && encounter_npc.requires_death {
@@ -31,6 +33,6 @@ impl RetrieveEncounterNpc for Data {
     }
 
     fn encounter_has_pivot(&self, encounter_id: u32) -> bool {
-        self.encounter_npcs.iter().any(|(_, encounter_npc)| encounter_npc.is_pivot && encounter_npc.encounter_id == encounter_id)
+        self.encounter_npcs.read().unwrap().iter().any(|(_, encounter_npc)| encounter_npc.is_pivot && encounter_npc.encounter_id == encounter_id)
     }
 }
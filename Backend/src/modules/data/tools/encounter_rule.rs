@@ -0,0 +1,121 @@
+use crate::modules::data::{domain_value::EncounterRule, Data};
+
+pub trait RetrieveEncounterRule {
+    fn get_encounter_rule(&self, encounter_id: u32) -> Option<EncounterRule>;
+    fn get_all_encounter_rules(&self) -> Vec<EncounterRule>;
+    fn get_encounter_duration_variant(&self, encounter_id: u32, duration: u64) -> Option<u32>;
+    fn get_hard_mode_encounter_id(&self, encounter_id: u32, found_triggers: usize, duration: u64) -> Option<u32>;
+    fn is_hard_mode_aura(&self, encounter_id: u32, spell_id: u32) -> bool;
+    fn is_hard_mode_npc(&self, encounter_id: u32, npc_id: u32) -> bool;
+    fn encounter_has_add_phase(&self, encounter_id: u32) -> bool;
+    fn get_add_phase_grace_period(&self, encounter_id: u32) -> Option<u64>;
+    fn validate_encounter_rules(&self) -> Vec<String>;
+}
+
+impl RetrieveEncounterRule for Data {
+    fn get_encounter_rule(&self, encounter_id: u32) -> Option<EncounterRule> {
+        self.encounter_rules.read().unwrap().get(&encounter_id).cloned()
+    }
+
+    fn get_all_encounter_rules(&self) -> Vec<EncounterRule> {
+        self.encounter_rules.read().unwrap().iter().map(|(_, encounter_rule)| encounter_rule.clone()).collect()
+    }
+
+    fn get_encounter_duration_variant(&self, encounter_id: u32, duration: u64) -> Option<u32> {
+        self.encounter_rules.read().unwrap().get(&encounter_id).and_then(|encounter_rule| {
+            encounter_rule
+                .duration_variants
+                .iter()
+                .find(|variant| variant.min_duration.map_or(true, |min_duration| duration >= min_duration) && variant.max_duration.map_or(true, |max_duration| duration <= max_duration))
+                .map(|variant| variant.variant_encounter_id)
+        })
+    }
+
+    fn get_hard_mode_encounter_id(&self, encounter_id: u32, found_triggers: usize, duration: u64) -> Option<u32> {
+        self.encounter_rules.read().unwrap().get(&encounter_id).and_then(|encounter_rule| {
+            encounter_rule
+                .hard_modes
+                .iter()
+                .find(|hard_mode| {
+                    found_triggers >= hard_mode.min_triggers as usize
+                        && hard_mode.max_triggers.map_or(true, |max_triggers| found_triggers <= max_triggers as usize)
+                        && hard_mode.max_duration.map_or(true, |max_duration| duration <= max_duration)
+                })
+                .map(|hard_mode| hard_mode.hard_mode_encounter_id)
+        })
+    }
+
+    fn is_hard_mode_aura(&self, encounter_id: u32, spell_id: u32) -> bool {
+        self.encounter_rules.read().unwrap().get(&encounter_id).map_or(false, |encounter_rule| encounter_rule.hard_mode_aura_spell_ids.contains(&spell_id))
+    }
+
+    fn is_hard_mode_npc(&self, encounter_id: u32, npc_id: u32) -> bool {
+        self.encounter_rules.read().unwrap().get(&encounter_id).map_or(false, |encounter_rule| encounter_rule.hard_mode_npc_ids.contains(&npc_id))
+    }
+
+    fn encounter_has_add_phase(&self, encounter_id: u32) -> bool {
+        self.encounter_rules.read().unwrap().get(&encounter_id).map_or(false, |encounter_rule| encounter_rule.add_phase.is_some())
+    }
+
+    fn get_add_phase_grace_period(&self, encounter_id: u32) -> Option<u64> {
+        self.encounter_rules.read().unwrap().get(&encounter_id).and_then(|encounter_rule| encounter_rule.add_phase.as_ref()).and_then(|add_phase| add_phase.grace_period)
+    }
+
+    /// Returns a description of every rule that references unknown encounters or is ambiguous.
+    fn validate_encounter_rules(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        let encounters = self.encounters.read().unwrap();
+        let all_encounter_rules = self.encounter_rules.read().unwrap();
+        let mut encounter_rules = all_encounter_rules.values().collect::<Vec<&EncounterRule>>();
+        encounter_rules.sort_by_key(|encounter_rule| encounter_rule.encounter_id);
+
+        for encounter_rule in encounter_rules {
+            let encounter_id = encounter_rule.encounter_id;
+            if !encounters.contains_key(&encounter_id) {
+                issues.push(format!("Encounter {}: Unknown encounter", encounter_id));
+            }
+
+            for (index, variant) in encounter_rule.duration_variants.iter().enumerate() {
+                if !encounters.contains_key(&variant.variant_encounter_id) {
+                    issues.push(format!("Encounter {}: Unknown variant encounter {}", encounter_id, variant.variant_encounter_id));
+                }
+                let min_duration = variant.min_duration.unwrap_or(0);
+                let max_duration = variant.max_duration.unwrap_or(u64::MAX);
+                if variant.min_duration.is_none() && variant.max_duration.is_none() {
+                    issues.push(format!("Encounter {}: Variant {} has no duration bounds", encounter_id, variant.variant_encounter_id));
+                } else if min_duration > max_duration {
+                    issues.push(format!("Encounter {}: Variant {} can never apply", encounter_id, variant.variant_encounter_id));
+                }
+                for other_variant in encounter_rule.duration_variants.iter().skip(index + 1) {
+                    let other_min_duration = other_variant.min_duration.unwrap_or(0);
+                    let other_max_duration = other_variant.max_duration.unwrap_or(u64::MAX);
+                    if min_duration <= max_duration && other_min_duration <= other_max_duration && min_duration <= other_max_duration && other_min_duration <= max_duration {
+                        issues.push(format!("Encounter {}: Variants {} and {} overlap", encounter_id, variant.variant_encounter_id, other_variant.variant_encounter_id));
+                    }
+                }
+            }
+
+            for (index, hard_mode) in encounter_rule.hard_modes.iter().enumerate() {
+                if !encounters.contains_key(&hard_mode.hard_mode_encounter_id) {
+                    issues.push(format!("Encounter {}: Unknown hard mode encounter {}", encounter_id, hard_mode.hard_mode_encounter_id));
+                }
+                let max_triggers = hard_mode.max_triggers.unwrap_or(u8::MAX);
+                if hard_mode.min_triggers > max_triggers {
+                    issues.push(format!("Encounter {}: Hard mode {} can never apply", encounter_id, hard_mode.hard_mode_encounter_id));
+                }
+                // Durations are only bounded above, hence overlapping trigger counts are always ambiguous
+                for other_hard_mode in encounter_rule.hard_modes.iter().skip(index + 1) {
+                    let other_max_triggers = other_hard_mode.max_triggers.unwrap_or(u8::MAX);
+                    if hard_mode.min_triggers <= max_triggers && other_hard_mode.min_triggers <= other_max_triggers && hard_mode.min_triggers <= other_max_triggers && other_hard_mode.min_triggers <= max_triggers {
+                        issues.push(format!("Encounter {}: Hard modes {} and {} overlap", encounter_id, hard_mode.hard_mode_encounter_id, other_hard_mode.hard_mode_encounter_id));
+                    }
+                }
+            }
+
+            if (!encounter_rule.hard_mode_aura_spell_ids.is_empty() || !encounter_rule.hard_mode_npc_ids.is_empty()) && encounter_rule.hard_modes.is_empty() {
+                issues.push(format!("Encounter {}: Hard mode triggers without hard modes", encounter_id));
+            }
+        }
+        issues
+    }
+}
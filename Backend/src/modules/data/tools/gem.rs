@@ -12,7 +12,7 @@ impl RetrieveGem for Data {
         }
 
         // Gems are introduced with TBC
-        self.gems.get(expansion_id as usize - 2).and_then(|map| map.get(&item_id).cloned())
+        self.gems.read().unwrap().get(expansion_id as usize - 2).and_then(|map| map.get(&item_id).cloned())
     }
 
     fn get_gem_by_enchant_id(&self, expansion_id: u8, enchant_id: u32) -> Option<Gem> {
@@ -20,6 +20,10 @@ impl RetrieveGem for Data {
             return None;
         }
 
-        self.gems.get(expansion_id as usize - 2).and_then(|map| map.iter().find(|(_, gem)| gem.enchant_id == enchant_id).map(|(_, gem)| gem.clone()))
+        self.gems
+            .read()
+            .unwrap()
+            .get(expansion_id as usize - 2)
+            .and_then(|map| map.iter().find(|(_, gem)| gem.enchant_id == enchant_id).map(|(_, gem)| gem.clone()))
     }
 }
@@ -0,0 +1,12 @@
+use crate::modules::data::Data;
+
+pub trait RetrieveInstanceEncounter {
+    fn get_instance_encounters(&self, map_id: u16) -> Option<Vec<u32>>;
+}
+
+impl RetrieveInstanceEncounter for Data {
+    /// Returns the encounters that have to be killed to complete a speed run of the map.
+    fn get_instance_encounters(&self, map_id: u16) -> Option<Vec<u32>> {
+        self.instance_encounters.read().unwrap().get(&map_id).cloned()
+    }
+}
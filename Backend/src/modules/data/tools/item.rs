@@ -10,6 +10,6 @@ impl RetrieveItem for Data {
             return None;
         }
 
-        self.items.get(expansion_id as usize - 1).and_then(|map| map.get(&item_id).cloned())
+        self.items.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&item_id).cloned())
     }
 }
@@ -10,6 +10,6 @@ impl RetrieveItemDamage for Data {
             return None;
         }
 
-        self.item_damages.get(expansion_id as usize - 1).and_then(|map| map.get(&item_id).cloned())
+        self.item_damages.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&item_id).cloned())
     }
 }
@@ -10,6 +10,6 @@ impl RetrieveItemEffect for Data {
             return None;
         }
 
-        self.item_effects.get(expansion_id as usize - 1).and_then(|map| map.get(&item_id).cloned())
+        self.item_effects.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&item_id).cloned())
     }
 }
@@ -10,6 +10,6 @@ impl RetrieveItemRandomProperty for Data {
             return None;
         }
 
-        self.item_random_properties.get(expansion_id as usize - 1).and_then(|map| map.get(&random_property_id).cloned())
+        self.item_random_properties.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&random_property_id).cloned())
     }
 }
@@ -10,6 +10,6 @@ impl RetrieveItemSocket for Data {
             return None;
         }
 
-        self.item_sockets.get(expansion_id as usize - 2).and_then(|map| map.get(&item_id).cloned())
+        self.item_sockets.read().unwrap().get(expansion_id as usize - 2).and_then(|map| map.get(&item_id).cloned())
     }
 }
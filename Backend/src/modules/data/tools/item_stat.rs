@@ -10,6 +10,6 @@ impl RetrieveItemStat for Data {
             return None;
         }
 
-        self.item_stats.get(expansion_id as usize - 1).and_then(|map| map.get(&item_id).cloned())
+        self.item_stats.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&item_id).cloned())
     }
 }
@@ -10,6 +10,6 @@ impl RetrieveItemsetEffect for Data {
             return None;
         }
 
-        self.itemset_effects.get(expansion_id as usize - 1).and_then(|map| map.get(&itemset_id).cloned())
+        self.itemset_effects.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&itemset_id).cloned())
     }
 }
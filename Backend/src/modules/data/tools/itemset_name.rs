@@ -11,7 +11,7 @@ impl RetrieveItemsetName for Data {
             return None;
         }
 
-        self.itemset_names.get(expansion_id as usize - 1).and_then(|map| map.get(&itemset_id).cloned())
+        self.itemset_names.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&itemset_id).cloned())
     }
 
     fn get_itemset_item_ids(&self, expansion_id: u8, itemset_id: u16) -> Option<Vec<u32>> {
@@ -20,6 +20,8 @@ impl RetrieveItemsetName for Data {
         }
 
         self.items
+            .read()
+            .unwrap()
             .get(expansion_id as usize - 1)
             .map(|map| map.iter().filter(|(_, item)| item.itemset.is_some() && *item.itemset.as_ref().unwrap() == itemset_id).map(|(item_id, _)| *item_id).collect())
     }
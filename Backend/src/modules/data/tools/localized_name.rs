@@ -0,0 +1,27 @@
+use crate::modules::data::domain_value::DataDomain;
+use crate::modules::data::dto::LocalizedNames;
+use crate::modules::data::tools::ReloadData;
+use crate::modules::data::Data;
+use crate::params;
+use crate::util::database::{Execute, Select};
+
+pub trait ImportLocalizedNames {
+    fn import_localized_names(&self, db_main: &mut (impl Execute + Select), localized_names: LocalizedNames) -> bool;
+}
+
+impl ImportLocalizedNames for Data {
+    /// Replaces names that were imported before and reloads spells and NPCs, such that the parsers resolve the names.
+    fn import_localized_names(&self, db_main: &mut (impl Execute + Select), localized_names: LocalizedNames) -> bool {
+        let success = db_main.execute_batch_wparams(
+            "REPLACE INTO data_spell_name (`expansion_id`, `spell_id`, `language_id`, `name`) VALUES (:expansion_id, :spell_id, :language_id, :name)",
+            localized_names.spell_names,
+            |spell_name| params!("expansion_id" => spell_name.expansion_id, "spell_id" => spell_name.spell_id, "language_id" => spell_name.language_id, "name" => spell_name.name),
+        ) && db_main.execute_batch_wparams(
+            "REPLACE INTO data_npc_name (`expansion_id`, `npc_id`, `language_id`, `name`) VALUES (:expansion_id, :npc_id, :language_id, :name)",
+            localized_names.npc_names,
+            |npc_name| params!("expansion_id" => npc_name.expansion_id, "npc_id" => npc_name.npc_id, "language_id" => npc_name.language_id, "name" => npc_name.name),
+        );
+        self.reload_data(db_main, &[DataDomain::Spells, DataDomain::Npcs]);
+        success
+    }
+}
@@ -10,15 +10,15 @@ pub trait RetrieveMap {
 
 impl RetrieveMap for Data {
     fn get_map(&self, id: u16) -> Option<Map> {
-        self.maps.get(&id).cloned()
+        self.maps.read().unwrap().get(&id).cloned()
     }
 
     fn get_all_maps(&self) -> Vec<Map> {
-        self.maps.iter().map(|(_, map)| map.clone()).collect()
+        self.maps.read().unwrap().iter().map(|(_, map)| map.clone()).collect()
     }
 
     fn get_map_by_name(&self, name: &String) -> Option<Map> {
-        self.maps.iter().find_map(|(_, map)| {
+        self.maps.read().unwrap().iter().find_map(|(_, map)| {
             if self.get_localization(1, map.localization_id).map(|localization| localization.content).contains(name) {
                     return Some(map.clone());
             }
@@ -1,9 +1,9 @@
 pub use self::{
-    difficulty::RetrieveDifficulty, dispel_type::RetrieveDispelType, enchant::RetrieveEnchant, encounter::RetrieveEncounter, encounter_npc::RetrieveEncounterNpc, expansion::RetrieveExpansion, gem::RetrieveGem, hero_class::RetrieveHeroClass,
-    icon::RetrieveIcon, item::RetrieveItem, item_bonding::RetrieveItemBonding, item_class::RetrieveItemClass, item_damage::RetrieveItemDamage, item_damage_type::RetrieveItemDamageType, item_effect::RetrieveItemEffect,
+    difficulty::RetrieveDifficulty, dispel_type::RetrieveDispelType, enchant::RetrieveEnchant, encounter::RetrieveEncounter, encounter_npc::RetrieveEncounterNpc, encounter_rule::RetrieveEncounterRule, expansion::RetrieveExpansion, gem::RetrieveGem, hero_class::RetrieveHeroClass,
+    icon::RetrieveIcon, instance_encounter::RetrieveInstanceEncounter, item::RetrieveItem, item_bonding::RetrieveItemBonding, item_class::RetrieveItemClass, item_damage::RetrieveItemDamage, item_damage_type::RetrieveItemDamageType, item_effect::RetrieveItemEffect,
     item_inventory_type::RetrieveItemInventoryType, item_quality::RetrieveItemQuality, item_random_property::RetrieveItemRandomProperty, item_random_property_points::RetrieveItemRandomPropertyPoints, item_sheath::RetrieveItemSheath,
-    item_socket::RetrieveItemSocket, item_stat::RetrieveItemStat, itemset_effect::RetrieveItemsetEffect, itemset_name::RetrieveItemsetName, language::RetrieveLanguage, localization::RetrieveLocalization, map::RetrieveMap, npc::RetrieveNPC,
-    power_type::RetrievePowerType, profession::RetrieveProfession, race::RetrieveRace, server::RetrieveServer, spell::RetrieveSpell, spell_description::SpellDescription, spell_effect::RetrieveSpellEffect, stat_type::RetrieveStatType,
+    item_socket::RetrieveItemSocket, item_stat::RetrieveItemStat, itemset_effect::RetrieveItemsetEffect, itemset_name::RetrieveItemsetName, language::RetrieveLanguage, localization::RetrieveLocalization, localized_name::ImportLocalizedNames, map::RetrieveMap, npc::RetrieveNPC,
+    power_type::RetrievePowerType, profession::RetrieveProfession, race::RetrieveRace, reload::ReloadData, season::RetrieveSeason, server::RetrieveServer, spec_rule::RetrieveSpecRule, spell::RetrieveSpell, spell_description::SpellDescription, spell_effect::RetrieveSpellEffect, stat_type::RetrieveStatType,
     title::RetrieveTitle, addon::RetrieveAddon
 };
 
@@ -12,10 +12,12 @@ mod dispel_type;
 mod enchant;
 mod encounter;
 mod encounter_npc;
+mod encounter_rule;
 mod expansion;
 mod gem;
 mod hero_class;
 mod icon;
+mod instance_encounter;
 mod item;
 mod item_bonding;
 mod item_class;
@@ -33,12 +35,16 @@ mod itemset_effect;
 mod itemset_name;
 mod language;
 mod localization;
+mod localized_name;
 mod map;
 mod npc;
 mod power_type;
 mod profession;
 mod race;
+mod reload;
+mod season;
 mod server;
+mod spec_rule;
 mod spell;
 mod spell_description;
 mod spell_effect;
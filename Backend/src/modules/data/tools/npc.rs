@@ -4,6 +4,7 @@ use crate::modules::data::{domain_value::NPC, Data};
 pub trait RetrieveNPC {
     fn get_npc(&self, expansion_id: u8, npc_id: u32) -> Option<NPC>;
     fn get_npc_by_name(&self, expansion_id: u8, unit_name: &String) -> Option<NPC>;
+    fn get_npc_by_localized_name(&self, expansion_id: u8, language_id: u8, unit_name: &String) -> Option<NPC>;
 }
 
 impl RetrieveNPC for Data {
@@ -12,17 +13,33 @@ impl RetrieveNPC for Data {
             return None;
         }
 
-        self.npcs.get(expansion_id as usize - 1).and_then(|map| map.get(&npc_id).cloned())
+        self.npcs.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&npc_id).cloned())
     }
 
     fn get_npc_by_name(&self, expansion_id: u8, unit_name: &String) -> Option<NPC> {
+        self.get_npc_by_localized_name(expansion_id, 1, unit_name)
+    }
+
+    fn get_npc_by_localized_name(&self, expansion_id: u8, language_id: u8, unit_name: &String) -> Option<NPC> {
         if expansion_id == 0 {
             return None;
         }
 
-        self.npcs
-            .get(expansion_id as usize - 1)
-            .and_then(|map| map.iter().find(|(_, npc)| self.get_localization(1, npc.localization_id).map(|localization| localization.content).contains(unit_name)))
-            .map(|(_, npc)| npc.clone())
+        let localized_npc = self
+            .npc_names
+            .read()
+            .unwrap()
+            .get(&language_id)
+            .and_then(|names| names.get(unit_name))
+            .and_then(|localized_names| localized_names.iter().find(|localized_name| localized_name.expansion_id == expansion_id))
+            .and_then(|localized_name| self.get_npc(expansion_id, localized_name.npc_id));
+        localized_npc.or_else(|| {
+            self.npcs
+                .read()
+                .unwrap()
+                .get(expansion_id as usize - 1)
+                .and_then(|map| map.iter().find(|(_, npc)| self.get_localization(language_id, npc.localization_id).map(|localization| localization.content).contains(unit_name)))
+                .map(|(_, npc)| npc.clone())
+        })
     }
 }
@@ -0,0 +1,24 @@
+use std::sync::atomic::Ordering;
+
+use crate::modules::data::domain_value::DataDomain;
+use crate::modules::data::Data;
+use crate::util::database::Select;
+
+pub trait ReloadData {
+    fn reload_data(&self, db_main: &mut impl Select, domains: &[DataDomain]) -> Vec<DataDomain>;
+    fn get_generation(&self) -> u64;
+}
+
+impl ReloadData for Data {
+    /// Reloads the given domains, or all if none are given, and returns the reloaded domains.
+    fn reload_data(&self, db_main: &mut impl Select, domains: &[DataDomain]) -> Vec<DataDomain> {
+        let reloaded_domains: Vec<DataDomain> = DataDomain::ALL.iter().filter(|domain| domains.is_empty() || domains.contains(domain)).copied().collect();
+        reloaded_domains.iter().for_each(|domain| self.load_domain(*domain, db_main));
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        reloaded_domains
+    }
+
+    fn get_generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::modules::data::domain_value::Season;
+use crate::modules::data::Data;
+
+pub trait RetrieveSeason {
+    fn get_season(&self, id: u32) -> Option<Season>;
+    fn get_all_seasons(&self) -> Vec<Season>;
+    fn get_seasons_by_server(&self, server_id: u32) -> Vec<Season>;
+    fn find_season(&self, server_id: u32, map_id: u16, ts: u64) -> Option<u32>;
+}
+
+impl RetrieveSeason for Data {
+    fn get_season(&self, id: u32) -> Option<Season> {
+        self.seasons.read().unwrap().get(&id).cloned()
+    }
+
+    fn get_all_seasons(&self) -> Vec<Season> {
+        let mut seasons: Vec<Season> = self.seasons.read().unwrap().iter().map(|(_, season)| season.clone()).collect();
+        seasons.sort_by_key(|season| (season.start_ts, season.id));
+        seasons
+    }
+
+    fn get_seasons_by_server(&self, server_id: u32) -> Vec<Season> {
+        self.get_all_seasons().into_iter().filter(|season| season.server_id == 0 || season.server_id == server_id).collect()
+    }
+
+    /// Seasons of the server take precedence over seasons of all servers, and later seasons over earlier ones.
+    fn find_season(&self, server_id: u32, map_id: u16, ts: u64) -> Option<u32> {
+        self.seasons
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, season)| season.contains(server_id, map_id, ts))
+            .max_by_key(|(_, season)| (season.server_id == server_id, season.start_ts, season.id))
+            .map(|(id, _)| *id)
+    }
+}
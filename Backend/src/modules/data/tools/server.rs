@@ -1,5 +1,4 @@
-use crate::modules::data::domain_value::Server;
-use crate::modules::data::material::Init;
+use crate::modules::data::domain_value::{DataDomain, Server};
 use crate::modules::data::{dto::AvailableServer, Data};
 use crate::params;
 use crate::util::database::{Execute, Select};
@@ -30,8 +29,7 @@ impl RetrieveServer for Data {
     }
 
     fn reload_server(&self, db_main: &mut impl Select) {
-        let mut servers = self.servers.write().unwrap();
-        (*servers).init(db_main);
+        self.load_domain(DataDomain::Servers, db_main);
     }
 
     fn get_internal_server_by_retail_id(&self, retail_id: u32) -> Option<Server> {
@@ -0,0 +1,24 @@
+use crate::modules::data::domain_value::{SpecRule, SpecRuleSource};
+use crate::modules::data::Data;
+
+pub trait RetrieveSpecRule {
+    fn get_spec_rules(&self, source: SpecRuleSource, spell_name: &str) -> Vec<SpecRule>;
+    fn get_all_spec_rules(&self) -> Vec<SpecRule>;
+}
+
+impl RetrieveSpecRule for Data {
+    fn get_spec_rules(&self, source: SpecRuleSource, spell_name: &str) -> Vec<SpecRule> {
+        self.spec_rules
+            .read()
+            .unwrap()
+            .get(spell_name)
+            .map(|spec_rules| spec_rules.iter().filter(|spec_rule| spec_rule.source == source).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn get_all_spec_rules(&self) -> Vec<SpecRule> {
+        let mut spec_rules: Vec<SpecRule> = self.spec_rules.read().unwrap().iter().flat_map(|(_, spec_rules)| spec_rules.iter().cloned()).collect();
+        spec_rules.sort_by_key(|spec_rule| spec_rule.id);
+        spec_rules
+    }
+}
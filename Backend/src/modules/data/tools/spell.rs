@@ -3,6 +3,7 @@ use crate::modules::data::{domain_value::Spell, Data};
 pub trait RetrieveSpell {
     fn get_spell(&self, expansion_id: u8, spell_id: u32) -> Option<Spell>;
     fn get_spell_by_name(&self, expansion_id: u8, spell_name: &String) -> Option<Spell>;
+    fn get_spell_by_localized_name(&self, expansion_id: u8, language_id: u8, spell_name: &String) -> Option<Spell>;
 }
 
 impl RetrieveSpell for Data {
@@ -11,7 +12,7 @@ impl RetrieveSpell for Data {
             return None;
         }
 
-        self.spells.get(expansion_id as usize - 1).and_then(|map| map.get(&spell_id).cloned())
+        self.spells.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&spell_id).cloned())
     }
 
     fn get_spell_by_name(&self, expansion_id: u8, spell_name: &String) -> Option<Spell> {
@@ -20,6 +21,8 @@ impl RetrieveSpell for Data {
         }
 
         self.spells
+            .read()
+            .unwrap()
             .get(expansion_id as usize - 1)
             .and_then(|map| {
                 // Try finding an exact match first
@@ -29,4 +32,20 @@ impl RetrieveSpell for Data {
             })
             .map(|(_, spell)| spell.clone())
     }
+
+    fn get_spell_by_localized_name(&self, expansion_id: u8, language_id: u8, spell_name: &String) -> Option<Spell> {
+        if language_id == 1 {
+            return self.get_spell_by_name(expansion_id, spell_name);
+        }
+
+        self.spell_names
+            .read()
+            .unwrap()
+            .get(&language_id)
+            .and_then(|names| names.get(spell_name))
+            .and_then(|localized_names| localized_names.iter().find(|localized_name| localized_name.expansion_id == expansion_id))
+            .and_then(|localized_name| self.get_spell(expansion_id, localized_name.spell_id))
+            // Some servers keep the English names for spells they added themselves
+            .or_else(|| self.get_spell_by_name(expansion_id, spell_name))
+    }
 }
@@ -10,6 +10,6 @@ impl RetrieveSpellEffect for Data {
             return None;
         }
 
-        self.spell_effects.get(expansion_id as usize - 1).and_then(|map| map.get(&spell_id).cloned())
+        self.spell_effects.read().unwrap().get(expansion_id as usize - 1).and_then(|map| map.get(&spell_id).cloned())
     }
 }
@@ -0,0 +1,16 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::data::{domain_value::EncounterRule, tools::RetrieveEncounterRule, Data};
+
+#[openapi]
+#[get("/encounter_rule/<encounter_id>")]
+pub fn get_encounter_rule(me: State<Data>, encounter_id: u32) -> Option<Json<EncounterRule>> {
+    me.get_encounter_rule(encounter_id).map(Json)
+}
+
+#[openapi]
+#[get("/encounter_rule")]
+pub fn get_all_encounter_rules(me: State<Data>) -> Json<Vec<EncounterRule>> {
+    Json(me.get_all_encounter_rules())
+}
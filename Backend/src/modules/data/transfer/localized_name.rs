@@ -0,0 +1,12 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::account::guard::{HasPermission, Moderate};
+use crate::modules::data::{dto::LocalizedNames, tools::ImportLocalizedNames, Data};
+use crate::MainDb;
+
+#[openapi]
+#[post("/localized_names", format = "application/json", data = "<localized_names>")]
+pub fn import_localized_names(mut db_main: MainDb, me: State<Data>, localized_names: Json<LocalizedNames>, _auth: HasPermission<Moderate>) -> Json<bool> {
+    Json(me.import_localized_names(&mut *db_main, localized_names.into_inner()))
+}
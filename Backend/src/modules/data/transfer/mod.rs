@@ -3,6 +3,7 @@ pub mod dispel_type;
 pub mod enchant;
 pub mod encounter;
 pub mod encounter_npc;
+pub mod encounter_rule;
 pub mod expansion;
 pub mod gem;
 pub mod hero_class;
@@ -24,11 +25,14 @@ pub mod itemset_effect;
 pub mod itemset_name;
 pub mod language;
 pub mod localization;
+pub mod localized_name;
 pub mod map;
 pub mod npc;
 pub mod power_type;
 pub mod profession;
 pub mod race;
+pub mod reload;
+pub mod season;
 pub mod server;
 pub mod spell;
 pub mod spell_effect;
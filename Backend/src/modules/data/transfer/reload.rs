@@ -0,0 +1,12 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::account::guard::{HasPermission, Moderate};
+use crate::modules::data::{domain_value::DataDomain, tools::ReloadData, Data};
+use crate::MainDb;
+
+#[openapi]
+#[post("/reload", format = "application/json", data = "<domains>")]
+pub fn reload_data(mut db_main: MainDb, me: State<Data>, domains: Json<Vec<DataDomain>>, _auth: HasPermission<Moderate>) -> Json<Vec<DataDomain>> {
+    Json(me.reload_data(&mut *db_main, &domains.into_inner()))
+}
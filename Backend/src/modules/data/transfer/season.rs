@@ -0,0 +1,22 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::data::{domain_value::Season, tools::RetrieveSeason, Data};
+
+#[openapi]
+#[get("/season/<id>")]
+pub fn get_season(me: State<Data>, id: u32) -> Option<Json<Season>> {
+    me.get_season(id).map(Json)
+}
+
+#[openapi]
+#[get("/season")]
+pub fn get_all_seasons(me: State<Data>) -> Json<Vec<Season>> {
+    Json(me.get_all_seasons())
+}
+
+#[openapi]
+#[get("/season/by_server/<server_id>")]
+pub fn get_seasons_by_server(me: State<Data>, server_id: u32) -> Json<Vec<Season>> {
+    Json(me.get_seasons_by_server(server_id))
+}
@@ -7,5 +7,5 @@ pub struct InstanceAttempt {
     pub is_kill: bool,
     pub difficulty_id: u8,
     pub rankable: bool,
-    pub season_index: u8
+    pub season_id: u32
 }
\ No newline at end of file
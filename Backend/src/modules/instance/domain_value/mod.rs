@@ -2,8 +2,12 @@ pub use self::instance_meta::InstanceMeta;
 pub use self::meta_type::MetaType;
 pub use self::instance_attempt::InstanceAttempt;
 pub use self::privacy_type::PrivacyType;
+pub use self::moderation_action::ModerationAction;
+pub use self::moderation_audit_entry::ModerationAuditEntry;
 
 mod instance_meta;
 mod meta_type;
 mod instance_attempt;
-mod privacy_type;
\ No newline at end of file
+mod privacy_type;
+mod moderation_action;
+mod moderation_audit_entry;
\ No newline at end of file
@@ -0,0 +1,26 @@
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationAction {
+    Approve,
+    Unrank,
+    Delete,
+}
+
+impl ModerationAction {
+    pub fn new(action: u8) -> Option<Self> {
+        match action {
+            1 => Some(Self::Approve),
+            2 => Some(Self::Unrank),
+            3 => Some(Self::Delete),
+            _ => None,
+        }
+    }
+
+    // 0 marks flagged attempts that are not moderated yet
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::Approve => 1,
+            Self::Unrank => 2,
+            Self::Delete => 3,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use crate::modules::instance::domain_value::ModerationAction;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct ModerationAuditEntry {
+    pub id: u32,
+    pub member_id: u32,
+    pub attempt_id: u32,
+    pub instance_meta_id: u32,
+    pub action: ModerationAction,
+    // The reasons the attempt was flagged for, empty if it was moderated without being flagged
+    pub reasons: Vec<String>,
+    pub timestamp: u64,
+}
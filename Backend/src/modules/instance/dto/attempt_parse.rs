@@ -0,0 +1,10 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct AttemptParse {
+    pub attempt_id: u32,
+    pub encounter_id: u32,
+    pub character_id: u32,
+    pub character_spec: u8,
+    pub amount: u32,
+    pub duration: u64,
+    pub percentile: u8,
+}
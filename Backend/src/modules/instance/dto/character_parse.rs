@@ -0,0 +1,9 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct CharacterParse {
+    pub encounter_id: u32,
+    pub difficulty_id: u8,
+    pub best_percentile: u8,
+    pub median_percentile: u8,
+    pub best_attempt_id: u32,
+    pub num_parses: u32,
+}
@@ -0,0 +1,12 @@
+use crate::modules::instance::dto::MeterUnit;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct EventExportFilter {
+    pub attempt_id: Option<u32>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+    pub source: Option<MeterUnit>,
+    pub target: Option<MeterUnit>,
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
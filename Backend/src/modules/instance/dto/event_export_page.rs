@@ -0,0 +1,7 @@
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventExportPage {
+    pub events: Vec<Value>,
+    pub next_offset: Option<u32>,
+}
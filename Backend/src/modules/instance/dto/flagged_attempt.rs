@@ -0,0 +1,8 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct FlaggedAttempt {
+    pub attempt_id: u32,
+    pub instance_meta_id: u32,
+    pub encounter_id: u32,
+    pub flagged_ts: u64,
+    pub reasons: Vec<String>,
+}
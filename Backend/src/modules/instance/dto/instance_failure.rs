@@ -8,6 +8,7 @@ use std::io::Cursor;
 pub enum InstanceFailure {
     InvalidInput,
     RankingsUpdating,
+    AccessDenied,
     Unknown
 }
 
@@ -23,6 +24,10 @@ impl Responder<'static> for InstanceFailure {
                 body = "Rankings are currently updating!".to_owned();
                 Status::new(540, "RankingsUpdating")
             },
+            Self::AccessDenied => {
+                body = "Access denied!".to_owned();
+                Status::new(541, "AccessDenied")
+            },
             Self::Unknown => {
                 body = "Unknown error!".to_owned();
                 Status::new(599, "Unknown")
@@ -38,6 +43,7 @@ impl OpenApiResponder<'static> for InstanceFailure {
         let schema = gen.json_schema::<String>();
         add_schema_response(&mut responses, 534, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 540, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 541, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 599, "text/plain", schema)?;
         Ok(responses)
     }
@@ -1,4 +1,5 @@
 use crate::modules::instance::material::Role;
+use crate::modules::live_data_processor::dto::SpecEvidence;
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct InstanceViewerParticipant {
@@ -6,4 +7,8 @@ pub struct InstanceViewerParticipant {
     pub name: String,
     pub hero_class_id: u8,
     pub role: Role,
+    // Only known if the spec was detected from the combat log, the confidence is given in percent
+    pub talent_tree: Option<u8>,
+    pub spec_confidence: u8,
+    pub spec_evidence: Vec<SpecEvidence>,
 }
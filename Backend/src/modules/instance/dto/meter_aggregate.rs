@@ -0,0 +1,12 @@
+use crate::modules::instance::dto::MeterRecord;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeterAggregate {
+    pub instance_meta_id: u32,
+    pub attempt_id: Option<u32>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+    pub damage: Vec<MeterRecord>,
+    pub healing: Vec<MeterRecord>,
+    pub threat: Vec<MeterRecord>,
+}
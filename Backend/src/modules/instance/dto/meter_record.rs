@@ -0,0 +1,13 @@
+use crate::modules::instance::dto::MeterUnit;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct MeterRecord {
+    pub source: MeterUnit,
+    pub target: MeterUnit,
+    pub spell_id: u32,
+    pub school_mask: u8,
+    pub hit_mask: u32,
+    pub count: u32,
+    pub amount: i64,
+    pub effective: i64,
+}
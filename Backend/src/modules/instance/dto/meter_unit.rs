@@ -0,0 +1,7 @@
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MeterUnit {
+    pub is_player: bool,
+    // Character id for players, npc entry for creatures
+    pub unit_id: u64,
+    pub owner_id: Option<u32>,
+}
@@ -1,4 +1,9 @@
+pub use self::attempt_parse::AttemptParse;
 pub use self::battleground_search_filter::BattlegroundSearchFilter;
+pub use self::character_parse::CharacterParse;
+pub use self::event_export_filter::EventExportFilter;
+pub use self::event_export_page::EventExportPage;
+pub use self::flagged_attempt::FlaggedAttempt;
 pub use self::instance_failure::InstanceFailure;
 pub use self::instance_privacy::InstancePrivacy;
 pub use self::instance_viewer_attempt::InstanceViewerAttempt;
@@ -9,6 +14,11 @@ pub use self::meta_battleground_search::MetaBattlegroundSearch;
 pub use self::meta_raid_search::MetaRaidSearch;
 pub use self::meta_rated_arena_search::MetaRatedArenaSearch;
 pub use self::meta_skirmish_search::MetaSkirmishSearch;
+pub use self::meter_aggregate::MeterAggregate;
+pub use self::meter_filter::MeterFilter;
+pub use self::meter_record::MeterRecord;
+pub use self::meter_unit::MeterUnit;
+pub use self::moderation_decision::ModerationDecision;
 pub use self::raid_search_filter::RaidSearchFilter;
 pub use self::ranking_character_meta::RankingCharacterMeta;
 pub use self::ranking_result::RankingResult;
@@ -17,9 +27,16 @@ pub use self::rated_arena_search_filter::RatedArenaSearchFilter;
 pub use self::search_arena_team::SearchArenaTeam;
 pub use self::skirmish_search_filter::SkirmishSearchFilter;
 pub use self::speed_kill::SpeedKill;
+pub use self::speed_kill_filter::SpeedKillFilter;
 pub use self::speed_run::SpeedRun;
+pub use self::speed_run_filter::SpeedRunFilter;
 
+mod attempt_parse;
 mod battleground_search_filter;
+mod character_parse;
+mod event_export_filter;
+mod event_export_page;
+mod flagged_attempt;
 mod instance_failure;
 mod instance_privacy;
 mod instance_viewer_attempt;
@@ -30,6 +47,11 @@ mod meta_battleground_search;
 mod meta_raid_search;
 mod meta_rated_arena_search;
 mod meta_skirmish_search;
+mod meter_aggregate;
+mod meter_filter;
+mod meter_record;
+mod meter_unit;
+mod moderation_decision;
 mod raid_search_filter;
 mod ranking_character_meta;
 mod ranking_result;
@@ -39,4 +61,6 @@ mod responder_raw_json;
 mod search_arena_team;
 mod skirmish_search_filter;
 mod speed_kill;
+mod speed_kill_filter;
 mod speed_run;
+mod speed_run_filter;
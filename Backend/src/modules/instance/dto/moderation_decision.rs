@@ -0,0 +1,7 @@
+use crate::modules::instance::domain_value::ModerationAction;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModerationDecision {
+    pub attempt_id: u32,
+    pub action: ModerationAction,
+}
@@ -16,7 +16,8 @@ pub struct RankingResult {
     pub duration: u64,
     pub difficulty_id: u8,
     pub character_spec: u8,
-    pub season_index: u8,
+    pub season_id: u32,
+    pub percentile: u8,
 
     pub ranking_result: RankingResultMeta,
 }
\ No newline at end of file
@@ -13,5 +13,7 @@ pub struct RankingResultMeta {
     #[serde(rename = "f")]
     pub character_spec: u8,
     #[serde(rename = "g")]
-    pub season_index: u8
+    pub season_id: u32,
+    #[serde(rename = "h")]
+    pub percentile: u8
 }
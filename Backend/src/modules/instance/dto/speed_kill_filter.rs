@@ -0,0 +1,8 @@
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SpeedKillFilter {
+    pub encounter_id: Option<u32>,
+    pub server_id: Option<u32>,
+    pub season_id: Option<u32>,
+    pub difficulty_id: Option<u8>,
+    pub guild_id: Option<u32>,
+}
@@ -7,5 +7,5 @@ pub struct SpeedRun {
     pub server_id: u32,
     pub duration: u64,
     pub difficulty_id: u8,
-    pub season_index: u8
+    pub season_id: u32
 }
\ No newline at end of file
@@ -0,0 +1,8 @@
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SpeedRunFilter {
+    pub map_id: Option<u16>,
+    pub server_id: Option<u32>,
+    pub season_id: Option<u32>,
+    pub difficulty_id: Option<u8>,
+    pub guild_id: Option<u32>,
+}
@@ -1,17 +1,18 @@
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
+use std::path::Path;
 use std::sync::{Arc, RwLock};
 use sha2::{Sha256, Digest};
 
-use crate::modules::armory::tools::{GetCharacter};
-use crate::modules::armory::util::talent_tree::get_talent_tree;
+use crate::modules::armory::material::NameCachePolicy;
 use crate::modules::armory::Armory;
 use crate::modules::data::Data;
-use crate::modules::instance::domain_value::{InstanceAttempt, InstanceMeta, MetaType, PrivacyType};
+use crate::modules::instance::domain_value::{InstanceMeta, MetaType, PrivacyType};
 use crate::modules::instance::dto::{SpeedKill, SpeedRun};
-use crate::modules::instance::tools::FindInstanceGuild;
+use crate::modules::instance::tools::{flag_attempt_anomalies_in_range, load_speed_kills, load_speed_runs, remove_ranking_results, update_ranking_results, update_speed_runs_and_kills, RankingScope};
 use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use crate::modules::live_data_processor::tools::event_segment::{convert_zip_to_event_segment, get_event_segment_path};
 use crate::modules::live_data_processor::tools::log_parser::parse_cbl;
 use crate::mysql::Opts;
 use crate::util::database::*;
@@ -35,33 +36,41 @@ impl Default for Instance {
 }
 
 impl Instance {
-    pub fn init(self, mut db_main: (impl Select + Send + Execute + 'static)) -> Self {
+    pub fn init(self, mut db_main: (impl Select + Send + Execute + 'static), data: &Data, armory: &Armory) -> Self {
         let instance_metas_arc_clone = Arc::clone(&self.instance_metas);
+        let speed_runs_arc_clone = Arc::clone(&self.speed_runs);
+        let speed_kills_arc_clone = Arc::clone(&self.speed_kills);
+        *self.speed_runs.write().unwrap() = load_speed_runs(&mut db_main);
+        *self.speed_kills.write().unwrap() = load_speed_kills(&mut db_main);
+        let mut processed_kill_attempt_id = self.speed_kills.read().unwrap().iter().map(|speed_kill| speed_kill.attempt_id).max().unwrap_or(0);
 
         let dns = std::env::var("MYSQL_URL").unwrap();
         let opts = Opts::from_url(&dns).unwrap();
         let mut conn = mysql::Conn::new(opts.clone()).unwrap();
 
-        let data = Data::default().init(&mut conn);
-        let live_data_processor = crate::modules::live_data_processor::LiveDataProcessor::default().init(&mut conn);
+        let data = data.share();
+        // Merges and uploads update the same characters as the update loop
+        let armory = armory.share();
+        let name_cache_policy = NameCachePolicy::from_env();
 
         std::thread::spawn(move || {
-            let mut armory_counter = 1;
-            let armory = Armory::default().init(&mut db_main);
-
+            // Attempts are checked one update after they were committed, such that their ranking results are complete.
+            // Kills that were not checked before the start are checked by the first update.
+            let mut checked_attempt_id = 0;
+            let mut committed_attempt_id = get_last_attempt_id(&mut db_main);
             loop {
                 println!("[Update loop] starting update {}", time_util::now());
                 delete_old_character_data(&mut db_main);
                 println!("[Update loop] finish delete old character data");
                 update_instance_metas(Arc::clone(&instance_metas_arc_clone), &mut db_main);
                 println!("[Update loop] finish update_instance_metas");
-
-                if armory_counter % 10 == 1 {
-                    println!("Updating rankings at {}", time_util::now());
-                    update_instance_rankings_dps( &mut db_main, &armory);
-                    update_instance_rankings_hps( &mut db_main, &armory);
-                    println!("Updating rankings complete at {}", time_util::now());
-                }
+                processed_kill_attempt_id = update_speed_runs_and_kills(&mut db_main, &armory, &data, &instance_metas_arc_clone, &speed_runs_arc_clone, &speed_kills_arc_clone, processed_kill_attempt_id);
+                println!("[Update loop] finish speed runs and kills");
+                let last_attempt_id = get_last_attempt_id(&mut db_main);
+                let flagged_attempts = flag_attempt_anomalies_in_range(&mut db_main, checked_attempt_id, committed_attempt_id);
+                checked_attempt_id = committed_attempt_id;
+                committed_attempt_id = last_attempt_id;
+                println!("[Update loop] finish anomaly detection, flagged {} attempts for moderation", flagged_attempts);
 
                 // update an instance metas that doesn't have updated specs
                 if let Some(instance_meta) = db_main
@@ -126,7 +135,6 @@ impl Instance {
 
                                 parse_cbl(
                                     &mut combat_log_parser,
-                                    &live_data_processor,
                                     &mut conn,
                                     &data,
                                     &armory,
@@ -134,11 +142,16 @@ impl Instance {
                                     instance_meta.start_ts,
                                     instance_meta.end_ts.unwrap_or(instance_meta.start_ts),
                                     instance_meta.uploaded_user,
+                                    instance_meta.upload_id,
                                     false,
                                 );
 
                                 // mark instance meta as updated
                                 db_main.execute_wparams("UPDATE instance_meta SET updated_specs = 1 WHERE id = :instance_meta_id", params!("instance_meta_id" => instance_meta.instance_meta_id));
+
+                                // the specs of the ranked characters may have changed
+                                remove_ranking_results(&mut db_main, RankingScope::InstanceMeta(instance_meta.instance_meta_id));
+                                update_ranking_results(&mut db_main, &armory, &data, RankingScope::InstanceMeta(instance_meta.instance_meta_id));
                             }
                         }
                     }
@@ -224,10 +237,13 @@ impl Instance {
                 }
                 println!("[Update loop] finish hash update");
 
+                convert_legacy_instance_zips(Arc::clone(&instance_metas_arc_clone));
+                println!("[Update loop] finish legacy zip conversion");
+
                 armory.update(&mut db_main);
+                armory.evict_name_cache(&name_cache_policy, time_util::now());
                 println!("[Update loop] finish armory update");
 
-                armory_counter += 1;
                 println!("[Update loop] Updating instance data done at {}", time_util::now());
                 std::thread::sleep(std::time::Duration::from_secs(30));
             }
@@ -239,307 +255,59 @@ impl Instance {
     pub fn delete_instance_meta(&self, instance_meta_id: u32) {
         let mut instance_metas = self.instance_metas.write().unwrap();
         instance_metas.1.remove(&instance_meta_id);
+        // Stored speed runs and kills are removed with the instance meta
+        self.speed_runs.write().unwrap().retain(|speed_run| speed_run.instance_meta_id != instance_meta_id);
+        self.speed_kills.write().unwrap().retain(|speed_kill| speed_kill.instance_meta_id != instance_meta_id);
     }
-}
-
-fn calculate_speed_runs(
-    instance_metas: Arc<RwLock<(u32, HashMap<u32, InstanceMeta>)>>, instance_kill_attempts: Arc<RwLock<(u32, HashMap<u32, Vec<InstanceAttempt>>)>>, speed_runs: Arc<RwLock<Vec<SpeedRun>>>, db_main: &mut impl Select, armory: &Armory,
-) {
-    lazy_static! {
-        static ref INSTANCE_ENCOUNTERS: HashMap<u16, Vec<u32>> = {
-            let mut instance_encounters = HashMap::new();
-            instance_encounters.insert(409, vec![80, 1, 2, 81, 82, 4, 5, 6, 7, 8, 9, 10]);
-            instance_encounters.insert(249, vec![11]);
-            instance_encounters.insert(309, vec![12, 13, 14, 15, 17, 19, 20, 21]);
-            instance_encounters.insert(469, vec![22, 23, 24, 25, 26, 27, 28, 29]);
-            instance_encounters.insert(509, vec![30, 31, 32, 33, 34, 35]);
-            instance_encounters.insert(531, vec![36, 37, 38, 39, 40, 41, 42, 163, 164, 165]);
-            instance_encounters.insert(533, vec![43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57]);
-            instance_encounters.insert(532, vec![201, 202, 203, 204, 205]); // lower kara
-            instance_encounters.insert(565, vec![69, 70]);
-            instance_encounters.insert(544, vec![71]);
-            instance_encounters.insert(550, vec![72, 73, 74, 75]);
-            instance_encounters.insert(548, vec![76, 78, 79, 80, 81]);
-            instance_encounters.insert(568, vec![82, 83, 84, 85, 86, 87]);
-            instance_encounters.insert(534, vec![88, 89, 90, 91, 92]);
-            instance_encounters.insert(564, vec![93, 94, 95, 96, 97, 98, 99, 100, 101]);
-            instance_encounters.insert(580, vec![103, 104, 105, 106, 107]);
-            instance_encounters.insert(615, vec![108]);
-            instance_encounters.insert(616, vec![109]);
-            instance_encounters.insert(624, vec![110, 111, 112, 113]);
-            instance_encounters.insert(603, vec![114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126]);
-            instance_encounters.insert(649, vec![128, 129, 130, 131, 132]);
-            instance_encounters.insert(631, vec![133, 134, 136, 137, 138, 139, 140, 141, 143, 144]);
-            instance_encounters.insert(724, vec![145]);
-            instance_encounters.insert(807, vec![200]); // ES
-            instance_encounters.insert(814, vec![206, 207, 208, 209, 210, 211, 212, 213, 214]); // upper kara
-
-            instance_encounters
-        };
-    }
-
-    let kill_attempts = instance_kill_attempts.read().unwrap();
-    let instance_metas = instance_metas.read().unwrap();
-    let mut speed_runs = speed_runs.write().unwrap();
-    let already_calculated_speed_runs: Vec<u32> = speed_runs.iter().map(|speed_run| speed_run.instance_meta_id).collect();
-
-    for (instance_meta_id, attempts) in kill_attempts.1.iter().filter(|(im_id, _)| !already_calculated_speed_runs.contains(*im_id)) {
-        if !instance_metas.1.contains_key(instance_meta_id) || attempts.len() == 0 {
-            continue;
-        }
-
-        let instance_meta = instance_metas.1.get(instance_meta_id).unwrap();
-        if instance_meta.privacy_type != PrivacyType::Public {
-            continue;
-        }
-
-        let has_killed_all_encounters = INSTANCE_ENCOUNTERS
-            .get(&instance_meta.map_id)
-            .unwrap()
-            .iter()
-            .all(|encounter_id| attempts.iter().any(|attempt| attempt.encounter_id == *encounter_id && attempt.rankable));
-        let all_difficulties_are_same = attempts.iter().all(|attempt| attempt.difficulty_id == attempts[0].difficulty_id);
-        if !has_killed_all_encounters || !all_difficulties_are_same {
-            continue;
-        }
-
-        let start = attempts.iter().map(|attempt| attempt.start_ts).min().unwrap();
-        let end = attempts.iter().map(|attempt| attempt.end_ts).max().unwrap();
-        let (guild_id, guild_name) = instance_meta
-            .participants
-            .find_instance_guild(db_main, armory, instance_meta.end_ts.unwrap_or(instance_meta.start_ts))
-            .map(|guild| (guild.id, guild.name))
-            .unwrap_or((0, "Pug Raid".to_string()));
-
-        speed_runs.push(SpeedRun {
-            instance_meta_id: *instance_meta_id,
-            map_id: instance_meta.map_id,
-            guild_id,
-            guild_name,
-            server_id: instance_meta.server_id,
-            duration: end - start,
-            difficulty_id: attempts[0].difficulty_id,
-            season_index: attempts[0].season_index,
-        });
-    }
-}
-
-fn calculate_speed_kills(
-    instance_metas: Arc<RwLock<(u32, HashMap<u32, InstanceMeta>)>>, instance_kill_attempts: Arc<RwLock<(u32, HashMap<u32, Vec<InstanceAttempt>>)>>, speed_kills: Arc<RwLock<Vec<SpeedKill>>>, db_main: &mut impl Select, armory: &Armory,
-) {
-    let kill_attempts = instance_kill_attempts.read().unwrap();
-    let instance_metas = instance_metas.read().unwrap();
-    let mut speed_kills = speed_kills.write().unwrap();
-    let already_calculated_speed_kills: Vec<u32> = speed_kills.iter().map(|speed_kill| speed_kill.attempt_id).collect();
-
-    for (instance_meta_id, attempts) in kill_attempts.1.iter() {
-        if !instance_metas.1.contains_key(instance_meta_id) {
-            continue;
-        }
-        let instance_meta = instance_metas.1.get(instance_meta_id).unwrap();
-        if instance_meta.privacy_type != PrivacyType::Public {
-            continue;
-        }
 
-        let (guild_id, guild_name) = instance_meta
-            .participants
-            .find_instance_guild(db_main, armory, instance_meta.end_ts.unwrap_or(instance_meta.start_ts))
-            .map(|guild| (guild.id, guild.name))
-            .unwrap_or((0, "Pug Raid".to_string()));
-
-        for attempt in attempts.iter().filter(|attempt| attempt.rankable && !already_calculated_speed_kills.contains(&attempt.attempt_id)) {
-            speed_kills.push(SpeedKill {
-                instance_meta_id: *instance_meta_id,
-                attempt_id: attempt.attempt_id,
-                encounter_id: attempt.encounter_id,
-                guild_id,
-                guild_name: guild_name.clone(),
-                server_id: instance_meta.server_id,
-                duration: attempt.end_ts - attempt.start_ts,
-                difficulty_id: attempt.difficulty_id,
-                season_index: attempt.season_index,
-            });
+    /// Replaces the participation of a character that was merged into another character.
+    pub fn merge_participants(&self, source_character_id: u32, target_character_id: u32) {
+        let mut instance_metas = self.instance_metas.write().unwrap();
+        for instance_meta in instance_metas.1.values_mut().filter(|instance_meta| instance_meta.participants.contains(&source_character_id)) {
+            instance_meta.participants.retain(|character_id| *character_id != source_character_id && *character_id != target_character_id);
+            instance_meta.participants.push(target_character_id);
         }
     }
 }
 
-fn calculate_season_index(ts: u64) -> u8 {
-    let starting_unix_time = 1731470400000; // Wed Nov 13 2024 04:00:00 GMT+0000 1st instance reset after CC2
-    let one_week_in_ms = 604800000;
-
-    if ts < starting_unix_time {
-        return 0;
-    }
-
-    (1 + (ts - starting_unix_time) / one_week_in_ms) as u8
-}
-
-
-
-fn update_instance_rankings_dps(db_main: &mut (impl Execute + Select), armory: &Armory) {
-    let results = db_main
-        .select(
-            "SELECT A.id, A.character_id, B.encounter_id, A.attempt_id, A.damage, (B.end_ts - B.start_ts) as duration,
-             B.instance_meta_id, C.map_difficulty, B.start_ts, D.server_id
-             FROM instance_ranking_damage A
-             JOIN instance_attempt B ON A.attempt_id = B.id
-             JOIN instance_raid C ON B.instance_meta_id = C.instance_meta_id
-             JOIN instance_meta D ON B.instance_meta_id = D.id
-             WHERE B.rankable = 1 AND B.start_ts >= 1731470400000
-             AND A.id not in (select id from ranking_results_damage)",
-            |mut row| {
-                let id: u32 = row.take(0).unwrap();
-                let character_id: u32 = row.take(1).unwrap();
-                let encounter_id: u32 = row.take(2).unwrap();
-                let attempt_id: u32 = row.take(3).unwrap();
-                let amount: u32 = row.take(4).unwrap();
-                let duration: u64 = row.take(5).unwrap();
-                let instance_meta_id: u32 = row.take(6).unwrap();
-                let difficulty_id: u8 = row.take(7).unwrap();
-                let start_ts: u64 = row.take(8).unwrap();
-                let server_id: u32 = row.take(9).unwrap();
-                (id, character_id, encounter_id, attempt_id, amount, duration, instance_meta_id, difficulty_id, start_ts, server_id)
+// Instances that were processed before event segments existed are converted a few at a time
+fn convert_legacy_instance_zips(instance_metas: Arc<RwLock<(u32, HashMap<u32, InstanceMeta>)>>) {
+    let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
+    let legacy_zips = {
+        let instance_metas = instance_metas.read().unwrap();
+        instance_metas
+            .1
+            .values()
+            .map(|instance_meta| (instance_meta.server_id, instance_meta.instance_meta_id))
+            .filter(|(server_id, instance_meta_id)| Path::new(&format!("{}/{}/{}.zip", storage_path, server_id, instance_meta_id)).exists())
+            .take(25)
+            .collect::<Vec<(u32, u32)>>()
+    };
+
+    // The zips are archived rather than deleted, failed ones separately such that they are not retried on every update
+    for (server_id, instance_meta_id) in legacy_zips {
+        let zip_path = format!("{}/{}/{}.zip", storage_path, server_id, instance_meta_id);
+        let segment_path = get_event_segment_path(&storage_path, server_id, instance_meta_id);
+        // A segment that already exists is newer than the zip
+        let archive_dir = if Path::new(&segment_path).exists() {
+            format!("{}/{}/legacy", storage_path, server_id)
+        } else {
+            match convert_zip_to_event_segment(&zip_path, &segment_path) {
+                Ok(_) => format!("{}/{}/legacy", storage_path, server_id),
+                Err(err) => {
+                    println!("[Update loop] Failed to convert {} to an event segment: {:?}", zip_path, err);
+                    format!("{}/{}/legacy/failed", storage_path, server_id)
+                },
             }
-        );
-
-    if results.is_empty() {
-        return;
-    }
-
-    // Build value strings
-    let mut value_strings = Vec::with_capacity(results.len());
-
-    for (id, character_id, encounter_id, attempt_id, amount, duration, instance_meta_id, difficulty_id, start_ts, server_id) in results {
-        let character_info = armory.get_character_moment(db_main, character_id, start_ts);
-
-        let hero_class_id = character_info
-            .as_ref()
-            .map(|char_history| char_history.character_info.hero_class_id)
-            .unwrap_or(0);
-
-        if hero_class_id == 0 || hero_class_id == 12 {
-            continue; // ignore unknown class
-        }
-
-        let character_name = character_info
-            .as_ref()
-            .map(|char_history| char_history.character_name.clone())
-            .unwrap_or_else(|| "Unknown".to_string());
-
-        let character_spec = character_info
-            .as_ref()
-            .and_then(|char_history| char_history.character_info.talent_specialization.as_ref().map(|talents| get_talent_tree(&talents) + 1))
-            .unwrap_or(0);
-
-        let season_index = calculate_season_index(start_ts);
-
-        // Create value string for this record
-        value_strings.push(format!(
-            "({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, '{}')",
-            id, encounter_id, server_id, character_id, hero_class_id, instance_meta_id,
-            attempt_id, amount, duration, difficulty_id, character_spec, season_index,
-            character_name
-        ));
-    }
-
-    // Execute batch inserts with max 1000 records per batch
-    const BATCH_SIZE: usize = 1000;
-
-    for chunk in value_strings.chunks(BATCH_SIZE) {
-        if !chunk.is_empty() {
-            let batch_insert = format!(
-                "INSERT INTO ranking_results_damage (id, encounter_id, server_id, character_id, hero_class_id,
-                instance_meta_id, attempt_id, amount, duration, difficulty_id, character_spec, season_index, character_name)
-                VALUES {}",
-                chunk.join(",")
-            );
-
-            db_main.execute_one(&batch_insert);
+        };
+        if let Err(err) = std::fs::create_dir_all(&archive_dir).and_then(|_| std::fs::rename(&zip_path, format!("{}/{}.zip", archive_dir, instance_meta_id))) {
+            println!("[Update loop] Failed to archive {}: {}", zip_path, err);
         }
     }
 }
 
-fn update_instance_rankings_hps(db_main: &mut (impl Execute + Select), armory: &Armory) {
-    let results = db_main
-        .select(
-            "SELECT A.id, A.character_id, B.encounter_id, A.attempt_id, A.heal, (B.end_ts - B.start_ts) as duration,
-             B.instance_meta_id, C.map_difficulty, B.start_ts, D.server_id
-             FROM instance_ranking_heal A
-             JOIN instance_attempt B ON A.attempt_id = B.id
-             JOIN instance_raid C ON B.instance_meta_id = C.instance_meta_id
-             JOIN instance_meta D ON B.instance_meta_id = D.id
-             WHERE B.rankable = 1 AND B.start_ts >= 1731470400000
-             AND A.id not in (select id from ranking_results_heal)",
-            |mut row| {
-                let id: u32 = row.take(0).unwrap();
-                let character_id: u32 = row.take(1).unwrap();
-                let encounter_id: u32 = row.take(2).unwrap();
-                let attempt_id: u32 = row.take(3).unwrap();
-                let amount: u32 = row.take(4).unwrap();
-                let duration: u64 = row.take(5).unwrap();
-                let instance_meta_id: u32 = row.take(6).unwrap();
-                let difficulty_id: u8 = row.take(7).unwrap();
-                let start_ts: u64 = row.take(8).unwrap();
-                let server_id: u32 = row.take(9).unwrap();
-                (id, character_id, encounter_id, attempt_id, amount, duration, instance_meta_id, difficulty_id, start_ts, server_id)
-            }
-        );
-
-    if results.is_empty() {
-        return;
-    }
-
-    // Build value strings
-    let mut value_strings = Vec::with_capacity(results.len());
-
-    for (id, character_id, encounter_id, attempt_id, amount, duration, instance_meta_id, difficulty_id, start_ts, server_id) in results {
-        let character_info = armory.get_character_moment(db_main, character_id, start_ts);
-
-        let hero_class_id = character_info
-            .as_ref()
-            .map(|char_history| char_history.character_info.hero_class_id)
-            .unwrap_or(0);
-
-        if hero_class_id == 0 || hero_class_id == 12 {
-            continue; // ignore unknown class
-        }
-
-        let character_name = character_info
-            .as_ref()
-            .map(|char_history| char_history.character_name.clone())
-            .unwrap_or_else(|| "Unknown".to_string());
-
-        let character_spec = character_info
-            .as_ref()
-            .and_then(|char_history| char_history.character_info.talent_specialization.as_ref().map(|talents| get_talent_tree(&talents) + 1))
-            .unwrap_or(0);
-
-        let season_index = calculate_season_index(start_ts);
-
-        // Create value string for this record
-        value_strings.push(format!(
-            "({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, '{}')",
-            id, encounter_id, server_id, character_id, hero_class_id, instance_meta_id,
-            attempt_id, amount, duration, difficulty_id, character_spec, season_index,
-            character_name
-        ));
-    }
-
-    // Execute batch inserts with max 1000 records per batch
-    const BATCH_SIZE: usize = 1000;
-
-    for chunk in value_strings.chunks(BATCH_SIZE) {
-        if !chunk.is_empty() {
-            let batch_insert = format!(
-                "INSERT INTO ranking_results_heal (id, encounter_id, server_id, character_id, hero_class_id,
-                instance_meta_id, attempt_id, amount, duration, difficulty_id, character_spec, season_index, character_name)
-                VALUES {}",
-                chunk.join(",")
-            );
-
-            db_main.execute_one(&batch_insert);
-        }
-    }
+fn get_last_attempt_id(db_main: &mut impl Select) -> u32 {
+    db_main.select_value("SELECT IFNULL(MAX(id), 0) FROM instance_attempt", |mut row| row.take::<u32, usize>(0).unwrap()).unwrap_or(0)
 }
 
 fn delete_old_character_data(db_main: &mut (impl Select + Execute)) {
@@ -8,9 +8,25 @@ pub enum Role {
 }
 
 impl Role {
+    pub fn new(role: u8) -> Option<Self> {
+        match role {
+            1 => Some(Self::Tank),
+            2 => Some(Self::Healer),
+            3 => Some(Self::Dps),
+            _ => None,
+        }
+    }
+
+    // Roles of spec rules take precedence over the role that is guessed from the talent tree
+    pub fn from_detected_spec(hero_class_id: u8, talent_tree: u8, role: Option<u8>) -> Self {
+        role.and_then(Self::new).unwrap_or_else(|| Self::from_class_tree(hero_class_id, talent_tree))
+    }
+
     pub fn from_class_talent_string(hero_class_id: u8, talent_str: &str) -> Self {
-        let tree = get_talent_tree(talent_str);
+        Self::from_class_tree(hero_class_id, get_talent_tree(talent_str))
+    }
 
+    fn from_class_tree(hero_class_id: u8, tree: u8) -> Self {
         // TODO: This only handles TBC and Vanilla ish
         match hero_class_id {
             1 => {
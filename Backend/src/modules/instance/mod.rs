@@ -10,7 +10,7 @@ pub use self::material::Instance;
 mod domain_value;
 mod dto;
 mod material;
-mod tools;
+pub(crate) mod tools;
 pub mod transfer;
 
 #[cfg(test)]
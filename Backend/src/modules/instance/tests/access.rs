@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use crate::modules::account::domain_value::GroupRole;
+use crate::modules::account::material::{Account, Group, GroupMember};
+use crate::modules::instance::domain_value::{InstanceMeta, MetaType, PrivacyType};
+use crate::modules::instance::dto::InstanceFailure;
+use crate::modules::instance::tools::{can_access_instance, can_list_instance, ExportMeta, InstanceAccess};
+use crate::modules::instance::Instance;
+
+const UPLOADER: u32 = 7;
+const GROUP_ID: u32 = 4;
+const GROUP_MEMBER: u32 = 8;
+const INVITED_MEMBER: u32 = 9;
+
+fn account_with_group() -> Account {
+    let account = Account::default();
+    let mut members = HashMap::new();
+    for (member_id, accepted) in [(UPLOADER, true), (GROUP_MEMBER, true), (INVITED_MEMBER, false)].iter() {
+        members.insert(
+            *member_id,
+            GroupMember {
+                member_id: *member_id,
+                role: GroupRole::Member,
+                accepted: *accepted,
+            },
+        );
+    }
+    account.groups.write().unwrap().insert(
+        GROUP_ID,
+        Group {
+            id: GROUP_ID,
+            name: "Raid".to_string(),
+            members,
+        },
+    );
+    account
+}
+
+fn instance_meta(instance_meta_id: u32, privacy_type: PrivacyType) -> InstanceMeta {
+    InstanceMeta {
+        instance_meta_id,
+        server_id: 1,
+        start_ts: 1000,
+        end_ts: Some(2000),
+        map_id: 409,
+        expired: None,
+        participants: vec![1, 2],
+        instance_specific: MetaType::Raid { map_difficulty: 9 },
+        uploaded_user: UPLOADER,
+        upload_id: 3,
+        privacy_type,
+        updated_specs: false,
+    }
+}
+
+#[test]
+fn test_public_instance_is_accessible_by_anyone() {
+    let account = Account::default();
+    let instance_meta = instance_meta(1, PrivacyType::Public);
+    assert!(can_access_instance(&account, &instance_meta, None));
+    assert!(can_access_instance(&account, &instance_meta, Some(UPLOADER + 1)));
+    assert!(can_access_instance(&account, &instance_meta, Some(UPLOADER)));
+}
+
+#[test]
+fn test_not_listed_instance_is_accessible_by_anyone_but_only_listed_to_uploader() {
+    let account = Account::default();
+    let instance_meta = instance_meta(1, PrivacyType::NotListed);
+    assert!(can_access_instance(&account, &instance_meta, None));
+    assert!(can_access_instance(&account, &instance_meta, Some(UPLOADER + 1)));
+    assert!(!can_list_instance(&account, &instance_meta, None));
+    assert!(!can_list_instance(&account, &instance_meta, Some(UPLOADER + 1)));
+    assert!(can_list_instance(&account, &instance_meta, Some(UPLOADER)));
+}
+
+#[test]
+fn test_only_groups_instance_is_only_accessible_by_uploader_and_group_members() {
+    let account = account_with_group();
+    let group_instance_meta = instance_meta(1, PrivacyType::OnlyGroups(GROUP_ID));
+    let other_group_instance_meta = instance_meta(2, PrivacyType::OnlyGroups(GROUP_ID + 1));
+    assert!(!can_access_instance(&account, &group_instance_meta, None));
+    assert!(!can_access_instance(&account, &group_instance_meta, Some(UPLOADER + 10)));
+    assert!(!can_access_instance(&account, &group_instance_meta, Some(INVITED_MEMBER)));
+    assert!(can_access_instance(&account, &group_instance_meta, Some(GROUP_MEMBER)));
+    assert!(can_access_instance(&account, &group_instance_meta, Some(UPLOADER)));
+    assert!(!can_access_instance(&account, &other_group_instance_meta, Some(GROUP_MEMBER)));
+}
+
+#[test]
+fn test_check_instance_access() {
+    let account = account_with_group();
+    let instance = Instance::default();
+    {
+        let mut instance_metas = instance.instance_metas.write().unwrap();
+        instance_metas.1.insert(1, instance_meta(1, PrivacyType::Public));
+        instance_metas.1.insert(2, instance_meta(2, PrivacyType::NotListed));
+        instance_metas.1.insert(4, instance_meta(4, PrivacyType::OnlyGroups(GROUP_ID)));
+    }
+
+    assert!(instance.check_instance_access(&account, 1, None).is_ok());
+    assert!(instance.check_instance_access(&account, 2, Some(UPLOADER)).is_ok());
+    assert!(instance.check_instance_access(&account, 2, None).is_ok());
+    assert!(matches!(instance.check_instance_access(&account, 3, Some(UPLOADER)), Err(InstanceFailure::InvalidInput)));
+    assert!(instance.check_instance_access(&account, 4, Some(GROUP_MEMBER)).is_ok());
+    assert!(matches!(instance.check_instance_access(&account, 4, Some(INVITED_MEMBER)), Err(InstanceFailure::AccessDenied)));
+}
+
+#[test]
+fn test_export_listed_meta() {
+    let account = account_with_group();
+    let instance = Instance::default();
+    {
+        let mut instance_metas = instance.instance_metas.write().unwrap();
+        instance_metas.1.insert(1, instance_meta(1, PrivacyType::Public));
+        instance_metas.1.insert(2, instance_meta(2, PrivacyType::NotListed));
+        instance_metas.1.insert(4, instance_meta(4, PrivacyType::OnlyGroups(GROUP_ID)));
+    }
+
+    let listed_instance_meta_ids = |current_user: Option<u32>| {
+        let mut instance_meta_ids = instance.export_listed_meta(&account, 0, current_user).into_iter().map(|instance_meta| instance_meta.instance_meta_id).collect::<Vec<u32>>();
+        instance_meta_ids.sort_unstable();
+        instance_meta_ids
+    };
+    assert_eq!(listed_instance_meta_ids(None), vec![1]);
+    assert_eq!(listed_instance_meta_ids(Some(GROUP_MEMBER)), vec![1, 4]);
+    assert_eq!(listed_instance_meta_ids(Some(UPLOADER)), vec![1, 2, 4]);
+    assert!(instance.export_listed_meta(&account, 1, Some(UPLOADER)).is_empty());
+}
@@ -0,0 +1,53 @@
+use crate::modules::instance::dto::{EventExportFilter, MeterUnit};
+use crate::modules::instance::tools::filter_exported_events;
+use serde_json::{from_str, Value};
+
+const SPELL_DAMAGE: [&str; 3] = [
+    "[2,1000,1,[1,5],[0,900,11502],133,4,[[500,4,0,0,0]]]",
+    "[4,2000,3,[1,6],[0,900,11502],133,2,[[300,4,0,0,0]]]",
+    "[6,3000,5,[0,77,416,[1,5]],[0,900,11502],17,2,[[40,1,0,0,0]]]",
+];
+
+fn events() -> Vec<Value> {
+    SPELL_DAMAGE.iter().map(|event| from_str::<Value>(event).unwrap()).collect()
+}
+
+#[test]
+fn test_filter_by_source() {
+    let filter = EventExportFilter {
+        source: Some(MeterUnit { is_player: true, unit_id: 5, owner_id: None }),
+        ..EventExportFilter::default()
+    };
+    let page = filter_exported_events(events(), 13, &filter);
+    assert_eq!(page.events.len(), 2);
+    assert_eq!(page.next_offset, None);
+}
+
+#[test]
+fn test_filter_by_target() {
+    let filter = EventExportFilter {
+        target: Some(MeterUnit { is_player: false, unit_id: 11502, owner_id: None }),
+        ..EventExportFilter::default()
+    };
+    assert_eq!(filter_exported_events(events(), 13, &filter).events.len(), 3);
+}
+
+#[test]
+fn test_pagination() {
+    let filter = EventExportFilter {
+        limit: Some(2),
+        ..EventExportFilter::default()
+    };
+    let page = filter_exported_events(events(), 13, &filter);
+    assert_eq!(page.events.len(), 2);
+    assert_eq!(page.next_offset, Some(2));
+
+    let filter = EventExportFilter {
+        offset: page.next_offset,
+        limit: Some(2),
+        ..EventExportFilter::default()
+    };
+    let page = filter_exported_events(events(), 13, &filter);
+    assert_eq!(page.events.len(), 1);
+    assert_eq!(page.next_offset, None);
+}
@@ -0,0 +1,50 @@
+use crate::modules::armory::dto::{CharacterDto, CharacterGearDto, CharacterHistoryDto, CharacterInfoDto};
+use crate::modules::armory::tools::SetCharacter;
+use crate::modules::armory::Armory;
+
+/// A character with a history of the hero class at the timestamp, such that its attempts are ranked.
+pub fn create_character(armory: &Armory, conn: &mut crate::mysql::Conn, server_id: u32, server_uid: u64, hero_class_id: u8, timestamp: u64) -> u32 {
+    let character_dto = CharacterDto {
+        server_uid,
+        character_history: Some(CharacterHistoryDto {
+            character_info: CharacterInfoDto {
+                gear: CharacterGearDto {
+                    head: None,
+                    neck: None,
+                    shoulder: None,
+                    back: None,
+                    chest: None,
+                    shirt: None,
+                    tabard: None,
+                    wrist: None,
+                    main_hand: None,
+                    off_hand: None,
+                    ternary_hand: None,
+                    glove: None,
+                    belt: None,
+                    leg: None,
+                    boot: None,
+                    ring1: None,
+                    ring2: None,
+                    trinket1: None,
+                    trinket2: None,
+                },
+                hero_class_id,
+                level: 60,
+                gender: false,
+                profession1: None,
+                profession2: None,
+                talent_specialization: None,
+                race_id: 1,
+            },
+            character_name: format!("Character{}", server_uid),
+            character_title: None,
+            profession_skill_points1: None,
+            profession_skill_points2: None,
+            facial: None,
+            arena_teams: Vec::new(),
+            character_guild: None,
+        }),
+    };
+    armory.set_character(conn, server_id, character_dto, timestamp).unwrap().id
+}
@@ -0,0 +1,53 @@
+use crate::params;
+use crate::util::database::{Execute, Select};
+
+/// A raid uploaded by the member, its kills are added by `create_kill`.
+pub fn create_instance(conn: &mut crate::mysql::Conn, member_id: u32, server_id: u32, map_id: u16, map_difficulty: u8, start_ts: u64) -> u32 {
+    let hash = format!("{}_{}_{}", member_id, server_id, start_ts);
+    assert!(conn.execute_wparams(
+        "INSERT INTO `instance_uploads` (`member_id`, `timestamp`, `hash`) VALUES (:member_id, :timestamp, :hash)",
+        params!("member_id" => member_id, "timestamp" => start_ts / 1000, "hash" => hash.clone())
+    ));
+    let upload_id: u32 = conn
+        .select_wparams_value(
+            "SELECT id FROM `instance_uploads` WHERE `member_id`=:member_id AND `hash`=:hash",
+            |mut row| row.take(0).unwrap(),
+            params!("member_id" => member_id, "hash" => hash),
+        )
+        .unwrap();
+
+    assert!(conn.execute_wparams(
+        "INSERT INTO instance_meta (`server_id`, `start_ts`, `instance_id`, `map_id`, `upload_id`, `privacy_type`, `updated_specs`) VALUES (:server_id, :start_ts, :upload_id, :map_id, :upload_id, 0, 1)",
+        params!("server_id" => server_id, "start_ts" => start_ts, "map_id" => map_id, "upload_id" => upload_id)
+    ));
+    let instance_meta_id: u32 = conn
+        .select_wparams_value("SELECT id FROM instance_meta WHERE upload_id=:upload_id", |mut row| row.take(0).unwrap(), params!("upload_id" => upload_id))
+        .unwrap();
+    assert!(conn.execute_wparams(
+        "INSERT INTO instance_raid (`instance_meta_id`, `map_difficulty`) VALUES (:instance_meta_id, :map_difficulty)",
+        params!("instance_meta_id" => instance_meta_id, "map_difficulty" => map_difficulty)
+    ));
+    instance_meta_id
+}
+
+/// A rankable kill with the damage done by each character.
+pub fn create_kill(conn: &mut crate::mysql::Conn, instance_meta_id: u32, encounter_id: u32, start_ts: u64, end_ts: u64, damage: &[(u32, u32)]) -> u32 {
+    let params = params!("instance_meta_id" => instance_meta_id, "encounter_id" => encounter_id, "start_ts" => start_ts, "end_ts" => end_ts);
+    assert!(conn.execute_wparams(
+        "INSERT INTO `instance_attempt` (`instance_meta_id`, `encounter_id`, `start_ts`, `end_ts`, `is_kill`) VALUES (:instance_meta_id, :encounter_id, :start_ts, :end_ts, 1)",
+        params.clone()
+    ));
+    let attempt_id: u32 = conn
+        .select_wparams_value(
+            "SELECT id FROM `instance_attempt` WHERE instance_meta_id=:instance_meta_id AND encounter_id=:encounter_id AND start_ts=:start_ts AND end_ts=:end_ts",
+            |mut row| row.take(0).unwrap(),
+            params,
+        )
+        .unwrap();
+    conn.execute_batch_wparams(
+        "INSERT INTO `instance_ranking_damage` (`character_id`, `attempt_id`, `damage`, `hits`, `max_hit`) VALUES (:character_id, :attempt_id, :damage, 0, 0)",
+        damage.to_vec(),
+        move |(character_id, damage)| params!("character_id" => character_id, "attempt_id" => attempt_id, "damage" => damage),
+    );
+    attempt_id
+}
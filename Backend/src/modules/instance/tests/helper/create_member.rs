@@ -0,0 +1,6 @@
+use crate::modules::account::material::Account;
+use crate::modules::account::tools::Create;
+
+pub fn create_member(account: &Account, conn: &mut crate::mysql::Conn, nickname: &str) -> u32 {
+    account.create(conn, &format!("{}@abc.de", nickname), nickname, "Password123456Password123456Password123456").unwrap().member_id
+}
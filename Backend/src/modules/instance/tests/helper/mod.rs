@@ -0,0 +1,9 @@
+pub use self::create_character::create_character;
+pub use self::create_instance::{create_instance, create_kill};
+pub use self::create_member::create_member;
+pub use self::season::all_time_season;
+
+mod create_character;
+mod create_instance;
+mod create_member;
+mod season;
@@ -0,0 +1,13 @@
+use crate::modules::data::domain_value::Season;
+
+/// A season of all servers and maps, such that every attempt is ranked within it.
+pub fn all_time_season(id: u32) -> Season {
+    Season {
+        id,
+        server_id: 0,
+        name: "All time".to_string(),
+        start_ts: 0,
+        end_ts: None,
+        map_ids: Vec::new(),
+    }
+}
@@ -0,0 +1,63 @@
+use crate::modules::instance::dto::{MeterFilter, MeterUnit};
+use crate::modules::instance::tools::{aggregate_meter_events, filter_meter_records};
+use serde_json::{from_str, Value};
+
+const MELEE_DAMAGE: [&str; 3] = [
+    "[1,1000,[1,5],[0,900,11502],6,[[100,1,0,0,0]]]",
+    "[2,2000,[1,5],[0,900,11502],6,[[250,1,0,0,0]]]",
+    "[3,3000,[0,77,416,[1,5]],[0,900,11502],2,[[40,1,0,0,0]]]",
+];
+const HEAL: [&str; 2] = ["[4,1500,3,[1,7],[1,5],2061,2,2,1200,800,0,0,0]", "[5,2500,4,[1,7],[1,5],2061,2,2,1000,1000,0,0,0]"];
+
+fn parse_events(event_type: u8, events: &[&str]) -> Vec<(u8, Value)> {
+    events.iter().filter_map(|event| from_str::<Value>(event).ok()).map(|event| (event_type, event)).collect()
+}
+
+fn player(unit_id: u64) -> MeterUnit {
+    MeterUnit { is_player: true, unit_id, owner_id: None }
+}
+
+#[test]
+fn test_aggregate_melee_damage() {
+    let records = aggregate_meter_events(parse_events(12, &MELEE_DAMAGE).iter().map(|(event_type, event)| (*event_type, event)), None, None);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].source, player(5));
+    assert_eq!(records[0].count, 2);
+    assert_eq!(records[0].amount, 350);
+    assert_eq!(records[1].source.owner_id, Some(5));
+    assert_eq!(records[1].amount, 40);
+}
+
+#[test]
+fn test_aggregate_heal_in_time_range() {
+    let records = aggregate_meter_events(parse_events(14, &HEAL).iter().map(|(event_type, event)| (*event_type, event)), Some(2000), Some(3000));
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].spell_id, 2061);
+    assert_eq!(records[0].amount, 1000);
+    assert_eq!(records[0].effective, 1000);
+}
+
+#[test]
+fn test_malformed_events_are_skipped() {
+    let events = vec![(12, Value::Null), (13, from_str::<Value>("[1,2]").unwrap()), (14, from_str::<Value>(HEAL[0]).unwrap())];
+    let records = aggregate_meter_events(events.iter().map(|(event_type, event)| (*event_type, event)), None, None);
+    assert_eq!(records.len(), 1);
+}
+
+#[test]
+fn test_filter_includes_pets_of_player() {
+    let mut records = aggregate_meter_events(parse_events(12, &MELEE_DAMAGE).iter().map(|(event_type, event)| (*event_type, event)), None, None);
+    let filter = MeterFilter {
+        source: Some(player(5)),
+        ..MeterFilter::default()
+    };
+    filter_meter_records(&mut records, &filter);
+    assert_eq!(records.len(), 2);
+
+    let filter = MeterFilter {
+        source: Some(player(6)),
+        ..MeterFilter::default()
+    };
+    filter_meter_records(&mut records, &filter);
+    assert!(records.is_empty());
+}
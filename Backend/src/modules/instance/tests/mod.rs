@@ -1 +1,8 @@
-
+mod access;
+mod event_export;
+mod helper;
+mod meter;
+mod moderation;
+mod ranking_parse;
+mod ranking_results;
+mod speed_run;
@@ -0,0 +1,125 @@
+use super::helper::{all_time_season, create_character, create_instance, create_kill, create_member};
+use crate::modules::account::domain_value::Role;
+use crate::modules::account::material::Account;
+use crate::modules::account::tools::Permissions;
+use crate::modules::armory::Armory;
+use crate::modules::data::Data;
+use crate::modules::instance::domain_value::ModerationAction;
+use crate::modules::instance::dto::InstanceFailure;
+use crate::modules::instance::tools::{flag_attempt_anomalies_in_range, modified_z_scores, update_ranking_results, ModerateAttempts, RankingScope, ANOMALY_MIN_SAMPLES, ANOMALY_THRESHOLD};
+use crate::modules::instance::Instance;
+use crate::params;
+use crate::tests::TestContainer;
+use crate::util::database::Select;
+
+fn attempt_state(conn: &mut crate::mysql::Conn, attempt_id: u32) -> Option<(u8, Option<u8>, usize)> {
+    conn.select_wparams_value(
+        "SELECT A.rankable, B.status, (SELECT COUNT(*) FROM ranking_results_damage WHERE attempt_id = A.id) FROM instance_attempt A LEFT JOIN instance_attempt_flag B ON A.id = B.attempt_id WHERE A.id = :attempt_id",
+        |mut row| (row.take(0).unwrap(), row.take(1).unwrap(), row.take::<u64, usize>(2).unwrap() as usize),
+        params!("attempt_id" => attempt_id),
+    )
+}
+
+#[test]
+fn moderation_action() {
+    for action in [ModerationAction::Approve, ModerationAction::Unrank, ModerationAction::Delete].iter() {
+        assert_eq!(ModerationAction::new(action.to_u8()), Some(*action));
+    }
+    assert!(ModerationAction::new(0).is_none());
+}
+
+#[test]
+fn z_scores_require_samples() {
+    let values = vec![100.0, 110.0, 90.0, 5000.0];
+    assert!(values.len() < ANOMALY_MIN_SAMPLES);
+    assert_eq!(modified_z_scores(&values), vec![0.0; 4]);
+    assert_eq!(modified_z_scores(&[50.0; 12]), vec![0.0; 12]);
+}
+
+#[test]
+fn z_scores_find_outliers() {
+    let mut values = vec![100.0, 104.0, 96.0, 102.0, 98.0, 101.0, 99.0, 103.0, 97.0, 100.0];
+    values.push(400.0);
+    values.push(20.0);
+    let scores = modified_z_scores(&values);
+    assert!(scores[..10].iter().all(|score| score.abs() < ANOMALY_THRESHOLD));
+    assert!(scores[10] > ANOMALY_THRESHOLD);
+    assert!(scores[11] < -ANOMALY_THRESHOLD);
+}
+
+#[test]
+fn flag_and_moderate_attempts() {
+    let container = TestContainer::new(true);
+    let (mut conn, _dns, _node) = container.run();
+    let storage_path = std::env::temp_dir().join(format!("moderation_{}", std::process::id()));
+    std::env::set_var("INSTANCE_STORAGE_PATH", storage_path.to_str().unwrap());
+
+    let account = Account::default();
+    let armory = Armory::default();
+    let data = Data::default();
+    let instance = Instance::default();
+    data.seasons.write().unwrap().insert(4, all_time_season(4));
+
+    let uploader = create_member(&account, &mut conn, "uploader");
+    let moderator = create_member(&account, &mut conn, "moderator");
+    let other_member = create_member(&account, &mut conn, "other");
+    account.grant_role(&mut conn, None, moderator, Role::Moderator, 0).unwrap();
+
+    // Every kill is its own raid, such that deleting one leaves the others
+    let start_ts = time_util::now() * 1000;
+    let damages: Vec<u32> = (0..10).map(|index| 600000 + index * 10000).chain(vec![6000000, 6100000, 6200000]).collect();
+    let mut kills = Vec::new();
+    for (index, damage) in damages.into_iter().enumerate() {
+        let kill_ts = start_ts + index as u64 * 3600000;
+        let warrior = create_character(&armory, &mut conn, 3, 8000 + index as u64, 1, kill_ts);
+        let instance_meta_id = create_instance(&mut conn, uploader, 3, 409, 0, kill_ts);
+        let attempt_id = create_kill(&mut conn, instance_meta_id, 1, kill_ts, kill_ts + 60000, &[(warrior, damage)]);
+        update_ranking_results(&mut conn, &armory, &data, RankingScope::Attempt(attempt_id));
+        kills.push((instance_meta_id, attempt_id));
+    }
+    let (approved, unranked, deleted) = (kills[10].1, kills[11].1, kills[12].1);
+
+    // Only the outliers are flagged and only once
+    assert_eq!(flag_attempt_anomalies_in_range(&mut conn, 0, kills[9].1), 0);
+    assert_eq!(flag_attempt_anomalies_in_range(&mut conn, 0, u32::MAX), 3);
+    assert_eq!(flag_attempt_anomalies_in_range(&mut conn, 0, u32::MAX), 0);
+    let queue = instance.get_moderation_queue(&mut conn);
+    assert_eq!(queue.iter().map(|flagged_attempt| flagged_attempt.attempt_id).collect::<Vec<u32>>(), vec![approved, unranked, deleted]);
+    assert!(queue.iter().all(|flagged_attempt| flagged_attempt.reasons.len() == 1 && flagged_attempt.reasons[0].starts_with("Damage per second of character")));
+    assert!(kills[..10].iter().all(|(_, attempt_id)| attempt_state(&mut conn, *attempt_id) == Some((1, None, 1))));
+
+    // Approve
+    assert!(instance.moderate_attempt(&mut conn, &account, moderator, approved, ModerationAction::Approve).is_ok());
+    assert_eq!(attempt_state(&mut conn, approved), Some((1, Some(ModerationAction::Approve.to_u8()), 1)));
+
+    // Unrank
+    assert!(instance.moderate_attempt(&mut conn, &account, moderator, unranked, ModerationAction::Unrank).is_ok());
+    assert_eq!(attempt_state(&mut conn, unranked), Some((0, Some(ModerationAction::Unrank.to_u8()), 0)));
+
+    // Delete, which requires the uploader or a moderator
+    assert!(matches!(instance.moderate_attempt(&mut conn, &account, other_member, deleted, ModerationAction::Delete), Err(InstanceFailure::AccessDenied)));
+    assert_eq!(attempt_state(&mut conn, deleted), Some((1, Some(0), 1)));
+    assert!(instance.moderate_attempt(&mut conn, &account, moderator, deleted, ModerationAction::Delete).is_ok());
+    assert_eq!(attempt_state(&mut conn, deleted), None);
+    assert!(conn
+        .select_wparams_value("SELECT id FROM instance_meta WHERE id = :instance_meta_id", |mut row| row.take::<u32, usize>(0).unwrap(), params!("instance_meta_id" => kills[12].0))
+        .is_none());
+    assert!(matches!(instance.moderate_attempt(&mut conn, &account, moderator, deleted, ModerationAction::Approve), Err(InstanceFailure::InvalidInput)));
+    assert!(instance.get_moderation_queue(&mut conn).is_empty());
+
+    // The audit log keeps the decisions, also of the deleted attempt
+    let audit_log = instance.get_moderation_audit_log(&mut conn, None);
+    assert_eq!(
+        audit_log
+            .iter()
+            .map(|entry| (entry.member_id, entry.attempt_id, entry.instance_meta_id, entry.action))
+            .collect::<Vec<(u32, u32, u32, ModerationAction)>>(),
+        vec![
+            (moderator, deleted, kills[12].0, ModerationAction::Delete),
+            (moderator, unranked, kills[11].0, ModerationAction::Unrank),
+            (moderator, approved, kills[10].0, ModerationAction::Approve),
+        ]
+    );
+    assert!(audit_log.iter().all(|entry| entry.reasons.len() == 1));
+    assert_eq!(instance.get_moderation_audit_log(&mut conn, Some(unranked)), vec![audit_log[1].clone()]);
+}
@@ -0,0 +1,46 @@
+use crate::modules::instance::dto::CharacterParse;
+use crate::modules::instance::tools::{median_percentile, summarize_character_parses};
+
+#[test]
+fn median() {
+    assert_eq!(median_percentile(&[]), 0);
+    assert_eq!(median_percentile(&[42]), 42);
+    assert_eq!(median_percentile(&[10, 50, 90]), 50);
+    assert_eq!(median_percentile(&[10, 50, 61, 90]), 55);
+}
+
+#[test]
+fn character_parses() {
+    assert!(summarize_character_parses(Vec::new()).is_empty());
+
+    let parses = vec![(1, 206, 9, 40), (2, 207, 9, 75), (3, 206, 9, 95), (4, 206, 9, 60), (5, 206, 10, 20)];
+    assert_eq!(
+        summarize_character_parses(parses),
+        vec![
+            CharacterParse {
+                encounter_id: 206,
+                difficulty_id: 9,
+                best_percentile: 95,
+                median_percentile: 60,
+                best_attempt_id: 3,
+                num_parses: 3,
+            },
+            CharacterParse {
+                encounter_id: 206,
+                difficulty_id: 10,
+                best_percentile: 20,
+                median_percentile: 20,
+                best_attempt_id: 5,
+                num_parses: 1,
+            },
+            CharacterParse {
+                encounter_id: 207,
+                difficulty_id: 9,
+                best_percentile: 75,
+                median_percentile: 75,
+                best_attempt_id: 2,
+                num_parses: 1,
+            },
+        ]
+    );
+}
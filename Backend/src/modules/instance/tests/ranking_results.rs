@@ -0,0 +1,96 @@
+use super::helper::{all_time_season, create_character, create_instance, create_kill, create_member};
+use crate::modules::account::material::Account;
+use crate::modules::armory::Armory;
+use crate::modules::data::Data;
+use crate::modules::instance::tools::{backfill_ranking_results, calculate_percentiles, check_ranking_results, remove_ranking_results, update_ranking_results, RankingScope, RankingType};
+use crate::params;
+use crate::tests::TestContainer;
+use crate::util::database::{Execute, Select};
+
+// (attempt_id, character_id, season_id, percentile) of the damage results, ordered by the damage per second
+fn damage_results(conn: &mut crate::mysql::Conn) -> Vec<(u32, u32, u32, u8)> {
+    conn.select("SELECT attempt_id, character_id, season_id, percentile FROM ranking_results_damage ORDER BY amount / duration DESC", |mut row| {
+        (row.take(0).unwrap(), row.take(1).unwrap(), row.take(2).unwrap(), row.take(3).unwrap())
+    })
+}
+
+#[test]
+fn ranking_scope() {
+    assert_eq!(RankingScope::Attempt(12).scope_id(), 12);
+    assert_eq!(RankingScope::Attempt(12).attempt_condition(), "B.id = :scope_id");
+    assert_eq!(RankingScope::Attempt(12).result_condition(), "R.attempt_id = :scope_id");
+    assert_eq!(RankingScope::InstanceMeta(3).scope_id(), 3);
+    assert_eq!(RankingScope::InstanceMeta(3).attempt_condition(), "B.instance_meta_id = :scope_id");
+    assert_eq!(RankingScope::InstanceMeta(3).result_condition(), "R.instance_meta_id = :scope_id");
+    assert_eq!(RankingScope::All.scope_id(), 0);
+}
+
+#[test]
+fn ranking_type_tables() {
+    assert_eq!(RankingType::Damage.source_table(), "instance_ranking_damage");
+    assert_eq!(RankingType::Damage.results_table(), "ranking_results_damage");
+    assert_eq!(RankingType::Heal.source_column(), "heal");
+    assert_eq!(RankingType::Heal.results_table(), "ranking_results_heal");
+    assert_eq!(RankingType::Threat.source_table(), "instance_ranking_threat");
+    assert_eq!(RankingType::Threat.source_column(), "threat");
+    assert_eq!(RankingType::Threat.results_table(), "ranking_results_threat");
+}
+
+#[test]
+fn percentiles() {
+    assert!(calculate_percentiles(&[]).is_empty());
+    assert_eq!(calculate_percentiles(&[12.5]), vec![100]);
+    assert_eq!(calculate_percentiles(&[30.0, 10.0, 20.0]), vec![100, 0, 50]);
+    assert_eq!(calculate_percentiles(&[10.0, 40.0, 20.0, 30.0]), vec![0, 100, 33, 66]);
+
+    // Ties share the better percentile
+    assert_eq!(calculate_percentiles(&[20.0, 20.0, 10.0]), vec![100, 100, 0]);
+    assert_eq!(calculate_percentiles(&[10.0, 10.0, 20.0]), vec![50, 50, 100]);
+}
+
+#[test]
+fn update_remove_and_backfill_ranking_results() {
+    let container = TestContainer::new(true);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let armory = Armory::default();
+    let data = Data::default();
+    data.seasons.write().unwrap().insert(4, all_time_season(4));
+
+    let start_ts = time_util::now() * 1000;
+    let member_id = create_member(&account, &mut conn, "uploader");
+    let warrior = create_character(&armory, &mut conn, 3, 7001, 1, start_ts);
+    let other_warrior = create_character(&armory, &mut conn, 3, 7002, 1, start_ts);
+    let instance_meta_id = create_instance(&mut conn, member_id, 3, 409, 0, start_ts);
+    let first_kill = create_kill(&mut conn, instance_meta_id, 1, start_ts, start_ts + 60000, &[(warrior, 600000), (other_warrior, 300000)]);
+    let second_kill = create_kill(&mut conn, instance_meta_id, 1, start_ts + 120000, start_ts + 180000, &[(warrior, 150000)]);
+
+    // Insert
+    update_ranking_results(&mut conn, &armory, &data, RankingScope::Attempt(first_kill));
+    assert_eq!(damage_results(&mut conn), vec![(first_kill, warrior, 4, 100), (first_kill, other_warrior, 4, 0)]);
+    update_ranking_results(&mut conn, &armory, &data, RankingScope::Attempt(first_kill));
+    assert_eq!(damage_results(&mut conn).len(), 2);
+
+    // Percentiles of the group are recalculated by the insert of another attempt
+    update_ranking_results(&mut conn, &armory, &data, RankingScope::Attempt(second_kill));
+    assert_eq!(damage_results(&mut conn), vec![(first_kill, warrior, 4, 100), (first_kill, other_warrior, 4, 50), (second_kill, warrior, 4, 0)]);
+    assert!(check_ranking_results(&mut conn, &data).is_empty());
+
+    // Remove
+    remove_ranking_results(&mut conn, RankingScope::Attempt(first_kill));
+    assert_eq!(damage_results(&mut conn), vec![(second_kill, warrior, 4, 100)]);
+    assert_eq!(check_ranking_results(&mut conn, &data), vec!["ranking_results_damage: 1 ranked attempts have no results".to_string()]);
+
+    // Backfill
+    backfill_ranking_results(&mut conn, &armory, &data);
+    assert_eq!(damage_results(&mut conn), vec![(first_kill, warrior, 4, 100), (first_kill, other_warrior, 4, 50), (second_kill, warrior, 4, 0)]);
+    assert!(check_ranking_results(&mut conn, &data).is_empty());
+
+    // Check finds results of attempts that are no longer ranked, the backfill removes them
+    assert!(conn.execute_wparams("UPDATE instance_attempt SET rankable = 0 WHERE id = :attempt_id", params!("attempt_id" => second_kill)));
+    assert_eq!(check_ranking_results(&mut conn, &data), vec!["ranking_results_damage: 1 results are not backed by a ranked attempt".to_string()]);
+    backfill_ranking_results(&mut conn, &armory, &data);
+    assert_eq!(damage_results(&mut conn), vec![(first_kill, warrior, 4, 100), (first_kill, other_warrior, 4, 0)]);
+    assert!(check_ranking_results(&mut conn, &data).is_empty());
+}
@@ -0,0 +1,167 @@
+use crate::modules::instance::domain_value::{InstanceAttempt, InstanceMeta, MetaType, PrivacyType};
+use crate::modules::instance::dto::{SpeedKill, SpeedKillFilter, SpeedRun, SpeedRunFilter};
+use crate::modules::instance::tools::{calculate_speed_run, speed_kill_matches, speed_run_matches, SpeedRuns};
+use crate::modules::instance::Instance;
+
+fn instance_meta(instance_meta_id: u32, privacy_type: PrivacyType) -> InstanceMeta {
+    InstanceMeta {
+        instance_meta_id,
+        server_id: 1,
+        start_ts: 1000,
+        end_ts: Some(2000),
+        map_id: 814,
+        expired: None,
+        participants: vec![1, 2],
+        instance_specific: MetaType::Raid { map_difficulty: 9 },
+        uploaded_user: 1,
+        upload_id: 3,
+        privacy_type,
+        updated_specs: false,
+    }
+}
+
+fn speed_run(instance_meta_id: u32, server_id: u32, guild_id: u32, season_id: u32) -> SpeedRun {
+    SpeedRun {
+        instance_meta_id,
+        map_id: 814,
+        guild_id,
+        guild_name: "Guild".to_string(),
+        server_id,
+        duration: 3600000,
+        difficulty_id: 9,
+        season_id,
+    }
+}
+
+fn speed_kill(instance_meta_id: u32, attempt_id: u32, encounter_id: u32) -> SpeedKill {
+    SpeedKill {
+        instance_meta_id,
+        attempt_id,
+        encounter_id,
+        guild_id: 0,
+        guild_name: "Pug Raid".to_string(),
+        server_id: 1,
+        duration: 120000,
+        difficulty_id: 9,
+        season_id: 2,
+    }
+}
+
+fn kill(encounter_id: u32, start_ts: u64, end_ts: u64, difficulty_id: u8) -> InstanceAttempt {
+    InstanceAttempt {
+        attempt_id: encounter_id,
+        encounter_id,
+        start_ts,
+        end_ts,
+        is_kill: true,
+        difficulty_id,
+        rankable: true,
+        season_id: 3,
+    }
+}
+
+#[test]
+fn speed_run_filter() {
+    let entry = speed_run(1, 5, 7, 2);
+    assert!(speed_run_matches(&entry, &SpeedRunFilter::default()));
+    assert!(speed_run_matches(
+        &entry,
+        &SpeedRunFilter {
+            map_id: Some(814),
+            server_id: Some(5),
+            season_id: Some(2),
+            difficulty_id: Some(9),
+            guild_id: Some(7),
+        }
+    ));
+    assert!(!speed_run_matches(
+        &entry,
+        &SpeedRunFilter {
+            server_id: Some(4),
+            ..SpeedRunFilter::default()
+        }
+    ));
+    assert!(!speed_run_matches(
+        &entry,
+        &SpeedRunFilter {
+            guild_id: Some(0),
+            ..SpeedRunFilter::default()
+        }
+    ));
+    assert!(!speed_run_matches(
+        &entry,
+        &SpeedRunFilter {
+            map_id: Some(532),
+            ..SpeedRunFilter::default()
+        }
+    ));
+}
+
+#[test]
+fn speed_kill_filter() {
+    let entry = speed_kill(1, 10, 206);
+    assert!(speed_kill_matches(&entry, &SpeedKillFilter::default()));
+    assert!(speed_kill_matches(
+        &entry,
+        &SpeedKillFilter {
+            encounter_id: Some(206),
+            guild_id: Some(0),
+            ..SpeedKillFilter::default()
+        }
+    ));
+    assert!(!speed_kill_matches(
+        &entry,
+        &SpeedKillFilter {
+            season_id: Some(1),
+            ..SpeedKillFilter::default()
+        }
+    ));
+    assert!(!speed_kill_matches(
+        &entry,
+        &SpeedKillFilter {
+            difficulty_id: Some(3),
+            ..SpeedKillFilter::default()
+        }
+    ));
+}
+
+#[test]
+fn speed_run_requires_all_encounters() {
+    let instance_encounters = vec![206, 207, 208];
+    let attempts = vec![kill(207, 5000, 6000, 9), kill(206, 1000, 2000, 9), kill(208, 8000, 9500, 9)];
+    assert_eq!(calculate_speed_run(&instance_encounters, &attempts), Some((8500, 9, 3)));
+    assert_eq!(calculate_speed_run(&instance_encounters, &attempts[..2]), None);
+    assert_eq!(calculate_speed_run(&instance_encounters, &[]), None);
+
+    let mut mixed_difficulties = attempts.clone();
+    mixed_difficulties[2].difficulty_id = 3;
+    assert_eq!(calculate_speed_run(&instance_encounters, &mixed_difficulties), None);
+
+    let mut unranked = attempts;
+    unranked[0].rankable = false;
+    assert_eq!(calculate_speed_run(&instance_encounters, &unranked), None);
+}
+
+#[test]
+fn only_public_instances_are_listed() {
+    let instance = Instance::default();
+    {
+        let mut instance_metas = instance.instance_metas.write().unwrap();
+        instance_metas.1.insert(1, instance_meta(1, PrivacyType::Public));
+        instance_metas.1.insert(2, instance_meta(2, PrivacyType::NotListed));
+    }
+    *instance.speed_runs.write().unwrap() = vec![speed_run(1, 1, 0, 2), speed_run(2, 1, 0, 2), speed_run(3, 1, 0, 2)];
+    *instance.speed_kills.write().unwrap() = vec![speed_kill(1, 10, 206), speed_kill(2, 11, 206)];
+
+    let speed_runs = instance.get_speed_runs(&SpeedRunFilter::default());
+    assert_eq!(speed_runs.len(), 1);
+    assert_eq!(speed_runs[0].instance_meta_id, 1);
+
+    let speed_kills = instance.get_speed_kills(&SpeedKillFilter::default());
+    assert_eq!(speed_kills.len(), 1);
+    assert_eq!(speed_kills[0].attempt_id, 10);
+
+    instance.delete_instance_meta(1);
+    assert!(instance.speed_runs.read().unwrap().iter().all(|speed_run| speed_run.instance_meta_id != 1));
+    assert!(instance.get_speed_kills(&SpeedKillFilter::default()).is_empty());
+}
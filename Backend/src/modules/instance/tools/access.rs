@@ -0,0 +1,36 @@
+use crate::modules::account::material::Account;
+use crate::modules::account::tools::Groups;
+use crate::modules::instance::domain_value::{InstanceMeta, PrivacyType};
+use crate::modules::instance::dto::InstanceFailure;
+use crate::modules::instance::Instance;
+
+pub trait InstanceAccess {
+    fn check_instance_access(&self, account: &Account, instance_meta_id: u32, current_user: Option<u32>) -> Result<(), InstanceFailure>;
+}
+
+impl InstanceAccess for Instance {
+    fn check_instance_access(&self, account: &Account, instance_meta_id: u32, current_user: Option<u32>) -> Result<(), InstanceFailure> {
+        let instance_metas = self.instance_metas.read().unwrap();
+        let instance_meta = instance_metas.1.get(&instance_meta_id).ok_or(InstanceFailure::InvalidInput)?;
+        if can_access_instance(account, instance_meta, current_user) {
+            return Ok(());
+        }
+        Err(InstanceFailure::AccessDenied)
+    }
+}
+
+/// Public and not listed instances can be accessed by anyone, group instances only by the members of the group and their uploader.
+pub fn can_access_instance(account: &Account, instance_meta: &InstanceMeta, current_user: Option<u32>) -> bool {
+    match instance_meta.privacy_type {
+        PrivacyType::Public | PrivacyType::NotListed => true,
+        PrivacyType::OnlyGroups(group_id) => current_user.map_or(false, |member_id| member_id == instance_meta.uploaded_user || account.is_group_member(group_id, member_id)),
+    }
+}
+
+/// Not listed instances are shared by their link, hence they are only listed to their uploader.
+pub fn can_list_instance(account: &Account, instance_meta: &InstanceMeta, current_user: Option<u32>) -> bool {
+    match instance_meta.privacy_type {
+        PrivacyType::NotListed => current_user.contains(&instance_meta.uploaded_user),
+        _ => can_access_instance(account, instance_meta, current_user),
+    }
+}
@@ -3,25 +3,36 @@ use crate::modules::armory::Armory;
 use crate::modules::data::tools::RetrieveServer;
 use crate::modules::data::Data;
 use crate::modules::instance::domain_value::MetaType;
-use crate::modules::instance::dto::{InstanceFailure, InstanceViewerAttempt, InstanceViewerGuild, InstanceViewerMeta, InstanceViewerParticipant};
+use crate::modules::instance::dto::{EventExportFilter, EventExportPage, InstanceFailure, InstanceViewerAttempt, InstanceViewerGuild, InstanceViewerMeta, InstanceViewerParticipant, MeterUnit};
 use crate::modules::instance::material::Role;
-use crate::modules::instance::tools::FindInstanceGuild;
+use crate::modules::instance::tools::{is_meter_unit_match, parse_meter_unit, FindInstanceGuild};
 use crate::modules::instance::Instance;
+use crate::modules::live_data_processor::tools::event_segment::{get_event_segment_path, EventSegmentReader};
+use crate::modules::live_data_processor::tools::upload_spec::get_upload_specs;
 use crate::params;
 use crate::util::database::Select;
+use serde_json::{from_str, Value};
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
+const EVENT_EXPORT_MAX_LIMIT: u32 = 50000;
+
 pub trait ExportInstance {
-    fn export_instance_event_type(&self, instance_meta_id: u32, event_type: u8) -> Result<Vec<String>, InstanceFailure>;
+    fn export_instance_event_type(&self, instance_meta_id: u32, event_type: u8) -> Result<Vec<Value>, InstanceFailure>;
+    fn export_instance_event_type_range(&self, instance_meta_id: u32, event_type: u8, start_ts: u64, end_ts: u64) -> Result<Vec<Value>, InstanceFailure>;
+    fn export_instance_event_slice(&self, db_main: &mut impl Select, instance_meta_id: u32, event_type: u8, filter: &EventExportFilter) -> Result<EventExportPage, InstanceFailure>;
     fn get_instance_meta(&self, db_main: &mut impl Select, data: &Data, armory: &Armory, instance_meta_id: u32) -> Result<InstanceViewerMeta, InstanceFailure>;
     fn get_instance_participants(&self, db_main: &mut impl Select, armory: &Armory, instance_meta_id: u32) -> Result<Vec<InstanceViewerParticipant>, InstanceFailure>;
     fn get_instance_attempts(&self, db_main: &mut impl Select, instance_meta_id: u32) -> Result<Vec<InstanceViewerAttempt>, InstanceFailure>;
 }
 
 impl ExportInstance for Instance {
-    fn export_instance_event_type(&self, instance_meta_id: u32, event_type: u8) -> Result<Vec<String>, InstanceFailure> {
+    fn export_instance_event_type(&self, instance_meta_id: u32, event_type: u8) -> Result<Vec<Value>, InstanceFailure> {
+        self.export_instance_event_type_range(instance_meta_id, event_type, 0, u64::MAX)
+    }
+
+    fn export_instance_event_type_range(&self, instance_meta_id: u32, event_type: u8, start_ts: u64, end_ts: u64) -> Result<Vec<Value>, InstanceFailure> {
         let server_id = {
             let instance_metas = self.instance_metas.read().unwrap();
             let instance_meta = instance_metas.1.get(&instance_meta_id).ok_or(InstanceFailure::InvalidInput)?;
@@ -29,6 +40,13 @@ impl ExportInstance for Instance {
         };
 
         let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
+        let segment_path = get_event_segment_path(&storage_path, server_id, instance_meta_id);
+        if Path::new(&segment_path).exists() {
+            let mut reader = EventSegmentReader::open(&segment_path).map_err(|_| InstanceFailure::Unknown)?;
+            return reader.read_time_range(event_type, start_ts, end_ts).map_err(|_| InstanceFailure::Unknown);
+        }
+
+        // Instances that have not been converted to a segment yet
         if Path::new(&format!("{}/{}/{}.zip", storage_path, server_id, instance_meta_id)).exists() {
             let reader = File::open(format!("{}/{}/{}.zip", storage_path, server_id, instance_meta_id)).unwrap();
             let zip = zip::ZipArchive::new(reader);
@@ -45,8 +63,7 @@ impl ExportInstance for Instance {
                     let mut content = String::with_capacity(file.size() as usize);
                     let read_result = file.read_to_string(&mut content);
                     if read_result.is_ok() {
-                        let events = content.lines().into_iter().map(|cont| cont.to_owned()).collect::<Vec<String>>();
-                        return Ok(events);
+                        return Ok(parse_event_lines(&content, start_ts, end_ts));
                     } else {
                         return Ok(Vec::new());
                     }
@@ -55,13 +72,18 @@ impl ExportInstance for Instance {
         } else {
             let event_path = format!("{}/{}/{}/{}", storage_path, server_id, instance_meta_id, event_type);
             if let Ok(file_content) = std::fs::read_to_string(event_path) {
-                let events = file_content.lines().into_iter().map(|cont| cont.to_owned()).collect::<Vec<String>>();
-                return Ok(events);
+                return Ok(parse_event_lines(&file_content, start_ts, end_ts));
             }
         }
         Ok(vec![])
     }
 
+    fn export_instance_event_slice(&self, db_main: &mut impl Select, instance_meta_id: u32, event_type: u8, filter: &EventExportFilter) -> Result<EventExportPage, InstanceFailure> {
+        let (start_ts, end_ts) = get_event_time_range(db_main, instance_meta_id, filter.attempt_id, filter.start_ts, filter.end_ts)?;
+        let events = self.export_instance_event_type_range(instance_meta_id, event_type, start_ts.unwrap_or(0), end_ts.unwrap_or(u64::MAX))?;
+        Ok(filter_exported_events(events, event_type, filter))
+    }
+
     fn get_instance_meta(&self, db_main: &mut impl Select, data: &Data, armory: &Armory, instance_meta_id: u32) -> Result<InstanceViewerMeta, InstanceFailure> {
         let instance_metas = self.instance_metas.read().unwrap();
         if let Some(instance_meta) = instance_metas.1.get(&instance_meta_id) {
@@ -89,17 +111,33 @@ impl ExportInstance for Instance {
     fn get_instance_participants(&self, db_main: &mut impl Select, armory: &Armory, instance_meta_id: u32) -> Result<Vec<InstanceViewerParticipant>, InstanceFailure> {
         let instance_metas = self.instance_metas.read().unwrap();
         if let Some(instance_meta) = instance_metas.1.get(&instance_meta_id) {
+            let mut upload_specs = get_upload_specs(db_main, instance_meta.upload_id);
             return Ok(instance_meta
                 .participants
                 .iter()
                 .map(|character_id| (character_id, armory.get_character_moment(db_main, *character_id, instance_meta.start_ts)))
                 .map(|(char_id, char_history)| {
                     if let Some(char_history) = char_history {
-                        InstanceViewerParticipant {
-                            character_id: char_history.character_id,
-                            name: char_history.character_name,
-                            hero_class_id: char_history.character_info.hero_class_id,
-                            role: Role::from_class_talent_string(char_history.character_info.hero_class_id, &char_history.character_info.talent_specialization.unwrap_or_else(|| String::from(""))),
+                        let hero_class_id = char_history.character_info.hero_class_id;
+                        match upload_specs.remove(char_id).filter(|(spec, _)| spec.hero_class_id == hero_class_id) {
+                            Some((spec, spec_evidence)) => InstanceViewerParticipant {
+                                character_id: char_history.character_id,
+                                name: char_history.character_name,
+                                hero_class_id,
+                                role: Role::from_detected_spec(hero_class_id, spec.talent_tree, spec.role),
+                                talent_tree: Some(spec.talent_tree),
+                                spec_confidence: spec.confidence,
+                                spec_evidence,
+                            },
+                            None => InstanceViewerParticipant {
+                                character_id: char_history.character_id,
+                                name: char_history.character_name,
+                                hero_class_id,
+                                role: Role::from_class_talent_string(hero_class_id, &char_history.character_info.talent_specialization.unwrap_or_else(|| String::from(""))),
+                                talent_tree: None,
+                                spec_confidence: 0,
+                                spec_evidence: Vec::new(),
+                            },
                         }
                     } else {
                         InstanceViewerParticipant {
@@ -107,6 +145,9 @@ impl ExportInstance for Instance {
                             name: String::from("Unknown"),
                             hero_class_id: 1,
                             role: Role::Dps,
+                            talent_tree: None,
+                            spec_confidence: 0,
+                            spec_evidence: Vec::new(),
                         }
                     }
                 })
@@ -140,3 +181,59 @@ impl ExportInstance for Instance {
         Ok(attempts)
     }
 }
+
+pub fn get_event_time_range(db_main: &mut impl Select, instance_meta_id: u32, attempt_id: Option<u32>, start_ts: Option<u64>, end_ts: Option<u64>) -> Result<(Option<u64>, Option<u64>), InstanceFailure> {
+    if let Some(attempt_id) = attempt_id {
+        let (attempt_start_ts, attempt_end_ts) = db_main
+            .select_wparams_value(
+                "SELECT start_ts, end_ts FROM instance_attempt WHERE id=:attempt_id AND instance_meta_id=:instance_meta_id",
+                |mut row| (row.take::<u64, usize>(0).unwrap(), row.take::<u64, usize>(1).unwrap()),
+                params!("attempt_id" => attempt_id, "instance_meta_id" => instance_meta_id),
+            )
+            .ok_or(InstanceFailure::InvalidInput)?;
+        return Ok((Some(start_ts.map_or(attempt_start_ts, |ts| ts.max(attempt_start_ts))), Some(end_ts.map_or(attempt_end_ts, |ts| ts.min(attempt_end_ts)))));
+    }
+    Ok((start_ts, end_ts))
+}
+
+/// Returns the positions of the source and target unit in an exported event of the given type.
+pub fn get_event_unit_indices(event_type: u8) -> (Option<usize>, Option<usize>) {
+    match event_type {
+        0 | 1 | 11 | 12 => (Some(2), Some(3)),
+        2 | 3 | 4 | 5 | 10 => (Some(2), None),
+        6 => (Some(3), Some(2)),
+        7 | 13 | 14 | 15 => (Some(3), Some(4)),
+        8 | 9 => (Some(4), Some(5)),
+        _ => (None, None),
+    }
+}
+
+pub fn filter_exported_events(events: Vec<Value>, event_type: u8, filter: &EventExportFilter) -> EventExportPage {
+    let (source_index, target_index) = get_event_unit_indices(event_type);
+    let matches_unit = |event: &Value, index: Option<usize>, filter_unit: &Option<MeterUnit>| match filter_unit {
+        Some(filter_unit) => index
+            .and_then(|index| event.get(index))
+            .and_then(parse_meter_unit)
+            .map_or(false, |unit| is_meter_unit_match(&unit, filter_unit)),
+        None => true,
+    };
+
+    let offset = filter.offset.unwrap_or(0) as usize;
+    let limit = filter.limit.unwrap_or(EVENT_EXPORT_MAX_LIMIT).min(EVENT_EXPORT_MAX_LIMIT) as usize;
+    let mut matching_events = events
+        .into_iter()
+        .filter(|event| matches_unit(event, source_index, &filter.source) && matches_unit(event, target_index, &filter.target))
+        .skip(offset);
+
+    let events = matching_events.by_ref().take(limit).collect::<Vec<Value>>();
+    let next_offset = if matching_events.next().is_some() { Some((offset + events.len()) as u32) } else { None };
+    EventExportPage { events, next_offset }
+}
+
+fn parse_event_lines(content: &str, start_ts: u64, end_ts: u64) -> Vec<Value> {
+    content
+        .lines()
+        .filter_map(|line| from_str::<Value>(line).ok())
+        .filter(|event| event.get(1).and_then(Value::as_u64).map_or(false, |timestamp| timestamp >= start_ts && timestamp <= end_ts))
+        .collect()
+}
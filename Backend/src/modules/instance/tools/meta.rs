@@ -1,11 +1,15 @@
+use crate::modules::account::material::Account;
+use crate::modules::account::tools::Groups;
 use crate::modules::instance::domain_value::{InstanceMeta, PrivacyType};
 use crate::modules::instance::dto::{InstanceFailure, InstancePrivacy};
+use crate::modules::instance::tools::can_list_instance;
 use crate::modules::instance::Instance;
 use crate::params;
 use crate::util::database::Execute;
 
 pub trait ExportMeta {
     fn export_meta(&self, meta_type: u8) -> Vec<InstanceMeta>;
+    fn export_listed_meta(&self, account: &Account, meta_type: u8, current_user: Option<u32>) -> Vec<InstanceMeta>;
 }
 
 impl ExportMeta for Instance {
@@ -17,14 +21,29 @@ impl ExportMeta for Instance {
             .map(|(_, instance_meta)| instance_meta.clone())
             .collect()
     }
+
+    fn export_listed_meta(&self, account: &Account, meta_type: u8, current_user: Option<u32>) -> Vec<InstanceMeta> {
+        self.export_meta(meta_type).into_iter().filter(|instance_meta| can_list_instance(account, instance_meta, current_user)).collect()
+    }
 }
 
 pub trait UpdateMeta {
-    fn update_privacy(&self, db_main: &mut impl Execute, instance_privacy: InstancePrivacy, member_id: u32) -> Result<(), InstanceFailure>;
+    fn update_privacy(&self, db_main: &mut impl Execute, account: &Account, instance_privacy: InstancePrivacy, member_id: u32) -> Result<(), InstanceFailure>;
 }
 
 impl UpdateMeta for Instance {
-    fn update_privacy(&self, db_main: &mut impl Execute, instance_privacy: InstancePrivacy, member_id: u32) -> Result<(), InstanceFailure> {
+    fn update_privacy(&self, db_main: &mut impl Execute, account: &Account, mut instance_privacy: InstancePrivacy, member_id: u32) -> Result<(), InstanceFailure> {
+        match instance_privacy.privacy_option {
+            0 | 1 => instance_privacy.privacy_group = 0,
+            // Uploaders may only share their logs with groups they belong to
+            2 => {
+                if !account.is_group_member(instance_privacy.privacy_group, member_id) {
+                    return Err(InstanceFailure::AccessDenied);
+                }
+            },
+            _ => return Err(InstanceFailure::InvalidInput),
+        }
+
         if db_main.execute_wparams("UPDATE `instance_meta` A \
         JOIN `instance_uploads` B ON A.upload_id = B.id \
         SET `privacy_type`=:privacy_type, `privacy_ref`=:privacy_ref \
@@ -36,20 +55,6 @@ impl UpdateMeta for Instance {
                 instance_meta.privacy_type = PrivacyType::new(instance_privacy.privacy_option, instance_privacy.privacy_group);
             }
 
-            {
-                let mut speed_runs = self.speed_runs.write().unwrap();
-                if let Some(index) = speed_runs.iter().position(|speed_run| speed_run.instance_meta_id == instance_privacy.instance_meta_id) {
-                    speed_runs.remove(index);
-                }
-            }
-
-            {
-                let mut speed_kills = self.speed_kills.write().unwrap();
-                while let Some(index) = speed_kills.iter().position(|speed_kill| speed_kill.instance_meta_id == instance_privacy.instance_meta_id) {
-                    speed_kills.remove(index);
-                }
-            }
-
             return Ok(());
         }
         Err(InstanceFailure::Unknown)
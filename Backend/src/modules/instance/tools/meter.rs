@@ -0,0 +1,229 @@
+use crate::modules::instance::dto::{InstanceFailure, MeterAggregate, MeterFilter, MeterRecord, MeterUnit};
+use crate::modules::instance::tools::{get_event_time_range, ExportInstance};
+use crate::modules::instance::Instance;
+use crate::modules::live_data_processor::tools::event_segment::get_event_segment_path;
+use crate::util::database::Select;
+use serde_json::{from_str, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+const EVENT_TYPE_MELEE_DAMAGE: u8 = 12;
+const EVENT_TYPE_SPELL_DAMAGE: u8 = 13;
+const EVENT_TYPE_HEAL: u8 = 14;
+const EVENT_TYPE_THREAT: u8 = 15;
+
+pub trait MeterAggregation {
+    fn get_meter_aggregate(&self, db_main: &mut impl Select, instance_meta_id: u32, filter: MeterFilter) -> Result<MeterAggregate, InstanceFailure>;
+}
+
+impl MeterAggregation for Instance {
+    fn get_meter_aggregate(&self, db_main: &mut impl Select, instance_meta_id: u32, filter: MeterFilter) -> Result<MeterAggregate, InstanceFailure> {
+        let server_id = {
+            let instance_metas = self.instance_metas.read().unwrap();
+            let instance_meta = instance_metas.1.get(&instance_meta_id).ok_or(InstanceFailure::InvalidInput)?;
+            instance_meta.server_id
+        };
+
+        let (start_ts, end_ts) = get_event_time_range(db_main, instance_meta_id, filter.attempt_id, filter.start_ts, filter.end_ts)?;
+
+        // Only whole attempts or instances are cached and only once the events of the instance have been finalized,
+        // as the event files of an instance that is still being processed change all the time.
+        let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
+        let segment_path = get_event_segment_path(&storage_path, server_id, instance_meta_id);
+        let events_path = if Path::new(&segment_path).exists() { segment_path } else { format!("{}/{}/{}.zip", storage_path, server_id, instance_meta_id) };
+        let cache_dir = format!("{}/{}/{}_meter", storage_path, server_id, instance_meta_id);
+        let cache_path = format!("{}/{}.json", cache_dir, filter.attempt_id.map(|attempt_id| attempt_id.to_string()).unwrap_or_else(|| "all".to_owned()));
+        let is_cacheable = filter.start_ts.is_none() && filter.end_ts.is_none() && Path::new(&events_path).exists();
+
+        let cached_aggregate = if is_cacheable { read_cached_meter_aggregate(&events_path, &cache_path) } else { None };
+        let mut aggregate = match cached_aggregate {
+            Some(aggregate) => aggregate,
+            None => {
+                let (range_start_ts, range_end_ts) = (start_ts.unwrap_or(0), end_ts.unwrap_or(u64::MAX));
+                let melee_damage = self.export_instance_event_type_range(instance_meta_id, EVENT_TYPE_MELEE_DAMAGE, range_start_ts, range_end_ts)?;
+                let spell_damage = self.export_instance_event_type_range(instance_meta_id, EVENT_TYPE_SPELL_DAMAGE, range_start_ts, range_end_ts)?;
+                let heal = self.export_instance_event_type_range(instance_meta_id, EVENT_TYPE_HEAL, range_start_ts, range_end_ts)?;
+                let threat = self.export_instance_event_type_range(instance_meta_id, EVENT_TYPE_THREAT, range_start_ts, range_end_ts)?;
+
+                let aggregate = MeterAggregate {
+                    instance_meta_id,
+                    attempt_id: filter.attempt_id,
+                    start_ts,
+                    end_ts,
+                    damage: aggregate_meter_events(
+                        melee_damage
+                            .iter()
+                            .map(|event| (EVENT_TYPE_MELEE_DAMAGE, event))
+                            .chain(spell_damage.iter().map(|event| (EVENT_TYPE_SPELL_DAMAGE, event))),
+                        start_ts,
+                        end_ts,
+                    ),
+                    healing: aggregate_meter_events(heal.iter().map(|event| (EVENT_TYPE_HEAL, event)), start_ts, end_ts),
+                    threat: aggregate_meter_events(threat.iter().map(|event| (EVENT_TYPE_THREAT, event)), start_ts, end_ts),
+                };
+
+                if is_cacheable {
+                    let _ = std::fs::create_dir_all(&cache_dir);
+                    if let Ok(serialized_aggregate) = serde_json::to_string(&aggregate) {
+                        let _ = std::fs::write(&cache_path, serialized_aggregate);
+                    }
+                }
+                aggregate
+            },
+        };
+
+        filter_meter_records(&mut aggregate.damage, &filter);
+        filter_meter_records(&mut aggregate.healing, &filter);
+        filter_meter_records(&mut aggregate.threat, &filter);
+        Ok(aggregate)
+    }
+}
+
+pub fn aggregate_meter_events<'a>(events: impl Iterator<Item = (u8, &'a Value)>, start_ts: Option<u64>, end_ts: Option<u64>) -> Vec<MeterRecord> {
+    let mut records: HashMap<(MeterUnit, MeterUnit, u32, u8, u32), MeterRecord> = HashMap::new();
+    for (event_type, event) in events {
+        let (timestamp, record) = match parse_meter_event(event_type, event) {
+            Some(parsed_event) => parsed_event,
+            None => continue,
+        };
+        if start_ts.map_or(false, |start_ts| timestamp < start_ts) || end_ts.map_or(false, |end_ts| timestamp > end_ts) {
+            continue;
+        }
+
+        let key = (record.source.clone(), record.target.clone(), record.spell_id, record.school_mask, record.hit_mask);
+        if let Some(aggregated_record) = records.get_mut(&key) {
+            aggregated_record.count += 1;
+            aggregated_record.amount += record.amount;
+            aggregated_record.effective += record.effective;
+        } else {
+            records.insert(key, record);
+        }
+    }
+
+    let mut records = records.into_iter().map(|(_, record)| record).collect::<Vec<MeterRecord>>();
+    records.sort_by(|left, right| {
+        right
+            .amount
+            .cmp(&left.amount)
+            .then_with(|| left.source.cmp(&right.source))
+            .then_with(|| left.target.cmp(&right.target))
+            .then_with(|| left.spell_id.cmp(&right.spell_id))
+            .then_with(|| left.hit_mask.cmp(&right.hit_mask))
+    });
+    records
+}
+
+/// A unit filter matches the unit itself and, for players, also their pets and guardians.
+pub fn filter_meter_records(records: &mut Vec<MeterRecord>, filter: &MeterFilter) {
+    records.retain(|record| {
+        filter.source.as_ref().map_or(true, |source| is_meter_unit_match(&record.source, source))
+            && filter.target.as_ref().map_or(true, |target| is_meter_unit_match(&record.target, target))
+    });
+}
+
+pub fn is_meter_unit_match(unit: &MeterUnit, filter_unit: &MeterUnit) -> bool {
+    if unit.is_player == filter_unit.is_player && unit.unit_id == filter_unit.unit_id {
+        return true;
+    }
+    filter_unit.is_player && unit.owner_id.map_or(false, |owner_id| owner_id as u64 == filter_unit.unit_id)
+}
+
+fn read_cached_meter_aggregate(events_path: &str, cache_path: &str) -> Option<MeterAggregate> {
+    let events_modified = std::fs::metadata(events_path).and_then(|metadata| metadata.modified()).ok()?;
+    let cache_modified = std::fs::metadata(cache_path).and_then(|metadata| metadata.modified()).ok()?;
+    if cache_modified < events_modified {
+        return None;
+    }
+    std::fs::read_to_string(cache_path).ok().and_then(|content| from_str::<MeterAggregate>(&content).ok())
+}
+
+// Reads the exported event formats of the live data processor deserializer
+fn parse_meter_event(event_type: u8, event: &Value) -> Option<(u64, MeterRecord)> {
+    let event = event.as_array()?;
+    let timestamp = event.get(1)?.as_u64()?;
+    let record = match event_type {
+        EVENT_TYPE_MELEE_DAMAGE => {
+            let (amount, school_mask) = parse_damage_components(event.get(5)?)?;
+            MeterRecord {
+                source: parse_meter_unit(event.get(2)?)?,
+                target: parse_meter_unit(event.get(3)?)?,
+                spell_id: 0,
+                school_mask,
+                hit_mask: event.get(4)?.as_u64()? as u32,
+                count: 1,
+                amount,
+                effective: amount,
+            }
+        },
+        EVENT_TYPE_SPELL_DAMAGE => {
+            let (amount, school_mask) = parse_damage_components(event.get(7)?)?;
+            MeterRecord {
+                source: parse_meter_unit(event.get(3)?)?,
+                target: parse_meter_unit(event.get(4)?)?,
+                spell_id: event.get(5)?.as_u64()? as u32,
+                school_mask,
+                hit_mask: event.get(6)?.as_u64()? as u32,
+                count: 1,
+                amount,
+                effective: amount,
+            }
+        },
+        EVENT_TYPE_HEAL => MeterRecord {
+            source: parse_meter_unit(event.get(3)?)?,
+            target: parse_meter_unit(event.get(4)?)?,
+            spell_id: event.get(5)?.as_u64()? as u32,
+            school_mask: event.get(7)?.as_u64()? as u8,
+            hit_mask: event.get(6)?.as_u64()? as u32,
+            count: 1,
+            amount: event.get(8)?.as_i64()?,
+            effective: event.get(9)?.as_i64()?,
+        },
+        EVENT_TYPE_THREAT => {
+            let amount = event.get(8)?.as_i64()?;
+            MeterRecord {
+                source: parse_meter_unit(event.get(3)?)?,
+                target: parse_meter_unit(event.get(4)?)?,
+                spell_id: event.get(5)?.as_u64()? as u32,
+                school_mask: event.get(7)?.as_u64()? as u8,
+                hit_mask: event.get(6)?.as_u64()? as u32,
+                count: 1,
+                amount,
+                effective: amount,
+            }
+        },
+        _ => return None,
+    };
+    Some((timestamp, record))
+}
+
+fn parse_damage_components(components: &Value) -> Option<(i64, u8)> {
+    let mut amount = 0;
+    let mut school_mask = 0;
+    for component in components.as_array()? {
+        let component = component.as_array()?;
+        amount += component.get(0)?.as_i64()?;
+        school_mask |= component.get(1)?.as_u64()? as u8;
+    }
+    Some((amount, school_mask))
+}
+
+pub fn parse_meter_unit(unit: &Value) -> Option<MeterUnit> {
+    let unit = unit.as_array()?;
+    if unit.get(0)?.as_u64()? == 1 {
+        return Some(MeterUnit {
+            is_player: true,
+            unit_id: unit.get(1)?.as_u64()?,
+            owner_id: None,
+        });
+    }
+
+    let owner_id = unit
+        .get(3)
+        .and_then(parse_meter_unit)
+        .and_then(|owner| if owner.is_player { Some(owner.unit_id as u32) } else { owner.owner_id });
+    Some(MeterUnit {
+        is_player: false,
+        unit_id: unit.get(2)?.as_u64()?,
+        owner_id,
+    })
+}
@@ -1,15 +1,27 @@
+pub use self::access::*;
 pub use self::delete::DeleteInstance;
-pub use self::export::ExportInstance;
+pub use self::export::*;
 pub use self::instance_guild::FindInstanceGuild;
 pub use self::meta::*;
 pub use self::meta_search::MetaSearch;
+pub use self::meter::*;
+pub use self::moderation::*;
 pub use self::ranking::*;
+pub use self::ranking_parse::*;
+pub use self::ranking_results::*;
+pub use self::speed_run::*;
 pub use self::unrank::*;
 
+mod access;
 mod delete;
 mod export;
 mod instance_guild;
 mod meta;
 mod meta_search;
+mod meter;
+mod moderation;
 mod ranking;
+mod ranking_parse;
+mod ranking_results;
+mod speed_run;
 mod unrank;
\ No newline at end of file
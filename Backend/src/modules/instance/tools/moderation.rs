@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use crate::modules::account::material::Account;
+use crate::modules::instance::domain_value::{ModerationAction, ModerationAuditEntry};
+use crate::modules::instance::dto::{FlaggedAttempt, InstanceFailure};
+use crate::modules::instance::tools::{DeleteInstance, UnrankAttempt};
+use crate::modules::instance::Instance;
+use crate::params;
+use crate::util::database::{Execute, Select};
+
+/// Modified z-score above which a value is considered an outlier
+pub const ANOMALY_THRESHOLD: f64 = 3.5;
+/// Minimum number of values of an encounter before outliers are detected
+pub const ANOMALY_MIN_SAMPLES: usize = 10;
+
+pub trait ModerateAttempts {
+    fn get_moderation_queue(&self, db_main: &mut impl Select) -> Vec<FlaggedAttempt>;
+    fn get_moderation_audit_log(&self, db_main: &mut impl Select, attempt_id: Option<u32>) -> Vec<ModerationAuditEntry>;
+    fn moderate_attempt(&self, db_main: &mut (impl Execute + Select), account: &Account, member_id: u32, attempt_id: u32, action: ModerationAction) -> Result<(), InstanceFailure>;
+}
+
+impl ModerateAttempts for Instance {
+    fn get_moderation_queue(&self, db_main: &mut impl Select) -> Vec<FlaggedAttempt> {
+        db_main.select(
+            "SELECT A.attempt_id, A.instance_meta_id, B.encounter_id, A.flagged_ts, A.reasons FROM instance_attempt_flag A JOIN instance_attempt B ON A.attempt_id = B.id WHERE A.status = 0 ORDER BY A.flagged_ts, A.attempt_id",
+            |mut row| FlaggedAttempt {
+                attempt_id: row.take(0).unwrap(),
+                instance_meta_id: row.take(1).unwrap(),
+                encounter_id: row.take(2).unwrap(),
+                flagged_ts: row.take(3).unwrap(),
+                reasons: split_reasons(&row.take::<String, usize>(4).unwrap()),
+            },
+        )
+    }
+
+    fn get_moderation_audit_log(&self, db_main: &mut impl Select, attempt_id: Option<u32>) -> Vec<ModerationAuditEntry> {
+        db_main
+            .select_wparams(
+                "SELECT id, member_id, attempt_id, instance_meta_id, action, reasons, timestamp FROM instance_moderation_audit WHERE :attempt_id IS NULL OR attempt_id = :attempt_id ORDER BY id DESC",
+                |mut row| {
+                    (
+                        row.take::<u32, usize>(0).unwrap(),
+                        row.take::<u32, usize>(1).unwrap(),
+                        row.take::<u32, usize>(2).unwrap(),
+                        row.take::<u32, usize>(3).unwrap(),
+                        row.take::<u8, usize>(4).unwrap(),
+                        row.take::<String, usize>(5).unwrap(),
+                        row.take::<u64, usize>(6).unwrap(),
+                    )
+                },
+                params!("attempt_id" => attempt_id),
+            )
+            .into_iter()
+            .filter_map(|(id, member_id, attempt_id, instance_meta_id, action, reasons, timestamp)| {
+                ModerationAction::new(action).map(|action| ModerationAuditEntry {
+                    id,
+                    member_id,
+                    attempt_id,
+                    instance_meta_id,
+                    action,
+                    reasons: split_reasons(&reasons),
+                    timestamp,
+                })
+            })
+            .collect()
+    }
+
+    fn moderate_attempt(&self, db_main: &mut (impl Execute + Select), account: &Account, member_id: u32, attempt_id: u32, action: ModerationAction) -> Result<(), InstanceFailure> {
+        let instance_meta_id = db_main
+            .select_wparams_value(
+                "SELECT instance_meta_id FROM instance_attempt WHERE id = :attempt_id",
+                |mut row| row.take::<u32, usize>(0).unwrap(),
+                params!("attempt_id" => attempt_id),
+            )
+            .ok_or(InstanceFailure::InvalidInput)?;
+        let reasons = db_main
+            .select_wparams_value(
+                "SELECT reasons FROM instance_attempt_flag WHERE attempt_id = :attempt_id",
+                |mut row| row.take::<String, usize>(0).unwrap(),
+                params!("attempt_id" => attempt_id),
+            )
+            .unwrap_or_default();
+
+        match action {
+            ModerationAction::Approve => {},
+            ModerationAction::Unrank => self.unrank_attempt(db_main, attempt_id)?,
+            ModerationAction::Delete => self.delete_instance(db_main, account, instance_meta_id, member_id)?,
+        }
+        // The flag of a deleted instance is already removed together with its attempts
+        db_main.execute_wparams("UPDATE instance_attempt_flag SET status = :status WHERE attempt_id = :attempt_id", params!("status" => action.to_u8(), "attempt_id" => attempt_id));
+
+        db_main.execute_wparams(
+            "INSERT INTO instance_moderation_audit (`member_id`, `attempt_id`, `instance_meta_id`, `action`, `reasons`, `timestamp`) VALUES (:member_id, :attempt_id, :instance_meta_id, :action, :reasons, :timestamp)",
+            params!(
+                "member_id" => member_id,
+                "attempt_id" => attempt_id,
+                "instance_meta_id" => instance_meta_id,
+                "action" => action.to_u8(),
+                "reasons" => reasons,
+                "timestamp" => time_util::now()
+            ),
+        );
+        Ok(())
+    }
+}
+
+fn split_reasons(reasons: &str) -> Vec<String> {
+    reasons.lines().filter(|reason| !reason.is_empty()).map(|reason| reason.to_string()).collect()
+}
+
+/// Modified z-scores (Iglewicz and Hoaglin), based on the median and the median absolute deviation,
+/// which unlike the mean and the standard deviation are not skewed by the outliers themselves.
+/// All scores are 0 if there are too few values or the values do not deviate.
+pub fn modified_z_scores(values: &[f64]) -> Vec<f64> {
+    if values.len() < ANOMALY_MIN_SAMPLES {
+        return vec![0.0; values.len()];
+    }
+
+    let median_value = median(values);
+    let absolute_deviations: Vec<f64> = values.iter().map(|value| (value - median_value).abs()).collect();
+    let median_absolute_deviation = median(&absolute_deviations);
+    if median_absolute_deviation == 0.0 {
+        return vec![0.0; values.len()];
+    }
+
+    values.iter().map(|value| 0.6745 * (value - median_value) / median_absolute_deviation).collect()
+}
+
+/// Returns the indices of the subject values that are unusually high, or unusually low if `low` is set.
+fn find_outliers(values: &[f64], subjects: &[usize], low: bool) -> Vec<usize> {
+    let scores = modified_z_scores(values);
+    subjects.iter().filter(|index| if low { scores[**index] < -ANOMALY_THRESHOLD } else { scores[**index] > ANOMALY_THRESHOLD }).cloned().collect()
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted_values = values.to_vec();
+    sorted_values.sort_by(|left, right| left.partial_cmp(right).unwrap());
+    let middle = sorted_values.len() / 2;
+    if sorted_values.len() % 2 == 1 {
+        sorted_values[middle]
+    } else {
+        (sorted_values[middle - 1] + sorted_values[middle]) / 2.0
+    }
+}
+
+// (attempt_id, character_id, damage per second, hits per second, highest hit)
+type DamageResult = (u32, u32, f64, f64, f64);
+// (attempt_id, character_id, heal per second)
+type HealResult = (u32, u32, f64);
+
+/// The ranked kills of an encounter and difficulty that attempts are compared with.
+/// Damage and heal results are grouped by class and spec.
+pub struct AnomalyStatistics {
+    durations: Vec<(u32, f64)>,
+    damage_groups: HashMap<(u8, u8), Vec<DamageResult>>,
+    heal_groups: HashMap<(u8, u8), Vec<HealResult>>,
+}
+
+impl AnomalyStatistics {
+    pub fn load(db_main: &mut impl Select, encounter_id: u32, difficulty_id: u8) -> Self {
+        let durations = db_main.select_wparams(
+            "SELECT A.id, (A.end_ts - A.start_ts) FROM instance_attempt A JOIN instance_raid B ON A.instance_meta_id = B.instance_meta_id WHERE A.encounter_id = :encounter_id AND B.map_difficulty = :difficulty_id AND A.rankable = 1 AND A.is_kill = 1",
+            |mut row| (row.take::<u32, usize>(0).unwrap(), row.take::<u64, usize>(1).unwrap() as f64),
+            params!("encounter_id" => encounter_id, "difficulty_id" => difficulty_id),
+        );
+
+        let mut damage_groups: HashMap<(u8, u8), Vec<DamageResult>> = HashMap::new();
+        db_main
+            .select_wparams(
+                "SELECT R.attempt_id, R.character_id, R.hero_class_id, R.character_spec, R.amount, R.duration, A.hits, A.max_hit FROM ranking_results_damage R JOIN instance_ranking_damage A ON R.id = A.id WHERE R.encounter_id = :encounter_id AND \
+                 R.difficulty_id = :difficulty_id",
+                |mut row| {
+                    let duration = row.take::<u64, usize>(5).unwrap().max(1) as f64 / 1000.0;
+                    (
+                        (row.take::<u8, usize>(2).unwrap(), row.take::<u8, usize>(3).unwrap()),
+                        (
+                            row.take::<u32, usize>(0).unwrap(),
+                            row.take::<u32, usize>(1).unwrap(),
+                            row.take::<u32, usize>(4).unwrap() as f64 / duration,
+                            row.take::<u32, usize>(6).unwrap() as f64 / duration,
+                            row.take::<u32, usize>(7).unwrap() as f64,
+                        ),
+                    )
+                },
+                params!("encounter_id" => encounter_id, "difficulty_id" => difficulty_id),
+            )
+            .into_iter()
+            .for_each(|(group, result)| damage_groups.entry(group).or_insert_with(Vec::new).push(result));
+
+        let mut heal_groups: HashMap<(u8, u8), Vec<HealResult>> = HashMap::new();
+        db_main
+            .select_wparams(
+                "SELECT attempt_id, character_id, hero_class_id, character_spec, amount, duration FROM ranking_results_heal WHERE encounter_id = :encounter_id AND difficulty_id = :difficulty_id",
+                |mut row| {
+                    let duration = row.take::<u64, usize>(5).unwrap().max(1) as f64 / 1000.0;
+                    (
+                        (row.take::<u8, usize>(2).unwrap(), row.take::<u8, usize>(3).unwrap()),
+                        (row.take::<u32, usize>(0).unwrap(), row.take::<u32, usize>(1).unwrap(), row.take::<u32, usize>(4).unwrap() as f64 / duration),
+                    )
+                },
+                params!("encounter_id" => encounter_id, "difficulty_id" => difficulty_id),
+            )
+            .into_iter()
+            .for_each(|(group, result)| heal_groups.entry(group).or_insert_with(Vec::new).push(result));
+
+        AnomalyStatistics { durations, damage_groups, heal_groups }
+    }
+
+    /// Compares the attempt with the other ranked kills. Durations are compared for all kills, damage and heal per second,
+    /// hits per second and the highest hit only among characters of the same class and spec.
+    pub fn detect(&self, attempt_id: u32) -> Vec<String> {
+        let mut reasons = Vec::new();
+
+        let duration_values: Vec<f64> = self.durations.iter().map(|(_, duration)| *duration).collect();
+        let subjects: Vec<usize> = self.durations.iter().enumerate().filter(|(_, (id, _))| *id == attempt_id).map(|(index, _)| index).collect();
+        for index in find_outliers(&duration_values, &subjects, true) {
+            reasons.push(format!("Duration of {:.0}s is unusually short, the median is {:.0}s", duration_values[index] / 1000.0, median(&duration_values) / 1000.0));
+        }
+
+        for results in self.damage_groups.values() {
+            if !results.iter().any(|result| result.0 == attempt_id) {
+                continue;
+            }
+
+            // Kills that were ranked before hits were recorded have none, they are left out of the hit metrics
+            let hit_results: Vec<DamageResult> = results.iter().filter(|result| result.3 > 0.0).cloned().collect();
+            let metrics: [(&str, &[DamageResult], fn(&DamageResult) -> f64); 3] = [
+                ("Damage per second", results, |result| result.2),
+                ("Hits per second", &hit_results, |result| result.3),
+                ("Highest hit", &hit_results, |result| result.4),
+            ];
+            for (metric, metric_results, value) in metrics.iter() {
+                let subjects: Vec<usize> = metric_results.iter().enumerate().filter(|(_, result)| result.0 == attempt_id).map(|(index, _)| index).collect();
+                let values: Vec<f64> = metric_results.iter().map(value).collect();
+                for index in find_outliers(&values, &subjects, false) {
+                    reasons.push(format!("{} of character {} is unusually high: {:.0}, the median is {:.0}", metric, metric_results[index].1, values[index], median(&values)));
+                }
+            }
+        }
+
+        for results in self.heal_groups.values() {
+            let subjects: Vec<usize> = results.iter().enumerate().filter(|(_, result)| result.0 == attempt_id).map(|(index, _)| index).collect();
+            let values: Vec<f64> = results.iter().map(|result| result.2).collect();
+            for index in find_outliers(&values, &subjects, false) {
+                reasons.push(format!("Heal per second of character {} is unusually high: {:.0}, the median is {:.0}", results[index].1, values[index], median(&values)));
+            }
+        }
+
+        reasons
+    }
+}
+
+/// Compares the ranked attempt with the other ranked kills of its encounter and difficulty.
+pub fn detect_attempt_anomalies(db_main: &mut impl Select, attempt_id: u32) -> Vec<String> {
+    match db_main.select_wparams_value(
+        "SELECT A.encounter_id, B.map_difficulty FROM instance_attempt A JOIN instance_raid B ON A.instance_meta_id = B.instance_meta_id WHERE A.id = :attempt_id AND A.rankable = 1 AND A.is_kill = 1",
+        |mut row| (row.take::<u32, usize>(0).unwrap(), row.take::<u8, usize>(1).unwrap()),
+        params!("attempt_id" => attempt_id),
+    ) {
+        Some((encounter_id, difficulty_id)) => AnomalyStatistics::load(db_main, encounter_id, difficulty_id).detect(attempt_id),
+        None => Vec::new(),
+    }
+}
+
+/// Adds the attempt to the moderation queue if anomalies are detected. Attempts that were moderated before are not flagged again.
+pub fn flag_attempt_anomalies(db_main: &mut (impl Execute + Select), attempt_id: u32) -> bool {
+    let reasons = detect_attempt_anomalies(db_main, attempt_id);
+    flag_attempt(db_main, attempt_id, reasons)
+}
+
+fn flag_attempt(db_main: &mut impl Execute, attempt_id: u32, reasons: Vec<String>) -> bool {
+    if reasons.is_empty() {
+        return false;
+    }
+
+    db_main.execute_wparams(
+        "INSERT IGNORE INTO instance_attempt_flag (`attempt_id`, `instance_meta_id`, `flagged_ts`, `reasons`, `status`) SELECT id, instance_meta_id, :flagged_ts, :reasons, 0 FROM instance_attempt WHERE id = :attempt_id",
+        params!("attempt_id" => attempt_id, "flagged_ts" => time_util::now(), "reasons" => reasons.join("\n")),
+    )
+}
+
+/// Checks the ranked kills with an id in the range, loading the statistics once per encounter and difficulty.
+/// Returns the number of newly flagged attempts.
+pub fn flag_attempt_anomalies_in_range(db_main: &mut (impl Execute + Select), after_attempt_id: u32, up_to_attempt_id: u32) -> usize {
+    let attempts = db_main.select_wparams(
+        "SELECT A.id, A.encounter_id, B.map_difficulty FROM instance_attempt A JOIN instance_raid B ON A.instance_meta_id = B.instance_meta_id LEFT JOIN instance_attempt_flag C ON A.id = C.attempt_id WHERE A.id > :after_attempt_id AND \
+         A.id <= :up_to_attempt_id AND A.rankable = 1 AND A.is_kill = 1 AND C.attempt_id IS NULL",
+        |mut row| (row.take::<u32, usize>(0).unwrap(), row.take::<u32, usize>(1).unwrap(), row.take::<u8, usize>(2).unwrap()),
+        params!("after_attempt_id" => after_attempt_id, "up_to_attempt_id" => up_to_attempt_id),
+    );
+
+    let mut attempts_by_group: HashMap<(u32, u8), Vec<u32>> = HashMap::new();
+    for (attempt_id, encounter_id, difficulty_id) in attempts {
+        attempts_by_group.entry((encounter_id, difficulty_id)).or_insert_with(Vec::new).push(attempt_id);
+    }
+
+    let mut flagged_attempts = 0;
+    for ((encounter_id, difficulty_id), attempt_ids) in attempts_by_group {
+        let statistics = AnomalyStatistics::load(db_main, encounter_id, difficulty_id);
+        for attempt_id in attempt_ids {
+            if flag_attempt(db_main, attempt_id, statistics.detect(attempt_id)) {
+                flagged_attempts += 1;
+            }
+        }
+    }
+    flagged_attempts
+}
+
+/// Checks all ranked kills for anomalies, e.g. after the detection was introduced. Returns the number of newly flagged attempts.
+pub fn flag_all_attempt_anomalies(db_main: &mut (impl Execute + Select)) -> usize {
+    flag_attempt_anomalies_in_range(db_main, 0, u32::MAX)
+}
@@ -72,7 +72,8 @@ fn helper_get_best_ranking(ranking: Vec<RankingResultMeta>) -> RankingResultMeta
             duration: 1,
             difficulty_id: 0,
             character_spec: 0,
-            season_index: 0,
+            season_id: 0,
+            percentile: 0,
         },
         |best, ranking_result| {
             if (best.amount as f64 / best.duration as f64) < (ranking_result.amount as f64 / ranking_result.duration as f64) {
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use crate::modules::instance::domain_value::PrivacyType;
+use crate::modules::instance::dto::{AttemptParse, CharacterParse};
+use crate::modules::instance::tools::RankingType;
+use crate::modules::instance::Instance;
+use crate::params;
+use crate::util::database::Select;
+
+pub trait RankingParses {
+    fn get_character_parses(&self, db_main: &mut impl Select, ranking_type: RankingType, character_id: u32) -> Vec<CharacterParse>;
+    fn get_instance_parses(&self, db_main: &mut impl Select, ranking_type: RankingType, instance_meta_id: u32) -> Vec<AttemptParse>;
+}
+
+impl RankingParses for Instance {
+    /// Only parses of public instances are considered, like in the ranking export.
+    fn get_character_parses(&self, db_main: &mut impl Select, ranking_type: RankingType, character_id: u32) -> Vec<CharacterParse> {
+        let parses = db_main.select_wparams(
+            &format!(
+                "SELECT instance_meta_id, attempt_id, encounter_id, difficulty_id, percentile FROM {} WHERE character_id = :character_id",
+                ranking_type.results_table()
+            ),
+            |mut row| {
+                (
+                    row.take::<u32, usize>(0).unwrap(),
+                    row.take::<u32, usize>(1).unwrap(),
+                    row.take::<u32, usize>(2).unwrap(),
+                    row.take::<u8, usize>(3).unwrap(),
+                    row.take::<u8, usize>(4).unwrap(),
+                )
+            },
+            params!("character_id" => character_id),
+        );
+
+        let instance_metas = self.instance_metas.read().unwrap();
+        summarize_character_parses(
+            parses
+                .into_iter()
+                .filter(|(instance_meta_id, _, _, _, _)| instance_metas.1.get(instance_meta_id).map_or(false, |instance_meta| instance_meta.privacy_type == PrivacyType::Public))
+                .map(|(_, attempt_id, encounter_id, difficulty_id, percentile)| (attempt_id, encounter_id, difficulty_id, percentile))
+                .collect(),
+        )
+    }
+
+    /// The access to the instance is checked by the caller.
+    fn get_instance_parses(&self, db_main: &mut impl Select, ranking_type: RankingType, instance_meta_id: u32) -> Vec<AttemptParse> {
+        db_main.select_wparams(
+            &format!(
+                "SELECT attempt_id, encounter_id, character_id, character_spec, amount, duration, percentile FROM {} WHERE instance_meta_id = :instance_meta_id ORDER BY attempt_id, percentile DESC",
+                ranking_type.results_table()
+            ),
+            |mut row| AttemptParse {
+                attempt_id: row.take(0).unwrap(),
+                encounter_id: row.take(1).unwrap(),
+                character_id: row.take(2).unwrap(),
+                character_spec: row.take(3).unwrap(),
+                amount: row.take(4).unwrap(),
+                duration: row.take(5).unwrap(),
+                percentile: row.take(6).unwrap(),
+            },
+            params!("instance_meta_id" => instance_meta_id),
+        )
+    }
+}
+
+/// Summarizes the parses, i.e. (attempt_id, encounter_id, difficulty_id, percentile), per encounter and difficulty.
+pub fn summarize_character_parses(parses: Vec<(u32, u32, u8, u8)>) -> Vec<CharacterParse> {
+    let mut parses_per_encounter: HashMap<(u32, u8), Vec<(u32, u8)>> = HashMap::new();
+    for (attempt_id, encounter_id, difficulty_id, percentile) in parses {
+        parses_per_encounter.entry((encounter_id, difficulty_id)).or_insert_with(Vec::new).push((attempt_id, percentile));
+    }
+
+    let mut character_parses: Vec<CharacterParse> = parses_per_encounter
+        .into_iter()
+        .map(|((encounter_id, difficulty_id), mut parses)| {
+            parses.sort_by_key(|(attempt_id, percentile)| (*percentile, *attempt_id));
+            let (best_attempt_id, best_percentile) = *parses.last().unwrap();
+            CharacterParse {
+                encounter_id,
+                difficulty_id,
+                best_percentile,
+                median_percentile: median_percentile(&parses.iter().map(|(_, percentile)| *percentile).collect::<Vec<u8>>()),
+                best_attempt_id,
+                num_parses: parses.len() as u32,
+            }
+        })
+        .collect();
+    character_parses.sort_by_key(|character_parse| (character_parse.encounter_id, character_parse.difficulty_id));
+    character_parses
+}
+
+/// Expects the percentiles to be sorted, the median of an even number of percentiles is rounded down.
+pub fn median_percentile(sorted_percentiles: &[u8]) -> u8 {
+    if sorted_percentiles.is_empty() {
+        return 0;
+    }
+
+    let middle = sorted_percentiles.len() / 2;
+    if sorted_percentiles.len() % 2 == 1 {
+        sorted_percentiles[middle]
+    } else {
+        ((sorted_percentiles[middle - 1] as u16 + sorted_percentiles[middle] as u16) / 2) as u8
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::HashSet;
+
+use crate::modules::armory::tools::GetCharacter;
+use crate::modules::armory::util::talent_tree::get_talent_tree;
+use crate::modules::armory::Armory;
+use crate::modules::data::tools::RetrieveSeason;
+use crate::modules::data::Data;
+use crate::params;
+use crate::util::database::{Execute, Select};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RankingType {
+    Damage,
+    Heal,
+    Threat,
+}
+
+/// The attempts whose ranking results are updated or removed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RankingScope {
+    Attempt(u32),
+    InstanceMeta(u32),
+    All,
+}
+
+/// Ranking results are compared with the results of the same encounter, difficulty, class and spec within the season of their server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RankingGroup {
+    pub encounter_id: u32,
+    pub difficulty_id: u8,
+    pub hero_class_id: u8,
+    pub character_spec: u8,
+    pub season_id: u32,
+    pub server_id: u32,
+}
+
+impl RankingType {
+    pub fn all() -> [RankingType; 3] {
+        [RankingType::Damage, RankingType::Heal, RankingType::Threat]
+    }
+
+    pub fn source_table(&self) -> &'static str {
+        match self {
+            RankingType::Damage => "instance_ranking_damage",
+            RankingType::Heal => "instance_ranking_heal",
+            RankingType::Threat => "instance_ranking_threat",
+        }
+    }
+
+    pub fn source_column(&self) -> &'static str {
+        match self {
+            RankingType::Damage => "damage",
+            RankingType::Heal => "heal",
+            RankingType::Threat => "threat",
+        }
+    }
+
+    pub fn results_table(&self) -> &'static str {
+        match self {
+            RankingType::Damage => "ranking_results_damage",
+            RankingType::Heal => "ranking_results_heal",
+            RankingType::Threat => "ranking_results_threat",
+        }
+    }
+}
+
+impl RankingScope {
+    /// Condition on the attempt (aliased as B) of the ranking entries.
+    pub fn attempt_condition(&self) -> &'static str {
+        match self {
+            RankingScope::Attempt(_) => "B.id = :scope_id",
+            RankingScope::InstanceMeta(_) => "B.instance_meta_id = :scope_id",
+            RankingScope::All => ":scope_id = 0",
+        }
+    }
+
+    /// Condition on the ranking results (aliased as R).
+    pub fn result_condition(&self) -> &'static str {
+        match self {
+            RankingScope::Attempt(_) => "R.attempt_id = :scope_id",
+            RankingScope::InstanceMeta(_) => "R.instance_meta_id = :scope_id",
+            RankingScope::All => ":scope_id = 0",
+        }
+    }
+
+    pub fn scope_id(&self) -> u32 {
+        match self {
+            RankingScope::Attempt(attempt_id) => *attempt_id,
+            RankingScope::InstanceMeta(instance_meta_id) => *instance_meta_id,
+            RankingScope::All => 0,
+        }
+    }
+}
+
+/// Inserts the ranking results of all rankable attempts within the scope that are not yet ranked.
+/// Attempts outside of any season of their server and characters of an unknown class at the time of the attempt are not ranked.
+pub fn update_ranking_results(db_main: &mut (impl Execute + Select), armory: &Armory, data: &Data, scope: RankingScope) {
+    for ranking_type in RankingType::all().iter() {
+        update_ranking_results_of_type(db_main, armory, data, *ranking_type, scope);
+    }
+}
+
+fn update_ranking_results_of_type(db_main: &mut (impl Execute + Select), armory: &Armory, data: &Data, ranking_type: RankingType, scope: RankingScope) {
+    let entries = db_main.select_wparams(
+        &format!(
+            "SELECT A.id, A.character_id, B.encounter_id, A.attempt_id, A.{}, (B.end_ts - B.start_ts) as duration, B.instance_meta_id, C.map_difficulty, B.start_ts, D.server_id, D.map_id FROM {} A JOIN instance_attempt B ON A.attempt_id = B.id \
+             JOIN instance_raid C ON B.instance_meta_id = C.instance_meta_id JOIN instance_meta D ON B.instance_meta_id = D.id LEFT JOIN {} R ON A.id = R.id WHERE B.rankable = 1 AND R.id IS NULL AND {}",
+            ranking_type.source_column(),
+            ranking_type.source_table(),
+            ranking_type.results_table(),
+            scope.attempt_condition()
+        ),
+        |mut row| {
+            let id: u32 = row.take(0).unwrap();
+            let character_id: u32 = row.take(1).unwrap();
+            let encounter_id: u32 = row.take(2).unwrap();
+            let attempt_id: u32 = row.take(3).unwrap();
+            let amount: u32 = row.take(4).unwrap();
+            let duration: u64 = row.take(5).unwrap();
+            let instance_meta_id: u32 = row.take(6).unwrap();
+            let difficulty_id: u8 = row.take(7).unwrap();
+            let start_ts: u64 = row.take(8).unwrap();
+            let server_id: u32 = row.take(9).unwrap();
+            let map_id: u16 = row.take(10).unwrap();
+            (id, character_id, encounter_id, attempt_id, amount, duration, instance_meta_id, difficulty_id, start_ts, server_id, map_id)
+        },
+        params!("scope_id" => scope.scope_id()),
+    );
+
+    let mut ranking_results = Vec::with_capacity(entries.len());
+    for (id, character_id, encounter_id, attempt_id, amount, duration, instance_meta_id, difficulty_id, start_ts, server_id, map_id) in entries {
+        let season_id = match data.find_season(server_id, map_id, start_ts) {
+            Some(season_id) => season_id,
+            None => continue,
+        };
+
+        let character_info = armory.get_character_moment(db_main, character_id, start_ts);
+        let hero_class_id = character_info.as_ref().map(|char_history| char_history.character_info.hero_class_id).unwrap_or(0);
+        if hero_class_id == 0 || hero_class_id == 12 {
+            continue; // ignore unknown class
+        }
+
+        let character_name = character_info.as_ref().map(|char_history| char_history.character_name.clone()).unwrap_or_else(|| "Unknown".to_string());
+        let character_spec = character_info
+            .as_ref()
+            .and_then(|char_history| char_history.character_info.talent_specialization.as_ref().map(|talents| get_talent_tree(&talents) + 1))
+            .unwrap_or(0);
+
+        ranking_results.push((
+            id,
+            encounter_id,
+            server_id,
+            character_id,
+            hero_class_id,
+            instance_meta_id,
+            attempt_id,
+            amount,
+            duration,
+            difficulty_id,
+            character_spec,
+            season_id,
+            character_name,
+        ));
+    }
+
+    if ranking_results.is_empty() {
+        return;
+    }
+
+    let groups: HashSet<RankingGroup> = ranking_results
+        .iter()
+        .map(|(_, encounter_id, server_id, _, hero_class_id, _, _, _, _, difficulty_id, character_spec, season_id, _)| RankingGroup {
+            encounter_id: *encounter_id,
+            difficulty_id: *difficulty_id,
+            hero_class_id: *hero_class_id,
+            character_spec: *character_spec,
+            season_id: *season_id,
+            server_id: *server_id,
+        })
+        .collect();
+
+    db_main.execute_batch_wparams(
+        &format!(
+            "INSERT IGNORE INTO {} (id, encounter_id, server_id, character_id, hero_class_id, instance_meta_id, attempt_id, amount, duration, difficulty_id, character_spec, season_id, character_name) VALUES (:id, :encounter_id, :server_id, \
+             :character_id, :hero_class_id, :instance_meta_id, :attempt_id, :amount, :duration, :difficulty_id, :character_spec, :season_id, :character_name)",
+            ranking_type.results_table()
+        ),
+        ranking_results,
+        |(id, encounter_id, server_id, character_id, hero_class_id, instance_meta_id, attempt_id, amount, duration, difficulty_id, character_spec, season_id, character_name)| {
+            params!(
+                "id" => id,
+                "encounter_id" => encounter_id,
+                "server_id" => server_id,
+                "character_id" => character_id,
+                "hero_class_id" => hero_class_id,
+                "instance_meta_id" => instance_meta_id,
+                "attempt_id" => attempt_id,
+                "amount" => amount,
+                "duration" => duration,
+                "difficulty_id" => difficulty_id,
+                "character_spec" => character_spec,
+                "season_id" => season_id,
+                "character_name" => character_name
+            )
+        },
+    );
+
+    update_ranking_percentiles(db_main, ranking_type, &groups);
+}
+
+/// Removes all ranking results within the scope, e.g. after an attempt was unranked or an instance was deleted.
+pub fn remove_ranking_results(db_main: &mut (impl Execute + Select), scope: RankingScope) {
+    for ranking_type in RankingType::all().iter() {
+        let groups = select_ranking_groups(db_main, *ranking_type, scope);
+        db_main.execute_wparams(&format!("DELETE R FROM {} R WHERE {}", ranking_type.results_table(), scope.result_condition()), params!("scope_id" => scope.scope_id()));
+        update_ranking_percentiles(db_main, *ranking_type, &groups);
+    }
+}
+
+fn select_ranking_groups(db_main: &mut impl Select, ranking_type: RankingType, scope: RankingScope) -> HashSet<RankingGroup> {
+    db_main
+        .select_wparams(
+            &format!(
+                "SELECT DISTINCT R.encounter_id, R.difficulty_id, R.hero_class_id, R.character_spec, R.season_id, R.server_id FROM {} R WHERE {}",
+                ranking_type.results_table(),
+                scope.result_condition()
+            ),
+            |mut row| RankingGroup {
+                encounter_id: row.take(0).unwrap(),
+                difficulty_id: row.take(1).unwrap(),
+                hero_class_id: row.take(2).unwrap(),
+                character_spec: row.take(3).unwrap(),
+                season_id: row.take(4).unwrap(),
+                server_id: row.take(5).unwrap(),
+            },
+            params!("scope_id" => scope.scope_id()),
+        )
+        .into_iter()
+        .collect()
+}
+
+/// The percentile of a result is the share of the other results of its group that it beats or ties,
+/// i.e. the best result of a group scores 100 and the worst 0. A result without competition scores 100.
+pub fn calculate_percentiles(amounts_per_second: &[f64]) -> Vec<u8> {
+    if amounts_per_second.len() <= 1 {
+        return vec![100; amounts_per_second.len()];
+    }
+
+    let mut sorted = amounts_per_second.to_vec();
+    sorted.sort_by(|left, right| left.partial_cmp(right).unwrap());
+    let others = (sorted.len() - 1) as f64;
+    amounts_per_second
+        .iter()
+        .map(|amount_per_second| {
+            let not_better = sorted.partition_point(|other| other <= amount_per_second) - 1;
+            (100.0 * not_better as f64 / others).floor() as u8
+        })
+        .collect()
+}
+
+/// Recalculates the percentiles of all results of the groups, but only writes the ones that changed.
+fn update_ranking_percentiles(db_main: &mut (impl Execute + Select), ranking_type: RankingType, groups: &HashSet<RankingGroup>) {
+    for group in groups.iter() {
+        let results = db_main.select_wparams(
+            &format!(
+                "SELECT id, amount, duration, percentile FROM {} WHERE encounter_id = :encounter_id AND difficulty_id = :difficulty_id AND hero_class_id = :hero_class_id AND character_spec = :character_spec AND season_id = :season_id AND \
+                 server_id = :server_id",
+                ranking_type.results_table()
+            ),
+            |mut row| {
+                let id: u32 = row.take(0).unwrap();
+                let amount: u32 = row.take(1).unwrap();
+                let duration: u64 = row.take(2).unwrap();
+                let percentile: u8 = row.take(3).unwrap();
+                (id, amount as f64 / duration.max(1) as f64, percentile)
+            },
+            params!(
+                "encounter_id" => group.encounter_id,
+                "difficulty_id" => group.difficulty_id,
+                "hero_class_id" => group.hero_class_id,
+                "character_spec" => group.character_spec,
+                "season_id" => group.season_id,
+                "server_id" => group.server_id
+            ),
+        );
+
+        let amounts_per_second: Vec<f64> = results.iter().map(|(_, amount_per_second, _)| *amount_per_second).collect();
+        let changed_percentiles: Vec<(u32, u8)> = results
+            .iter()
+            .zip(calculate_percentiles(&amounts_per_second))
+            .filter(|((_, _, current_percentile), percentile)| current_percentile != percentile)
+            .map(|((id, _, _), percentile)| (*id, percentile))
+            .collect();
+        if changed_percentiles.is_empty() {
+            continue;
+        }
+
+        db_main.execute_batch_wparams(
+            &format!("UPDATE {} SET percentile = :percentile WHERE id = :id", ranking_type.results_table()),
+            changed_percentiles,
+            |(id, percentile)| params!("id" => id, "percentile" => percentile),
+        );
+    }
+}
+
+// Results whose entry is gone, whose attempt is no longer rankable or whose amount deviates from the entry
+fn stale_ranking_results_query(ranking_type: RankingType, select_clause: &str) -> String {
+    format!(
+        "{} FROM {} R LEFT JOIN {} A ON R.id = A.id LEFT JOIN instance_attempt B ON A.attempt_id = B.id WHERE A.id IS NULL OR B.id IS NULL OR B.rankable = 0 OR R.amount <> A.{}",
+        select_clause,
+        ranking_type.results_table(),
+        ranking_type.source_table(),
+        ranking_type.source_column()
+    )
+}
+
+/// Returns a description of every inconsistency between the ranking results and the ranked attempts.
+pub fn check_ranking_results(db_main: &mut impl Select, data: &Data) -> Vec<String> {
+    let mut issues = Vec::new();
+    for ranking_type in RankingType::all().iter() {
+        let stale_results: u64 = db_main.select_value(&stale_ranking_results_query(*ranking_type, "SELECT COUNT(*)"), |mut row| row.take(0).unwrap()).unwrap_or(0);
+        if stale_results > 0 {
+            issues.push(format!("{}: {} results are not backed by a ranked attempt", ranking_type.results_table(), stale_results));
+        }
+
+        let missing_attempts = db_main
+            .select(
+                &format!(
+                    "SELECT DISTINCT B.id, B.start_ts, D.server_id, D.map_id FROM {} A JOIN instance_attempt B ON A.attempt_id = B.id JOIN instance_meta D ON B.instance_meta_id = D.id WHERE B.rankable = 1 AND NOT EXISTS (SELECT 1 FROM {} R WHERE \
+                     R.attempt_id = B.id)",
+                    ranking_type.source_table(),
+                    ranking_type.results_table()
+                ),
+                |mut row| (row.take::<u32, usize>(0).unwrap(), row.take::<u64, usize>(1).unwrap(), row.take::<u32, usize>(2).unwrap(), row.take::<u16, usize>(3).unwrap()),
+            )
+            .into_iter()
+            .filter(|(_, start_ts, server_id, map_id)| data.find_season(*server_id, *map_id, *start_ts).is_some())
+            .count();
+        if missing_attempts > 0 {
+            issues.push(format!("{}: {} ranked attempts have no results", ranking_type.results_table(), missing_attempts));
+        }
+    }
+    issues
+}
+
+/// Removes stale ranking results, inserts the missing ones and recalculates all percentiles.
+pub fn backfill_ranking_results(db_main: &mut (impl Execute + Select), armory: &Armory, data: &Data) {
+    for ranking_type in RankingType::all().iter() {
+        db_main.execute_one(&stale_ranking_results_query(*ranking_type, "DELETE R"));
+    }
+    update_ranking_results(db_main, armory, data, RankingScope::All);
+    for ranking_type in RankingType::all().iter() {
+        let groups = select_ranking_groups(db_main, *ranking_type, RankingScope::All);
+        update_ranking_percentiles(db_main, *ranking_type, &groups);
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+use crate::modules::armory::Armory;
+use crate::modules::data::tools::{RetrieveInstanceEncounter, RetrieveSeason};
+use crate::modules::data::Data;
+use crate::modules::instance::domain_value::{InstanceAttempt, InstanceMeta, PrivacyType};
+use crate::modules::instance::dto::{SpeedKill, SpeedKillFilter, SpeedRun, SpeedRunFilter};
+use crate::modules::instance::tools::FindInstanceGuild;
+use crate::modules::instance::Instance;
+use crate::params;
+use crate::util::database::{Execute, Select};
+
+pub trait SpeedRuns {
+    fn get_speed_runs(&self, filter: &SpeedRunFilter) -> Vec<SpeedRun>;
+    fn get_speed_kills(&self, filter: &SpeedKillFilter) -> Vec<SpeedKill>;
+    fn remove_speed_run(&self, db_main: &mut impl Execute, instance_meta_id: u32);
+    fn remove_speed_kill(&self, db_main: &mut impl Execute, attempt_id: u32);
+}
+
+impl SpeedRuns for Instance {
+    fn get_speed_runs(&self, filter: &SpeedRunFilter) -> Vec<SpeedRun> {
+        let instance_metas = self.instance_metas.read().unwrap();
+        let speed_runs = self.speed_runs.read().unwrap();
+        speed_runs
+            .iter()
+            .filter(|speed_run| is_public(&instance_metas.1, speed_run.instance_meta_id) && speed_run_matches(speed_run, filter))
+            .cloned()
+            .collect()
+    }
+
+    fn get_speed_kills(&self, filter: &SpeedKillFilter) -> Vec<SpeedKill> {
+        let instance_metas = self.instance_metas.read().unwrap();
+        let speed_kills = self.speed_kills.read().unwrap();
+        speed_kills
+            .iter()
+            .filter(|speed_kill| is_public(&instance_metas.1, speed_kill.instance_meta_id) && speed_kill_matches(speed_kill, filter))
+            .cloned()
+            .collect()
+    }
+
+    fn remove_speed_run(&self, db_main: &mut impl Execute, instance_meta_id: u32) {
+        db_main.execute_wparams("DELETE FROM instance_speed_run WHERE instance_meta_id=:instance_meta_id", params!("instance_meta_id" => instance_meta_id));
+        let mut speed_runs = self.speed_runs.write().unwrap();
+        speed_runs.retain(|speed_run| speed_run.instance_meta_id != instance_meta_id);
+    }
+
+    fn remove_speed_kill(&self, db_main: &mut impl Execute, attempt_id: u32) {
+        db_main.execute_wparams("DELETE FROM instance_speed_kill WHERE attempt_id=:attempt_id", params!("attempt_id" => attempt_id));
+        let mut speed_kills = self.speed_kills.write().unwrap();
+        speed_kills.retain(|speed_kill| speed_kill.attempt_id != attempt_id);
+    }
+}
+
+// Speed runs and kills are stored regardless of the privacy of the instance, as it may change afterwards
+fn is_public(instance_metas: &HashMap<u32, InstanceMeta>, instance_meta_id: u32) -> bool {
+    instance_metas.get(&instance_meta_id).map_or(false, |instance_meta| instance_meta.privacy_type == PrivacyType::Public)
+}
+
+pub fn speed_run_matches(speed_run: &SpeedRun, filter: &SpeedRunFilter) -> bool {
+    filter.map_id.map_or(true, |map_id| speed_run.map_id == map_id)
+        && filter.server_id.map_or(true, |server_id| speed_run.server_id == server_id)
+        && filter.season_id.map_or(true, |season_id| speed_run.season_id == season_id)
+        && filter.difficulty_id.map_or(true, |difficulty_id| speed_run.difficulty_id == difficulty_id)
+        && filter.guild_id.map_or(true, |guild_id| speed_run.guild_id == guild_id)
+}
+
+pub fn speed_kill_matches(speed_kill: &SpeedKill, filter: &SpeedKillFilter) -> bool {
+    filter.encounter_id.map_or(true, |encounter_id| speed_kill.encounter_id == encounter_id)
+        && filter.server_id.map_or(true, |server_id| speed_kill.server_id == server_id)
+        && filter.season_id.map_or(true, |season_id| speed_kill.season_id == season_id)
+        && filter.difficulty_id.map_or(true, |difficulty_id| speed_kill.difficulty_id == difficulty_id)
+        && filter.guild_id.map_or(true, |guild_id| speed_kill.guild_id == guild_id)
+}
+
+/// A speed run requires every encounter of the instance to be killed on the same difficulty.
+pub fn calculate_speed_run(instance_encounters: &[u32], attempts: &[InstanceAttempt]) -> Option<(u64, u8, u32)> {
+    let first_attempt = attempts.iter().min_by_key(|attempt| attempt.start_ts)?;
+    let has_killed_all_encounters = instance_encounters.iter().all(|encounter_id| attempts.iter().any(|attempt| attempt.encounter_id == *encounter_id && attempt.rankable));
+    let all_difficulties_are_same = attempts.iter().all(|attempt| attempt.difficulty_id == first_attempt.difficulty_id);
+    if !has_killed_all_encounters || !all_difficulties_are_same {
+        return None;
+    }
+
+    let end = attempts.iter().map(|attempt| attempt.end_ts).max().unwrap();
+    Some((end - first_attempt.start_ts, first_attempt.difficulty_id, first_attempt.season_id))
+}
+
+pub fn load_speed_runs(db_main: &mut impl Select) -> Vec<SpeedRun> {
+    db_main.select("SELECT instance_meta_id, map_id, guild_id, guild_name, server_id, duration, difficulty_id, season_id FROM instance_speed_run", |mut row| SpeedRun {
+        instance_meta_id: row.take(0).unwrap(),
+        map_id: row.take(1).unwrap(),
+        guild_id: row.take(2).unwrap(),
+        guild_name: row.take(3).unwrap(),
+        server_id: row.take(4).unwrap(),
+        duration: row.take(5).unwrap(),
+        difficulty_id: row.take(6).unwrap(),
+        season_id: row.take(7).unwrap(),
+    })
+}
+
+pub fn load_speed_kills(db_main: &mut impl Select) -> Vec<SpeedKill> {
+    db_main.select(
+        "SELECT instance_meta_id, attempt_id, encounter_id, guild_id, guild_name, server_id, duration, difficulty_id, season_id FROM instance_speed_kill",
+        |mut row| SpeedKill {
+            instance_meta_id: row.take(0).unwrap(),
+            attempt_id: row.take(1).unwrap(),
+            encounter_id: row.take(2).unwrap(),
+            guild_id: row.take(3).unwrap(),
+            guild_name: row.take(4).unwrap(),
+            server_id: row.take(5).unwrap(),
+            duration: row.take(6).unwrap(),
+            difficulty_id: row.take(7).unwrap(),
+            season_id: row.take(8).unwrap(),
+        },
+    )
+}
+
+// Kills outside of any season of their server are assigned to season 0
+fn select_kill_attempts(db_main: &mut impl Select, data: &Data, condition: &str, scope_id: u32) -> Vec<(u32, InstanceAttempt)> {
+    db_main.select_wparams(
+        &format!(
+            "SELECT A.instance_meta_id, A.id, A.encounter_id, A.start_ts, A.end_ts, B.map_difficulty, C.server_id, C.map_id FROM instance_attempt A JOIN instance_raid B ON A.instance_meta_id = B.instance_meta_id JOIN instance_meta C ON \
+             A.instance_meta_id = C.id WHERE A.is_kill = 1 AND A.rankable = 1 AND {} ORDER BY A.id",
+            condition
+        ),
+        |mut row| {
+            let instance_meta_id: u32 = row.take(0).unwrap();
+            let start_ts: u64 = row.take(3).unwrap();
+            let server_id: u32 = row.take(6).unwrap();
+            let map_id: u16 = row.take(7).unwrap();
+            (
+                instance_meta_id,
+                server_id,
+                map_id,
+                InstanceAttempt {
+                    attempt_id: row.take(1).unwrap(),
+                    encounter_id: row.take(2).unwrap(),
+                    start_ts,
+                    end_ts: row.take(4).unwrap(),
+                    is_kill: true,
+                    difficulty_id: row.take(5).unwrap(),
+                    rankable: true,
+                    season_id: 0,
+                },
+            )
+        },
+        params!("scope_id" => scope_id),
+    )
+    .into_iter()
+    .map(|(instance_meta_id, server_id, map_id, mut attempt)| {
+        attempt.season_id = data.find_season(server_id, map_id, attempt.start_ts).unwrap_or(0);
+        (instance_meta_id, attempt)
+    })
+    .collect()
+}
+
+/// Adds the speed kills of all kills that were committed after the given attempt and recalculates the speed runs of their instances.
+/// Returns the attempt up to which all kills were processed, kills of instances that are not loaded yet are retried by the next update.
+pub fn update_speed_runs_and_kills(
+    db_main: &mut (impl Execute + Select), armory: &Armory, data: &Data, instance_metas: &RwLock<(u32, HashMap<u32, InstanceMeta>)>, speed_runs: &RwLock<Vec<SpeedRun>>, speed_kills: &RwLock<Vec<SpeedKill>>, after_attempt_id: u32,
+) -> u32 {
+    let known_attempt_ids = speed_kills.read().unwrap().iter().map(|speed_kill| speed_kill.attempt_id).collect::<HashSet<u32>>();
+
+    let mut processed_attempt_id = after_attempt_id;
+    let mut skipped_kill = false;
+    let mut new_kills: Vec<(InstanceMeta, Vec<InstanceAttempt>)> = Vec::new();
+    {
+        let instance_metas = instance_metas.read().unwrap();
+        for (instance_meta_id, attempt) in select_kill_attempts(db_main, data, "A.id > :scope_id", after_attempt_id) {
+            let instance_meta = match instance_metas.1.get(&instance_meta_id) {
+                Some(instance_meta) => instance_meta,
+                None => {
+                    skipped_kill = true;
+                    continue;
+                },
+            };
+            if !skipped_kill {
+                processed_attempt_id = attempt.attempt_id;
+            }
+            // Kills after a skipped kill are selected again by the next update
+            if known_attempt_ids.contains(&attempt.attempt_id) {
+                continue;
+            }
+            match new_kills.iter_mut().find(|(kill_instance_meta, _)| kill_instance_meta.instance_meta_id == instance_meta_id) {
+                Some((_, attempts)) => attempts.push(attempt),
+                None => new_kills.push((instance_meta.clone(), vec![attempt])),
+            }
+        }
+    }
+
+    for (instance_meta, attempts) in new_kills {
+        let (guild_id, guild_name) = instance_meta
+            .participants
+            .find_instance_guild(db_main, armory, instance_meta.end_ts.unwrap_or(instance_meta.start_ts))
+            .map(|guild| (guild.id, guild.name))
+            .unwrap_or((0, "Pug Raid".to_string()));
+
+        for attempt in attempts.iter() {
+            let speed_kill = SpeedKill {
+                instance_meta_id: instance_meta.instance_meta_id,
+                attempt_id: attempt.attempt_id,
+                encounter_id: attempt.encounter_id,
+                guild_id,
+                guild_name: guild_name.clone(),
+                server_id: instance_meta.server_id,
+                duration: attempt.end_ts - attempt.start_ts,
+                difficulty_id: attempt.difficulty_id,
+                season_id: attempt.season_id,
+            };
+            if db_main.execute_wparams(
+                "INSERT IGNORE INTO instance_speed_kill (`instance_meta_id`, `attempt_id`, `encounter_id`, `guild_id`, `guild_name`, `server_id`, `duration`, `difficulty_id`, `season_id`) VALUES (:instance_meta_id, :attempt_id, :encounter_id, \
+                 :guild_id, :guild_name, :server_id, :duration, :difficulty_id, :season_id)",
+                params!(
+                    "instance_meta_id" => speed_kill.instance_meta_id,
+                    "attempt_id" => speed_kill.attempt_id,
+                    "encounter_id" => speed_kill.encounter_id,
+                    "guild_id" => speed_kill.guild_id,
+                    "guild_name" => speed_kill.guild_name.clone(),
+                    "server_id" => speed_kill.server_id,
+                    "duration" => speed_kill.duration,
+                    "difficulty_id" => speed_kill.difficulty_id,
+                    "season_id" => speed_kill.season_id
+                ),
+            ) {
+                speed_kills.write().unwrap().push(speed_kill);
+            }
+        }
+
+        let instance_encounters = match data.get_instance_encounters(instance_meta.map_id) {
+            Some(instance_encounters) => instance_encounters,
+            None => continue,
+        };
+        let all_attempts: Vec<InstanceAttempt> = select_kill_attempts(db_main, data, "A.instance_meta_id = :scope_id", instance_meta.instance_meta_id)
+            .into_iter()
+            .map(|(_, attempt)| attempt)
+            .collect();
+        if let Some((duration, difficulty_id, season_id)) = calculate_speed_run(&instance_encounters, &all_attempts) {
+            let speed_run = SpeedRun {
+                instance_meta_id: instance_meta.instance_meta_id,
+                map_id: instance_meta.map_id,
+                guild_id,
+                guild_name,
+                server_id: instance_meta.server_id,
+                duration,
+                difficulty_id,
+                season_id,
+            };
+            if db_main.execute_wparams(
+                "REPLACE INTO instance_speed_run (`instance_meta_id`, `map_id`, `guild_id`, `guild_name`, `server_id`, `duration`, `difficulty_id`, `season_id`) VALUES (:instance_meta_id, :map_id, :guild_id, :guild_name, :server_id, :duration, \
+                 :difficulty_id, :season_id)",
+                params!(
+                    "instance_meta_id" => speed_run.instance_meta_id,
+                    "map_id" => speed_run.map_id,
+                    "guild_id" => speed_run.guild_id,
+                    "guild_name" => speed_run.guild_name.clone(),
+                    "server_id" => speed_run.server_id,
+                    "duration" => speed_run.duration,
+                    "difficulty_id" => speed_run.difficulty_id,
+                    "season_id" => speed_run.season_id
+                ),
+            ) {
+                let mut speed_runs = speed_runs.write().unwrap();
+                speed_runs.retain(|entry| entry.instance_meta_id != speed_run.instance_meta_id);
+                speed_runs.push(speed_run);
+            }
+        }
+    }
+    processed_attempt_id
+}
@@ -1,4 +1,5 @@
 use crate::modules::instance::dto::InstanceFailure;
+use crate::modules::instance::tools::{remove_ranking_results, RankingScope, SpeedRuns};
 use crate::modules::instance::Instance;
 use crate::params;
 use crate::util::database::{Execute, Select};
@@ -22,20 +23,10 @@ impl UnrankAttempt for Instance {
 
         // Update the database to mark as unrankable
         let _ = db_main.execute_wparams("UPDATE `main`.`instance_attempt` SET rankable = 0 WHERE id=:attempt_id", params!("attempt_id" => attempt_id));
+        remove_ranking_results(db_main, RankingScope::Attempt(attempt_id));
 
-        {
-            let mut speed_runs = self.speed_runs.write().unwrap();
-            if let Some(index) = speed_runs.iter().position(|speed_run| speed_run.instance_meta_id == i_m_i) {
-                speed_runs.remove(index);
-            }
-        }
-
-        {
-            let mut speed_kills = self.speed_kills.write().unwrap();
-            if let Some(index) = speed_kills.iter().position(|speed_kill| speed_kill.attempt_id == attempt_id) {
-                speed_kills.remove(index);
-            }
-        }
+        self.remove_speed_run(db_main, i_m_i);
+        self.remove_speed_kill(db_main, attempt_id);
 
         Ok(())
     }
@@ -1,4 +1,5 @@
 use crate::modules::account::guard::Authenticate;
+use crate::modules::account::material::Account;
 use crate::modules::instance::dto::InstanceFailure;
 use crate::modules::instance::tools::DeleteInstance;
 use crate::modules::instance::Instance;
@@ -8,6 +9,6 @@ use rocket_contrib::json::Json;
 
 #[openapi]
 #[delete("/delete", data = "<data>")]
-pub fn delete_instance(mut db_main: MainDb, me: State<Instance>, data: Json<u32>, auth: Authenticate) -> Result<(), InstanceFailure> {
-    me.delete_instance(&mut *db_main, data.into_inner(), auth.0)
+pub fn delete_instance(mut db_main: MainDb, me: State<Instance>, account: State<Account>, data: Json<u32>, auth: Authenticate) -> Result<(), InstanceFailure> {
+    me.delete_instance(&mut *db_main, &account, data.into_inner(), auth.0)
 }
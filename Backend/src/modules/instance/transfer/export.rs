@@ -1,7 +1,9 @@
+use crate::modules::account::guard::CurrentUser;
+use crate::modules::account::material::Account;
 use crate::modules::armory::Armory;
 use crate::modules::data::Data;
-use crate::modules::instance::dto::{InstanceFailure, InstanceViewerAttempt, InstanceViewerMeta, InstanceViewerParticipant};
-use crate::modules::instance::tools::ExportInstance;
+use crate::modules::instance::dto::{EventExportFilter, InstanceFailure, InstanceViewerAttempt, InstanceViewerMeta, InstanceViewerParticipant};
+use crate::modules::instance::tools::{ExportInstance, InstanceAccess};
 use crate::modules::instance::{GzippedResponse, Instance};
 use crate::MainDb;
 use flate2::write::GzEncoder;
@@ -9,27 +11,29 @@ use flate2::Compression;
 use rocket::State;
 use rocket_contrib::json::Json;
 use std::io::Write;
-use serde_json::{from_str, Value};
 
 #[openapi(skip)]
 #[get("/export/<instance_meta_id>/<event_type>/<_last_event_id>")]
-pub fn get_instance_event_type(me: State<Instance>, instance_meta_id: u32, event_type: u8, _last_event_id: u32) -> Result<GzippedResponse, InstanceFailure> {
-    let event_data = me.export_instance_event_type(instance_meta_id, event_type);
+pub fn get_instance_event_type(me: State<Instance>, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32, event_type: u8, _last_event_id: u32) -> Result<GzippedResponse, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
+    let event_data = me.export_instance_event_type(instance_meta_id, event_type)?;
+    let serialized_data = serde_json::to_vec(&event_data).expect("Serialization failed");
 
-    if event_data.is_err() {
-        return Err(event_data.err().unwrap());
-    }
-
-    let event_data_ref: &Vec<String> = &event_data?;
+    // Compress the JSON using gzip
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&serialized_data).expect("Gzip compression failed");
+    let compressed_data = encoder.finish().expect("Gzip finalization failed");
 
-    let formatted_data: Vec<Value> = event_data_ref
-        .iter()
-        .filter_map(|s| from_str::<Value>(s).ok()) // Each `s` is a &String
-        .collect();
+    Ok(GzippedResponse(compressed_data))
+}
 
-    let serialized_data = serde_json::to_vec(&formatted_data).expect("Serialization failed");
+#[openapi(skip)]
+#[post("/export/<instance_meta_id>/<event_type>", format = "application/json", data = "<filter>")]
+pub fn get_instance_event_slice(mut db_main: MainDb, me: State<Instance>, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32, event_type: u8, filter: Json<EventExportFilter>) -> Result<GzippedResponse, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
+    let event_page = me.export_instance_event_slice(&mut *db_main, instance_meta_id, event_type, &filter.into_inner())?;
+    let serialized_data = serde_json::to_vec(&event_page).expect("Serialization failed");
 
-    // Compress the JSON using gzip
     let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
     encoder.write_all(&serialized_data).expect("Gzip compression failed");
     let compressed_data = encoder.finish().expect("Gzip finalization failed");
@@ -39,18 +43,21 @@ pub fn get_instance_event_type(me: State<Instance>, instance_meta_id: u32, event
 
 #[openapi]
 #[get("/export/<instance_meta_id>")]
-pub fn get_instance_meta(mut db_main: MainDb, me: State<Instance>, data: State<Data>, armory: State<Armory>, instance_meta_id: u32) -> Result<Json<InstanceViewerMeta>, InstanceFailure> {
+pub fn get_instance_meta(mut db_main: MainDb, me: State<Instance>, data: State<Data>, armory: State<Armory>, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32) -> Result<Json<InstanceViewerMeta>, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
     me.get_instance_meta(&mut *db_main, &data, &armory, instance_meta_id).map(Json)
 }
 
 #[openapi]
 #[get("/export/participants/<instance_meta_id>")]
-pub fn get_instance_participants(mut db_main: MainDb, me: State<Instance>, armory: State<Armory>, instance_meta_id: u32) -> Result<Json<Vec<InstanceViewerParticipant>>, InstanceFailure> {
+pub fn get_instance_participants(mut db_main: MainDb, me: State<Instance>, armory: State<Armory>, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32) -> Result<Json<Vec<InstanceViewerParticipant>>, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
     me.get_instance_participants(&mut *db_main, &armory, instance_meta_id).map(Json)
 }
 
 #[openapi]
 #[get("/export/attempts/<instance_meta_id>")]
-pub fn get_instance_attempts(me: State<Instance>, mut db_main: MainDb, instance_meta_id: u32) -> Result<Json<Vec<InstanceViewerAttempt>>, InstanceFailure> {
+pub fn get_instance_attempts(me: State<Instance>, mut db_main: MainDb, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32) -> Result<Json<Vec<InstanceViewerAttempt>>, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
     me.get_instance_attempts(&mut (*db_main), instance_meta_id).map(Json)
 }
@@ -5,34 +5,35 @@ use rocket::State;
 use rocket_contrib::json::Json;
 use crate::modules::instance::dto::{InstanceFailure, InstancePrivacy};
 use crate::MainDb;
-use crate::modules::account::guard::CanAdjustLogPrivacy;
+use crate::modules::account::guard::{AdjustLogPrivacy, CurrentUser, HasPermission};
+use crate::modules::account::material::Account;
 
 #[openapi]
 #[get("/meta/raids")]
-pub fn export_raids(me: State<Instance>) -> Json<Vec<InstanceMeta>> {
-    Json(me.export_meta(0))
+pub fn export_raids(me: State<Instance>, account: State<Account>, current_user: CurrentUser) -> Json<Vec<InstanceMeta>> {
+    Json(me.export_listed_meta(&account, 0, current_user.0))
 }
 
 #[openapi]
 #[get("/meta/rated_arenas")]
-pub fn export_rated_arenas(me: State<Instance>) -> Json<Vec<InstanceMeta>> {
-    Json(me.export_meta(1))
+pub fn export_rated_arenas(me: State<Instance>, account: State<Account>, current_user: CurrentUser) -> Json<Vec<InstanceMeta>> {
+    Json(me.export_listed_meta(&account, 1, current_user.0))
 }
 
 #[openapi]
 #[get("/meta/skirmishes")]
-pub fn export_skirmishes(me: State<Instance>) -> Json<Vec<InstanceMeta>> {
-    Json(me.export_meta(2))
+pub fn export_skirmishes(me: State<Instance>, account: State<Account>, current_user: CurrentUser) -> Json<Vec<InstanceMeta>> {
+    Json(me.export_listed_meta(&account, 2, current_user.0))
 }
 
 #[openapi]
 #[get("/meta/battlegrounds")]
-pub fn export_battlegrounds(me: State<Instance>) -> Json<Vec<InstanceMeta>> {
-    Json(me.export_meta(3))
+pub fn export_battlegrounds(me: State<Instance>, account: State<Account>, current_user: CurrentUser) -> Json<Vec<InstanceMeta>> {
+    Json(me.export_listed_meta(&account, 3, current_user.0))
 }
 
 #[openapi]
 #[post("/meta/update_privacy", format = "application/json", data = "<privacy>")]
-pub fn update_privacy(mut db_main: MainDb, me: State<Instance>, privacy: Json<InstancePrivacy>, auth: CanAdjustLogPrivacy) -> Result<(), InstanceFailure> {
-    me.update_privacy(&mut (*db_main), privacy.into_inner(), auth.0)
+pub fn update_privacy(mut db_main: MainDb, me: State<Instance>, account: State<Account>, privacy: Json<InstancePrivacy>, auth: HasPermission<AdjustLogPrivacy>) -> Result<(), InstanceFailure> {
+    me.update_privacy(&mut (*db_main), &account, privacy.into_inner(), auth.member_id)
 }
\ No newline at end of file
@@ -0,0 +1,33 @@
+use crate::modules::account::guard::CurrentUser;
+use crate::modules::account::material::Account;
+use crate::modules::instance::dto::{InstanceFailure, MeterAggregate, MeterFilter};
+use crate::modules::instance::tools::{InstanceAccess, MeterAggregation};
+use crate::modules::instance::Instance;
+use crate::MainDb;
+use rocket::State;
+use rocket_contrib::json::Json;
+
+#[openapi]
+#[get("/meter/<instance_meta_id>")]
+pub fn get_instance_meter(mut db_main: MainDb, me: State<Instance>, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32) -> Result<Json<MeterAggregate>, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
+    me.get_meter_aggregate(&mut *db_main, instance_meta_id, MeterFilter::default()).map(Json)
+}
+
+#[openapi]
+#[get("/meter/<instance_meta_id>/<attempt_id>")]
+pub fn get_attempt_meter(mut db_main: MainDb, me: State<Instance>, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32, attempt_id: u32) -> Result<Json<MeterAggregate>, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
+    let filter = MeterFilter {
+        attempt_id: Some(attempt_id),
+        ..MeterFilter::default()
+    };
+    me.get_meter_aggregate(&mut *db_main, instance_meta_id, filter).map(Json)
+}
+
+#[openapi]
+#[post("/meter/<instance_meta_id>", format = "application/json", data = "<filter>")]
+pub fn get_filtered_meter(mut db_main: MainDb, me: State<Instance>, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32, filter: Json<MeterFilter>) -> Result<Json<MeterAggregate>, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
+    me.get_meter_aggregate(&mut *db_main, instance_meta_id, filter.into_inner()).map(Json)
+}
@@ -2,6 +2,8 @@ pub mod delete;
 pub mod export;
 pub mod meta;
 pub mod meta_search;
+pub mod meter;
+pub mod moderation;
 pub mod ranking;
 pub mod speed_run;
 pub mod speed_kill;
\ No newline at end of file
@@ -0,0 +1,35 @@
+use rocket::State;
+use rocket_contrib::json::Json;
+
+use crate::modules::account::guard::{HasPermission, Moderate};
+use crate::modules::account::material::Account;
+use crate::modules::instance::domain_value::ModerationAuditEntry;
+use crate::modules::instance::dto::{FlaggedAttempt, InstanceFailure, ModerationDecision};
+use crate::modules::instance::tools::ModerateAttempts;
+use crate::modules::instance::Instance;
+use crate::MainDb;
+
+#[openapi]
+#[get("/moderation/queue")]
+pub fn get_moderation_queue(mut db_main: MainDb, me: State<Instance>, _auth: HasPermission<Moderate>) -> Json<Vec<FlaggedAttempt>> {
+    Json(me.get_moderation_queue(&mut *db_main))
+}
+
+#[openapi]
+#[get("/moderation/audit")]
+pub fn get_moderation_audit_log(mut db_main: MainDb, me: State<Instance>, _auth: HasPermission<Moderate>) -> Json<Vec<ModerationAuditEntry>> {
+    Json(me.get_moderation_audit_log(&mut *db_main, None))
+}
+
+#[openapi]
+#[get("/moderation/audit/<attempt_id>")]
+pub fn get_attempt_moderation_audit_log(mut db_main: MainDb, me: State<Instance>, attempt_id: u32, _auth: HasPermission<Moderate>) -> Json<Vec<ModerationAuditEntry>> {
+    Json(me.get_moderation_audit_log(&mut *db_main, Some(attempt_id)))
+}
+
+#[openapi]
+#[post("/moderation/decision", format = "application/json", data = "<decision>")]
+pub fn moderate_attempt(mut db_main: MainDb, me: State<Instance>, account: State<Account>, decision: Json<ModerationDecision>, auth: HasPermission<Moderate>) -> Result<(), InstanceFailure> {
+    let decision = decision.into_inner();
+    me.moderate_attempt(&mut *db_main, &account, auth.member_id, decision.attempt_id, decision.action)
+}
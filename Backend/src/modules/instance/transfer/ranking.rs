@@ -1,6 +1,8 @@
-use crate::modules::account::guard::IsModerator;
-use crate::modules::instance::dto::{InstanceFailure, RankingResult, RankingResultMeta};
-use crate::modules::instance::tools::{create_ranking_export, UnrankAttempt};
+use crate::modules::account::guard::{CurrentUser, HasPermission, Moderate};
+use crate::modules::account::material::Account;
+use crate::modules::instance::dto::{AttemptParse, CharacterParse, InstanceFailure, RankingResult, RankingResultMeta};
+use crate::modules::instance::domain_value::ModerationAction;
+use crate::modules::instance::tools::{create_ranking_export, InstanceAccess, ModerateAttempts, RankingParses, RankingType};
 use crate::modules::instance::{GzippedResponse, Instance};
 use crate::MainDb;
 use flate2::write::GzEncoder;
@@ -11,22 +13,22 @@ use std::io::Write;
 use crate::params;
 use crate::util::database::Select;
 
-/// Fetches ranking results (damage or healing) based on optional season and server filters
+/// Fetches ranking results (damage, healing or threat) based on optional season and server filters
 ///
 /// # Arguments
 /// * `db_main` - Database connection
-/// * `table_name` - Either "ranking_results_damage" or "ranking_results_heal"
+/// * `table_name` - Either "ranking_results_damage", "ranking_results_heal" or "ranking_results_threat"
 /// * `season` - Optional season filter
 /// * `server_id` - Optional server filter
 pub fn fetch_ranking_results(
     db_main: &mut MainDb,
     table_name: &str,
-    season: Option<u8>,
+    season: Option<u32>,
     server_id: Option<u32>,
 ) -> Vec<RankingResult> {
     let mut query = format!(
         "SELECT id, encounter_id, server_id, character_id, character_name, hero_class_id, instance_meta_id,
-         attempt_id, amount, duration, difficulty_id, character_spec, season_index
+         attempt_id, amount, duration, difficulty_id, character_spec, season_id, percentile
          FROM {}", table_name
     );
 
@@ -34,7 +36,7 @@ pub fn fetch_ranking_results(
     let mut conditions = Vec::new();
 
     if season.is_some() {
-        conditions.push("season_index = :season");
+        conditions.push("season_id = :season");
     }
 
     if server_id.is_some() {
@@ -90,7 +92,8 @@ fn create_ranking_result(row: &mut rocket_contrib::databases::mysql::Row) -> Ran
     let duration = row.take(9).unwrap();
     let difficulty_id = row.take(10).unwrap();
     let character_spec = row.take(11).unwrap();
-    let season_index = row.take(12).unwrap();
+    let season_id = row.take(12).unwrap();
+    let percentile = row.take(13).unwrap();
 
     RankingResult {
         id,
@@ -105,7 +108,8 @@ fn create_ranking_result(row: &mut rocket_contrib::databases::mysql::Row) -> Ran
         duration,
         difficulty_id,
         character_spec,
-        season_index,
+        season_id,
+        percentile,
         ranking_result: RankingResultMeta {
             instance_meta_id,
             attempt_id,
@@ -113,7 +117,8 @@ fn create_ranking_result(row: &mut rocket_contrib::databases::mysql::Row) -> Ran
             duration,
             difficulty_id,
             character_spec,
-            season_index,
+            season_id,
+            percentile,
         },
     }
 }
@@ -138,7 +143,7 @@ pub fn get_instance_ranking_dps(mut db_main: MainDb, me: State<Instance>) -> Res
 
 #[openapi]
 #[get("/ranking/dps/by_season/<season>")]
-pub fn get_instance_ranking_dps_by_season(mut db_main: MainDb, me: State<Instance>, season: u8) -> Result<GzippedResponse, InstanceFailure> {
+pub fn get_instance_ranking_dps_by_season(mut db_main: MainDb, me: State<Instance>, season: u32) -> Result<GzippedResponse, InstanceFailure> {
     let instance_metas = me.instance_metas.read().unwrap();
 
     let results = fetch_ranking_results(&mut db_main, "ranking_results_damage", Some(season), None);
@@ -156,7 +161,7 @@ pub fn get_instance_ranking_dps_by_season(mut db_main: MainDb, me: State<Instanc
 
 #[openapi]
 #[get("/ranking/dps/by_server/<server_id>/by_season/<season>")]
-pub fn get_instance_ranking_dps_by_server_and_season(mut db_main: MainDb, me: State<Instance>, server_id: u32, season: u8) -> Result<GzippedResponse, InstanceFailure> {
+pub fn get_instance_ranking_dps_by_server_and_season(mut db_main: MainDb, me: State<Instance>, server_id: u32, season: u32) -> Result<GzippedResponse, InstanceFailure> {
     let instance_metas = me.instance_metas.read().unwrap();
 
     let results = fetch_ranking_results(&mut db_main, "ranking_results_damage", Some(season), Some(server_id));
@@ -192,7 +197,7 @@ pub fn get_instance_ranking_hps(mut db_main: MainDb, me: State<Instance>) -> Res
 
 #[openapi]
 #[get("/ranking/hps/by_season/<season>")]
-pub fn get_instance_ranking_hps_by_season(mut db_main: MainDb, me: State<Instance>, season: u8) -> Result<GzippedResponse, InstanceFailure> {
+pub fn get_instance_ranking_hps_by_season(mut db_main: MainDb, me: State<Instance>, season: u32) -> Result<GzippedResponse, InstanceFailure> {
     let instance_metas = me.instance_metas.read().unwrap();
 
     let results = fetch_ranking_results(&mut db_main, "ranking_results_heal", Some(season), None);
@@ -210,7 +215,7 @@ pub fn get_instance_ranking_hps_by_season(mut db_main: MainDb, me: State<Instanc
 
 #[openapi]
 #[get("/ranking/hps/by_server/<server_id>/by_season/<season>")]
-pub fn get_instance_ranking_hps_by_server_and_season(mut db_main: MainDb, me: State<Instance>, server_id: u32, season: u8) -> Result<GzippedResponse, InstanceFailure> {
+pub fn get_instance_ranking_hps_by_server_and_season(mut db_main: MainDb, me: State<Instance>, server_id: u32, season: u32) -> Result<GzippedResponse, InstanceFailure> {
     let instance_metas = me.instance_metas.read().unwrap();
 
     let results = fetch_ranking_results(&mut db_main, "ranking_results_heal", Some(season), Some(server_id));
@@ -226,8 +231,101 @@ pub fn get_instance_ranking_hps_by_server_and_season(mut db_main: MainDb, me: St
     Ok(GzippedResponse(compressed_data))
 }
 
+#[openapi]
+#[get("/ranking/tps")]
+pub fn get_instance_ranking_tps(mut db_main: MainDb, me: State<Instance>) -> Result<GzippedResponse, InstanceFailure> {
+    let instance_metas = me.instance_metas.read().unwrap();
+
+    let results = fetch_ranking_results(&mut db_main, "ranking_results_threat", None, None);
+
+    let json_data = create_ranking_export(&instance_metas.1, &results);
+
+    let serialized_data = serde_json::to_vec(&json_data).expect("Serialization failed");
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&serialized_data).expect("Gzip compression failed");
+    let compressed_data = encoder.finish().expect("Gzip finalization failed");
+
+    Ok(GzippedResponse(compressed_data))
+}
+
+#[openapi]
+#[get("/ranking/tps/by_season/<season>")]
+pub fn get_instance_ranking_tps_by_season(mut db_main: MainDb, me: State<Instance>, season: u32) -> Result<GzippedResponse, InstanceFailure> {
+    let instance_metas = me.instance_metas.read().unwrap();
+
+    let results = fetch_ranking_results(&mut db_main, "ranking_results_threat", Some(season), None);
+
+    let json_data = create_ranking_export(&instance_metas.1, &results);
+
+    let serialized_data = serde_json::to_vec(&json_data).expect("Serialization failed");
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&serialized_data).expect("Gzip compression failed");
+    let compressed_data = encoder.finish().expect("Gzip finalization failed");
+
+    Ok(GzippedResponse(compressed_data))
+}
+
+#[openapi]
+#[get("/ranking/tps/by_server/<server_id>/by_season/<season>")]
+pub fn get_instance_ranking_tps_by_server_and_season(mut db_main: MainDb, me: State<Instance>, server_id: u32, season: u32) -> Result<GzippedResponse, InstanceFailure> {
+    let instance_metas = me.instance_metas.read().unwrap();
+
+    let results = fetch_ranking_results(&mut db_main, "ranking_results_threat", Some(season), Some(server_id));
+
+    let json_data = create_ranking_export(&instance_metas.1, &results);
+
+    let serialized_data = serde_json::to_vec(&json_data).expect("Serialization failed");
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&serialized_data).expect("Gzip compression failed");
+    let compressed_data = encoder.finish().expect("Gzip finalization failed");
+
+    Ok(GzippedResponse(compressed_data))
+}
+
+#[openapi]
+#[get("/ranking/dps/parses/by_character/<character_id>")]
+pub fn get_character_parses_dps(mut db_main: MainDb, me: State<Instance>, character_id: u32) -> Json<Vec<CharacterParse>> {
+    Json(me.get_character_parses(&mut *db_main, RankingType::Damage, character_id))
+}
+
+#[openapi]
+#[get("/ranking/dps/parses/by_instance/<instance_meta_id>")]
+pub fn get_instance_parses_dps(mut db_main: MainDb, me: State<Instance>, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32) -> Result<Json<Vec<AttemptParse>>, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
+    Ok(Json(me.get_instance_parses(&mut *db_main, RankingType::Damage, instance_meta_id)))
+}
+
+#[openapi]
+#[get("/ranking/hps/parses/by_character/<character_id>")]
+pub fn get_character_parses_hps(mut db_main: MainDb, me: State<Instance>, character_id: u32) -> Json<Vec<CharacterParse>> {
+    Json(me.get_character_parses(&mut *db_main, RankingType::Heal, character_id))
+}
+
+#[openapi]
+#[get("/ranking/hps/parses/by_instance/<instance_meta_id>")]
+pub fn get_instance_parses_hps(mut db_main: MainDb, me: State<Instance>, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32) -> Result<Json<Vec<AttemptParse>>, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
+    Ok(Json(me.get_instance_parses(&mut *db_main, RankingType::Heal, instance_meta_id)))
+}
+
+#[openapi]
+#[get("/ranking/tps/parses/by_character/<character_id>")]
+pub fn get_character_parses_tps(mut db_main: MainDb, me: State<Instance>, character_id: u32) -> Json<Vec<CharacterParse>> {
+    Json(me.get_character_parses(&mut *db_main, RankingType::Threat, character_id))
+}
+
+#[openapi]
+#[get("/ranking/tps/parses/by_instance/<instance_meta_id>")]
+pub fn get_instance_parses_tps(mut db_main: MainDb, me: State<Instance>, account: State<Account>, current_user: CurrentUser, instance_meta_id: u32) -> Result<Json<Vec<AttemptParse>>, InstanceFailure> {
+    me.check_instance_access(&account, instance_meta_id, current_user.0)?;
+    Ok(Json(me.get_instance_parses(&mut *db_main, RankingType::Threat, instance_meta_id)))
+}
+
 #[openapi]
 #[delete("/ranking/unrank", data = "<data>")]
-pub fn unrank_attempt(mut db_main: MainDb, me: State<Instance>, data: Json<u32>, _auth: IsModerator) -> Result<(), InstanceFailure> {
-    me.unrank_attempt(&mut *db_main, data.into_inner())
+pub fn unrank_attempt(mut db_main: MainDb, me: State<Instance>, account: State<Account>, data: Json<u32>, auth: HasPermission<Moderate>) -> Result<(), InstanceFailure> {
+    me.moderate_attempt(&mut *db_main, &account, auth.member_id, data.into_inner(), ModerationAction::Unrank)
 }
\ No newline at end of file
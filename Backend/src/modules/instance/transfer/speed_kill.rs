@@ -1,19 +1,27 @@
 use rocket::State;
 use rocket_contrib::json::Json;
 
-use crate::modules::instance::dto::SpeedKill;
+use crate::modules::instance::dto::{SpeedKill, SpeedKillFilter};
+use crate::modules::instance::tools::SpeedRuns;
 use crate::modules::instance::Instance;
 
 #[openapi]
 #[get("/speed_kill")]
 pub fn get_speed_kills(me: State<Instance>) -> Json<Vec<SpeedKill>> {
-    let speed_kills = me.speed_kills.read().unwrap();
-    Json(speed_kills.clone())
+    Json(me.get_speed_kills(&SpeedKillFilter::default()))
 }
 
 #[openapi]
 #[get("/speed_kill/by_season/<season>")]
-pub fn get_speed_kills_by_season(me: State<Instance>, season: u8) -> Json<Vec<SpeedKill>> {
-    let speed_kills = me.speed_kills.read().unwrap();
-    Json(speed_kills.iter().filter(|speed_kill| speed_kill.season_index == season).cloned().collect())
-}
\ No newline at end of file
+pub fn get_speed_kills_by_season(me: State<Instance>, season: u32) -> Json<Vec<SpeedKill>> {
+    Json(me.get_speed_kills(&SpeedKillFilter {
+        season_id: Some(season),
+        ..SpeedKillFilter::default()
+    }))
+}
+
+#[openapi]
+#[post("/speed_kill/search", format = "application/json", data = "<filter>")]
+pub fn search_speed_kills(me: State<Instance>, filter: Json<SpeedKillFilter>) -> Json<Vec<SpeedKill>> {
+    Json(me.get_speed_kills(&filter.into_inner()))
+}
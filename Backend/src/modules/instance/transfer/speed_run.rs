@@ -1,4 +1,5 @@
-use crate::modules::instance::dto::SpeedRun;
+use crate::modules::instance::dto::{SpeedRun, SpeedRunFilter};
+use crate::modules::instance::tools::SpeedRuns;
 use crate::modules::instance::Instance;
 use rocket::State;
 use rocket_contrib::json::Json;
@@ -6,13 +7,20 @@ use rocket_contrib::json::Json;
 #[openapi]
 #[get("/speed_run")]
 pub fn get_speed_runs(me: State<Instance>) -> Json<Vec<SpeedRun>> {
-    let speed_runs = me.speed_runs.read().unwrap();
-    Json(speed_runs.clone())
+    Json(me.get_speed_runs(&SpeedRunFilter::default()))
 }
 
 #[openapi]
 #[get("/speed_run/by_season/<season>")]
-pub fn get_speed_runs_by_season(me: State<Instance>, season: u8) -> Json<Vec<SpeedRun>> {
-    let speed_runs = me.speed_runs.read().unwrap();
-    Json(speed_runs.iter().filter(|speed_run| speed_run.season_index == season).cloned().collect())
-}
\ No newline at end of file
+pub fn get_speed_runs_by_season(me: State<Instance>, season: u32) -> Json<Vec<SpeedRun>> {
+    Json(me.get_speed_runs(&SpeedRunFilter {
+        season_id: Some(season),
+        ..SpeedRunFilter::default()
+    }))
+}
+
+#[openapi]
+#[post("/speed_run/search", format = "application/json", data = "<filter>")]
+pub fn search_speed_runs(me: State<Instance>, filter: Json<SpeedRunFilter>) -> Json<Vec<SpeedRun>> {
+    Json(me.get_speed_runs(&filter.into_inner()))
+}
@@ -0,0 +1,75 @@
+extern crate test;
+
+use test::Bencher;
+
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::material::WoWVanillaParser;
+use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
+use crate::modules::live_data_processor::tools::log_parser::parse_lines;
+use std::time::Instant;
+
+const SYNTHETIC_LOG_LINES: usize = 400000;
+
+/*
+ * Goal of this benchmark is to see how many lines
+ * per second the line parsing stage of an upload handles,
+ * sequentially and split into chunks parsed in parallel
+ */
+fn synthetic_log() -> Vec<(u64, String)> {
+    let templates = [
+        "{attacker} hits {victim} for 1234. (200 resisted) (glancing)",
+        "{victim} crits {attacker} for 2345 Fire damage. (100 absorbed)",
+        "{attacker} misses {victim}.",
+        "{attacker} 's Fireball crits {victim} for 3000 Fire damage. (50 resisted)",
+        "{attacker} 's Flash Heal critically heals {victim} for 1500.",
+        "{attacker} gains Shield Wall (1).",
+        "{victim} suffers 500 Shadow damage from {attacker} 's Corruption.",
+        "{attacker} begins to cast Fireball.",
+    ];
+    (0..SYNTHETIC_LOG_LINES)
+        .map(|index| {
+            let line = templates[index % templates.len()].replace("{attacker}", &format!("Player{}", index % 40)).replace("{victim}", &format!("Player{}", (index / 7) % 40));
+            (1_600_000_000_000 + index as u64 * 25, line)
+        })
+        .collect()
+}
+
+fn report_lines_per_second(name: &str, lines: usize, start: Instant) {
+    println!("{}: {:.0} lines/second", name, lines as f64 / start.elapsed().as_secs_f64());
+}
+
+#[bench]
+fn parse_lines_sequential(b: &mut Bencher) {
+    let data = Data::default();
+    let log = synthetic_log();
+    let lines = log.iter().map(|(timestamp, line)| (*timestamp, line.as_str())).collect::<Vec<(u64, &str)>>();
+
+    let start = Instant::now();
+    let mut parser = WoWVanillaParser::new(1);
+    for (event_timestamp, content) in lines.iter() {
+        let _ = parser.parse_cbl_line(&data, *event_timestamp, content);
+    }
+    report_lines_per_second("Sequential", lines.len(), start);
+
+    b.iter(|| {
+        let mut parser = WoWVanillaParser::new(1);
+        lines.iter().map(|(event_timestamp, content)| parser.parse_cbl_line(&data, *event_timestamp, content)).count()
+    });
+}
+
+#[bench]
+fn parse_lines_parallel(b: &mut Bencher) {
+    let data = Data::default();
+    let log = synthetic_log();
+    let lines = log.iter().map(|(timestamp, line)| (*timestamp, line.as_str())).collect::<Vec<(u64, &str)>>();
+
+    let start = Instant::now();
+    let mut parser = WoWVanillaParser::new(1);
+    parse_lines(&mut parser, &data, &lines);
+    report_lines_per_second("Parallel", lines.len(), start);
+
+    b.iter(|| {
+        let mut parser = WoWVanillaParser::new(1);
+        parse_lines(&mut parser, &data, &lines).len()
+    });
+}
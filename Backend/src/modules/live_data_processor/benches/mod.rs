@@ -0,0 +1 @@
+mod log_parser;
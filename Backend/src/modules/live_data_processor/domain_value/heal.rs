@@ -4,6 +4,8 @@ use crate::modules::live_data_processor::domain_value::{HitMask, Mitigation, Uni
 pub struct Heal {
     pub total: u32,
     pub effective: u32,
+    #[serde(default)]
+    pub overheal: u32,
     pub mitigation: Vec<Mitigation>,
     pub target: Unit,
     pub hit_mask: HitMask,
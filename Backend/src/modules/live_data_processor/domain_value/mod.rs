@@ -20,6 +20,7 @@ pub use self::spell_component::*;
 pub use self::threat::Threat;
 pub use self::unit::Unit;
 pub use self::unit_instance::UnitInstance;
+pub use self::upload_job_status::UploadJobStatus;
 
 mod aura_application;
 mod creature;
@@ -43,3 +44,4 @@ mod spell_component;
 mod threat;
 mod unit;
 mod unit_instance;
+mod upload_job_status;
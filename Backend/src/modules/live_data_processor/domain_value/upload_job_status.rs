@@ -0,0 +1,30 @@
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub enum UploadJobStatus {
+    Queued,
+    Processing,
+    Finished,
+    Failed,
+    Cancelled,
+}
+
+impl UploadJobStatus {
+    pub fn new(status: u8) -> Self {
+        match status {
+            0 => Self::Queued,
+            1 => Self::Processing,
+            2 => Self::Finished,
+            3 => Self::Failed,
+            _ => Self::Cancelled,
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::Queued => 0,
+            Self::Processing => 1,
+            Self::Finished => 2,
+            Self::Failed => 3,
+            Self::Cancelled => 4,
+        }
+    }
+}
@@ -7,6 +7,7 @@ pub struct HealDone {
     pub spell_id: u32,
     pub total_heal: u32,
     pub effective_heal: u32,
+    pub overheal: u32,
     pub absorb: u32,
     pub hit_mask: u32,
 }
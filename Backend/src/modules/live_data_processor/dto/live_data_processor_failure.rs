@@ -12,7 +12,11 @@ pub enum LiveDataProcessorFailure {
     InvalidZipFile,
     InvalidStartTime,
     InvalidEndTime,
-    DuplicateUpload
+    DuplicateUpload,
+    UploadNotFound,
+    InvalidUploadJobState,
+    InvalidEventSegment,
+    IoFailure(String),
 }
 
 impl Responder<'static> for LiveDataProcessorFailure {
@@ -47,6 +51,22 @@ impl Responder<'static> for LiveDataProcessorFailure {
                 body = "Upload already exists for this member!".to_owned();
                 Status::new(541, "DuplicateUpload")
             },
+            LiveDataProcessorFailure::UploadNotFound => {
+                body = "Upload not found!".to_owned();
+                Status::new(542, "UploadNotFound")
+            },
+            LiveDataProcessorFailure::InvalidUploadJobState => {
+                body = "Upload job can not be changed in its current state!".to_owned();
+                Status::new(543, "InvalidUploadJobState")
+            },
+            LiveDataProcessorFailure::InvalidEventSegment => {
+                body = "Event segment is corrupt or has an unsupported version!".to_owned();
+                Status::new(544, "InvalidEventSegment")
+            },
+            LiveDataProcessorFailure::IoFailure(reason) => {
+                body = reason;
+                Status::new(545, "IoFailure")
+            },
         };
         Response::build().status(status).sized_body(Cursor::new(body)).ok()
     }
@@ -62,7 +82,11 @@ impl OpenApiResponder<'static> for LiveDataProcessorFailure {
         add_schema_response(&mut responses, 537, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 538, "text/plain", schema.clone())?;
         add_schema_response(&mut responses, 539, "text/plain", schema.clone())?;
-        add_schema_response(&mut responses, 541, "text/plain", schema)?;
+        add_schema_response(&mut responses, 541, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 542, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 543, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 544, "text/plain", schema.clone())?;
+        add_schema_response(&mut responses, 545, "text/plain", schema)?;
         Ok(responses)
     }
 }
@@ -17,14 +17,18 @@ pub use self::live_data_processor_failure::LiveDataProcessorFailure;
 pub use self::loot::Loot;
 pub use self::message::Message;
 pub use self::message_type::*;
+pub use self::parse_report::{ParseReport, ParseReportFamily};
 pub use self::players_in_combat::PlayersInCombat;
 pub use self::position::Position;
 pub use self::power::Power;
+pub use self::spec_detection::SpecDetection;
+pub use self::spec_evidence::SpecEvidence;
 pub use self::spell_cast::SpellCast;
 pub use self::summon::Summon;
 pub use self::threat::Threat;
 pub use self::un_aura::UnAura;
 pub use self::unit::Unit;
+pub use self::upload_job::UploadJob;
 
 mod aura_application;
 mod combat_state;
@@ -45,11 +49,15 @@ mod live_data_processor_failure;
 mod loot;
 mod message;
 mod message_type;
+mod parse_report;
 mod players_in_combat;
 mod position;
 mod power;
+mod spec_detection;
+mod spec_evidence;
 mod spell_cast;
 mod summon;
 mod threat;
 mod un_aura;
 mod unit;
+mod upload_job;
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+
+const MAX_SAMPLES: usize = 25;
+const MAX_UNKNOWN_NAMES: usize = 200;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ParseReportFamily {
+    pub recognized: u32,
+    pub unrecognized: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ParseReport {
+    pub total_lines: u32,
+    pub recognized_lines: u32,
+    pub unrecognized_lines: u32,
+    pub families: BTreeMap<String, ParseReportFamily>,
+    pub failures: BTreeMap<String, u32>,
+    pub unmatched_samples: Vec<String>,
+    pub unknown_npc_names: Vec<String>,
+    pub unknown_spell_names: Vec<String>,
+    pub dropped_combatant_info: u32,
+    pub dropped_combatant_info_samples: Vec<String>,
+    pub timestamp_anomalies: BTreeMap<String, u32>,
+    pub timestamp_anomaly_samples: Vec<String>,
+}
+
+impl ParseReport {
+    pub fn record_line(&mut self, family: &str, line: &str, recognized: bool) {
+        self.total_lines += 1;
+        let entry = self.families.entry(family.to_string()).or_insert_with(ParseReportFamily::default);
+        if recognized {
+            entry.recognized += 1;
+            self.recognized_lines += 1;
+            return;
+        }
+
+        entry.unrecognized += 1;
+        self.unrecognized_lines += 1;
+        if family == "combatant_info" {
+            self.dropped_combatant_info += 1;
+            push_sample(&mut self.dropped_combatant_info_samples, line);
+        } else {
+            push_sample(&mut self.unmatched_samples, line);
+        }
+    }
+
+    pub fn record_failure(&mut self, kind: &str) {
+        *self.failures.entry(kind.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn record_timestamp_anomaly(&mut self, kind: &str, line: &str) {
+        *self.timestamp_anomalies.entry(kind.to_string()).or_insert(0) += 1;
+        push_sample(&mut self.timestamp_anomaly_samples, line);
+    }
+
+    pub fn record_unknown_npc_name(&mut self, name: &str) {
+        push_unknown_name(&mut self.unknown_npc_names, name);
+    }
+
+    pub fn record_unknown_spell_name(&mut self, name: &str) {
+        push_unknown_name(&mut self.unknown_spell_names, name);
+    }
+
+    // Merges the report of a subsequent part of the same log
+    pub fn merge(&mut self, other: ParseReport) {
+        self.total_lines += other.total_lines;
+        self.recognized_lines += other.recognized_lines;
+        self.unrecognized_lines += other.unrecognized_lines;
+        for (family, counts) in other.families {
+            let entry = self.families.entry(family).or_insert_with(ParseReportFamily::default);
+            entry.recognized += counts.recognized;
+            entry.unrecognized += counts.unrecognized;
+        }
+        for (kind, amount) in other.failures {
+            *self.failures.entry(kind).or_insert(0) += amount;
+        }
+        for (kind, amount) in other.timestamp_anomalies {
+            *self.timestamp_anomalies.entry(kind).or_insert(0) += amount;
+        }
+        self.dropped_combatant_info += other.dropped_combatant_info;
+        other.unmatched_samples.iter().for_each(|line| push_sample(&mut self.unmatched_samples, line));
+        other.dropped_combatant_info_samples.iter().for_each(|line| push_sample(&mut self.dropped_combatant_info_samples, line));
+        other.timestamp_anomaly_samples.iter().for_each(|line| push_sample(&mut self.timestamp_anomaly_samples, line));
+        other.unknown_npc_names.iter().for_each(|name| push_unknown_name(&mut self.unknown_npc_names, name));
+        other.unknown_spell_names.iter().for_each(|name| push_unknown_name(&mut self.unknown_spell_names, name));
+    }
+}
+
+fn push_sample(samples: &mut Vec<String>, line: &str) {
+    if samples.len() < MAX_SAMPLES {
+        samples.push(line.chars().take(512).collect());
+    }
+}
+
+fn push_unknown_name(names: &mut Vec<String>, name: &str) {
+    if names.len() < MAX_UNKNOWN_NAMES && !names.iter().any(|known_name| known_name == name) {
+        names.push(name.to_string());
+    }
+}
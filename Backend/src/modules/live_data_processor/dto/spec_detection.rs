@@ -0,0 +1,8 @@
+/// The spec with the highest evidence weight of a participant. The confidence is its share of the total evidence weight in percent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecDetection {
+    pub hero_class_id: u8,
+    pub talent_tree: u8,
+    pub role: Option<u8>,
+    pub confidence: u8,
+}
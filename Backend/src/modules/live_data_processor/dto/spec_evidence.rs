@@ -0,0 +1,13 @@
+/// A spec rule that matched a participant of a combat log, with how often and when it was observed.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct SpecEvidence {
+    pub rule_id: u32,
+    pub spell_name: String,
+    pub hero_class_id: u8,
+    pub talent_tree: u8,
+    pub role: Option<u8>,
+    pub weight: u32,
+    pub count: u32,
+    pub first_seen: u64,
+    pub last_seen: u64,
+}
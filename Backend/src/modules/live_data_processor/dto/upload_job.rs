@@ -0,0 +1,13 @@
+use crate::modules::live_data_processor::domain_value::UploadJobStatus;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UploadJob {
+    pub upload_id: u32,
+    pub server_id: u32,
+    pub status: UploadJobStatus,
+    pub progress: u8,
+    pub attempts: u8,
+    pub error: Option<String>,
+    pub created: u64,
+    pub updated: u64,
+}
@@ -34,6 +34,21 @@ impl ActiveMap {
             self.intervals.push((now, now));
         }
     }
+
+    // Merges the same map as seen in a subsequent part of the log
+    pub fn merge(&mut self, other: ActiveMap) {
+        for (start, end) in other.intervals {
+            if start >= self.intervals.last().unwrap().1 {
+                self.add_point(start);
+                self.add_point(end);
+            } else {
+                self.intervals.push((start, end));
+            }
+        }
+        for (difficulty, intervals) in other.active_difficulty {
+            self.active_difficulty.entry(difficulty).or_insert_with(Vec::new).extend(intervals);
+        }
+    }
 }
 
 pub trait CollectActiveMap {
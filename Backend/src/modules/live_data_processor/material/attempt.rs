@@ -1,6 +1,6 @@
 use std::collections::{BTreeSet, HashMap, VecDeque};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Attempt {
     pub encounter_id: u32,
     pub hard_mode_encounter_id: Option<u32>,
@@ -19,6 +19,9 @@ pub struct Attempt {
     pub infight_vehicle: BTreeSet<u64>,
     pub pivot_instant_debuff_removes: VecDeque<u64>,
     pub ranking_damage: HashMap<u32, u32>,
+    // Number of hits and the highest hit per character
+    #[serde(default)]
+    pub ranking_damage_hits: HashMap<u32, (u32, u32)>,
     pub ranking_heal: HashMap<u32, u32>,
     pub ranking_threat: HashMap<u32, i32>,
 }
@@ -36,6 +39,7 @@ impl Attempt {
             infight_vehicle: BTreeSet::new(),
             pivot_instant_debuff_removes: VecDeque::new(),
             ranking_damage: HashMap::new(),
+            ranking_damage_hits: HashMap::new(),
             ranking_heal: HashMap::new(),
             ranking_threat: HashMap::new(),
             encounter_has_pivot,
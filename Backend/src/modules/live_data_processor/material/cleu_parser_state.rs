@@ -0,0 +1,59 @@
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::dto::{Message, ParseReport, Unit};
+use crate::modules::live_data_processor::material::{ActiveMapMap, CollectActiveMap, Participant};
+use std::collections::{HashMap, HashSet};
+
+// State shared by the parsers of the GUID based COMBAT_LOG_EVENT_UNFILTERED (CLEU) format of TBC and WotLK
+pub struct CleuParserState {
+    pub expansion_id: u8,
+
+    pub participants: HashMap<u64, Participant>,
+    pub active_map: ActiveMapMap,
+    pub pet_owner: HashMap<u64, u64>,
+
+    // Addon messages only know the names of players
+    pub unit_by_name: HashMap<String, Unit>,
+    // Key: (caster, spell_id), Value: (timestamp, target) of the last SPELL_CAST_SUCCESS
+    pub last_spell_cast: HashMap<(u64, u32), (u64, Option<u64>)>,
+    // Key: (caster, target, spell_id)
+    pub active_auras: HashSet<(u64, u64, u32)>,
+
+    pub bonus_messages: Vec<Message>,
+
+    pub parse_report: ParseReport,
+    // Event family that handled the current line
+    pub line_family: &'static str,
+}
+
+impl CleuParserState {
+    pub fn new(expansion_id: u8) -> Self {
+        CleuParserState {
+            expansion_id,
+            participants: Default::default(),
+            active_map: Default::default(),
+            pet_owner: Default::default(),
+            unit_by_name: Default::default(),
+            last_spell_cast: Default::default(),
+            active_auras: Default::default(),
+            bonus_messages: Default::default(),
+            parse_report: Default::default(),
+            line_family: "unmatched",
+        }
+    }
+
+    pub fn collect_participant(&mut self, unit: &Unit, name: &str, now: u64) {
+        if let Some(participant) = self.participants.get_mut(&unit.unit_id) {
+            participant.add_participation_point(now);
+        } else {
+            let name = name.replace("\"", "");
+            if unit.is_player {
+                self.unit_by_name.insert(name.clone(), unit.clone());
+            }
+            self.participants.insert(unit.unit_id, Participant::new(unit.unit_id, unit.is_player, false, false, name, now));
+        }
+    }
+
+    pub fn collect_active_map(&mut self, data: &Data, unit: &Unit, now: u64) {
+        self.active_map.collect(data, unit, self.expansion_id, now);
+    }
+}
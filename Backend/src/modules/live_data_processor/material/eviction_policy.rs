@@ -0,0 +1,32 @@
+#[derive(Debug, Clone)]
+pub struct EvictionPolicy {
+    // Instances that were not active for longer than this many seconds, in wall clock time, are evicted
+    pub max_instance_age: u64,
+    pub max_active_instances: usize,
+    pub max_cache_entries: usize,
+}
+
+impl Default for EvictionPolicy {
+    fn default() -> Self {
+        EvictionPolicy {
+            max_instance_age: 24 * 60 * 60,
+            max_active_instances: 2000,
+            max_cache_entries: 200000,
+        }
+    }
+}
+
+impl EvictionPolicy {
+    pub fn from_env() -> Self {
+        let default = EvictionPolicy::default();
+        EvictionPolicy {
+            max_instance_age: read_env("LIVE_DATA_MAX_INSTANCE_AGE_SECS").unwrap_or(default.max_instance_age),
+            max_active_instances: read_env("LIVE_DATA_MAX_ACTIVE_INSTANCES").map(|amount| amount as usize).unwrap_or(default.max_active_instances),
+            max_cache_entries: read_env("LIVE_DATA_MAX_CACHE_ENTRIES").map(|amount| amount as usize).unwrap_or(default.max_cache_entries),
+        }
+    }
+}
+
+fn read_env(key: &str) -> Option<u64> {
+    std::env::var(key).ok().and_then(|value| value.parse::<u64>().ok())
+}
@@ -0,0 +1,55 @@
+/// Tracks how much health a unit is missing while the log is parsed, in order to tell effective heal from overheal.
+/// Without a known maximum health the missing health is only bounded by the damage taken.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HealthModel {
+    pub max_health: Option<u32>,
+    missing_health: u32,
+}
+
+impl HealthModel {
+    pub fn new(max_health: Option<u32>) -> Self {
+        HealthModel { max_health, missing_health: 0 }
+    }
+
+    pub fn missing_health(&self) -> u32 {
+        self.missing_health
+    }
+
+    // Buffs raise the maximum health temporarily, hence the highest observation is kept
+    pub fn observe_max_health(&mut self, max_health: u32) {
+        if max_health > 0 && self.max_health.map_or(true, |known_max_health| max_health > known_max_health) {
+            self.max_health = Some(max_health);
+        }
+    }
+
+    /// Absorbed damage is not part of the damage, so it does not cost health.
+    pub fn take_damage(&mut self, damage: u32) {
+        self.missing_health = self.missing_health.saturating_add(damage);
+        if let Some(max_health) = self.max_health {
+            self.missing_health = self.missing_health.min(max_health);
+        }
+    }
+
+    /// Returns the effective heal and the overheal. Absorbed heal neither restores health nor counts as overheal.
+    pub fn take_heal(&mut self, total_heal: u32, absorb: u32) -> (u32, u32) {
+        let heal = total_heal.saturating_sub(absorb);
+        let effective_heal = heal.min(self.missing_health);
+        self.missing_health -= effective_heal;
+        (effective_heal, heal - effective_heal)
+    }
+
+    // A dead unit misses its whole health, if it is known
+    pub fn die(&mut self) {
+        if let Some(max_health) = self.max_health {
+            self.missing_health = max_health;
+        }
+    }
+
+    // Continues with the state of the same unit as seen in a subsequent part of the log
+    pub fn merge(&mut self, other: HealthModel) {
+        self.missing_health = other.missing_health;
+        if let Some(max_health) = other.max_health {
+            self.observe_max_health(max_health);
+        }
+    }
+}
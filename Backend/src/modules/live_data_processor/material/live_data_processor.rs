@@ -1,19 +1,31 @@
-use crate::modules::live_data_processor::material::Server;
-use crate::params;
+use crate::modules::armory::Armory;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use crate::modules::live_data_processor::material::{EvictionPolicy, Server, SnapshotPolicy};
+use crate::modules::live_data_processor::tools::UploadJobQueue;
+use crate::mysql::Opts;
 use crate::util::database::Select;
+use crate::{mysql, params};
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::{Arc, Mutex, RwLock};
 
 pub struct LiveDataProcessor {
-    pub servers: RwLock<HashMap<u32, RwLock<Server>>>,
-    pub upload_progress: RwLock<HashMap<u32, u8>>
+    pub servers: Arc<RwLock<HashMap<u32, RwLock<Server>>>>,
+    pub upload_job_claim: Arc<Mutex<()>>,
+    pub eviction_policy: EvictionPolicy,
+    pub snapshot_policy: SnapshotPolicy,
+    // Key: server_id, Value: Sequence of the last written snapshot
+    pub written_snapshots: Arc<Mutex<HashMap<u32, u64>>>,
 }
 
 impl Default for LiveDataProcessor {
     fn default() -> Self {
         LiveDataProcessor {
-            servers: RwLock::new(HashMap::new()),
-            upload_progress: RwLock::new(HashMap::new())
+            servers: Arc::new(RwLock::new(HashMap::new())),
+            upload_job_claim: Arc::new(Mutex::new(())),
+            eviction_policy: EvictionPolicy::from_env(),
+            snapshot_policy: SnapshotPolicy::from_env(),
+            written_snapshots: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -32,6 +44,51 @@ impl LiveDataProcessor {
         self
     }
 
+    // The workers share the reloadable data and the armory with the ones that are managed by rocket
+    pub fn start_upload_workers(self, data: &Data, armory: &Armory) -> Self {
+        let dns = std::env::var("MYSQL_URL").unwrap();
+        let opts = Opts::from_url(&dns).unwrap();
+        let worker_count = std::env::var("UPLOAD_WORKER_COUNT").ok().and_then(|count| count.parse::<usize>().ok()).unwrap_or(2);
+
+        // Workers share the server state with the processor that is managed by rocket
+        let supervisor = self.share();
+        let data = Arc::new(data.share());
+        let armory = Arc::new(armory.share());
+        std::thread::spawn(move || {
+            // Interrupted jobs are recovered before any worker claims a job, otherwise a claimed job could be queued again
+            let mut conn = connect_with_backoff(&opts, "Upload worker");
+            supervisor.recover_upload_jobs(&mut conn);
+
+            for _ in 0..worker_count {
+                let worker = supervisor.share();
+                let data = Arc::clone(&data);
+                let armory = Arc::clone(&armory);
+                let opts = opts.clone();
+                std::thread::spawn(move || {
+                    let mut db_main = connect_with_backoff(&opts, "Upload worker");
+                    loop {
+                        if let Some(job) = worker.claim_upload_job(&mut db_main) {
+                            worker.run_upload_job(&mut db_main, &data, &armory, job);
+                        } else {
+                            std::thread::sleep(std::time::Duration::from_secs(5));
+                        }
+                    }
+                });
+            }
+        });
+        self
+    }
+
+    fn share(&self) -> Self {
+        LiveDataProcessor {
+            servers: Arc::clone(&self.servers),
+            upload_job_claim: Arc::clone(&self.upload_job_claim),
+            eviction_policy: self.eviction_policy.clone(),
+            snapshot_policy: self.snapshot_policy.clone(),
+            written_snapshots: Arc::clone(&self.written_snapshots),
+        }
+    }
+
     pub fn create_server_if_not_exist(&self, db_main: &mut impl Select, server_id: u32) {
         let create_server = {
             let servers = self.servers.read().unwrap();
@@ -40,6 +97,10 @@ impl LiveDataProcessor {
 
         if create_server {
             let mut servers = self.servers.write().unwrap();
+            // Another upload worker may have created it in the meantime
+            if servers.contains_key(&server_id) {
+                return;
+            }
             let (server_id, expansion_id) = db_main
                 .select_wparams_value(
                     "SELECT id, expansion_id FROM data_server WHERE id = :server_id",
@@ -51,3 +112,28 @@ impl LiveDataProcessor {
         }
     }
 }
+
+fn connect(opts: &Opts) -> Result<mysql::Conn, LiveDataProcessorFailure> {
+    mysql::Conn::new(opts.clone()).map_err(|error| LiveDataProcessorFailure::DatabaseFailure(format!("connect: {}", error)))
+}
+
+/// Seconds to wait before the next connection attempt, doubled after every failed attempt up to a minute.
+pub fn connect_retry_delay(failed_attempts: u32) -> u64 {
+    (1_u64 << failed_attempts.min(6)).min(60)
+}
+
+// Background loops outlive a database that is temporarily unavailable, e.g. during a restart of both
+fn connect_with_backoff(opts: &Opts, name: &str) -> mysql::Conn {
+    let mut failed_attempts = 0;
+    loop {
+        match connect(opts) {
+            Ok(conn) => return conn,
+            Err(failure) => {
+                let delay = connect_retry_delay(failed_attempts);
+                println!("[{}] {:?}, retrying in {}s", name, failure, delay);
+                std::thread::sleep(std::time::Duration::from_secs(delay));
+                failed_attempts += 1;
+            },
+        }
+    }
+}
@@ -1,16 +1,30 @@
 pub use self::active_map::*;
 pub use self::attempt::Attempt;
-pub use self::live_data_processor::LiveDataProcessor;
+pub use self::cleu_parser_state::CleuParserState;
+pub use self::eviction_policy::EvictionPolicy;
+pub use self::health_model::HealthModel;
+pub use self::live_data_processor::{connect_retry_delay, LiveDataProcessor};
 pub use self::participant::Participant;
 pub use self::server::Server;
+pub use self::server_snapshot::{ServerSnapshot, SERVER_SNAPSHOT_VERSION};
+pub use self::snapshot_policy::SnapshotPolicy;
+pub use self::wow_tbc_parser::WoWTBCParser;
 pub use self::wow_vanilla_parser::WoWVanillaParser;
+pub use self::wow_wotlk_parser::WoWWotLKParser;
 pub use self::interval_bucket::IntervalBucket;
 
 mod attempt;
+mod cleu_parser_state;
+mod eviction_policy;
+mod health_model;
 mod live_data_processor;
 mod server;
+mod server_snapshot;
+mod snapshot_policy;
 
 mod active_map;
 mod participant;
+mod wow_tbc_parser;
 mod wow_vanilla_parser;
+mod wow_wotlk_parser;
 mod interval_bucket;
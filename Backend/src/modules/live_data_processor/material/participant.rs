@@ -1,6 +1,9 @@
 #![allow(clippy::type_complexity)]
 
+use crate::modules::data::domain_value::SpecRule;
+use crate::modules::live_data_processor::dto::{SpecDetection, SpecEvidence};
 use crate::modules::live_data_processor::material::interval_bucket::UniqueBucketId;
+use crate::modules::live_data_processor::material::HealthModel;
 
 #[derive(Debug, Clone)]
 pub struct Participant {
@@ -12,18 +15,21 @@ pub struct Participant {
     pub hero_class_id: Option<u8>,
     pub gender_id: Option<bool>,
     pub race_id: Option<u8>,
+    pub has_name_collision: bool,
     pub guild_args: Option<(String, String, u8)>,
     pub talents: Vec<(u64, Option<String>)>,
     pub last_seen_talents: String,
+    pub spec_evidence: Vec<SpecEvidence>,
     pub server: Option<(u32, String)>,
     pub gear_setups: Option<Vec<(u64, Vec<Option<(u32, Option<u32>, Option<Vec<Option<u32>>>)>>)>>,
     pub active_intervals: Vec<(u64, u64)>,
-    available_effective_heal: u32,
+    pub health: HealthModel,
 
     // Technical
     pub first_seen: u64,
     pub last_seen: u64,
     pub last_brainwash: u64,
+    pub last_spec_reset: u64,
 }
 
 impl Participant {
@@ -36,6 +42,7 @@ impl Participant {
             hero_class_id: None,
             gender_id: None,
             race_id: None,
+            has_name_collision: false,
             name,
             server: None,
             gear_setups: None,
@@ -43,13 +50,25 @@ impl Participant {
             first_seen: last_seen,
             last_seen,
             guild_args: None,
-            available_effective_heal: 0,
+            health: HealthModel::default(),
             talents: Vec::new(),
             last_seen_talents: String::new(),
+            spec_evidence: Vec::new(),
             last_brainwash: 0,
+            last_spec_reset: 0,
         }
     }
 
+    /// Players of different realms may share a name and hence a unit id within one log.
+    /// They tell apart by a contradicting class or race.
+    pub fn record_identity(&mut self, hero_class_id: u8, race_id: u8) {
+        if self.hero_class_id.map_or(false, |own_hero_class_id| own_hero_class_id != hero_class_id) || self.race_id.map_or(false, |own_race_id| own_race_id != race_id) {
+            self.has_name_collision = true;
+        }
+        self.hero_class_id.get_or_insert(hero_class_id);
+        self.race_id.get_or_insert(race_id);
+    }
+
     pub fn record_talents(&mut self, timestamp: u64, talent_string: &str) {
         if self.last_seen_talents != talent_string {
             if self.last_brainwash > 0 {
@@ -67,6 +86,76 @@ impl Participant {
         }
     }
 
+    pub fn add_spec_evidence(&mut self, spec_rule: &SpecRule, timestamp: u64) {
+        if self.hero_class_id.map_or(false, |hero_class_id| hero_class_id != spec_rule.hero_class_id) {
+            return;
+        }
+
+        if let Some(evidence) = self.spec_evidence.iter_mut().find(|evidence| evidence.rule_id == spec_rule.id) {
+            evidence.count += 1;
+            evidence.last_seen = evidence.last_seen.max(timestamp);
+        } else {
+            self.spec_evidence.push(SpecEvidence {
+                rule_id: spec_rule.id,
+                spell_name: spec_rule.spell_name.clone(),
+                hero_class_id: spec_rule.hero_class_id,
+                talent_tree: spec_rule.talent_tree,
+                role: spec_rule.role,
+                weight: spec_rule.weight,
+                count: 1,
+                first_seen: timestamp,
+                last_seen: timestamp,
+            });
+        }
+    }
+
+    // A spec change invalidates the evidence collected so far
+    pub fn reset_spec_evidence(&mut self, timestamp: u64) {
+        self.spec_evidence.clear();
+        self.last_spec_reset = timestamp;
+    }
+
+    // Each rule counts once, so that spamming a single ability does not outweigh other evidence.
+    // Ties are resolved in favor of the spec that was observed most recently.
+    pub fn get_detected_spec(&self) -> Option<SpecDetection> {
+        let evidence: Vec<&SpecEvidence> = self.spec_evidence.iter().filter(|evidence| self.hero_class_id.map_or(true, |hero_class_id| hero_class_id == evidence.hero_class_id)).collect();
+        let total_weight: u32 = evidence.iter().map(|evidence| evidence.weight).sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        let mut specs: Vec<((u8, u8), u32, u64)> = Vec::new();
+        for evidence in evidence.iter() {
+            let spec = (evidence.hero_class_id, evidence.talent_tree);
+            if let Some((_, weight, last_seen)) = specs.iter_mut().find(|(other_spec, _, _)| *other_spec == spec) {
+                *weight += evidence.weight;
+                *last_seen = (*last_seen).max(evidence.last_seen);
+            } else {
+                specs.push((spec, evidence.weight, evidence.last_seen));
+            }
+        }
+        let ((hero_class_id, talent_tree), weight, _) = specs.into_iter().max_by_key(|(_, weight, last_seen)| (*weight, *last_seen))?;
+
+        let mut roles: Vec<(u8, u32)> = Vec::new();
+        for (role, role_weight) in evidence
+            .iter()
+            .filter(|evidence| evidence.hero_class_id == hero_class_id && evidence.talent_tree == talent_tree)
+            .filter_map(|evidence| evidence.role.map(|role| (role, evidence.weight)))
+        {
+            if let Some((_, weight)) = roles.iter_mut().find(|(other_role, _)| *other_role == role) {
+                *weight += role_weight;
+            } else {
+                roles.push((role, role_weight));
+            }
+        }
+
+        Some(SpecDetection {
+            hero_class_id,
+            talent_tree,
+            role: roles.into_iter().max_by_key(|(_, weight)| *weight).map(|(role, _)| role),
+            confidence: (100 * weight / total_weight) as u8,
+        })
+    }
 
     // Assumes that now > last_seen
     pub fn add_participation_point(&mut self, now: u64) {
@@ -83,19 +172,71 @@ impl Participant {
     }
 
     pub fn attribute_damage(&mut self, damage: u32) {
-        self.available_effective_heal += damage;
+        self.health.take_damage(damage);
+    }
+
+    /// Returns the effective heal and the overheal
+    pub fn attribute_heal(&mut self, heal: u32, absorb: u32) -> (u32, u32) {
+        self.health.take_heal(heal, absorb)
+    }
+
+    pub fn attribute_death(&mut self) {
+        self.health.die();
     }
 
-    pub fn attribute_heal(&mut self, heal: u32) -> u32 {
-        let effective_heal;
-        if heal > self.available_effective_heal {
-            effective_heal = self.available_effective_heal;
-            self.available_effective_heal = 0;
+    // Merges the same participant as seen in a subsequent part of the log
+    pub fn merge(&mut self, other: Participant) {
+        self.has_name_collision |= other.has_name_collision;
+        if let (Some(hero_class_id), Some(race_id)) = (other.hero_class_id, other.race_id) {
+            self.record_identity(hero_class_id, race_id);
+        }
+        self.hero_class_id = self.hero_class_id.or(other.hero_class_id);
+        self.gender_id = self.gender_id.or(other.gender_id);
+        self.race_id = self.race_id.or(other.race_id);
+        if self.guild_args.is_none() {
+            self.guild_args = other.guild_args;
+        }
+        if self.server.is_none() {
+            self.server = other.server;
+        }
+
+        // The other part started without knowing the last talents, so it recorded them again
+        for (timestamp, talents) in other.talents {
+            if self.talents.last().map_or(true, |(_, last_talents)| *last_talents != talents) {
+                self.talents.push((timestamp, talents));
+            }
+        }
+        if !other.last_seen_talents.is_empty() {
+            self.last_seen_talents = other.last_seen_talents;
+        }
+        if other.last_brainwash > 0 {
+            self.last_brainwash = other.last_brainwash;
+        }
+        if other.last_spec_reset > 0 {
+            self.spec_evidence = other.spec_evidence;
+            self.last_spec_reset = other.last_spec_reset;
         } else {
-            self.available_effective_heal -= heal;
-            effective_heal = heal;
+            for evidence in other.spec_evidence {
+                if let Some(own_evidence) = self.spec_evidence.iter_mut().find(|own_evidence| own_evidence.rule_id == evidence.rule_id) {
+                    own_evidence.count += evidence.count;
+                    own_evidence.first_seen = own_evidence.first_seen.min(evidence.first_seen);
+                    own_evidence.last_seen = own_evidence.last_seen.max(evidence.last_seen);
+                } else {
+                    self.spec_evidence.push(evidence);
+                }
+            }
+        }
+
+        if let Some(other_gear_setups) = other.gear_setups {
+            self.gear_setups.get_or_insert_with(Vec::new).extend(other_gear_setups);
+        }
+
+        for (start, end) in other.active_intervals {
+            self.add_participation_point(start);
+            self.add_participation_point(end);
         }
-        effective_heal
+        self.first_seen = self.first_seen.min(other.first_seen);
+        self.health.merge(other.health);
     }
 }
 
@@ -11,7 +11,6 @@ pub struct Server {
 
     // Meta Data
     pub summons: HashMap<u64, Unit>,
-    // TODO: This grows uncontrollable
     // Key: (instance_id, member_id)
     pub active_instances: HashMap<(u32, u32), UnitInstance>,
     pub last_raid_instance_id: u32,
@@ -41,6 +40,15 @@ pub struct Server {
 
     // PERFORMANCE
     pub cache_unit: HashMap<u64, Unit>,
+
+    // Eviction
+    // Key: (instance_id, member_id), Value: (committed_events_count, last activity in wall clock seconds)
+    pub state_last_activity: HashMap<(u32, u32), (u32, u64)>,
+
+    // Snapshots
+    pub snapshot_sequence: u64,
+    pub packages_since_snapshot: u32,
+    pub last_snapshot_ts: u64,
 }
 
 impl Server {
@@ -48,7 +56,6 @@ impl Server {
         Server {
             server_id,
             expansion_id,
-            // TODO: Preserve all data when the app is restarted?
             summons: HashMap::new(),
             active_instances: HashMap::new(),
             last_raid_instance_id: 0,
@@ -64,6 +71,10 @@ impl Server {
             post_processing_last_precessed_event_id: HashMap::new(),
             recently_committed_spell_cast_and_aura_applications: HashMap::new(),
             cache_unit: HashMap::new(),
+            state_last_activity: HashMap::new(),
+            snapshot_sequence: 0,
+            packages_since_snapshot: 0,
+            last_snapshot_ts: time_util::now(),
         }
     }
 
@@ -128,6 +139,9 @@ impl Server {
             .for_each(|result| {
                 self.instance_resets.insert(result.map_id, result);
             });
+
+        // Continue where processing stopped before the restart
+        self.restore_snapshot();
         self
     }
 }
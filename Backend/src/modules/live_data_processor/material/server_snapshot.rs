@@ -0,0 +1,25 @@
+use crate::modules::live_data_processor::domain_value::{Event, NonCommittedEvent, Unit, UnitInstance};
+use crate::modules::live_data_processor::material::Attempt;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+pub const SERVER_SNAPSHOT_VERSION: u8 = 1;
+
+// JSON maps only allow scalar keys, hence the (instance_id, member_id) keyed maps are stored as lists
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServerSnapshot {
+    pub version: u8,
+    pub server_id: u32,
+    pub summons: HashMap<u64, Unit>,
+    pub active_instances: Vec<((u32, u32), UnitInstance)>,
+    pub last_raid_instance_id: u32,
+    pub unit_instance_id: HashMap<u64, u32>,
+    pub instance_participants: HashMap<u32, BTreeSet<u32>>,
+    pub active_attempts: Vec<((u32, u32), HashMap<u32, Attempt>)>,
+    pub completed_attempts: Vec<((u32, u32), HashMap<u32, Attempt>)>,
+    pub subject_prepend_mode_set: BTreeSet<u64>,
+    pub post_processing_last_precessed_event_id: HashMap<u32, u32>,
+    pub non_committed_events: HashMap<u64, NonCommittedEvent>,
+    pub committed_events: Vec<((u32, u32), VecDeque<Event>)>,
+    pub committed_events_count: Vec<((u32, u32), u32)>,
+    pub recently_committed_spell_cast_and_aura_applications: Vec<((u32, u32), VecDeque<Event>)>,
+}
@@ -0,0 +1,26 @@
+#[derive(Debug, Clone)]
+pub struct SnapshotPolicy {
+    // A snapshot is taken once either this many seconds or this many packages passed since the last one
+    pub interval_secs: u64,
+    pub max_packages: u32,
+}
+
+impl Default for SnapshotPolicy {
+    fn default() -> Self {
+        SnapshotPolicy { interval_secs: 60, max_packages: 50 }
+    }
+}
+
+impl SnapshotPolicy {
+    pub fn from_env() -> Self {
+        let default = SnapshotPolicy::default();
+        SnapshotPolicy {
+            interval_secs: read_env("LIVE_DATA_SNAPSHOT_INTERVAL_SECS").unwrap_or(default.interval_secs),
+            max_packages: read_env("LIVE_DATA_SNAPSHOT_MAX_PACKAGES").map(|amount| amount as u32).unwrap_or(default.max_packages),
+        }
+    }
+}
+
+fn read_env(key: &str) -> Option<u64> {
+    std::env::var(key).ok().and_then(|value| value.parse::<u64>().ok())
+}
@@ -0,0 +1,15 @@
+use crate::modules::live_data_processor::material::CleuParserState;
+
+pub struct WoWTBCParser {
+    pub server_id: u32,
+    pub state: CleuParserState,
+}
+
+impl WoWTBCParser {
+    pub fn new(server_id: u32) -> Self {
+        WoWTBCParser {
+            server_id,
+            state: CleuParserState::new(2),
+        }
+    }
+}
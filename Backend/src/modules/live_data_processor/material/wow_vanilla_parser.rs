@@ -1,32 +1,45 @@
 use crate::modules::data::Data;
-use crate::modules::live_data_processor::dto::{Message, Unit};
+use crate::modules::live_data_processor::dto::{Message, ParseReport, Unit};
 use crate::modules::live_data_processor::material::{ActiveMapMap, CollectActiveMap, Participant};
+use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::locale::CombatLogLocale;
+use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::parse_unit::CachedUnit;
 use std::collections::HashMap;
 
 pub struct WoWVanillaParser {
     pub server_id: u32,
+    pub locale: CombatLogLocale,
 
     pub participants: HashMap<u64, Participant>,
     pub active_map: ActiveMapMap,
     pub pet_owner: HashMap<u64, u64>,
 
-    pub cache_unit: HashMap<String, Unit>,
+    pub cache_unit: HashMap<String, CachedUnit>,
     pub cache_spell_id: HashMap<String, Option<u32>>,
+    // Generation of the data the caches were filled with
+    pub data_generation: u64,
 
     // Hacky
     pub bonus_messages: Vec<Message>,
+
+    pub parse_report: ParseReport,
+    // Regex family that handled the current line
+    pub line_family: &'static str,
 }
 
 impl WoWVanillaParser {
     pub fn new(server_id: u32) -> Self {
         WoWVanillaParser {
             server_id,
+            locale: CombatLogLocale::English,
             participants: Default::default(),
             active_map: Default::default(),
             pet_owner: Default::default(),
             cache_unit: Default::default(),
             cache_spell_id: Default::default(),
+            data_generation: 0,
             bonus_messages: Default::default(),
+            parse_report: Default::default(),
+            line_family: "unmatched",
         }
     }
 
@@ -0,0 +1,15 @@
+use crate::modules::live_data_processor::material::CleuParserState;
+
+pub struct WoWWotLKParser {
+    pub server_id: u32,
+    pub state: CleuParserState,
+}
+
+impl WoWWotLKParser {
+    pub fn new(server_id: u32) -> Self {
+        WoWWotLKParser {
+            server_id,
+            state: CleuParserState::new(3),
+        }
+    }
+}
@@ -9,3 +9,6 @@ pub mod transfer;
 
 #[cfg(test)]
 mod tests;
+
+#[cfg(test)]
+mod benches;
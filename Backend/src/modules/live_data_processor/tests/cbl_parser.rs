@@ -0,0 +1,127 @@
+extern crate proptest;
+
+use self::proptest::prelude::*;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::material::WoWVanillaParser;
+use crate::modules::live_data_processor::tools::cbl_parser::{CblParseFailure, CombatLogParser};
+use std::sync::atomic::Ordering;
+
+const SAMPLE_LINES: [&str; 12] = [
+    "Ragnaros hits Tank for 1234. (200 resisted) (glancing)",
+    "Ragnaros crits Tank for 1234 Fire damage. (100 absorbed)",
+    "Ragnaros misses Tank.",
+    "Ragnaros attacks. Tank parries.",
+    "Mage 's Fireball crits Ragnaros for 3000 Fire damage. (50 resisted)",
+    "Priest 's Flash Heal critically heals Tank for 1500.",
+    "Tank gains Shield Wall (1).",
+    "Ragnaros dies.",
+    "Mage begins to cast Fireball.",
+    "PET: 01.01.21 20:00:00&Hunter&Wolf",
+    "ZONE_INFO: 01.01.21 20:00:00&Molten Core&0",
+    "COMBATANT_INFO: 01.01.21 20:00:00&Mage&MAGE&GNOME&2&nil",
+];
+
+fn parse(line: &str) -> Result<(), CblParseFailure> {
+    let data = Data::default();
+    let mut parser = WoWVanillaParser::new(1);
+    parser.parse_cbl_line(&data, 0, line).map(|_| ())
+}
+
+#[test]
+fn test_unrecognized_line() {
+    assert_eq!(parse("This is not a combat log line"), Err(CblParseFailure::UnrecognizedLine));
+}
+
+#[test]
+fn test_combatant_info_with_missing_args() {
+    assert_eq!(parse(SAMPLE_LINES[11]), Err(CblParseFailure::MalformedCombatantInfo(6)));
+}
+
+#[test]
+fn test_malformed_trailer() {
+    assert_eq!(parse("Ragnaros hits Tank for 1234. (200 splashed)"), Err(CblParseFailure::MalformedTrailer("200 splashed".to_string())));
+}
+
+#[test]
+fn test_sample_lines_are_counted() {
+    let data = Data::default();
+    let mut parser = WoWVanillaParser::new(1);
+    for line in SAMPLE_LINES.iter() {
+        let _ = parser.parse_cbl_line(&data, 0, line);
+    }
+    let report = parser.get_parse_report();
+    assert_eq!(report.total_lines, SAMPLE_LINES.len() as u32);
+    assert_eq!(report.recognized_lines + report.unrecognized_lines, report.total_lines);
+}
+
+#[test]
+fn test_combatant_info_of_players_sharing_a_name() {
+    let data = Data::default();
+    let mut parser = WoWVanillaParser::new(1);
+    let combatant_info = |hero_class: &str, race: &str| format!("COMBATANT_INFO: 01.01.21 20:00:00&Mage&{}&{}&2&nil&nil&nil&nil{}", hero_class, race, "&nil".repeat(19));
+    assert!(parser.parse_cbl_line(&data, 0, &combatant_info("MAGE", "GNOME")).is_ok());
+    assert!(parser.parse_cbl_line(&data, 1000, &combatant_info("MAGE", "GNOME")).is_ok());
+    assert!(parser.get_involved_character_builds().iter().any(|(_, _, character)| character.character_history.is_some()));
+
+    // Another player of the same name, e.g. of another realm, cannot be told apart in the events
+    assert!(parser.parse_cbl_line(&data, 2000, &combatant_info("WARLOCK", "HUMAN")).is_ok());
+    assert!(parser.get_participants().iter().all(|participant| participant.has_name_collision));
+    assert!(parser.get_involved_character_builds().iter().all(|(_, _, character)| character.character_history.is_none()));
+}
+
+proptest! {
+    #[test]
+    fn test_arbitrary_lines_do_not_panic(line in "\\PC*") {
+        let _ = parse(&line);
+    }
+
+    #[test]
+    fn test_truncated_sample_lines_do_not_panic(index in 0..SAMPLE_LINES.len(), cut in 0..80usize) {
+        let line = SAMPLE_LINES[index];
+        let truncated = line.chars().take(cut).collect::<String>();
+        let _ = parse(&truncated);
+    }
+
+    #[test]
+    fn test_mutated_sample_lines_do_not_panic(index in 0..SAMPLE_LINES.len(), position in 0..80usize, insertion in "[0-9a-zA-Z&:()'. ]{0,12}") {
+        let line = SAMPLE_LINES[index];
+        let position = position.min(line.chars().count());
+        let mut mutated = line.chars().take(position).collect::<String>();
+        mutated.push_str(&insertion);
+        mutated.extend(line.chars().skip(position));
+        let _ = parse(&mutated);
+    }
+
+    #[test]
+    fn test_arbitrary_trailers_do_not_panic(trailer in "(\\([0-9a-z ]{0,16}\\) ?){0,4}") {
+        let _ = parse(&format!("Ragnaros hits Tank for 1234. {}", trailer));
+    }
+}
+
+#[test]
+fn test_reloaded_data_clears_the_caches() {
+    let data = Data::default();
+    let mut parser = WoWVanillaParser::new(1);
+    parser.cache_spell_id.insert("Fireball".to_string(), None);
+    let _ = parser.parse_cbl_line(&data, 1000, "Ragnaros hits Tank for 1234.");
+    assert!(parser.cache_spell_id.contains_key("Fireball"));
+
+    data.generation.fetch_add(1, Ordering::SeqCst);
+    let _ = parser.parse_cbl_line(&data, 2000, "Ragnaros hits Tank for 1234.");
+    assert!(!parser.cache_spell_id.contains_key("Fireball"));
+    assert!(parser.cache_unit.contains_key("Ragnaros"));
+    assert_eq!(parser.data_generation, 1);
+}
+
+#[test]
+fn test_unresolved_names_with_spaces_are_reported_as_unknown_npcs() {
+    let data = Data::default();
+    let mut parser = WoWVanillaParser::new(1);
+    let _ = parser.parse_cbl_line(&data, 1000, "Molten Giant hits Tank for 1234.");
+    let _ = parser.parse_cbl_line(&data, 2000, "Tank hits Molten Giant (Mage) for 1234.");
+    parser.do_message_post_processing(&data, &mut Vec::new());
+
+    assert!(parser.cache_unit.get("Molten Giant").unwrap().is_unknown_npc);
+    assert!(!parser.cache_unit.get("Tank").unwrap().is_unknown_npc);
+    assert_eq!(parser.get_parse_report().unknown_npc_names, vec!["Molten Giant".to_string()]);
+}
@@ -0,0 +1,153 @@
+extern crate proptest;
+
+use self::proptest::prelude::*;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::dto::MessageType;
+use crate::modules::live_data_processor::material::{WoWTBCParser, WoWWotLKParser};
+use crate::modules::live_data_processor::tools::cbl_parser::{select_combat_log_format, sniff_combat_log_format, CblParseFailure, CombatLogFormat, CombatLogParser};
+
+const TBC_SWING_DAMAGE: &str = "SWING_DAMAGE,0xF130002D8A000001,\"Ragnaros\",0xa48,0x0000000000000001,\"Tank\",0x511,1234,1,0,100,0,nil,nil,nil";
+const TBC_SPELL_HEAL: &str = "SPELL_HEAL,0x0000000000000003,\"Priest\",0x514,0x0000000000000001,\"Tank\",0x511,25235,\"Flash Heal\",0x2,1500,nil";
+const WOTLK_SWING_DAMAGE: &str = "SWING_DAMAGE,0xF130002D8A000001,\"Ragnaros\",0xa48,0x0000000000000001,\"Tank\",0x511,1234,0,1,0,100,0,nil,nil,nil";
+const WOTLK_SPELL_DAMAGE: &str = "SPELL_DAMAGE,0x0000000000000002,\"Mage\",0x514,0xF130002D8A000001,\"Ragnaros\",0xa48,42833,\"Fireball\",0x4,3000,0,4,50,0,0,1,nil,nil";
+const WOTLK_SPELL_CAST_SUCCESS: &str = "SPELL_CAST_SUCCESS,0x0000000000000002,\"Mage\",0x514,0xF130002D8A000001,\"Ragnaros\",0xa48,42833,\"Fireball\",0x4";
+const WOTLK_PERIODIC_DAMAGE: &str = "SPELL_PERIODIC_DAMAGE,0x0000000000000004,\"Warlock\",0x514,0xF130002D8A000001,\"Ragnaros\",0xa48,47813,\"Corruption\",0x20,900,0,32,0,0,0,nil,nil,nil";
+const WOTLK_SPELL_HEAL: &str = "SPELL_HEAL,0x0000000000000003,\"Priest\",0x514,0x0000000000000001,\"Tank\",0x511,48071,\"Flash Heal\",0x2,1500,300,0,nil";
+
+fn wotlk_parse(parser: &mut WoWWotLKParser, event_ts: u64, line: &str) -> Result<Vec<MessageType>, CblParseFailure> {
+    parser.parse_cbl_line(&Data::default(), event_ts, line)
+}
+
+#[test]
+fn test_tbc_swing_damage() {
+    let mut parser = WoWTBCParser::new(1);
+    let message_types = parser.parse_cbl_line(&Data::default(), 0, TBC_SWING_DAMAGE).unwrap();
+    assert_eq!(message_types.len(), 1);
+    if let MessageType::MeleeDamage(damage_done) = &message_types[0] {
+        assert!(!damage_done.attacker.is_player);
+        assert!(damage_done.victim.is_player);
+        assert_eq!(damage_done.damage_components[0].damage, 1234);
+        assert_eq!(damage_done.damage_components[0].absorbed, 0);
+        assert_eq!(damage_done.blocked, 100);
+    } else {
+        panic!("Expected melee damage");
+    }
+}
+
+#[test]
+fn test_tbc_heal_is_attributed_to_a_synthesized_spell_cast() {
+    let mut parser = WoWTBCParser::new(1);
+    let message_types = parser.parse_cbl_line(&Data::default(), 0, TBC_SPELL_HEAL).unwrap();
+    assert_eq!(message_types.len(), 2);
+    assert!(matches!(message_types[0], MessageType::SpellCast(_)));
+    assert!(matches!(message_types[1], MessageType::Heal(_)));
+}
+
+#[test]
+fn test_wotlk_swing_damage_with_overkill() {
+    let mut parser = WoWWotLKParser::new(1);
+    let message_types = wotlk_parse(&mut parser, 0, WOTLK_SWING_DAMAGE).unwrap();
+    if let MessageType::MeleeDamage(damage_done) = &message_types[0] {
+        assert_eq!(damage_done.damage_components[0].damage, 1234);
+        assert_eq!(damage_done.damage_components[0].school_mask, 1);
+        assert_eq!(damage_done.blocked, 100);
+    } else {
+        panic!("Expected melee damage");
+    }
+}
+
+#[test]
+fn test_wotlk_spell_damage_links_to_preceding_cast() {
+    let mut parser = WoWWotLKParser::new(1);
+    assert!(matches!(wotlk_parse(&mut parser, 0, WOTLK_SPELL_CAST_SUCCESS).unwrap()[0], MessageType::SpellCast(_)));
+    let message_types = wotlk_parse(&mut parser, 1000, WOTLK_SPELL_DAMAGE).unwrap();
+    assert_eq!(message_types.len(), 1);
+    assert!(matches!(message_types[0], MessageType::SpellDamage(_)));
+
+    // Outside of the link window the damage needs a cast of its own
+    let message_types = wotlk_parse(&mut parser, 60000, WOTLK_SPELL_DAMAGE).unwrap();
+    assert_eq!(message_types.len(), 2);
+    assert!(matches!(message_types[0], MessageType::SpellCast(_)));
+}
+
+#[test]
+fn test_wotlk_periodic_damage_synthesizes_aura_application_once() {
+    let mut parser = WoWWotLKParser::new(1);
+    let message_types = wotlk_parse(&mut parser, 0, WOTLK_PERIODIC_DAMAGE).unwrap();
+    assert_eq!(message_types.len(), 2);
+    assert!(matches!(message_types[0], MessageType::AuraApplication(_)));
+    if let MessageType::SpellDamage(damage_done) = &message_types[1] {
+        assert!(damage_done.damage_over_time);
+    } else {
+        panic!("Expected spell damage");
+    }
+    assert_eq!(wotlk_parse(&mut parser, 3000, WOTLK_PERIODIC_DAMAGE).unwrap().len(), 1);
+}
+
+#[test]
+fn test_wotlk_heal_subtracts_overheal() {
+    let mut parser = WoWWotLKParser::new(1);
+    let message_types = wotlk_parse(&mut parser, 0, WOTLK_SPELL_HEAL).unwrap();
+    if let MessageType::Heal(heal_done) = message_types.last().unwrap() {
+        assert_eq!(heal_done.total_heal, 1500);
+        assert_eq!(heal_done.effective_heal, 1200);
+        assert_eq!(heal_done.overheal, 300);
+    } else {
+        panic!("Expected heal");
+    }
+}
+
+#[test]
+fn test_addon_pet_summon_creates_summon_on_post_processing() {
+    let mut parser = WoWWotLKParser::new(1);
+    let line = "SPELL_CAST_FAILED,0x0000000000000001,\"Tank\",0x511,0x0000000000000000,nil,0x80000000,6603,\"Attack\",0x1,\"CONSOLIDATED: PET_SUMMON: 01.01.21 20:00:00&0x0000000000000005&0xF140000000000001\"";
+    assert_eq!(wotlk_parse(&mut parser, 0, line), Ok(Vec::new()));
+    let mut messages = Vec::new();
+    parser.do_message_post_processing(&Data::default(), &mut messages);
+    assert_eq!(messages.len(), 1);
+    assert!(matches!(messages[0].message_type, MessageType::Summon(_)));
+}
+
+#[test]
+fn test_unknown_miss_type() {
+    let mut parser = WoWWotLKParser::new(1);
+    let line = "SWING_MISSED,0xF130002D8A000001,\"Ragnaros\",0xa48,0x0000000000000001,\"Tank\",0x511,SPLASH";
+    assert_eq!(wotlk_parse(&mut parser, 0, line), Err(CblParseFailure::UnknownKeyword("SPLASH".to_string())));
+}
+
+#[test]
+fn test_malformed_guid() {
+    let mut parser = WoWWotLKParser::new(1);
+    let line = "UNIT_DIED,0x0000000000000000,nil,0x80000000,Ragnaros,\"Ragnaros\",0xa48";
+    assert_eq!(wotlk_parse(&mut parser, 0, line), Err(CblParseFailure::UnknownUnit("Ragnaros".to_string())));
+}
+
+#[test]
+fn test_sniff_combat_log_format() {
+    assert_eq!(sniff_combat_log_format(&format!("1/2 20:00:00.000  {}", TBC_SWING_DAMAGE)), Some(CombatLogFormat::TBC));
+    assert_eq!(sniff_combat_log_format(&format!("1/2 20:00:00.000  {}", WOTLK_SWING_DAMAGE)), Some(CombatLogFormat::WotLK));
+    assert_eq!(sniff_combat_log_format("1/2 20:00:00.000  Ragnaros hits Tank for 1234."), Some(CombatLogFormat::Vanilla));
+    assert_eq!(sniff_combat_log_format(&format!("1/2 20:00:00.000  {}", WOTLK_SPELL_CAST_SUCCESS)), None);
+}
+
+#[test]
+fn test_select_combat_log_format_falls_back_to_server_expansion() {
+    let content = format!("1/2 20:00:00.000  {}", WOTLK_SPELL_CAST_SUCCESS);
+    assert_eq!(select_combat_log_format(Some(3), &content), CombatLogFormat::WotLK);
+    assert_eq!(select_combat_log_format(Some(1), &content), CombatLogFormat::TBC);
+    assert_eq!(select_combat_log_format(None, ""), CombatLogFormat::Vanilla);
+}
+
+proptest! {
+    #[test]
+    fn test_arbitrary_lines_do_not_panic(line in "\\PC*") {
+        let _ = WoWTBCParser::new(1).parse_cbl_line(&Data::default(), 0, &line);
+        let _ = wotlk_parse(&mut WoWWotLKParser::new(1), 0, &line);
+    }
+
+    #[test]
+    fn test_arbitrary_arguments_do_not_panic(event in "(SWING|SPELL|SPELL_PERIODIC|RANGE)_(DAMAGE|MISSED|HEAL)|SPELL_AURA_APPLIED_DOSE|SPELL_CAST_FAILED", args in proptest::collection::vec("[0-9a-zA-Z\"{:&x ]{0,12}", 0..20)) {
+        let line = format!("{},0x0000000000000001,\"Tank\",0x511,0xF130002D8A000001,\"Ragnaros\",0xa48,{}", event, args.join(","));
+        let _ = wotlk_parse(&mut WoWWotLKParser::new(1), 0, &line);
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use crate::modules::data::domain_value::{NpcName, Spell, SpellName, NPC};
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::domain_value::{HitType, School};
+use crate::modules::live_data_processor::dto::MessageType;
+use crate::modules::live_data_processor::material::WoWVanillaParser;
+use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::locale::{sniff_combat_log_locale, CombatLogLocale};
+use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
+use crate::modules::live_data_processor::tools::GUID;
+
+#[test]
+fn translates_localized_lines() {
+    let lines = [
+        (
+            CombatLogLocale::German,
+            "Magiers Feuerball trifft Ragnaros kritisch für 3000 Feuerschaden. (50 widerstanden)",
+            "Magier 's Feuerball crits Ragnaros for 3000 Fire damage. (50 resisted)",
+        ),
+        (
+            CombatLogLocale::German,
+            "Ragnaros trifft Tank für 1234 Schaden. (200 widerstanden) (gestreift)",
+            "Ragnaros hits Tank for 1234. (200 resisted) (glancing)",
+        ),
+        (CombatLogLocale::German, "Tank bekommt 'Schildwall' (1).", "Tank gains Schildwall (1)."),
+        (CombatLogLocale::French, "Soins rapides de Prêtre soigne Tank et lui rend 1500 points de vie.", "Prêtre 's Soins rapides heals Tank for 1500."),
+        (
+            CombatLogLocale::French,
+            "Ragnaros subit 300 points de dégâts de Feu (Immolation de Démoniste).",
+            "Ragnaros suffers 300 Fire damage from Démoniste 's Immolation.",
+        ),
+        (CombatLogLocale::Spanish, "Ragnaros muere.", "Ragnaros dies."),
+        (CombatLogLocale::Russian, "Ragnaros наносит Tank 1234 ед. урона. (сопротивление: 200)", "Ragnaros hits Tank for 1234. (200 resisted)"),
+        (CombatLogLocale::Chinese, "法师对战士施放了奥术智慧。", "法师 casts 奥术智慧 on 战士."),
+        (
+            CombatLogLocale::Chinese,
+            "法师的火球术击中拉格纳罗斯造成3000点火焰伤害。（50点被抵抗）",
+            "法师 's 火球术 hits 拉格纳罗斯 for 3000 Fire damage. (50 resisted)",
+        ),
+    ];
+
+    for (locale, line, translation) in lines.iter() {
+        assert_eq!(locale.translate(line), Some(translation.to_string()), "{}", line);
+    }
+}
+
+#[test]
+fn keeps_lines_that_are_not_localized() {
+    assert_eq!(CombatLogLocale::English.translate("Ragnaros dies."), None);
+    assert_eq!(CombatLogLocale::German.translate("ZONE_INFO: 01.01.21 20:00:00&Geschmolzener Kern&0"), None);
+}
+
+#[test]
+fn sniffs_locale_of_log() {
+    let german = [(0, "Ragnaros trifft Tank für 1234 Schaden."), (1, "ZONE_INFO: 01.01.21 20:00:00&Geschmolzener Kern&0"), (2, "Ragnaros stirbt.")];
+    assert_eq!(sniff_combat_log_locale(&german), CombatLogLocale::German);
+
+    let english = [(0, "Ragnaros hits Tank for 1234."), (1, "Ragnaros dies.")];
+    assert_eq!(sniff_combat_log_locale(&english), CombatLogLocale::English);
+
+    // Inconclusive logs are considered English
+    let addon_messages = [(0, "ZONE_INFO: 01.01.21 20:00:00&Molten Core&0")];
+    assert_eq!(sniff_combat_log_locale(&addon_messages), CombatLogLocale::English);
+}
+
+#[test]
+fn language_ids_refer_to_data_language() {
+    let language_ids: Vec<u8> = CombatLogLocale::all().iter().map(|locale| locale.language_id()).collect();
+    assert_eq!(language_ids, vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn parses_localized_log() {
+    let data = Data::default();
+    let mut parser = WoWVanillaParser::new(1);
+    assert_eq!(parser.detect_language(&[(0, "Magiers Feuerball trifft Ragnaros für 3000 Feuerschaden.")]), Some(2));
+    parser.cache_spell_id.insert("Feuerball".to_string(), Some(133));
+
+    let fork = parser.fork().unwrap();
+    assert_eq!(fork.locale, CombatLogLocale::German);
+
+    let message_types = parser.parse_cbl_line(&data, 0, "Magiers Feuerball trifft Ragnaros kritisch für 3000 Feuerschaden. (50 widerstanden)").unwrap();
+    let damage = message_types
+        .iter()
+        .find_map(|message_type| match message_type {
+            MessageType::SpellDamage(damage) => Some(damage),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(damage.spell_id, Some(133));
+    assert_eq!(damage.hit_mask & HitType::Crit as u32, HitType::Crit as u32);
+    assert_eq!(damage.damage_components[0].school_mask, School::Fire as u8);
+    assert_eq!(damage.damage_components[0].damage, 3000);
+    assert_eq!(damage.damage_components[0].resisted_or_glanced, 50);
+    assert_eq!(parser.parse_report.recognized_lines, 1);
+}
+
+#[test]
+fn parses_localized_names_from_data() {
+    let data = Data::default();
+    let mut spells = HashMap::new();
+    spells.insert(
+        133,
+        Spell {
+            id: 133,
+            expansion_id: 1,
+            name: "Fireball".to_string(),
+            subtext: String::new(),
+            cost: 0,
+            cost_in_percent: 0,
+            power_type: 0,
+            cast_time: 0,
+            school_mask: 4,
+            dispel_type: 0,
+            range_max: 0,
+            cooldown: 0,
+            duration: 0,
+            icon: 0,
+            description: String::new(),
+            aura: String::new(),
+        },
+    );
+    data.spells.write().unwrap().push(spells);
+    let spell_name = SpellName {
+        expansion_id: 1,
+        spell_id: 133,
+        language_id: 2,
+        name: "Feuerball".to_string(),
+    };
+    data.spell_names.write().unwrap().entry(2).or_insert_with(HashMap::new).insert(spell_name.name.clone(), vec![spell_name]);
+    let mut npcs = HashMap::new();
+    npcs.insert(
+        11988,
+        NPC {
+            expansion_id: 1,
+            id: 11988,
+            localization_id: 1,
+            is_boss: true,
+            friend: 0,
+            family: 0,
+            map_id: Some(409),
+            max_health: None,
+        },
+    );
+    data.npcs.write().unwrap().push(npcs);
+    let npc_name = NpcName {
+        expansion_id: 1,
+        npc_id: 11988,
+        language_id: 2,
+        name: "Golemagg der Verbrenner".to_string(),
+    };
+    data.npc_names.write().unwrap().entry(2).or_insert_with(HashMap::new).insert(npc_name.name.clone(), vec![npc_name]);
+
+    let mut parser = WoWVanillaParser::new(1);
+    assert_eq!(parser.detect_language(&[(0, "Magiers Feuerball trifft Golemagg der Verbrenner für 3000 Feuerschaden.")]), Some(2));
+    let message_types = parser.parse_cbl_line(&data, 0, "Magiers Feuerball trifft Golemagg der Verbrenner für 3000 Feuerschaden.").unwrap();
+    let damage = message_types
+        .iter()
+        .find_map(|message_type| match message_type {
+            MessageType::SpellDamage(damage) => Some(damage),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(damage.spell_id, Some(133));
+    assert!(!damage.victim.is_player);
+    assert_eq!(damage.victim.unit_id.get_entry(), Some(11988));
+    assert!(damage.attacker.is_player);
+}
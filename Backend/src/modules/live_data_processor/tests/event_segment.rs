@@ -0,0 +1,92 @@
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use crate::modules::live_data_processor::tools::event_segment::{convert_directory_to_event_segment, EventSegmentReader, EventSegmentWriter};
+use serde_json::{from_str, Value};
+use std::io::Cursor;
+
+const SPELL_CASTS: [&str; 3] = ["[3,3000,[1,5],null,133,2,4]", "[1,1000,[1,5],[0,900,11502],133,2,4]", "[2,2000,[0,77,416,[1,5]],null,17,2,2]"];
+const THREAT: [&str; 1] = ["[4,1500,1,[1,5],[0,900,11502],0,2,1,-250]"];
+
+fn write_segment() -> Vec<u8> {
+    let mut writer = EventSegmentWriter::default();
+    for line in SPELL_CASTS.iter() {
+        writer.push_line(0, line).unwrap();
+    }
+    for line in THREAT.iter() {
+        writer.push_line(15, line).unwrap();
+    }
+    assert_eq!(writer.len(), 4);
+
+    let mut segment = Vec::new();
+    writer.write(&mut segment).unwrap();
+    segment
+}
+
+#[test]
+fn test_round_trip() {
+    let mut reader = EventSegmentReader::new(Cursor::new(write_segment())).unwrap();
+    assert_eq!(reader.event_types(), vec![0, 15]);
+    assert_eq!(reader.event_count(0), 3);
+
+    let events = reader.read_events(0).unwrap();
+    let expected = vec![SPELL_CASTS[1], SPELL_CASTS[2], SPELL_CASTS[0]].into_iter().map(|line| from_str::<Value>(line).unwrap()).collect::<Vec<Value>>();
+    assert_eq!(events, expected);
+    assert_eq!(reader.read_events(15).unwrap(), vec![from_str::<Value>(THREAT[0]).unwrap()]);
+}
+
+#[test]
+fn test_read_time_range() {
+    let mut reader = EventSegmentReader::new(Cursor::new(write_segment())).unwrap();
+    let events = reader.read_time_range(0, 1500, 3000).unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0][0], 2);
+    assert_eq!(events[1][0], 3);
+    assert!(reader.read_time_range(0, 3001, 4000).unwrap().is_empty());
+    assert!(reader.read_time_range(12, 0, 4000).unwrap().is_empty());
+}
+
+#[test]
+fn test_invalid_lines_are_rejected() {
+    let mut writer = EventSegmentWriter::default();
+    assert!(writer.push_line(0, "?!?").is_err());
+    assert!(writer.push_line(0, "[1]").is_err());
+    assert!(writer.is_empty());
+}
+
+#[test]
+fn test_corrupt_segment() {
+    let mut segment = write_segment();
+    segment[4] = 99;
+    assert!(matches!(EventSegmentReader::new(Cursor::new(segment)), Err(LiveDataProcessorFailure::InvalidEventSegment)));
+
+    let mut segment = write_segment();
+    segment.truncate(40);
+    assert!(EventSegmentReader::new(Cursor::new(segment)).is_err());
+
+    // The directory is intact, but the checksum of the compressed threat block does not match
+    let mut segment = write_segment();
+    let checksum_index = segment.len() - 8;
+    segment[checksum_index] ^= 0xFF;
+    let mut reader = EventSegmentReader::new(Cursor::new(segment)).unwrap();
+    assert_eq!(reader.read_events(0).unwrap().len(), 3);
+    assert!(matches!(reader.read_events(15), Err(LiveDataProcessorFailure::InvalidEventSegment)));
+}
+
+#[test]
+fn test_convert_directory_is_lossless() {
+    let src_dir = std::env::temp_dir().join(format!("event_segment_converter_{}", std::process::id()));
+    let segment_path = src_dir.join("segment");
+    let _ = std::fs::remove_dir_all(&src_dir);
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(src_dir.join("0"), format!("{}\n", SPELL_CASTS.join("\n"))).unwrap();
+    std::fs::write(src_dir.join("15"), THREAT.join("\n")).unwrap();
+
+    assert_eq!(convert_directory_to_event_segment(src_dir.to_str().unwrap(), segment_path.to_str().unwrap()).unwrap(), 4);
+    std::fs::remove_file(&segment_path).unwrap();
+
+    // A single line that can not be converted fails the whole conversion
+    std::fs::write(src_dir.join("15"), format!("{}\n?!?", THREAT.join("\n"))).unwrap();
+    assert!(convert_directory_to_event_segment(src_dir.to_str().unwrap(), segment_path.to_str().unwrap()).is_err());
+    assert!(!segment_path.exists());
+
+    std::fs::remove_dir_all(&src_dir).unwrap();
+}
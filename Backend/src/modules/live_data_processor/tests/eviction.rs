@@ -0,0 +1,124 @@
+use crate::modules::live_data_processor::domain_value::{Player, Unit, UnitInstance};
+use crate::modules::live_data_processor::material::{Attempt, EvictionPolicy, Server};
+use std::collections::{BTreeSet, HashMap};
+
+fn policy(max_instance_age: u64, max_active_instances: usize, max_cache_entries: usize) -> EvictionPolicy {
+    EvictionPolicy {
+        max_instance_age,
+        max_active_instances,
+        max_cache_entries,
+    }
+}
+
+#[test]
+fn test_evict_inactive_instances() {
+    let mut server = Server::new(1, 1);
+    server.committed_events_count.insert((1, 1), 10);
+    server.committed_events_count.insert((2, 1), 10);
+    server.completed_attempts.insert((1, 1), Default::default());
+    server.unit_instance_id.insert(100, 1);
+    server.unit_instance_id.insert(200, 2);
+    server.post_processing_last_precessed_event_id.insert(1, 9);
+    server.instance_participants.insert(5, BTreeSet::new());
+
+    let policy = policy(1000, 10, 10);
+    server.evict(&policy, 1000);
+    assert_eq!(server.committed_events_count.len(), 2);
+    // Participants of instances that are no longer active are pruned right away
+    assert!(server.instance_participants.is_empty());
+
+    server.committed_events_count.insert((2, 1), 11);
+    server.evict(&policy, 1500);
+    server.evict(&policy, 2001);
+    assert!(!server.committed_events_count.contains_key(&(1, 1)));
+    assert!(!server.completed_attempts.contains_key(&(1, 1)));
+    assert!(!server.unit_instance_id.contains_key(&100));
+    assert!(server.post_processing_last_precessed_event_id.is_empty());
+    assert_eq!(server.committed_events_count.get(&(2, 1)), Some(&11));
+    assert_eq!(server.unit_instance_id.get(&200), Some(&2));
+}
+
+#[test]
+fn test_evict_oldest_instances_above_limit() {
+    let mut server = Server::new(2, 1);
+    server.committed_events_count.insert((1, 1), 1);
+    server.evict(&policy(u64::MAX, 2, 10), 1000);
+    server.committed_events_count.insert((2, 1), 1);
+    server.evict(&policy(u64::MAX, 2, 10), 2000);
+    server.committed_events_count.insert((3, 1), 1);
+    server.evict(&policy(u64::MAX, 2, 10), 3000);
+
+    assert_eq!(server.committed_events_count.keys().cloned().collect::<BTreeSet<(u32, u32)>>(), vec![(2, 1), (3, 1)].into_iter().collect());
+    assert_eq!(server.state_last_activity.len(), 2);
+}
+
+fn unit_instance(instance_id: u32, entered: u64) -> UnitInstance {
+    UnitInstance {
+        instance_meta_id: instance_id + 100,
+        entered,
+        map_id: 409,
+        instance_id,
+        uploaded_user: 1,
+        ready_to_zip: false,
+        upload_id: 1,
+    }
+}
+
+#[test]
+fn test_evict_active_instances() {
+    let mut server = Server::new(4, 1);
+    server.active_instances.insert((1, 1), unit_instance(1, 1000));
+    server.active_instances.insert((2, 1), unit_instance(2, 1000));
+    server.active_instances.insert((3, 1), unit_instance(3, 1000));
+    server.unit_instance_id.insert(100, 1);
+    server.unit_instance_id.insert(300, 3);
+    // A running attempt is kept, no matter how long ago its instance was active
+    server.active_attempts.insert((3, 1), vec![(1, Attempt::new(1, 1000, false))].into_iter().collect::<HashMap<u32, Attempt>>());
+
+    server.evict(&policy(1000, 10, 10), 1000);
+    assert_eq!(server.active_instances.len(), 3);
+
+    server.committed_events_count.insert((2, 1), 1);
+    server.evict(&policy(1000, 10, 10), 1500);
+    server.evict(&policy(1000, 10, 10), 2001);
+    assert!(!server.active_instances.contains_key(&(1, 1)));
+    assert!(!server.unit_instance_id.contains_key(&100));
+    assert!(server.active_instances.contains_key(&(2, 1)));
+    assert!(server.active_instances.contains_key(&(3, 1)));
+    assert_eq!(server.unit_instance_id.get(&300), Some(&3));
+
+    // The size limit evicts the least recently active instance without pending state
+    server.active_instances.insert((4, 1), unit_instance(4, 2001));
+    server.evict(&policy(1000, 2, 10), 2001);
+    assert_eq!(server.active_instances.keys().cloned().collect::<BTreeSet<(u32, u32)>>(), vec![(3, 1), (4, 1)].into_iter().collect());
+}
+
+#[test]
+fn test_evict_unit_cache_above_limit() {
+    let mut server = Server::new(3, 1);
+    for character_id in 0..3 {
+        server.cache_unit.insert(character_id as u64, Unit::Player(Player { character_id }));
+    }
+    server.summons.insert(1, Unit::Player(Player { character_id: 1 }));
+
+    server.evict(&policy(1000, 10, 3), 1000);
+    assert_eq!(server.cache_unit.len(), 3);
+    assert_eq!(server.summons.len(), 1);
+
+    server.cache_unit.insert(3, Unit::Player(Player { character_id: 3 }));
+    server.evict(&policy(1000, 10, 3), 1000);
+    assert!(server.cache_unit.is_empty());
+    assert_eq!(server.summons.len(), 1);
+}
+
+#[test]
+fn test_evict_ages_instances_by_wall_clock_time() {
+    let mut server = Server::new(5, 1);
+    // Uploads of old logs enter instances far in the past in event time
+    server.active_instances.insert((1, 1), unit_instance(1, 1000));
+
+    server.evict(&policy(1000, 10, 10), 1_700_000_000);
+    assert!(server.active_instances.contains_key(&(1, 1)));
+    server.evict(&policy(1000, 10, 10), 1_700_001_001);
+    assert!(!server.active_instances.contains_key(&(1, 1)));
+}
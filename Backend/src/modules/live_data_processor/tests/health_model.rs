@@ -0,0 +1,82 @@
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::dto::MessageType;
+use crate::modules::live_data_processor::material::{HealthModel, WoWVanillaParser};
+use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
+
+#[test]
+fn missing_health_is_capped_at_max_health() {
+    let mut health_model = HealthModel::new(Some(1000));
+    health_model.take_damage(5000);
+    assert_eq!(health_model.missing_health(), 1000);
+    assert_eq!(health_model.take_heal(1500, 0), (1000, 500));
+    assert_eq!(health_model.take_heal(200, 0), (0, 200));
+}
+
+#[test]
+fn missing_health_without_max_health_is_bound_by_damage_taken() {
+    let mut health_model = HealthModel::default();
+    health_model.take_damage(5000);
+    assert_eq!(health_model.take_heal(1500, 0), (1500, 0));
+    assert_eq!(health_model.take_heal(4000, 0), (3500, 500));
+}
+
+#[test]
+fn heals_from_multiple_sources_share_the_missing_health() {
+    let mut health_model = HealthModel::new(Some(4000));
+    health_model.take_damage(1000);
+    assert_eq!(health_model.take_heal(800, 0), (800, 0));
+    assert_eq!(health_model.take_heal(800, 0), (200, 600));
+}
+
+#[test]
+fn absorbed_heal_is_neither_effective_nor_overheal() {
+    let mut health_model = HealthModel::new(Some(4000));
+    health_model.take_damage(1000);
+    assert_eq!(health_model.take_heal(800, 300), (500, 0));
+    assert_eq!(health_model.take_heal(800, 800), (0, 0));
+}
+
+#[test]
+fn dead_unit_misses_its_max_health() {
+    let mut health_model = HealthModel::new(Some(4000));
+    health_model.die();
+    assert_eq!(health_model.take_heal(1500, 0), (1500, 0));
+
+    let mut unknown_health_model = HealthModel::default();
+    unknown_health_model.die();
+    assert_eq!(unknown_health_model.take_heal(1500, 0), (0, 1500));
+}
+
+#[test]
+fn highest_max_health_is_kept() {
+    let mut health_model = HealthModel::default();
+    health_model.observe_max_health(4000);
+    health_model.observe_max_health(0);
+    health_model.observe_max_health(3500);
+    assert_eq!(health_model.max_health, Some(4000));
+    health_model.observe_max_health(4500);
+    assert_eq!(health_model.max_health, Some(4500));
+}
+
+#[test]
+fn combatant_info_max_health_limits_effective_heal() {
+    let data = Data::default();
+    let mut parser = WoWVanillaParser::new(1);
+    parser.cache_spell_id.insert("Flash Heal".to_string(), Some(2061));
+
+    let combatant_info = format!("COMBATANT_INFO: 01.01.21 20:00:00&Tank&WARRIOR&HUMAN&2&nil&nil&nil&nil{}&nil&4000", "&nil".repeat(19));
+    assert!(parser.parse_cbl_line(&data, 0, &combatant_info).unwrap().is_empty());
+    assert!(parser.parse_cbl_line(&data, 1000, "Ragnaros hits Tank for 5000.").is_ok());
+
+    let message_types = parser.parse_cbl_line(&data, 2000, "Priest 's Flash Heal heals Tank for 4500.").unwrap();
+    let heal_done = message_types
+        .iter()
+        .find_map(|message_type| match message_type {
+            MessageType::Heal(heal_done) => Some(heal_done),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(heal_done.total_heal, 4500);
+    assert_eq!(heal_done.effective_heal, 4000);
+    assert_eq!(heal_done.overheal, 500);
+}
@@ -1,7 +1,18 @@
 mod byte_reader;
+mod cbl_parser;
+mod cleu_parser;
+mod combat_log_locale;
+mod event_segment;
+mod eviction;
 mod guid;
+mod health_model;
 mod message;
 mod message_type;
+mod parse_lines;
+mod parse_report;
 mod payload_mapper;
 mod server;
+mod server_snapshot;
+mod spec_detection;
 mod try_parse_interrupt;
+mod upload_job;
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use crate::modules::data::domain_value::Spell;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::dto::MessageType;
+use crate::modules::live_data_processor::material::{Participant, WoWVanillaParser};
+use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
+use crate::modules::live_data_processor::tools::log_parser::{parse_lines, parse_lines_in_chunks, PARSE_MIN_CHUNK_SIZE};
+
+#[test]
+fn test_parse_lines_keeps_order() {
+    let data = Data::default();
+    let mut parser = WoWVanillaParser::new(1);
+    let lines = vec![(1000, "Ragnaros hits Tank for 1234."), (2000, "Something odd happens."), (3000, "Ragnaros dies.")];
+    let parsed_lines = parse_lines(&mut parser, &data, &lines);
+    assert_eq!(parsed_lines.len(), 3);
+    assert!(matches!(parsed_lines[0].as_ref().unwrap()[0], MessageType::MeleeDamage(_)));
+    assert!(parsed_lines[1].is_err());
+    assert!(matches!(parsed_lines[2].as_ref().unwrap()[0], MessageType::Death(_)));
+    assert_eq!(parser.get_parse_report().total_lines, 3);
+}
+
+#[test]
+fn test_chunked_parse_equals_serial_parse() {
+    let data = Data::default();
+    let mut spells = HashMap::new();
+    spells.insert(
+        2061,
+        Spell {
+            id: 2061,
+            expansion_id: 1,
+            name: "Flash Heal".to_string(),
+            subtext: String::new(),
+            cost: 0,
+            cost_in_percent: 0,
+            power_type: 0,
+            cast_time: 0,
+            school_mask: 2,
+            dispel_type: 0,
+            range_max: 0,
+            cooldown: 0,
+            duration: 0,
+            icon: 0,
+            description: String::new(),
+            aura: String::new(),
+        },
+    );
+    data.spells.write().unwrap().push(spells);
+
+    // The maximum health of the tank is only known in the last chunk
+    let combatant_info = format!("COMBATANT_INFO: 01.01.21 20:00:00&Tank&WARRIOR&HUMAN&2&nil&nil&nil&nil{}&nil&4000", "&nil".repeat(19));
+    let log = (0..PARSE_MIN_CHUNK_SIZE + 1000)
+        .map(|index| {
+            let line = match index % 4 {
+                _ if index == PARSE_MIN_CHUNK_SIZE + 500 => combatant_info.clone(),
+                0 => "Ragnaros hits Tank for 1500.".to_string(),
+                1 | 2 => "Priest 's Flash Heal heals Tank for 1000.".to_string(),
+                _ if index % 1000 == 3 => "Tank dies.".to_string(),
+                _ => "Ragnaros hits Tank for 800.".to_string(),
+            };
+            (index as u64 * 10, line)
+        })
+        .collect::<Vec<(u64, String)>>();
+    let lines = log.iter().map(|(timestamp, line)| (*timestamp, line.as_str())).collect::<Vec<(u64, &str)>>();
+
+    let mut serial_parser = WoWVanillaParser::new(1);
+    let serial_parsed_lines = parse_lines_in_chunks(&mut serial_parser, &data, &lines, lines.len());
+    let mut chunked_parser = WoWVanillaParser::new(1);
+    let chunked_parsed_lines = parse_lines_in_chunks(&mut chunked_parser, &data, &lines, PARSE_MIN_CHUNK_SIZE);
+
+    assert_eq!(serial_parsed_lines, chunked_parsed_lines);
+    let heals = serial_parsed_lines
+        .iter()
+        .filter_map(|parsed_line| parsed_line.as_ref().ok())
+        .flatten()
+        .filter_map(|message_type| match message_type {
+            MessageType::Heal(heal_done) => Some(heal_done),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(heals.len(), (PARSE_MIN_CHUNK_SIZE + 1000) / 2);
+    assert!(heals.iter().any(|heal_done| heal_done.overheal > 0));
+    assert!(heals.iter().all(|heal_done| heal_done.effective_heal + heal_done.overheal == heal_done.total_heal));
+}
+
+#[test]
+fn test_merge_forked_parsers() {
+    let data = Data::default();
+    let mut parser = WoWVanillaParser::new(1);
+    let mut fork = parser.fork().unwrap();
+    let _ = parser.parse_cbl_line(&data, 1000, "Ragnaros hits Tank for 1234.");
+    let _ = fork.parse_cbl_line(&data, 2000, "Ragnaros hits Tank for 1234.");
+    let _ = fork.parse_cbl_line(&data, 3000, "Something odd happens.");
+
+    parser.merge(fork);
+    assert_eq!(parser.participants.len(), 2);
+    assert!(parser.participants.values().all(|participant| participant.first_seen == 1000 && participant.last_seen == 2000));
+    assert_eq!(parser.get_parse_report().total_lines, 3);
+    assert_eq!(parser.get_parse_report().unrecognized_lines, 1);
+}
+
+#[test]
+fn test_merge_participant_skips_repeated_talents() {
+    let mut participant = Participant::new(1, true, false, false, "Tank".to_string(), 1000);
+    participant.record_talents(1000, "0|0|51");
+    let mut other = Participant::new(1, true, false, false, "Tank".to_string(), 900000);
+    other.record_talents(900000, "0|0|51");
+    other.record_talents(950000, "51|0|0");
+
+    participant.merge(other);
+    assert_eq!(participant.talents.len(), 2);
+    assert_eq!(participant.active_intervals.len(), 2);
+    assert_eq!(participant.last_seen, 900000);
+}
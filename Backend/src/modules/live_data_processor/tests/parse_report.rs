@@ -0,0 +1,54 @@
+use crate::modules::live_data_processor::dto::ParseReport;
+
+#[test]
+fn parse_report_counts_lines_by_family() {
+    let mut report = ParseReport::default();
+    report.record_line("melee_damage", "Peter hits Ragnaros for 42.", true);
+    report.record_line("melee_damage", "Peter hits Unknown for 42.", false);
+    report.record_line("unmatched", "Something odd happens.", false);
+    report.record_line("combatant_info", "COMBATANT_INFO: 1.1.20 10:00:00&Peter", false);
+
+    assert_eq!(report.total_lines, 4);
+    assert_eq!(report.recognized_lines, 1);
+    assert_eq!(report.unrecognized_lines, 3);
+    assert_eq!(report.families.get("melee_damage").unwrap().recognized, 1);
+    assert_eq!(report.families.get("melee_damage").unwrap().unrecognized, 1);
+    assert_eq!(report.unmatched_samples.len(), 2);
+    assert_eq!(report.dropped_combatant_info, 1);
+    assert_eq!(report.dropped_combatant_info_samples.len(), 1);
+}
+
+#[test]
+fn parse_report_caps_samples_and_deduplicates_names() {
+    let mut report = ParseReport::default();
+    for _ in 0..100 {
+        report.record_line("unmatched", "Something odd happens.", false);
+        report.record_timestamp_anomaly("out_of_order", "1/1 10:00:00.000  Something odd happens.");
+        report.record_unknown_spell_name("Fancy Spell");
+    }
+
+    assert_eq!(report.unrecognized_lines, 100);
+    assert_eq!(report.unmatched_samples.len(), 25);
+    assert_eq!(*report.timestamp_anomalies.get("out_of_order").unwrap(), 100);
+    assert_eq!(report.timestamp_anomaly_samples.len(), 25);
+    assert_eq!(report.unknown_spell_names, vec!["Fancy Spell".to_string()]);
+}
+
+#[test]
+fn parse_report_merges_reports_of_chunks() {
+    let mut report = ParseReport::default();
+    report.record_line("melee_damage", "Peter hits Ragnaros for 42.", true);
+    report.record_unknown_spell_name("Fancy Spell");
+    let mut other = ParseReport::default();
+    other.record_line("melee_damage", "Peter hits Unknown for 42.", false);
+    other.record_failure("unknown_unit");
+    other.record_unknown_spell_name("Fancy Spell");
+
+    report.merge(other);
+    assert_eq!(report.total_lines, 2);
+    assert_eq!(report.recognized_lines, 1);
+    assert_eq!(report.families.get("melee_damage").unwrap().unrecognized, 1);
+    assert_eq!(*report.failures.get("unknown_unit").unwrap(), 1);
+    assert_eq!(report.unmatched_samples.len(), 1);
+    assert_eq!(report.unknown_spell_names, vec!["Fancy Spell".to_string()]);
+}
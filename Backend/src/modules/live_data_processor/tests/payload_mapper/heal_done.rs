@@ -26,6 +26,7 @@ fn map_heal_done_positive() {
     assert_eq!(heal_done.spell_id, 99);
     assert_eq!(heal_done.total_heal, 255);
     assert_eq!(heal_done.effective_heal, 100);
+    assert_eq!(heal_done.overheal, 100);
     assert_eq!(heal_done.absorb, 55);
     assert_eq!(heal_done.hit_mask, 4);
 }
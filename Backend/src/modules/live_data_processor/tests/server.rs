@@ -80,6 +80,7 @@ fn parse_spell_damage() {
                 attacker: Unit { is_player: false, unit_id: caster_unit_id, is_self_damage: false, is_mind_control: false },
                 victim: Unit { is_player: false, unit_id: target_unit_id, is_self_damage: false, is_mind_control: false },
                 spell_id: Some(26),
+                spell_name: None,
                 hit_mask: 1,
                 blocked: 1,
                 damage_components: vec![DamageComponent {
@@ -94,7 +95,7 @@ fn parse_spell_damage() {
     ];
 
     // Act + Assert
-    let parse_result1 = server.parse_events(&mut conn, &armory, &data, messages, member_id);
+    let parse_result1 = server.parse_events(&mut conn, &armory, &data, messages, member_id, 0);
     assert!(parse_result1.is_ok());
     assert_eq!(server.non_committed_events.get(&caster_unit_id).unwrap().len(), 1);
     assert_eq!(server.committed_events.get(&(caster_instance_id, member_id)).unwrap().len(), 2);
@@ -112,7 +113,7 @@ fn parse_spell_damage() {
         }),
     }];
 
-    let parse_result2 = server.parse_events(&mut conn, &armory, &data, messages, member_id);
+    let parse_result2 = server.parse_events(&mut conn, &armory, &data, messages, member_id, 0);
     assert!(parse_result2.is_ok());
     assert_eq!(server.non_committed_events.get(&caster_unit_id).unwrap().len(), 2);
 
@@ -126,14 +127,15 @@ fn parse_spell_damage() {
             target: Some(Unit {
                 is_player: false,
                 unit_id: 0xF140000000000000 + 22,
-                is_self_damage: false
+                is_self_damage: false,
+                is_mind_control: false,
             }),
             spell_id: 22,
             hit_mask: 1,
         }),
     }];
 
-    let parse_result3 = server.parse_events(&mut conn, &armory, &data, messages, member_id);
+    let parse_result3 = server.parse_events(&mut conn, &armory, &data, messages, member_id, 0);
     assert!(parse_result3.is_ok());
 
     let messages = vec![Message {
@@ -142,18 +144,19 @@ fn parse_spell_damage() {
         message_length: 0,
         timestamp: 75,
         message_type: MessageType::SpellCast(SpellCast {
-            caster: Unit { is_player: false, unit_id: target_unit_id },
+            caster: Unit { is_player: false, unit_id: target_unit_id, is_self_damage: false, is_mind_control: false },
             target: Some(Unit {
                 is_player: false,
                 unit_id: 0xF140000000000000 + 22,
-                is_self_damage: false
+                is_self_damage: false,
+                is_mind_control: false,
             }),
             spell_id: 22,
             hit_mask: 1,
         }),
     }];
 
-    let parse_result4 = server.parse_events(&mut conn, &armory, &data, messages, member_id);
+    let parse_result4 = server.parse_events(&mut conn, &armory, &data, messages, member_id, 0);
     assert!(parse_result4.is_ok());
     assert_eq!(server.committed_events.get(&(caster_instance_id, member_id)).unwrap().len(), 5);
     assert!(!server.non_committed_events.contains_key(&caster_unit_id));
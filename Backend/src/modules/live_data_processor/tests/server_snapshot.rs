@@ -0,0 +1,107 @@
+use crate::modules::live_data_processor::domain_value::UnitInstance;
+use crate::modules::live_data_processor::material::{Attempt, Server, ServerSnapshot, SnapshotPolicy, SERVER_SNAPSHOT_VERSION};
+use std::collections::{BTreeSet, HashMap};
+
+fn unit_instance(instance_meta_id: u32, instance_id: u32) -> UnitInstance {
+    UnitInstance {
+        instance_meta_id,
+        entered: 1000,
+        map_id: 409,
+        instance_id,
+        uploaded_user: 7,
+        ready_to_zip: false,
+        upload_id: 0,
+    }
+}
+
+// Instance 42 is still active, instance 43 expired after the snapshot was taken
+fn snapshot(server_id: u32) -> ServerSnapshot {
+    let mut attempts = HashMap::new();
+    let mut attempt = Attempt::new(11, 1000, true);
+    attempt.ranking_damage.insert(5, 1234);
+    attempts.insert(11, attempt);
+
+    ServerSnapshot {
+        version: SERVER_SNAPSHOT_VERSION,
+        server_id,
+        summons: HashMap::new(),
+        active_instances: vec![((42, 7), unit_instance(100, 42)), ((43, 7), unit_instance(101, 43))],
+        last_raid_instance_id: 43,
+        unit_instance_id: vec![(1, 42), (2, 43)].into_iter().collect(),
+        instance_participants: vec![(100, vec![5].into_iter().collect::<BTreeSet<u32>>()), (101, vec![6].into_iter().collect::<BTreeSet<u32>>())].into_iter().collect(),
+        active_attempts: vec![((42, 7), attempts.clone()), ((43, 7), attempts)],
+        completed_attempts: Vec::new(),
+        subject_prepend_mode_set: BTreeSet::new(),
+        post_processing_last_precessed_event_id: HashMap::new(),
+        non_committed_events: HashMap::new(),
+        committed_events: Vec::new(),
+        committed_events_count: vec![((42, 7), 17), ((43, 7), 3)],
+        recently_committed_spell_cast_and_aura_applications: Vec::new(),
+    }
+}
+
+// The state of the database after a restart
+fn server(server_id: u32) -> Server {
+    let mut server = Server::new(server_id, 1);
+    server.active_instances.insert((42, 7), unit_instance(100, 42));
+    server.instance_participants.insert(100, BTreeSet::new());
+    server.committed_events_count.insert((42, 7), 12);
+    server
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    let restored = serde_json::from_slice::<ServerSnapshot>(&serde_json::to_vec(&snapshot(3)).unwrap()).unwrap();
+    assert_eq!(restored.server_id, 3);
+    assert_eq!(restored.active_instances[0].0, (42, 7));
+    assert_eq!(restored.active_attempts[0].1.get(&11).unwrap().ranking_damage.get(&5), Some(&1234));
+    assert_eq!(restored.committed_events_count, vec![((42, 7), 17), ((43, 7), 3)]);
+}
+
+#[test]
+fn test_apply_snapshot_skips_stale_instances() {
+    let mut server = server(3);
+    server.apply_snapshot(snapshot(3));
+
+    assert_eq!(server.active_instances.len(), 1);
+    assert!(server.active_instances.contains_key(&(42, 7)));
+    assert_eq!(server.active_attempts.get(&(42, 7)).unwrap().get(&11).unwrap().ranking_damage.get(&5), Some(&1234));
+    assert!(!server.active_attempts.contains_key(&(43, 7)));
+    assert_eq!(server.committed_events_count.get(&(42, 7)), Some(&17));
+    assert!(!server.committed_events_count.contains_key(&(43, 7)));
+    assert_eq!(server.instance_participants.get(&100).unwrap().len(), 1);
+    assert!(!server.instance_participants.contains_key(&101));
+    assert_eq!(server.unit_instance_id.get(&1), Some(&42));
+    assert!(!server.unit_instance_id.contains_key(&2));
+}
+
+#[test]
+fn test_apply_snapshot_ignores_foreign_snapshots() {
+    let mut server = server(3);
+    server.apply_snapshot(snapshot(4));
+
+    let mut outdated_snapshot = snapshot(3);
+    outdated_snapshot.version = SERVER_SNAPSHOT_VERSION + 1;
+    server.apply_snapshot(outdated_snapshot);
+
+    assert!(server.active_attempts.is_empty());
+    assert!(server.unit_instance_id.is_empty());
+    assert_eq!(server.committed_events_count.get(&(42, 7)), Some(&12));
+    assert_eq!(server.last_raid_instance_id, 0);
+}
+
+#[test]
+fn test_take_snapshot_is_throttled() {
+    let policy = SnapshotPolicy { interval_secs: 60, max_packages: 3 };
+    let mut server = server(3);
+    server.last_snapshot_ts = 1000;
+
+    assert!(server.take_snapshot(&policy, 1010).is_none());
+    assert!(server.take_snapshot(&policy, 1020).is_none());
+    let (snapshot_sequence, snapshot) = server.take_snapshot(&policy, 1030).unwrap();
+    assert_eq!(snapshot_sequence, 1);
+    assert_eq!(snapshot.committed_events_count, vec![((42, 7), 12)]);
+
+    assert!(server.take_snapshot(&policy, 1040).is_none());
+    assert_eq!(server.take_snapshot(&policy, 1090).map(|(snapshot_sequence, _)| snapshot_sequence), Some(2));
+}
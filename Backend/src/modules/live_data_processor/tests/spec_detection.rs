@@ -0,0 +1,182 @@
+use crate::modules::data::domain_value::{SpecRule, SpecRuleSource};
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::material::WoWVanillaParser;
+use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::hashed_unit_id::get_hashed_player_unit_id;
+use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
+
+// Mirrors the rules of the data_spec_rule migration: (source, spell name, hero class, talent tree, role, weight)
+const SPEC_RULES: [(SpecRuleSource, &str, u8, u8, Option<u8>, u32); 32] = [
+    (SpecRuleSource::AuraGain, "Arcane Eclipse", 11, 0, Some(3), 10),
+    (SpecRuleSource::AuraGain, "Nature Eclipse", 11, 0, Some(3), 10),
+    (SpecRuleSource::AuraGain, "Blood Frenzy", 11, 1, None, 5),
+    (SpecRuleSource::AuraGain, "Berserk", 11, 1, None, 5),
+    (SpecRuleSource::AuraGain, "Tidal Surge", 7, 2, Some(2), 10),
+    (SpecRuleSource::AuraGain, "Holy Might", 2, 2, Some(3), 10),
+    (SpecRuleSource::AuraGain, "Tree of Life Form", 11, 2, Some(2), 10),
+    (SpecRuleSource::AuraGain, "Arcane Power", 8, 0, Some(3), 10),
+    (SpecRuleSource::AuraGain, "Combustion", 8, 1, Some(3), 10),
+    (SpecRuleSource::AuraGain, "Ice Barrier", 8, 2, Some(3), 10),
+    (SpecRuleSource::AuraGain, "Seal of Command", 2, 2, Some(3), 5),
+    (SpecRuleSource::AuraGain, "Elemental Mastery", 7, 0, Some(3), 10),
+    (SpecRuleSource::AuraGain, "Stormstrike", 7, 1, Some(3), 10),
+    (SpecRuleSource::AuraGain, "Envenom", 4, 0, Some(3), 10),
+    (SpecRuleSource::AuraGain, "Adrenaline Rush", 4, 1, Some(3), 10),
+    (SpecRuleSource::AuraGain, "Frenzy Effect", 3, 0, Some(3), 5),
+    (SpecRuleSource::Heal, "Holy Shock", 2, 0, Some(2), 10),
+    (SpecRuleSource::Cast, "Mortal Strike", 1, 0, Some(3), 10),
+    (SpecRuleSource::Cast, "Sweeping Strikes", 1, 0, Some(3), 5),
+    (SpecRuleSource::Cast, "Bloodthirst", 1, 1, Some(3), 10),
+    (SpecRuleSource::Cast, "Shield Slam", 1, 2, Some(1), 10),
+    (SpecRuleSource::Cast, "Bulwark of the Righteous", 2, 1, Some(1), 10),
+    (SpecRuleSource::Cast, "Bestial Wrath", 3, 0, Some(3), 10),
+    (SpecRuleSource::Cast, "Piercing Shots", 3, 1, Some(3), 10),
+    (SpecRuleSource::Cast, "Carve", 3, 2, Some(3), 10),
+    (SpecRuleSource::Cast, "Enlighten", 5, 0, Some(2), 10),
+    (SpecRuleSource::Cast, "Proclaim Champion", 5, 1, Some(2), 10),
+    (SpecRuleSource::Cast, "Vampiric Embrace", 5, 2, Some(3), 10),
+    (SpecRuleSource::Cast, "Dark Harvest", 9, 0, Some(3), 10),
+    (SpecRuleSource::Cast, "Power Overwhelming", 9, 1, Some(3), 10),
+    (SpecRuleSource::Cast, "Conflagrate", 9, 2, Some(3), 10),
+    (SpecRuleSource::Cast, "Mark for Death", 4, 2, Some(3), 10),
+];
+
+fn data_with_spec_rules() -> Data {
+    let data = Data::default();
+    for (id, (source, spell_name, hero_class_id, talent_tree, role, weight)) in SPEC_RULES.iter().enumerate() {
+        data.spec_rules.write().unwrap().entry(spell_name.to_string()).or_insert_with(Vec::new).push(SpecRule {
+            id: id as u32 + 1,
+            source: *source,
+            spell_name: spell_name.to_string(),
+            hero_class_id: *hero_class_id,
+            talent_tree: *talent_tree,
+            role: *role,
+            weight: *weight,
+        });
+    }
+    data
+}
+
+fn parser_with_known_spells() -> WoWVanillaParser {
+    let mut parser = WoWVanillaParser::new(1);
+    for (spell_id, (_, spell_name, _, _, _, _)) in SPEC_RULES.iter().enumerate() {
+        parser.cache_spell_id.insert(spell_name.to_string(), Some(spell_id as u32 + 1));
+    }
+    parser.cache_spell_id.insert("Scrambled Brain".to_string(), Some(100));
+    parser
+}
+
+fn spec_line(source: SpecRuleSource, spell_name: &str) -> String {
+    match source {
+        SpecRuleSource::AuraGain => format!("Player gains {} (1).", spell_name),
+        SpecRuleSource::Heal => format!("Player 's {} heals Tank for 1000.", spell_name),
+        SpecRuleSource::Cast => format!("Player casts {}.", spell_name),
+    }
+}
+
+#[test]
+fn detects_spec_of_every_rule() {
+    let data = data_with_spec_rules();
+    let player_id = get_hashed_player_unit_id("Player");
+    for (source, spell_name, hero_class_id, talent_tree, role, _) in SPEC_RULES.iter() {
+        let mut parser = parser_with_known_spells();
+        assert!(parser.parse_cbl_line(&data, 1000, &spec_line(*source, spell_name)).is_ok(), "{}", spell_name);
+
+        let participant = parser.participants.get(&player_id).unwrap();
+        let expected_talents = match talent_tree {
+            0 => "51|0|0",
+            1 => "0|51|0",
+            _ => "0|0|51",
+        };
+        assert_eq!(participant.last_seen_talents, expected_talents, "{}", spell_name);
+        assert_eq!(participant.talents, vec![(1000, Some(expected_talents.to_string()))], "{}", spell_name);
+
+        let spec = participant.get_detected_spec().unwrap();
+        assert_eq!(spec.hero_class_id, *hero_class_id, "{}", spell_name);
+        assert_eq!(spec.talent_tree, *talent_tree, "{}", spell_name);
+        assert_eq!(spec.role, *role, "{}", spell_name);
+        assert_eq!(spec.confidence, 100, "{}", spell_name);
+    }
+}
+
+#[test]
+fn rules_only_match_their_source() {
+    let data = data_with_spec_rules();
+    let mut parser = parser_with_known_spells();
+    let _ = parser.parse_cbl_line(&data, 1000, "Player gains Bloodthirst (1).");
+    let participant = parser.participants.get(&get_hashed_player_unit_id("Player")).unwrap();
+    assert!(participant.spec_evidence.is_empty());
+    assert!(participant.talents.is_empty());
+}
+
+#[test]
+fn weaker_evidence_does_not_override_spec() {
+    let data = data_with_spec_rules();
+    let mut parser = parser_with_known_spells();
+    let _ = parser.parse_cbl_line(&data, 1000, "Player casts Sweeping Strikes.");
+    let _ = parser.parse_cbl_line(&data, 2000, "Player casts Bloodthirst.");
+    // Repeated casts count once
+    let _ = parser.parse_cbl_line(&data, 3000, "Player casts Sweeping Strikes.");
+    let _ = parser.parse_cbl_line(&data, 4000, "Player casts Sweeping Strikes.");
+
+    let participant = parser.participants.get(&get_hashed_player_unit_id("Player")).unwrap();
+    assert_eq!(participant.last_seen_talents, "0|51|0");
+    assert_eq!(participant.talents, vec![(1000, Some("51|0|0".to_string())), (2000, Some("0|51|0".to_string()))]);
+
+    let spec = participant.get_detected_spec().unwrap();
+    assert_eq!(spec.talent_tree, 1);
+    assert_eq!(spec.confidence, 66);
+    let sweeping_strikes = participant.spec_evidence.iter().find(|evidence| evidence.spell_name == "Sweeping Strikes").unwrap();
+    assert_eq!(sweeping_strikes.count, 3);
+    assert_eq!(sweeping_strikes.first_seen, 1000);
+    assert_eq!(sweeping_strikes.last_seen, 4000);
+}
+
+#[test]
+fn ignores_rules_of_other_hero_classes() {
+    let data = data_with_spec_rules();
+    let mut parser = parser_with_known_spells();
+    let _ = parser.parse_cbl_line(&data, 1000, "Player casts Mortal Strike.");
+    parser.participants.get_mut(&get_hashed_player_unit_id("Player")).unwrap().hero_class_id = Some(1);
+    let _ = parser.parse_cbl_line(&data, 2000, "Player gains Berserk (1).");
+
+    let participant = parser.participants.get(&get_hashed_player_unit_id("Player")).unwrap();
+    assert_eq!(participant.spec_evidence.len(), 1);
+    assert_eq!(participant.last_seen_talents, "51|0|0");
+}
+
+#[test]
+fn brainwash_resets_spec_evidence() {
+    let data = data_with_spec_rules();
+    let mut parser = parser_with_known_spells();
+    let _ = parser.parse_cbl_line(&data, 1000, "Player casts Mortal Strike.");
+    let _ = parser.parse_cbl_line(&data, 5000, "Player gains Scrambled Brain (1).");
+    let _ = parser.parse_cbl_line(&data, 9000, "Player casts Sweeping Strikes.");
+    let _ = parser.parse_cbl_line(&data, 10000, "Player casts Shield Slam.");
+
+    let participant = parser.participants.get(&get_hashed_player_unit_id("Player")).unwrap();
+    assert_eq!(participant.last_spec_reset, 5000);
+    assert_eq!(participant.last_seen_talents, "0|0|51");
+    // The new spec is dated back to the brainwash
+    assert_eq!(participant.talents, vec![(1000, Some("51|0|0".to_string())), (4000, Some("51|0|0".to_string())), (5000, Some("0|0|51".to_string()))]);
+
+    let spec = participant.get_detected_spec().unwrap();
+    assert_eq!(spec.role, Some(1));
+    assert_eq!(spec.confidence, 66);
+}
+
+#[test]
+fn merges_spec_evidence_of_forks() {
+    let data = data_with_spec_rules();
+    let mut parser = parser_with_known_spells();
+    let mut fork = parser_with_known_spells();
+    let _ = parser.parse_cbl_line(&data, 1000, "Player casts Mortal Strike.");
+    let _ = fork.parse_cbl_line(&data, 2000, "Player casts Mortal Strike.");
+    let _ = fork.parse_cbl_line(&data, 3000, "Player casts Sweeping Strikes.");
+
+    parser.merge(fork);
+    let participant = parser.participants.get(&get_hashed_player_unit_id("Player")).unwrap();
+    assert_eq!(participant.spec_evidence.len(), 2);
+    let mortal_strike = participant.spec_evidence.iter().find(|evidence| evidence.spell_name == "Mortal Strike").unwrap();
+    assert_eq!((mortal_strike.count, mortal_strike.first_seen, mortal_strike.last_seen), (2, 1000, 2000));
+    assert_eq!(participant.get_detected_spec().unwrap().confidence, 100);
+}
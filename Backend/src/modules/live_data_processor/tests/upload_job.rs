@@ -0,0 +1,136 @@
+use crate::modules::account::material::Account;
+use crate::modules::account::tools::Create;
+use crate::modules::armory::Armory;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::domain_value::UploadJobStatus;
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use crate::modules::live_data_processor::material::connect_retry_delay;
+use crate::modules::live_data_processor::tools::upload_job::{set_upload_progress, UPLOAD_JOB_COMMIT_PROGRESS, UPLOAD_JOB_MAX_ATTEMPTS};
+use crate::modules::live_data_processor::tools::UploadJobQueue;
+use crate::modules::live_data_processor::LiveDataProcessor;
+use crate::params;
+use crate::tests::TestContainer;
+use crate::util::database::{Execute, Select};
+
+fn create_member(account: &Account, conn: &mut crate::mysql::Conn, nickname: &str) -> u32 {
+    account.create(conn, &format!("{}@abc.de", nickname), nickname, "Password123456Password123456Password123456").unwrap().member_id
+}
+
+fn create_upload(conn: &mut crate::mysql::Conn, member_id: u32, hash: &str) -> u32 {
+    assert!(conn.execute_wparams(
+        "INSERT INTO `instance_uploads` (`member_id`, `timestamp`, `hash`) VALUES (:member_id, :ts, :hash)",
+        params!("member_id" => member_id, "ts" => time_util::now(), "hash" => hash.to_string())
+    ));
+    conn.select_wparams_value(
+        "SELECT id FROM `instance_uploads` WHERE `member_id`=:member_id AND `hash`=:hash",
+        |mut row| row.take(0).unwrap(),
+        params!("member_id" => member_id, "hash" => hash.to_string()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn upload_job_status_round_trip() {
+    for status in [UploadJobStatus::Queued, UploadJobStatus::Processing, UploadJobStatus::Finished, UploadJobStatus::Failed, UploadJobStatus::Cancelled].iter() {
+        assert_eq!(UploadJobStatus::new(status.to_u8()), *status);
+    }
+}
+
+#[test]
+fn connect_retry_delay_backs_off() {
+    assert_eq!(connect_retry_delay(0), 1);
+    assert_eq!(connect_retry_delay(1), 2);
+    assert_eq!(connect_retry_delay(5), 32);
+    assert_eq!(connect_retry_delay(6), 60);
+    assert_eq!(connect_retry_delay(u32::MAX), 60);
+}
+
+#[test]
+fn claim_recover_and_cancel_upload_jobs() {
+    let container = TestContainer::new(true);
+    let (mut conn, _dns, _node) = container.run();
+
+    let account = Account::default();
+    let live_data_processor = LiveDataProcessor::default();
+    let member_id = create_member(&account, &mut conn, "uploader");
+    let other_member_id = create_member(&account, &mut conn, "other");
+    let first_upload_id = create_upload(&mut conn, member_id, "first");
+    let second_upload_id = create_upload(&mut conn, member_id, "second");
+
+    assert!(live_data_processor.enqueue_upload_job(&mut conn, first_upload_id, member_id, 3).is_ok());
+    assert!(live_data_processor.enqueue_upload_job(&mut conn, second_upload_id, member_id, 3).is_ok());
+    assert!(live_data_processor.enqueue_upload_job(&mut conn, first_upload_id, member_id, 3).is_err());
+    let job = live_data_processor.get_upload_job(&mut conn, member_id, first_upload_id).unwrap();
+    assert_eq!(job.status, UploadJobStatus::Queued);
+    assert_eq!(job.attempts, 0);
+    assert!(matches!(live_data_processor.get_upload_job(&mut conn, other_member_id, first_upload_id), Err(LiveDataProcessorFailure::UploadNotFound)));
+    assert_eq!(live_data_processor.get_upload_jobs(&mut conn, member_id).len(), 2);
+
+    // Jobs are claimed in the order they were enqueued
+    assert_eq!(live_data_processor.claim_upload_job(&mut conn).unwrap().upload_id, first_upload_id);
+    let job = live_data_processor.get_upload_job(&mut conn, member_id, first_upload_id).unwrap();
+    assert_eq!(job.status, UploadJobStatus::Processing);
+    assert_eq!(job.attempts, 1);
+    assert!(matches!(live_data_processor.retry_upload_job(&mut conn, member_id, first_upload_id), Err(LiveDataProcessorFailure::InvalidUploadJobState)));
+
+    // A restart queues the interrupted job again, until it was claimed too often
+    live_data_processor.recover_upload_jobs(&mut conn);
+    assert_eq!(live_data_processor.get_upload_job(&mut conn, member_id, first_upload_id).unwrap().status, UploadJobStatus::Queued);
+    for attempts in 2..=UPLOAD_JOB_MAX_ATTEMPTS {
+        assert_eq!(live_data_processor.claim_upload_job(&mut conn).unwrap().upload_id, first_upload_id);
+        assert_eq!(live_data_processor.get_upload_job(&mut conn, member_id, first_upload_id).unwrap().attempts, attempts);
+        live_data_processor.recover_upload_jobs(&mut conn);
+    }
+    let job = live_data_processor.get_upload_job(&mut conn, member_id, first_upload_id).unwrap();
+    assert_eq!(job.status, UploadJobStatus::Failed);
+    assert_eq!(job.error, Some("Interrupted by a restart".to_string()));
+
+    // Cancelled and failed jobs can be retried
+    assert!(live_data_processor.retry_upload_job(&mut conn, member_id, first_upload_id).is_ok());
+    let job = live_data_processor.get_upload_job(&mut conn, member_id, first_upload_id).unwrap();
+    assert_eq!(job.status, UploadJobStatus::Queued);
+    assert_eq!(job.attempts, 0);
+    assert_eq!(job.error, None);
+    assert!(live_data_processor.cancel_upload_job(&mut conn, member_id, second_upload_id).is_ok());
+    assert!(matches!(live_data_processor.cancel_upload_job(&mut conn, member_id, second_upload_id), Err(LiveDataProcessorFailure::InvalidUploadJobState)));
+    assert!(matches!(live_data_processor.cancel_upload_job(&mut conn, other_member_id, first_upload_id), Err(LiveDataProcessorFailure::UploadNotFound)));
+    assert_eq!(live_data_processor.claim_upload_job(&mut conn).unwrap().upload_id, first_upload_id);
+    assert!(live_data_processor.claim_upload_job(&mut conn).is_none());
+    assert!(live_data_processor.retry_upload_job(&mut conn, member_id, second_upload_id).is_ok());
+    assert_eq!(live_data_processor.claim_upload_job(&mut conn).unwrap().upload_id, second_upload_id);
+
+    // Once a job is committed it is neither cancelled nor repeated
+    set_upload_progress(&mut conn, second_upload_id, UPLOAD_JOB_COMMIT_PROGRESS);
+    assert!(matches!(live_data_processor.cancel_upload_job(&mut conn, member_id, second_upload_id), Err(LiveDataProcessorFailure::InvalidUploadJobState)));
+    live_data_processor.recover_upload_jobs(&mut conn);
+    assert_eq!(live_data_processor.get_upload_job(&mut conn, member_id, second_upload_id).unwrap().status, UploadJobStatus::Failed);
+    assert!(matches!(live_data_processor.retry_upload_job(&mut conn, member_id, second_upload_id), Err(LiveDataProcessorFailure::InvalidUploadJobState)));
+}
+
+#[test]
+fn failed_upload_jobs_are_retried() {
+    let container = TestContainer::new(true);
+    let (mut conn, _dns, _node) = container.run();
+
+    // The zip of the upload is missing, hence every run fails
+    let storage_path = std::env::temp_dir().join(format!("upload_job_{}", std::process::id()));
+    std::env::set_var("INSTANCE_STORAGE_PATH", storage_path.to_str().unwrap());
+
+    let account = Account::default();
+    let data = Data::default();
+    let armory = Armory::default();
+    let live_data_processor = LiveDataProcessor::default();
+    let member_id = create_member(&account, &mut conn, "uploader");
+    let upload_id = create_upload(&mut conn, member_id, "missing");
+    live_data_processor.enqueue_upload_job(&mut conn, upload_id, member_id, 3).unwrap();
+
+    for attempts in 1..=UPLOAD_JOB_MAX_ATTEMPTS {
+        let job = live_data_processor.claim_upload_job(&mut conn).unwrap();
+        live_data_processor.run_upload_job(&mut conn, &data, &armory, job);
+        let job = live_data_processor.get_upload_job(&mut conn, member_id, upload_id).unwrap();
+        assert_eq!(job.attempts, attempts);
+        assert_eq!(job.error, Some(format!("{:?}", LiveDataProcessorFailure::UploadNotFound)));
+        assert_eq!(job.status, if attempts < UPLOAD_JOB_MAX_ATTEMPTS { UploadJobStatus::Queued } else { UploadJobStatus::Failed });
+    }
+    assert!(live_data_processor.claim_upload_job(&mut conn).is_none());
+}
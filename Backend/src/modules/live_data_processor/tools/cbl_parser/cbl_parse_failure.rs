@@ -0,0 +1,34 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum CblParseFailure {
+    UnrecognizedLine,
+    MissingCapture(usize),
+    MissingArgument(usize),
+    InvalidNumber,
+    InvalidTimestamp,
+    UnknownUnit(String),
+    UnknownSpell(String),
+    UnknownNpc(String),
+    UnknownKeyword(String),
+    MalformedTrailer(String),
+    MalformedCombatantInfo(usize),
+    MalformedPet(usize),
+}
+
+impl CblParseFailure {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CblParseFailure::UnrecognizedLine => "unrecognized_line",
+            CblParseFailure::MissingCapture(_) => "missing_capture",
+            CblParseFailure::MissingArgument(_) => "missing_argument",
+            CblParseFailure::InvalidNumber => "invalid_number",
+            CblParseFailure::InvalidTimestamp => "invalid_timestamp",
+            CblParseFailure::UnknownUnit(_) => "unknown_unit",
+            CblParseFailure::UnknownSpell(_) => "unknown_spell",
+            CblParseFailure::UnknownNpc(_) => "unknown_npc",
+            CblParseFailure::UnknownKeyword(_) => "unknown_keyword",
+            CblParseFailure::MalformedTrailer(_) => "malformed_trailer",
+            CblParseFailure::MalformedCombatantInfo(_) => "malformed_combatant_info",
+            CblParseFailure::MalformedPet(_) => "malformed_pet",
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::modules::live_data_processor::tools::cbl_parser::wow_cleu::parse_args::split_cleu_args;
+
+// Lines that are inspected to determine the format of a log
+const SNIFF_LINES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CombatLogFormat {
+    Vanilla,
+    TBC,
+    WotLK,
+}
+
+impl CombatLogFormat {
+    pub fn from_expansion_id(expansion_id: u8) -> Option<Self> {
+        match expansion_id {
+            1 => Some(CombatLogFormat::Vanilla),
+            2 => Some(CombatLogFormat::TBC),
+            3 => Some(CombatLogFormat::WotLK),
+            _ => None,
+        }
+    }
+}
+
+/// Determines the format from the first lines of the log. The TBC and WotLK formats are told apart by the number
+/// of arguments of events whose suffix was extended in WotLK. Returns None if the lines are inconclusive.
+pub fn sniff_combat_log_format(content: &str) -> Option<CombatLogFormat> {
+    for line in content.lines().take(SNIFF_LINES) {
+        let event = match line.split("  ").nth(1) {
+            Some(event) => event.trim_end_matches('\r'),
+            None => continue,
+        };
+        let event_type = match get_cleu_event_type(event) {
+            Some(event_type) => event_type,
+            None => return Some(CombatLogFormat::Vanilla),
+        };
+        match (event_type, split_cleu_args(event).len()) {
+            ("SWING_DAMAGE", 15) | ("SPELL_HEAL", 12) | ("SPELL_PERIODIC_HEAL", 12) => return Some(CombatLogFormat::TBC),
+            ("SWING_DAMAGE", 16) | ("SPELL_HEAL", 14) | ("SPELL_PERIODIC_HEAL", 14) => return Some(CombatLogFormat::WotLK),
+            _ => {},
+        }
+    }
+    None
+}
+
+/// The format of the file takes precedence, as it might not match the expansion of the chosen server.
+/// If it is inconclusive, the expansion of the server decides.
+pub fn select_combat_log_format(expansion_id: Option<u8>, content: &str) -> CombatLogFormat {
+    if let Some(format) = sniff_combat_log_format(content) {
+        return format;
+    }
+    let is_cleu = content.lines().take(SNIFF_LINES).filter_map(|line| line.split("  ").nth(1)).any(|event| get_cleu_event_type(event).is_some());
+    match expansion_id.and_then(CombatLogFormat::from_expansion_id) {
+        Some(CombatLogFormat::Vanilla) | None if is_cleu => CombatLogFormat::TBC,
+        Some(format) => format,
+        None => CombatLogFormat::Vanilla,
+    }
+}
+
+// CLEU lines start with the upper case event type, followed by comma separated arguments
+fn get_cleu_event_type(event: &str) -> Option<&str> {
+    let event_type = &event[..event.find(',')?];
+    if event_type.is_empty() || !event_type.chars().all(|character| character.is_ascii_uppercase() || character == '_') {
+        return None;
+    }
+    Some(event_type)
+}
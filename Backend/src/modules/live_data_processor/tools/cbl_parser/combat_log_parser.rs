@@ -1,10 +1,11 @@
 use crate::modules::armory::dto::CharacterDto;
 use crate::modules::data::Data;
-use crate::modules::live_data_processor::dto::{Message, MessageType};
+use crate::modules::live_data_processor::dto::{Message, MessageType, ParseReport};
 use crate::modules::live_data_processor::material::{ActiveMapVec, Participant};
+use crate::modules::live_data_processor::tools::cbl_parser::CblParseFailure;
 
 pub trait CombatLogParser {
-    fn parse_cbl_line(&mut self, data: &Data, event_ts: u64, content: &str) -> Option<Vec<MessageType>>;
+    fn parse_cbl_line(&mut self, data: &Data, event_ts: u64, content: &str) -> Result<Vec<MessageType>, CblParseFailure>;
     fn do_message_post_processing(&mut self, data: &Data, messages: &mut Vec<Message>);
     // Server that need to be created have Id=0!
     fn get_involved_server(&self) -> Option<Vec<(u32, String, String)>>;
@@ -23,4 +24,17 @@ pub trait CombatLogParser {
     fn get_bonus_messages(&self) -> Option<Vec<Message>>;
     fn get_npc_in_combat_offset(&self, entry: u32) -> Option<i64>;
     fn get_ability_caster(&self, ability_id: u32) -> Option<u32>;
+    fn get_parse_report(&mut self) -> &mut ParseReport;
+    // Parsers of logs that are written in the language of the client detect it from the lines before they are parsed.
+    // Returns the id of the language in data_language.
+    fn detect_language(&mut self, lines: &[(u64, &str)]) -> Option<u8>;
+    // Parsers of logs that don't report the overheal estimate it from the health the target is missing.
+    // The estimate is attributed again once the maximum health of all units is known.
+    fn estimates_overheal(&self) -> bool;
+    // Parsers whose lines don't depend on the state of preceding lines return an empty parser,
+    // that parses a chunk of the log in parallel and is merged back in order afterwards.
+    fn fork(&self) -> Option<Self>
+    where Self: Sized;
+    fn merge(&mut self, other: Self)
+    where Self: Sized;
 }
@@ -1,4 +1,11 @@
+pub use self::cbl_parse_failure::CblParseFailure;
+pub use self::combat_log_format::{select_combat_log_format, sniff_combat_log_format, CombatLogFormat};
 pub use self::combat_log_parser::CombatLogParser;
 
+pub mod wow_cleu;
+pub mod wow_tbc;
 pub mod wow_vanilla;
+pub mod wow_wotlk;
+mod cbl_parse_failure;
+mod combat_log_format;
 mod combat_log_parser;
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use crate::modules::armory::domain_value::GuildRank;
+use crate::modules::armory::dto::{CharacterDto, CharacterGearDto, CharacterGuildDto, CharacterHistoryDto, CharacterInfoDto, CharacterItemDto, GuildDto};
+use crate::modules::live_data_processor::material::Participant;
+use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::hashed_unit_id::get_hashed_player_unit_id;
+
+/// Character builds of all players whose class is known, i.e. that were described by COMBATANT_INFO.
+/// Talents are emitted at first and last seen, such that the armory history covers the whole log.
+pub fn get_cleu_character_builds(participants: &HashMap<u64, Participant>, level: u8) -> Vec<(Option<u32>, u64, CharacterDto)> {
+    let mut result = Vec::new();
+    for participant in participants.values().filter(|participant| participant.is_player && participant.hero_class_id.is_some()) {
+        // Only the first gear setup is saved
+        let gear = participant
+            .gear_setups
+            .as_ref()
+            .and_then(|gear_setups| gear_setups.first())
+            .map(|(_, gear_setup)| create_character_gear_dto(gear_setup))
+            .unwrap_or_else(empty_character_gear_dto);
+
+        let mut talents = participant.talents.clone();
+        if talents.is_empty() {
+            talents.push((participant.first_seen, None));
+        } else {
+            talents.insert(0, (participant.first_seen, talents[0].1.clone()));
+            talents.push((participant.last_seen, talents.last().unwrap().1.clone()));
+        }
+
+        for (timestamp, talent_specialization) in talents {
+            result.push((
+                None,
+                timestamp,
+                CharacterDto {
+                    server_uid: participant.id,
+                    character_history: Some(CharacterHistoryDto {
+                        character_info: CharacterInfoDto {
+                            gear: gear.clone(),
+                            hero_class_id: participant.hero_class_id.unwrap(),
+                            level,
+                            gender: participant.gender_id.unwrap_or(false),
+                            profession1: None,
+                            profession2: None,
+                            talent_specialization,
+                            race_id: participant.race_id.unwrap_or(1),
+                        },
+                        character_name: participant.name.clone(),
+                        character_guild: participant.guild_args.as_ref().map(|(guild_name, rank_name, rank_index)| CharacterGuildDto {
+                            guild: GuildDto {
+                                server_uid: get_hashed_player_unit_id(guild_name),
+                                name: guild_name.clone(),
+                            },
+                            rank: GuildRank { index: *rank_index, name: rank_name.clone() },
+                        }),
+                        character_title: None,
+                        profession_skill_points1: None,
+                        profession_skill_points2: None,
+                        facial: None,
+                        arena_teams: vec![],
+                    }),
+                },
+            ));
+        }
+    }
+    result.push((None, time_util::now() * 1000, CharacterDto { server_uid: 0, character_history: None }));
+    result
+}
+
+fn empty_character_gear_dto() -> CharacterGearDto {
+    create_character_gear_dto(&[])
+}
+
+// Slots are ordered by inventory slot id, starting with the head
+fn create_character_gear_dto(gear_setup: &[Option<(u32, Option<u32>, Option<Vec<Option<u32>>>)>]) -> CharacterGearDto {
+    let item = |slot: usize| gear_setup.get(slot).and_then(create_character_item_dto);
+    CharacterGearDto {
+        head: item(0),
+        neck: item(1),
+        shoulder: item(2),
+        back: item(14),
+        chest: item(4),
+        shirt: item(3),
+        tabard: item(18),
+        wrist: item(8),
+        main_hand: item(15),
+        off_hand: item(16),
+        ternary_hand: item(17),
+        glove: item(9),
+        belt: item(5),
+        leg: item(6),
+        boot: item(7),
+        ring1: item(10),
+        ring2: item(11),
+        trinket1: item(12),
+        trinket2: item(13),
+    }
+}
+
+fn create_character_item_dto(item: &Option<(u32, Option<u32>, Option<Vec<Option<u32>>>)>) -> Option<CharacterItemDto> {
+    item.as_ref().map(|(item_id, enchant_id, gem_ids)| CharacterItemDto {
+        item_id: *item_id,
+        random_property_id: None,
+        enchant_id: *enchant_id,
+        gem_ids: gem_ids.clone().unwrap_or_default(),
+    })
+}
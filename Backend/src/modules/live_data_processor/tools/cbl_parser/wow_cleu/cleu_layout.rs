@@ -0,0 +1,25 @@
+// Differences of the event arguments between the client versions
+#[derive(Debug, Clone)]
+pub struct CleuLayout {
+    // WotLK inserts the overkill after the amount
+    pub damage_has_overkill: bool,
+    // WotLK inserts the overhealing and absorbed amount after the amount
+    pub heal_has_overheal_and_absorb: bool,
+    // The WotLK addon reports the difficulty of the instance
+    pub zone_info_has_difficulty: bool,
+    pub max_level: u8,
+}
+
+pub const TBC_CLEU_LAYOUT: CleuLayout = CleuLayout {
+    damage_has_overkill: false,
+    heal_has_overheal_and_absorb: false,
+    zone_info_has_difficulty: false,
+    max_level: 70,
+};
+
+pub const WOTLK_CLEU_LAYOUT: CleuLayout = CleuLayout {
+    damage_has_overkill: true,
+    heal_has_overheal_and_absorb: true,
+    zone_info_has_difficulty: true,
+    max_level: 80,
+};
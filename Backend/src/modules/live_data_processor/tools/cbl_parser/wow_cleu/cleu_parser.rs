@@ -0,0 +1,109 @@
+use crate::modules::armory::dto::CharacterDto;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::dto::{Message, MessageType, ParseReport};
+use crate::modules::live_data_processor::material::{ActiveMapVec, CleuParserState, Participant};
+use crate::modules::live_data_processor::tools::cbl_parser::wow_cleu::{do_cleu_message_post_processing, get_cleu_character_builds, parse_cleu_line, CleuLayout};
+use crate::modules::live_data_processor::tools::cbl_parser::{CblParseFailure, CombatLogParser};
+
+/// The parsers of the client versions that write the CLEU format only differ by the layout of the event arguments,
+/// hence they share the implementation of the combat log parser.
+pub trait CleuParser {
+    fn layout(&self) -> &'static CleuLayout;
+    fn server_id(&self) -> u32;
+    fn state(&self) -> &CleuParserState;
+    fn state_mut(&mut self) -> &mut CleuParserState;
+}
+
+impl<P: CleuParser> CombatLogParser for P {
+    fn parse_cbl_line(&mut self, data: &Data, event_ts: u64, content: &str) -> Result<Vec<MessageType>, CblParseFailure> {
+        let layout = self.layout();
+        let state = self.state_mut();
+        state.line_family = "unmatched";
+        let message_types = parse_cleu_line(state, layout, data, event_ts, content);
+        if let Err(failure) = &message_types {
+            state.parse_report.record_failure(failure.kind());
+        }
+        state.parse_report.record_line(state.line_family, content, message_types.is_ok());
+        message_types
+    }
+
+    fn do_message_post_processing(&mut self, _data: &Data, messages: &mut Vec<Message>) {
+        do_cleu_message_post_processing(self.state(), messages);
+    }
+
+    fn get_involved_server(&self) -> Option<Vec<(u32, String, String)>> {
+        None
+    }
+
+    fn get_involved_character_builds(&self) -> Vec<(Option<u32>, u64, CharacterDto)> {
+        get_cleu_character_builds(&self.state().participants, self.layout().max_level)
+    }
+
+    fn get_participants(&self) -> Vec<Participant> {
+        self.state().participants.values().cloned().collect()
+    }
+
+    fn get_active_maps(&self) -> ActiveMapVec {
+        self.state().active_map.values().cloned().collect()
+    }
+
+    fn get_npc_appearance_offset(&self, _entry: u32) -> Option<i64> {
+        None
+    }
+
+    fn get_npc_timeout(&self, _entry: u32) -> Option<u64> {
+        None
+    }
+
+    fn get_death_implied_npc_combat_state_and_offset(&self, _entry: u32) -> Option<Vec<(u32, i64, i64)>> {
+        None
+    }
+
+    fn get_in_combat_implied_npc_combat(&self, _entry: u32) -> Option<Vec<u32>> {
+        None
+    }
+
+    fn get_ignore_after_death_ignore_abilities(&self, _entry: u32) -> Option<Vec<u32>> {
+        None
+    }
+
+    fn get_expansion_id(&self) -> u8 {
+        self.state().expansion_id
+    }
+
+    fn get_server_id(&self) -> Option<u32> {
+        Some(self.server_id())
+    }
+
+    fn get_bonus_messages(&self) -> Option<Vec<Message>> {
+        Some(self.state().bonus_messages.clone())
+    }
+
+    fn get_npc_in_combat_offset(&self, _entry: u32) -> Option<i64> {
+        None
+    }
+
+    fn get_ability_caster(&self, _ability_id: u32) -> Option<u32> {
+        None
+    }
+
+    fn get_parse_report(&mut self) -> &mut ParseReport {
+        &mut self.state_mut().parse_report
+    }
+
+    // Events are logged by ids, independent of the language of the client
+    fn detect_language(&mut self, _lines: &[(u64, &str)]) -> Option<u8> {
+        None
+    }
+
+    fn estimates_overheal(&self) -> bool {
+        !self.layout().heal_has_overheal_and_absorb
+    }
+
+    // Casts and auras are linked across lines, hence the log is parsed sequentially
+    fn fork(&self) -> Option<Self> {
+        None
+    }
+
+    fn merge(&mut self, _other: Self) {}
+}
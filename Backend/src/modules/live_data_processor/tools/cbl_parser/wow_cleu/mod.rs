@@ -0,0 +1,14 @@
+pub use self::cleu_layout::{CleuLayout, TBC_CLEU_LAYOUT, WOTLK_CLEU_LAYOUT};
+pub use self::cleu_parser::CleuParser;
+pub use self::character_builds::get_cleu_character_builds;
+pub use self::parse_line::parse_cleu_line;
+pub use self::post_processing::do_cleu_message_post_processing;
+
+mod character_builds;
+mod cleu_layout;
+mod cleu_parser;
+mod parse_addon_message;
+pub(crate) mod parse_args;
+pub(crate) mod parse_unit;
+mod parse_line;
+mod post_processing;
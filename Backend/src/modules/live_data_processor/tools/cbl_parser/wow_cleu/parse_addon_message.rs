@@ -0,0 +1,218 @@
+use chrono::NaiveDateTime;
+use regex::Regex;
+
+use crate::modules::armory::tools::strip_talent_specialization;
+use crate::modules::data::tools::RetrieveMap;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::dto::{InstanceMap, Loot, Message, MessageType, Unit};
+use crate::modules::live_data_processor::material::{CleuParserState, Participant};
+use crate::modules::live_data_processor::tools::cbl_parser::wow_cleu::parse_unit::{parse_guid, parse_unit};
+use crate::modules::live_data_processor::tools::cbl_parser::wow_cleu::CleuLayout;
+use crate::modules::live_data_processor::tools::cbl_parser::CblParseFailure;
+
+/*
+ * The addon transports its messages in the failure reason of SPELL_CAST_FAILED:
+ * CONSOLIDATED: <PREFIX>: dd.mm.yy HH:MM:SS&<payload>{<PREFIX>: ...
+ * COMBATANT_INFO: last_updated&guid&name&race&class&gender&guild&rank_name&rank_index&gear&talents&arena2&arena3&arena5
+ */
+pub fn parse_addon_message(state: &mut CleuParserState, layout: &CleuLayout, data: &Data, event_ts: u64, content: &str) -> Result<Vec<MessageType>, CblParseFailure> {
+    if content.starts_with("COMBATANT_INFO: ") {
+        state.line_family = "combatant_info";
+        parse_combatant_info(state, event_ts, content.trim_start_matches("COMBATANT_INFO: "))?;
+        return Ok(Vec::new());
+    }
+
+    for message in content.trim_start_matches("CONSOLIDATED: ").split('{') {
+        let (prefix, message_args) = match message.find(": ") {
+            Some(index) => (&message[..index], message[index + 2..].split('&').collect::<Vec<&str>>()),
+            None => return Err(CblParseFailure::UnrecognizedLine),
+        };
+        let timestamp = parse_addon_timestamp(message_args[0])?;
+        match prefix {
+            "LOOT" => {
+                state.line_family = "loot";
+                parse_loot(state, timestamp, &message_args[1..].join("&"))?;
+            },
+            "ZONE_INFO" => {
+                state.line_family = "zone_info";
+                parse_zone_info(state, layout, data, timestamp, &message_args[1..])?;
+            },
+            "NONE_ZONE_INFO" => state.line_family = "zone_info",
+            "PET_SUMMON" => {
+                state.line_family = "pet";
+                if message_args.len() < 3 {
+                    return Err(CblParseFailure::MalformedPet(message_args.len()));
+                }
+                if let (Some(owner), Some(pet)) = (parse_unit(message_args[1])?, parse_unit(message_args[2])?) {
+                    state.pet_owner.insert(pet.unit_id, owner.unit_id);
+                }
+            },
+            unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
+        }
+    }
+    Ok(Vec::new())
+}
+
+fn parse_addon_timestamp(timestamp: &str) -> Result<u64, CblParseFailure> {
+    NaiveDateTime::parse_from_str(timestamp, "%d.%m.%y %H:%M:%S")
+        .map(|timestamp| timestamp.timestamp_millis() as u64)
+        .map_err(|_| CblParseFailure::InvalidTimestamp)
+}
+
+fn parse_loot(state: &mut CleuParserState, timestamp: u64, content: &str) -> Result<(), CblParseFailure> {
+    lazy_static! {
+        static ref RE_LOOT: Regex = Regex::new(r"^(.+[^\s]) receives loot: \|c[a-fA-F0-9]+\|Hitem:(\d+)[^|]*\|h\[[^\]]+\]\|h\|rx(\d+)\.").unwrap();
+    }
+    let captures = RE_LOOT.captures(content).ok_or(CblParseFailure::UnrecognizedLine)?;
+    let receiver_name = captures.get(1).ok_or(CblParseFailure::MissingCapture(1))?.as_str();
+    // Receivers are only known by name, players that were not seen in the log yet cannot be attributed
+    let unit = match state.unit_by_name.get(receiver_name) {
+        Some(unit) => unit.clone(),
+        None => return Err(CblParseFailure::UnknownUnit(receiver_name.to_string())),
+    };
+    let item_id = captures.get(2).ok_or(CblParseFailure::MissingCapture(2))?.as_str().parse::<u32>().map_err(|_| CblParseFailure::InvalidNumber)?;
+    let count = captures.get(3).ok_or(CblParseFailure::MissingCapture(3))?.as_str().parse::<u32>().map_err(|_| CblParseFailure::InvalidNumber)?;
+    state.bonus_messages.push(Message::new_parsed(timestamp, 0, MessageType::Loot(Loot { unit, item_id, count })));
+    Ok(())
+}
+
+// TBC: name&instance_id&guids..., WotLK: name&type&difficultyIndex&difficultyName&maxPlayers&playerDifficulty&mapAreaID&instance_id&guids...
+fn parse_zone_info(state: &mut CleuParserState, layout: &CleuLayout, data: &Data, timestamp: u64, message_args: &[&str]) -> Result<(), CblParseFailure> {
+    let instance_id_index = if layout.zone_info_has_difficulty { 7 } else { 1 };
+    if message_args.len() <= instance_id_index {
+        return Err(CblParseFailure::MissingArgument(instance_id_index));
+    }
+    let instance_id = if message_args[instance_id_index] == "nil" {
+        0
+    } else {
+        message_args[instance_id_index].parse::<u32>().map_err(|_| CblParseFailure::InvalidNumber)?
+    };
+    // The difficulty index of raids starts with 10 player normal, which is difficulty 3
+    let map_difficulty = if layout.zone_info_has_difficulty {
+        let difficulty_index = message_args[2].parse::<u8>().map_err(|_| CblParseFailure::InvalidNumber)?;
+        if message_args[1] == "raid" {
+            difficulty_index.saturating_add(2)
+        } else {
+            difficulty_index
+        }
+    } else {
+        0
+    };
+
+    if let Some(map) = data.get_map_by_name(&message_args[0].to_string()) {
+        state.bonus_messages.push(Message::new_parsed(
+            timestamp,
+            0,
+            MessageType::InstanceMap(InstanceMap {
+                map_id: map.id as u32,
+                instance_id,
+                map_difficulty,
+                unit: Unit {
+                    is_player: false,
+                    unit_id: 1,
+                    is_self_damage: false,
+                    is_mind_control: false,
+                },
+            }),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_combatant_info(state: &mut CleuParserState, event_ts: u64, content: &str) -> Result<(), CblParseFailure> {
+    let message_args = content.split('&').collect::<Vec<&str>>();
+    if message_args.len() < 11 {
+        return Err(CblParseFailure::MalformedCombatantInfo(message_args.len()));
+    }
+
+    let timestamp = parse_addon_timestamp(message_args[0])?;
+    let unit_id = parse_guid(message_args[1])?;
+    let player_name = message_args[2];
+    let participant = state.participants.entry(unit_id).or_insert_with(|| Participant::new(unit_id, true, false, false, player_name.to_string(), event_ts));
+    state.unit_by_name.insert(
+        player_name.to_string(),
+        Unit {
+            is_player: true,
+            unit_id,
+            is_self_damage: false,
+            is_mind_control: false,
+        },
+    );
+
+    if participant.race_id.is_none() && message_args[3] != "nil" {
+        participant.race_id = Some(match message_args[3].to_lowercase().as_str() {
+            "human" => 1,
+            "orc" => 2,
+            "dwarf" => 3,
+            "nightelf" => 4,
+            "scourge" => 5,
+            "tauren" => 6,
+            "gnome" => 7,
+            "troll" => 8,
+            "bloodelf" => 10,
+            "draenei" => 11,
+            unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
+        });
+    }
+
+    if participant.hero_class_id.is_none() && message_args[4] != "nil" {
+        participant.hero_class_id = Some(match message_args[4].to_lowercase().as_str() {
+            "warrior" => 1,
+            "paladin" => 2,
+            "hunter" => 3,
+            "rogue" => 4,
+            "priest" => 5,
+            "deathknight" => 6,
+            "shaman" => 7,
+            "mage" => 8,
+            "warlock" => 9,
+            "druid" => 11,
+            unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
+        });
+    }
+
+    if participant.gender_id.is_none() {
+        if message_args[5] == "2" {
+            participant.gender_id = Some(false);
+        } else if message_args[5] == "3" {
+            participant.gender_id = Some(true);
+        }
+    }
+
+    if participant.guild_args.is_none() && message_args[6] != "nil" && message_args[7] != "nil" {
+        let guild_rank_index = message_args[8].parse::<u8>().map_err(|_| CblParseFailure::InvalidNumber)?;
+        participant.guild_args = Some((message_args[6].to_string(), message_args[7].to_string(), guild_rank_index));
+    }
+
+    if message_args[9] != "nil" {
+        let mut gear = Vec::with_capacity(19);
+        for item in message_args[9].split('}').take(19) {
+            gear.push(parse_gear_item(item)?);
+        }
+        gear.resize(19, None);
+        if gear.iter().any(|item| item.is_some()) {
+            participant.gear_setups.get_or_insert_with(Vec::new).push((timestamp, gear));
+        }
+    }
+
+    let talents = message_args[10];
+    if talents != "nil" && talents.contains('}') && talents.chars().all(|character| character.is_ascii_digit() || character == '}') {
+        participant.talents.push((event_ts, strip_talent_specialization(&Some(talents.replace("}", "|")))));
+    }
+    Ok(())
+}
+
+// itemId:enchantId:gem1:gem2:gem3:gem4:suffixId:uniqueId
+fn parse_gear_item(item: &str) -> Result<Option<(u32, Option<u32>, Option<Vec<Option<u32>>>)>, CblParseFailure> {
+    if item == "nil" {
+        return Ok(None);
+    }
+    let item_args = item.split(':').map(|arg| if arg.is_empty() { Ok(0) } else { arg.parse::<u32>() }).collect::<Result<Vec<u32>, _>>().map_err(|_| CblParseFailure::InvalidNumber)?;
+    let item_id = match item_args.first() {
+        Some(item_id) if *item_id > 0 => *item_id,
+        _ => return Ok(None),
+    };
+    let enchant_id = item_args.get(1).copied().filter(|enchant_id| *enchant_id > 0);
+    let gem_ids = item_args.iter().skip(2).take(4).map(|gem_id| Some(*gem_id).filter(|gem_id| *gem_id > 0)).collect::<Vec<Option<u32>>>();
+    Ok(Some((item_id, enchant_id, if gem_ids.iter().any(|gem_id| gem_id.is_some()) { Some(gem_ids) } else { None })))
+}
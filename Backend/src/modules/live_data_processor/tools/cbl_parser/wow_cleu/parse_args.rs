@@ -0,0 +1,48 @@
+use crate::modules::live_data_processor::tools::cbl_parser::CblParseFailure;
+
+/// Splits the comma separated event arguments, names are quoted and may contain commas.
+pub fn split_cleu_args(content: &str) -> Vec<&str> {
+    let mut args = Vec::with_capacity(16);
+    let mut is_quoted = false;
+    let mut arg_start = 0;
+    for (index, character) in content.char_indices() {
+        match character {
+            '"' => is_quoted = !is_quoted,
+            ',' if !is_quoted => {
+                args.push(trim_quotes(&content[arg_start..index]));
+                arg_start = index + 1;
+            },
+            _ => {},
+        }
+    }
+    args.push(trim_quotes(&content[arg_start..]));
+    args
+}
+
+pub fn get_arg<'a>(args: &[&'a str], index: usize) -> Result<&'a str, CblParseFailure> {
+    args.get(index).copied().ok_or(CblParseFailure::MissingArgument(index))
+}
+
+/// Numbers are either decimal or hexadecimal, absent values are logged as nil.
+pub fn parse_number(arg: &str) -> Result<i64, CblParseFailure> {
+    if arg == "nil" {
+        return Ok(0);
+    }
+    if let Some(hex) = arg.strip_prefix("0x") {
+        return i64::from_str_radix(hex, 16).map_err(|_| CblParseFailure::InvalidNumber);
+    }
+    arg.parse::<i64>().map_err(|_| CblParseFailure::InvalidNumber)
+}
+
+pub fn parse_amount(args: &[&str], index: usize) -> Result<u32, CblParseFailure> {
+    parse_number(get_arg(args, index)?).map(|number| number.max(0).min(u32::MAX as i64) as u32)
+}
+
+pub fn parse_flag(args: &[&str], index: usize) -> bool {
+    args.get(index).map_or(false, |arg| *arg == "1")
+}
+
+fn trim_quotes(arg: &str) -> &str {
+    let arg = arg.trim();
+    arg.strip_prefix('"').and_then(|arg| arg.strip_suffix('"')).unwrap_or(arg)
+}
@@ -0,0 +1,364 @@
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::domain_value::HitType;
+use crate::modules::live_data_processor::dto::{AuraApplication, DamageComponent, DamageDone, Death, HealDone, Interrupt, MessageType, SpellCast, Summon, UnAura, Unit};
+use crate::modules::live_data_processor::material::CleuParserState;
+use crate::modules::live_data_processor::tools::cbl_parser::wow_cleu::parse_addon_message::parse_addon_message;
+use crate::modules::live_data_processor::tools::cbl_parser::wow_cleu::parse_args::{get_arg, parse_amount, parse_flag, split_cleu_args};
+use crate::modules::live_data_processor::tools::cbl_parser::wow_cleu::parse_unit::parse_unit;
+use crate::modules::live_data_processor::tools::cbl_parser::wow_cleu::CleuLayout;
+use crate::modules::live_data_processor::tools::cbl_parser::CblParseFailure;
+
+// Hits within this window after a SPELL_CAST_SUCCESS of the same spell are caused by that cast, e.g. due to travel time
+const SPELL_CAST_LINK_WINDOW: u64 = 5000;
+
+struct CleuDamage {
+    amount: u32,
+    school_mask: u8,
+    resisted: u32,
+    blocked: u32,
+    absorbed: u32,
+    hit_mask: u32,
+}
+
+/*
+ * Line: EVENT,sourceGUID,sourceName,sourceFlags,destGUID,destName,destFlags,<prefix args>,<suffix args>
+ * Prefix args: SWING_ => none, RANGE_/SPELL_/SPELL_PERIODIC_ => spellId,spellName,spellSchool
+ */
+pub fn parse_cleu_line(state: &mut CleuParserState, layout: &CleuLayout, data: &Data, event_ts: u64, content: &str) -> Result<Vec<MessageType>, CblParseFailure> {
+    let args = split_cleu_args(content);
+    let event = args[0];
+    if event.is_empty() || !event.chars().all(|character| character.is_ascii_uppercase() || character == '_') {
+        return Err(CblParseFailure::UnrecognizedLine);
+    }
+    if args.len() < 7 {
+        return Err(CblParseFailure::MissingArgument(args.len()));
+    }
+
+    let source = parse_unit(args[1])?;
+    let target = parse_unit(args[4])?;
+    if let Some(source) = &source {
+        state.collect_participant(source, args[2], event_ts);
+        state.collect_active_map(data, source, event_ts);
+    }
+    if let Some(target) = &target {
+        state.collect_participant(target, args[5], event_ts);
+        state.collect_active_map(data, target, event_ts);
+    }
+    let params = &args[7..];
+
+    match event {
+        "SWING_DAMAGE" => {
+            state.line_family = "melee_damage";
+            let (attacker, victim) = (require_unit(source)?, require_unit(target)?);
+            let damage = parse_damage(layout, params, 0)?;
+            attribute_damage(state, &victim, damage.amount);
+            Ok(vec![MessageType::MeleeDamage(create_damage_done(attacker, victim, None, None, damage, false))])
+        },
+        "SWING_MISSED" => {
+            state.line_family = "melee_damage";
+            let (attacker, victim) = (require_unit(source)?, require_unit(target)?);
+            Ok(vec![MessageType::MeleeDamage(create_damage_miss(attacker, victim, None, None, parse_miss_type(get_arg(params, 0)?)?))])
+        },
+        "RANGE_DAMAGE" | "SPELL_DAMAGE" | "SPELL_PERIODIC_DAMAGE" | "DAMAGE_SHIELD" | "DAMAGE_SPLIT" => {
+            state.line_family = "spell_damage";
+            let (attacker, victim) = (require_unit(source)?, require_unit(target)?);
+            let (spell_id, spell_name) = parse_spell(params)?;
+            let damage = parse_damage(layout, params, 3)?;
+            attribute_damage(state, &victim, damage.amount);
+
+            let is_periodic = event == "SPELL_PERIODIC_DAMAGE";
+            let mut message_types = if is_periodic {
+                link_aura_application(state, &attacker, &victim, spell_id)
+            } else {
+                link_spell_cast(state, &attacker, &victim, spell_id, damage.hit_mask, event_ts)
+            };
+            message_types.push(MessageType::SpellDamage(create_damage_done(attacker, victim, Some(spell_id), Some(spell_name), damage, is_periodic)));
+            Ok(message_types)
+        },
+        "RANGE_MISSED" | "SPELL_MISSED" | "SPELL_PERIODIC_MISSED" | "DAMAGE_SHIELD_MISSED" => {
+            state.line_family = "spell_damage";
+            let (attacker, victim) = (require_unit(source)?, require_unit(target)?);
+            let (spell_id, spell_name) = parse_spell(params)?;
+            let hit_mask = parse_miss_type(get_arg(params, 3)?)?;
+
+            let mut message_types = if event == "SPELL_PERIODIC_MISSED" {
+                link_aura_application(state, &attacker, &victim, spell_id)
+            } else {
+                link_spell_cast(state, &attacker, &victim, spell_id, hit_mask, event_ts)
+            };
+            message_types.push(MessageType::SpellDamage(create_damage_miss(attacker, victim, Some(spell_id), Some(spell_name), hit_mask)));
+            Ok(message_types)
+        },
+        "SPELL_HEAL" | "SPELL_PERIODIC_HEAL" => {
+            state.line_family = "heal";
+            let (caster, healed) = (require_unit(source)?, require_unit(target)?);
+            let (spell_id, _) = parse_spell(params)?;
+            let total_heal = parse_amount(params, 3)?;
+            let (effective_heal, overheal, absorb, is_critical) = if layout.heal_has_overheal_and_absorb {
+                let overheal = parse_amount(params, 4)?;
+                (total_heal.saturating_sub(overheal), overheal, parse_amount(params, 5)?, parse_flag(params, 6))
+            } else {
+                // Without overhealing in the log, it is estimated from the health the target is missing
+                let (effective_heal, overheal) = state.participants.get_mut(&healed.unit_id).map_or((total_heal, 0), |participant| participant.attribute_heal(total_heal, 0));
+                (effective_heal, overheal, 0, parse_flag(params, 4))
+            };
+            let hit_mask = if is_critical { HitType::Crit as u32 } else { HitType::Hit as u32 };
+
+            let mut message_types = if event == "SPELL_PERIODIC_HEAL" {
+                link_aura_application(state, &caster, &healed, spell_id)
+            } else {
+                link_spell_cast(state, &caster, &healed, spell_id, hit_mask, event_ts)
+            };
+            message_types.push(MessageType::Heal(HealDone {
+                caster,
+                target: healed,
+                spell_id,
+                total_heal,
+                effective_heal,
+                overheal,
+                absorb,
+                hit_mask,
+            }));
+            Ok(message_types)
+        },
+        "SPELL_AURA_APPLIED" | "SPELL_AURA_REMOVED" | "SPELL_AURA_APPLIED_DOSE" | "SPELL_AURA_REMOVED_DOSE" => {
+            state.line_family = "aura";
+            let aura_target = require_unit(target)?;
+            let (spell_id, _) = parse_spell(params)?;
+            // Some auras, e.g. of the environment, have no caster
+            let caster = source.unwrap_or(Unit {
+                is_player: true,
+                unit_id: 0,
+                is_self_damage: false,
+                is_mind_control: false,
+            });
+            let aura_key = (caster.unit_id, aura_target.unit_id, spell_id);
+            let (stack_amount, delta) = match event {
+                "SPELL_AURA_APPLIED" => {
+                    state.active_auras.insert(aura_key);
+                    (1, 1)
+                },
+                "SPELL_AURA_REMOVED" => {
+                    state.active_auras.remove(&aura_key);
+                    (0, -1)
+                },
+                "SPELL_AURA_APPLIED_DOSE" => (parse_amount(params, 4)?, 1),
+                _ => (parse_amount(params, 4)?, -1),
+            };
+            Ok(vec![MessageType::AuraApplication(AuraApplication {
+                caster,
+                target: aura_target,
+                spell_id,
+                stack_amount,
+                delta,
+            })])
+        },
+        "SPELL_CAST_START" => {
+            state.line_family = "spell_cast";
+            let (spell_id, _) = parse_spell(params)?;
+            Ok(vec![MessageType::SpellCastAttempt(SpellCast {
+                caster: require_unit(source)?,
+                target,
+                spell_id,
+                hit_mask: HitType::Hit as u32,
+            })])
+        },
+        "SPELL_CAST_SUCCESS" => {
+            state.line_family = "spell_cast";
+            let caster = require_unit(source)?;
+            let (spell_id, _) = parse_spell(params)?;
+            state.last_spell_cast.insert((caster.unit_id, spell_id), (event_ts, target.as_ref().map(|target| target.unit_id)));
+            Ok(vec![MessageType::SpellCast(SpellCast {
+                caster,
+                target,
+                spell_id,
+                hit_mask: HitType::Hit as u32,
+            })])
+        },
+        "SPELL_CAST_FAILED" => {
+            // The addon transports its messages as failure reasons
+            let failed_type = get_arg(params, 3)?;
+            if failed_type.starts_with("CONSOLIDATED: ") || failed_type.starts_with("COMBATANT_INFO: ") {
+                return parse_addon_message(state, layout, data, event_ts, failed_type);
+            }
+            state.line_family = "spell_cast";
+            Ok(Vec::new())
+        },
+        "SPELL_INTERRUPT" => {
+            state.line_family = "dispel_interrupt";
+            let (caster, interrupted) = (require_unit(source)?, require_unit(target)?);
+            let (spell_id, _) = parse_spell(params)?;
+            let interrupted_spell_id = parse_amount(params, 3)?;
+            let mut message_types = link_spell_cast(state, &caster, &interrupted, spell_id, HitType::Hit as u32, event_ts);
+            message_types.push(MessageType::Interrupt(Interrupt {
+                target: interrupted,
+                interrupted_spell_id,
+            }));
+            Ok(message_types)
+        },
+        "SPELL_DISPEL" | "SPELL_STOLEN" => {
+            state.line_family = "dispel_interrupt";
+            let (caster, dispelled) = (require_unit(source)?, require_unit(target)?);
+            let (spell_id, _) = parse_spell(params)?;
+            let target_spell_id = parse_amount(params, 3)?;
+            let mut message_types = link_spell_cast(state, &caster, &dispelled, spell_id, HitType::Hit as u32, event_ts);
+            let un_aura = UnAura {
+                un_aura_caster: caster,
+                target: dispelled,
+                aura_caster: None,
+                un_aura_spell_id: spell_id,
+                target_spell_id,
+                un_aura_amount: 1,
+            };
+            message_types.push(if event == "SPELL_DISPEL" { MessageType::Dispel(un_aura) } else { MessageType::SpellSteal(un_aura) });
+            Ok(message_types)
+        },
+        "SPELL_SUMMON" => {
+            state.line_family = "pet";
+            Ok(vec![MessageType::Summon(Summon {
+                owner: require_unit(source)?,
+                unit: require_unit(target)?,
+            })])
+        },
+        "UNIT_DIED" | "UNIT_DESTROYED" => {
+            state.line_family = "death";
+            let victim = require_unit(target)?;
+            if let Some(participant) = state.participants.get_mut(&victim.unit_id) {
+                participant.attribute_death();
+            }
+            Ok(vec![MessageType::Death(Death { cause: None, victim })])
+        },
+        // Known events that are not processed, e.g. power gains, enchants or kills that are also logged as UNIT_DIED
+        _ => {
+            state.line_family = "ignored";
+            Ok(Vec::new())
+        },
+    }
+}
+
+fn require_unit(unit: Option<Unit>) -> Result<Unit, CblParseFailure> {
+    unit.ok_or_else(|| CblParseFailure::UnknownUnit("nil".to_string()))
+}
+
+fn parse_spell(params: &[&str]) -> Result<(u32, String), CblParseFailure> {
+    Ok((parse_amount(params, 0)?, get_arg(params, 1)?.to_string()))
+}
+
+fn parse_damage(layout: &CleuLayout, params: &[&str], offset: usize) -> Result<CleuDamage, CblParseFailure> {
+    let amount = parse_amount(params, offset)?;
+    let offset = if layout.damage_has_overkill { offset + 1 } else { offset };
+    let school_mask = parse_amount(params, offset + 1)? as u8;
+    let resisted = parse_amount(params, offset + 2)?;
+    let blocked = parse_amount(params, offset + 3)?;
+    let absorbed = parse_amount(params, offset + 4)?;
+
+    let mut hit_mask = if parse_flag(params, offset + 5) { HitType::Crit as u32 } else { HitType::Hit as u32 };
+    if parse_flag(params, offset + 6) {
+        hit_mask |= HitType::Glancing as u32;
+    }
+    if parse_flag(params, offset + 7) {
+        hit_mask |= HitType::Crushing as u32;
+    }
+    if resisted > 0 {
+        hit_mask |= HitType::PartialResist as u32;
+    }
+    if blocked > 0 {
+        hit_mask |= HitType::PartialBlock as u32;
+    }
+    if absorbed > 0 {
+        hit_mask |= HitType::PartialAbsorb as u32;
+    }
+
+    Ok(CleuDamage {
+        amount,
+        school_mask,
+        resisted,
+        blocked,
+        absorbed,
+        hit_mask,
+    })
+}
+
+fn parse_miss_type(miss_type: &str) -> Result<u32, CblParseFailure> {
+    Ok(match miss_type {
+        "ABSORB" => HitType::FullAbsorb,
+        "BLOCK" => HitType::FullBlock,
+        "DEFLECT" => HitType::Deflect,
+        "DODGE" => HitType::Dodge,
+        "EVADE" => HitType::Evade,
+        "IMMUNE" => HitType::Immune,
+        "MISS" => HitType::Miss,
+        "PARRY" => HitType::Parry,
+        "REFLECT" => HitType::Reflect,
+        "RESIST" => HitType::FullResist,
+        unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
+    } as u32)
+}
+
+fn create_damage_done(attacker: Unit, victim: Unit, spell_id: Option<u32>, spell_name: Option<String>, damage: CleuDamage, damage_over_time: bool) -> DamageDone {
+    DamageDone {
+        attacker,
+        victim,
+        spell_id,
+        spell_name,
+        hit_mask: damage.hit_mask,
+        blocked: damage.blocked,
+        damage_over_time,
+        damage_components: vec![DamageComponent {
+            school_mask: damage.school_mask,
+            damage: damage.amount,
+            resisted_or_glanced: damage.resisted,
+            absorbed: damage.absorbed,
+        }],
+    }
+}
+
+fn create_damage_miss(attacker: Unit, victim: Unit, spell_id: Option<u32>, spell_name: Option<String>, hit_mask: u32) -> DamageDone {
+    DamageDone {
+        attacker,
+        victim,
+        spell_id,
+        spell_name,
+        hit_mask,
+        blocked: 0,
+        damage_over_time: false,
+        damage_components: vec![],
+    }
+}
+
+fn attribute_damage(state: &mut CleuParserState, victim: &Unit, amount: u32) {
+    if let Some(participant) = state.participants.get_mut(&victim.unit_id) {
+        participant.attribute_damage(amount);
+    }
+}
+
+// Damage and heal events are attributed to a spell cast, which is created if the log has none that matches,
+// e.g. for procs or casts that happened before the log started.
+fn link_spell_cast(state: &CleuParserState, caster: &Unit, target: &Unit, spell_id: u32, hit_mask: u32, event_ts: u64) -> Vec<MessageType> {
+    let has_matching_cast = state.last_spell_cast.get(&(caster.unit_id, spell_id)).map_or(false, |(cast_ts, cast_target)| {
+        event_ts.saturating_sub(*cast_ts) <= SPELL_CAST_LINK_WINDOW && cast_target.map_or(true, |cast_target| cast_target == target.unit_id)
+    });
+    if has_matching_cast {
+        return Vec::new();
+    }
+    vec![MessageType::SpellCast(SpellCast {
+        caster: caster.clone(),
+        target: Some(target.clone()),
+        spell_id,
+        hit_mask,
+    })]
+}
+
+// Periodic events are attributed to the aura application, which is created if the log started after it was applied
+fn link_aura_application(state: &mut CleuParserState, caster: &Unit, target: &Unit, spell_id: u32) -> Vec<MessageType> {
+    if !state.active_auras.insert((caster.unit_id, target.unit_id, spell_id)) {
+        return Vec::new();
+    }
+    vec![MessageType::AuraApplication(AuraApplication {
+        caster: caster.clone(),
+        target: target.clone(),
+        spell_id,
+        stack_amount: 1,
+        delta: 1,
+    })]
+}
@@ -0,0 +1,28 @@
+use crate::modules::live_data_processor::dto::Unit;
+use crate::modules::live_data_processor::tools::cbl_parser::CblParseFailure;
+use crate::modules::live_data_processor::tools::GUID;
+
+pub fn parse_guid(guid: &str) -> Result<u64, CblParseFailure> {
+    let hex = guid.strip_prefix("0x").ok_or_else(|| CblParseFailure::UnknownUnit(guid.to_string()))?;
+    if hex.is_empty() || hex.len() > 16 {
+        return Err(CblParseFailure::UnknownUnit(guid.to_string()));
+    }
+    u64::from_str_radix(hex, 16).map_err(|_| CblParseFailure::UnknownUnit(guid.to_string()))
+}
+
+/// Returns None for events without source or target.
+pub fn parse_unit(guid: &str) -> Result<Option<Unit>, CblParseFailure> {
+    if guid == "nil" {
+        return Ok(None);
+    }
+    let unit_id = parse_guid(guid)?;
+    if unit_id == 0 {
+        return Ok(None);
+    }
+    Ok(Some(Unit {
+        is_player: unit_id.is_player(),
+        unit_id,
+        is_self_damage: false,
+        is_mind_control: false,
+    }))
+}
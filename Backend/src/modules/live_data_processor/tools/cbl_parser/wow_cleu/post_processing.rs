@@ -0,0 +1,34 @@
+use crate::modules::live_data_processor::dto::{Message, MessageType, Summon, Unit};
+use crate::modules::live_data_processor::material::CleuParserState;
+use crate::modules::live_data_processor::tools::GUID;
+
+pub fn do_cleu_message_post_processing(state: &CleuParserState, messages: &mut Vec<Message>) {
+    // Pets that were summoned before the log started are only known through the addon
+    let mut summon_events = state
+        .pet_owner
+        .iter()
+        .map(|(pet_unit_id, owner_unit_id)| {
+            Message::new_parsed(
+                0,
+                0,
+                MessageType::Summon(Summon {
+                    owner: Unit {
+                        is_player: owner_unit_id.is_player(),
+                        unit_id: *owner_unit_id,
+                        is_self_damage: false,
+                        is_mind_control: false,
+                    },
+                    unit: Unit {
+                        is_player: false,
+                        unit_id: *pet_unit_id,
+                        is_self_damage: false,
+                        is_mind_control: false,
+                    },
+                }),
+            )
+        })
+        .collect::<Vec<Message>>();
+
+    messages.append(&mut summon_events);
+    messages.sort_by(|left, right| left.timestamp.cmp(&right.timestamp));
+}
@@ -9,3 +9,13 @@ pub fn get_hashed_player_unit_id(unit_name: &str) -> u64 {
 pub fn get_npc_unit_id(data: &Data, unit_name: &str) -> Option<u64> {
     data.get_npc_by_name(1, &unit_name.to_string()).map(|npc| 0xF130000000000000 + (npc.id as u64).rotate_left(24))
 }
+
+// NPCs whose name is not localized for the language are looked up by their English name
+pub fn get_localized_npc_unit_id(data: &Data, language_id: u8, unit_name: &str) -> Option<u64> {
+    if language_id == 1 {
+        return get_npc_unit_id(data, unit_name);
+    }
+    data.get_npc_by_localized_name(1, language_id, &unit_name.to_string())
+        .map(|npc| 0xF130000000000000 + (npc.id as u64).rotate_left(24))
+        .or_else(|| get_npc_unit_id(data, unit_name))
+}
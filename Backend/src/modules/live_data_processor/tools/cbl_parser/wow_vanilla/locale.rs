@@ -0,0 +1,501 @@
+use std::collections::HashMap;
+
+use regex::{Captures, Regex};
+
+// Lines that are inspected to determine the locale of a log
+const SNIFF_LINES: usize = 500;
+
+/*
+ * The events the parser understands, written the way the English client logs them.
+ * Localized logs are translated into these lines before they are parsed, names stay untranslated.
+ * Localized templates refer to the arguments of these templates, either in order or positional by "%n$s".
+ */
+const SPELL_CRIT_SCHOOL: &str = "%s 's %s crits %s for %d %s damage.";
+const SPELL_HIT_SCHOOL: &str = "%s 's %s hits %s for %d %s damage.";
+const SPELL_CRIT: &str = "%s 's %s crits %s for %d.";
+const SPELL_HIT: &str = "%s 's %s hits %s for %d.";
+const MELEE_CRIT_SCHOOL: &str = "%s crits %s for %d %s damage.";
+const MELEE_HIT_SCHOOL: &str = "%s hits %s for %d %s damage.";
+const MELEE_CRIT: &str = "%s crits %s for %d.";
+const MELEE_HIT: &str = "%s hits %s for %d.";
+const PERIODIC_DAMAGE: &str = "%s suffers %d %s damage from %s 's %s.";
+const HEAL_CRIT: &str = "%s 's %s critically heals %s for %d.";
+const HEAL: &str = "%s 's %s heals %s for %d.";
+const PERIODIC_HEAL: &str = "%s gains %d health from %s 's %s.";
+const AURA_GAIN_HARMFUL: &str = "%s is afflicted by %s (%d).";
+const AURA_GAIN_HELPFUL: &str = "%s gains %s (%d).";
+const AURA_FADE: &str = "%s fades from %s.";
+const UNIT_DIES: &str = "%s dies.";
+const UNIT_SLAIN: &str = "%s is slain by %s!";
+const SPELL_CAST_START: &str = "%s begins to cast %s.";
+const SPELL_CAST_ON: &str = "%s casts %s on %s.";
+const SPELL_CAST: &str = "%s casts %s.";
+const SPELL_PERFORM_ON: &str = "%s performs %s on %s.";
+const SPELL_PERFORM: &str = "%s performs %s.";
+const SPELL_MISS: &str = "%s 's %s missed %s.";
+const SPELL_RESIST: &str = "%s 's %s was resisted by %s.";
+const SPELL_INTERRUPT: &str = "%s interrupts %s 's %s.";
+const MELEE_MISS: &str = "%s misses %s.";
+const MELEE_DODGE: &str = "%s attacks. %s dodges.";
+const MELEE_PARRY: &str = "%s attacks. %s parries.";
+const MELEE_BLOCK: &str = "%s attacks. %s blocks.";
+
+const TRAILER_RESISTED: &str = "(%d resisted)";
+const TRAILER_BLOCKED: &str = "(%d blocked)";
+const TRAILER_ABSORBED: &str = "(%d absorbed)";
+const TRAILER_GLANCING: &str = "(glancing)";
+const TRAILER_CRUSHING: &str = "(crushing)";
+
+const SCHOOLS: [&str; 7] = ["Physical", "Arcane", "Fire", "Frost", "Shadow", "Nature", "Holy"];
+
+// Templates that are ambiguous are ordered from the most to the least specific, e.g. spell damage before melee damage
+const ENGLISH_LINES: [(&str, &str); 29] = [
+    (SPELL_CRIT_SCHOOL, SPELL_CRIT_SCHOOL),
+    (SPELL_HIT_SCHOOL, SPELL_HIT_SCHOOL),
+    (SPELL_CRIT, SPELL_CRIT),
+    (SPELL_HIT, SPELL_HIT),
+    (MELEE_CRIT_SCHOOL, MELEE_CRIT_SCHOOL),
+    (MELEE_HIT_SCHOOL, MELEE_HIT_SCHOOL),
+    (MELEE_CRIT, MELEE_CRIT),
+    (MELEE_HIT, MELEE_HIT),
+    (PERIODIC_DAMAGE, PERIODIC_DAMAGE),
+    (HEAL_CRIT, HEAL_CRIT),
+    (HEAL, HEAL),
+    (PERIODIC_HEAL, PERIODIC_HEAL),
+    (AURA_GAIN_HARMFUL, AURA_GAIN_HARMFUL),
+    (AURA_GAIN_HELPFUL, AURA_GAIN_HELPFUL),
+    (AURA_FADE, AURA_FADE),
+    (UNIT_DIES, UNIT_DIES),
+    (UNIT_SLAIN, UNIT_SLAIN),
+    (SPELL_CAST_START, SPELL_CAST_START),
+    (SPELL_CAST_ON, SPELL_CAST_ON),
+    (SPELL_CAST, SPELL_CAST),
+    (SPELL_PERFORM_ON, SPELL_PERFORM_ON),
+    (SPELL_PERFORM, SPELL_PERFORM),
+    (SPELL_MISS, SPELL_MISS),
+    (SPELL_RESIST, SPELL_RESIST),
+    (SPELL_INTERRUPT, SPELL_INTERRUPT),
+    (MELEE_MISS, MELEE_MISS),
+    (MELEE_DODGE, MELEE_DODGE),
+    (MELEE_PARRY, MELEE_PARRY),
+    (MELEE_BLOCK, MELEE_BLOCK),
+];
+
+const GERMAN_LINES: [(&str, &str); 29] = [
+    (SPELL_CRIT_SCHOOL, "%ss %s trifft %s kritisch für %d %sschaden."),
+    (SPELL_HIT_SCHOOL, "%ss %s trifft %s für %d %sschaden."),
+    (SPELL_CRIT, "%ss %s trifft %s kritisch für %d Schaden."),
+    (SPELL_HIT, "%ss %s trifft %s für %d Schaden."),
+    (MELEE_CRIT_SCHOOL, "%s trifft %s kritisch für %d %sschaden."),
+    (MELEE_HIT_SCHOOL, "%s trifft %s für %d %sschaden."),
+    (MELEE_CRIT, "%s trifft %s kritisch für %d Schaden."),
+    (MELEE_HIT, "%s trifft %s für %d Schaden."),
+    (PERIODIC_DAMAGE, "%1$s erleidet %2$d %3$sschaden von %4$s (durch %5$s)."),
+    (HEAL_CRIT, "%ss %s heilt %s kritisch um %d Punkte."),
+    (HEAL, "%ss %s heilt %s um %d Punkte."),
+    (PERIODIC_HEAL, "%1$s erhält %2$d Gesundheit von %3$ss %4$s."),
+    (AURA_GAIN_HARMFUL, "%s ist von %s betroffen (%d)."),
+    (AURA_GAIN_HELPFUL, "%s bekommt '%s' (%d)."),
+    (AURA_FADE, "'%s' schwindet von %s."),
+    (UNIT_DIES, "%s stirbt."),
+    (UNIT_SLAIN, "%s wurde von %s getötet!"),
+    (SPELL_CAST_START, "%s beginnt %s zu wirken."),
+    (SPELL_CAST_ON, "%s wirkt %s auf %s."),
+    (SPELL_CAST, "%s wirkt %s."),
+    (SPELL_PERFORM_ON, "%s führt %s auf %s aus."),
+    (SPELL_PERFORM, "%s führt %s aus."),
+    (SPELL_MISS, "%ss %s verfehlt %s."),
+    (SPELL_RESIST, "%ss %s wurde von %s widerstanden."),
+    (SPELL_INTERRUPT, "%s unterbricht %ss %s."),
+    (MELEE_MISS, "%s verfehlt %s."),
+    (MELEE_DODGE, "%s greift an. %s weicht aus."),
+    (MELEE_PARRY, "%s greift an. %s pariert."),
+    (MELEE_BLOCK, "%s greift an. %s blockt ab."),
+];
+
+const GERMAN_TRAILERS: [(&str, &str); 5] = [
+    (TRAILER_RESISTED, "(%d widerstanden)"),
+    (TRAILER_BLOCKED, "(%d geblockt)"),
+    (TRAILER_ABSORBED, "(%d absorbiert)"),
+    (TRAILER_GLANCING, "(gestreift)"),
+    (TRAILER_CRUSHING, "(schmetternd)"),
+];
+
+const GERMAN_SCHOOLS: [&str; 7] = ["Körperlich", "Arkan", "Feuer", "Frost", "Schatten", "Natur", "Heilig"];
+
+const FRENCH_LINES: [(&str, &str); 29] = [
+    (SPELL_CRIT_SCHOOL, "%2$s de %1$s inflige un coup critique à %3$s (%4$d points de dégâts de %5$s)."),
+    (SPELL_HIT_SCHOOL, "%2$s de %1$s touche %3$s et inflige %4$d points de dégâts de %5$s."),
+    (SPELL_CRIT, "%2$s de %1$s inflige un coup critique à %3$s (%4$d points de dégâts)."),
+    (SPELL_HIT, "%2$s de %1$s touche %3$s et inflige %4$d points de dégâts."),
+    (MELEE_CRIT_SCHOOL, "%s inflige un coup critique à %s (%d points de dégâts de %s)."),
+    (MELEE_HIT_SCHOOL, "%s touche %s et inflige %d points de dégâts de %s."),
+    (MELEE_CRIT, "%s inflige un coup critique à %s (%d points de dégâts)."),
+    (MELEE_HIT, "%s touche %s et inflige %d points de dégâts."),
+    (PERIODIC_DAMAGE, "%1$s subit %2$d points de dégâts de %3$s (%5$s de %4$s)."),
+    (HEAL_CRIT, "%2$s de %1$s soigne %3$s avec un effet critique et lui rend %4$d points de vie."),
+    (HEAL, "%2$s de %1$s soigne %3$s et lui rend %4$d points de vie."),
+    (PERIODIC_HEAL, "%1$s gagne %2$d points de vie grâce à %4$s de %3$s."),
+    (AURA_GAIN_HARMFUL, "%s subit les effets de %s (%d)."),
+    (AURA_GAIN_HELPFUL, "%s gagne %s (%d)."),
+    (AURA_FADE, "%s sur %s vient de se dissiper."),
+    (UNIT_DIES, "%s meurt."),
+    (UNIT_SLAIN, "%s est tué par %s !"),
+    (SPELL_CAST_START, "%s commence à lancer %s."),
+    (SPELL_CAST_ON, "%s lance %s sur %s."),
+    (SPELL_CAST, "%s lance %s."),
+    (SPELL_PERFORM_ON, "%s utilise %s sur %s."),
+    (SPELL_PERFORM, "%s utilise %s."),
+    (SPELL_MISS, "%2$s de %1$s rate %3$s."),
+    (SPELL_RESIST, "%3$s résiste à %2$s de %1$s."),
+    (SPELL_INTERRUPT, "%1$s interrompt %3$s de %2$s."),
+    (MELEE_MISS, "%s rate %s."),
+    (MELEE_DODGE, "%s attaque. %s esquive."),
+    (MELEE_PARRY, "%s attaque. %s pare."),
+    (MELEE_BLOCK, "%s attaque. %s bloque."),
+];
+
+const FRENCH_TRAILERS: [(&str, &str); 5] = [
+    (TRAILER_RESISTED, "(%d résisté)"),
+    (TRAILER_BLOCKED, "(%d bloqué)"),
+    (TRAILER_ABSORBED, "(%d absorbé)"),
+    (TRAILER_GLANCING, "(érafle)"),
+    (TRAILER_CRUSHING, "(écrase)"),
+];
+
+const FRENCH_SCHOOLS: [&str; 7] = ["Physique", "Arcanes", "Feu", "Givre", "Ombre", "Nature", "Sacré"];
+
+const SPANISH_LINES: [(&str, &str); 29] = [
+    (SPELL_CRIT_SCHOOL, "%2$s de %1$s golpea críticamente a %3$s por %4$d p. de daño de %5$s."),
+    (SPELL_HIT_SCHOOL, "%2$s de %1$s golpea a %3$s por %4$d p. de daño de %5$s."),
+    (SPELL_CRIT, "%2$s de %1$s golpea críticamente a %3$s por %4$d."),
+    (SPELL_HIT, "%2$s de %1$s golpea a %3$s por %4$d."),
+    (MELEE_CRIT_SCHOOL, "%s golpea críticamente a %s por %d p. de daño de %s."),
+    (MELEE_HIT_SCHOOL, "%s golpea a %s por %d p. de daño de %s."),
+    (MELEE_CRIT, "%s golpea críticamente a %s por %d."),
+    (MELEE_HIT, "%s golpea a %s por %d."),
+    (PERIODIC_DAMAGE, "%1$s sufre %2$d p. de daño de %3$s de %5$s de %4$s."),
+    (HEAL_CRIT, "%2$s de %1$s sana críticamente a %3$s por %4$d."),
+    (HEAL, "%2$s de %1$s sana a %3$s por %4$d."),
+    (PERIODIC_HEAL, "%1$s gana %2$d p. de salud de %4$s de %3$s."),
+    (AURA_GAIN_HARMFUL, "%s sufre de %s (%d)."),
+    (AURA_GAIN_HELPFUL, "%s gana %s (%d)."),
+    (AURA_FADE, "%s desaparece de %s."),
+    (UNIT_DIES, "%s muere."),
+    (UNIT_SLAIN, "%s ha sido asesinado por %s."),
+    (SPELL_CAST_START, "%s comienza a lanzar %s."),
+    (SPELL_CAST_ON, "%s lanza %s a %s."),
+    (SPELL_CAST, "%s lanza %s."),
+    (SPELL_PERFORM_ON, "%s realiza %s a %s."),
+    (SPELL_PERFORM, "%s realiza %s."),
+    (SPELL_MISS, "%2$s de %1$s falla a %3$s."),
+    (SPELL_RESIST, "%3$s resiste %2$s de %1$s."),
+    (SPELL_INTERRUPT, "%1$s interrumpe %3$s de %2$s."),
+    (MELEE_MISS, "%s falla a %s."),
+    (MELEE_DODGE, "%s ataca. %s esquiva."),
+    (MELEE_PARRY, "%s ataca. %s para."),
+    (MELEE_BLOCK, "%s ataca. %s bloquea."),
+];
+
+const SPANISH_TRAILERS: [(&str, &str); 5] = [
+    (TRAILER_RESISTED, "(%d resistido)"),
+    (TRAILER_BLOCKED, "(%d bloqueado)"),
+    (TRAILER_ABSORBED, "(%d absorbido)"),
+    (TRAILER_GLANCING, "(de refilón)"),
+    (TRAILER_CRUSHING, "(aplastante)"),
+];
+
+const SPANISH_SCHOOLS: [&str; 7] = ["Físico", "Arcano", "Fuego", "Escarcha", "Sombras", "Naturaleza", "Sagrado"];
+
+const RUSSIAN_LINES: [(&str, &str); 29] = [
+    (SPELL_CRIT_SCHOOL, "%2$s (%1$s) наносит %3$s критический удар: %4$d ед. урона (%5$s)."),
+    (SPELL_HIT_SCHOOL, "%2$s (%1$s) наносит %3$s %4$d ед. урона (%5$s)."),
+    (SPELL_CRIT, "%2$s (%1$s) наносит %3$s критический удар: %4$d ед. урона."),
+    (SPELL_HIT, "%2$s (%1$s) наносит %3$s %4$d ед. урона."),
+    (MELEE_CRIT_SCHOOL, "%s наносит %s критический удар: %d ед. урона (%s)."),
+    (MELEE_HIT_SCHOOL, "%s наносит %s %d ед. урона (%s)."),
+    (MELEE_CRIT, "%s наносит %s критический удар: %d ед. урона."),
+    (MELEE_HIT, "%s наносит %s %d ед. урона."),
+    (PERIODIC_DAMAGE, "%1$s получает %2$d ед. урона (%3$s) от эффекта \"%5$s\" (%4$s)."),
+    (HEAL_CRIT, "%2$s (%1$s) восполняет %3$s %4$d ед. здоровья (критический эффект)."),
+    (HEAL, "%2$s (%1$s) восполняет %3$s %4$d ед. здоровья."),
+    (PERIODIC_HEAL, "%1$s восполняет %2$d ед. здоровья благодаря эффекту \"%4$s\" (%3$s)."),
+    (AURA_GAIN_HARMFUL, "%s находится под воздействием эффекта \"%s\" (%d)."),
+    (AURA_GAIN_HELPFUL, "%s получает эффект \"%s\" (%d)."),
+    (AURA_FADE, "Действие эффекта \"%s\", наложенного на %s, заканчивается."),
+    (UNIT_DIES, "%s погибает."),
+    (UNIT_SLAIN, "%s убивает %s!"),
+    (SPELL_CAST_START, "%s начинает применять заклинание \"%s\"."),
+    (SPELL_CAST_ON, "%s применяет заклинание \"%s\" к %s."),
+    (SPELL_CAST, "%s применяет заклинание \"%s\"."),
+    (SPELL_PERFORM_ON, "%s использует \"%s\" на %s."),
+    (SPELL_PERFORM, "%s использует \"%s\"."),
+    (SPELL_MISS, "%2$s (%1$s) промахивается по %3$s."),
+    (SPELL_RESIST, "%3$s сопротивляется эффекту \"%2$s\" (%1$s)."),
+    (SPELL_INTERRUPT, "%1$s прерывает заклинание \"%3$s\" (%2$s)."),
+    (MELEE_MISS, "%s промахивается по %s."),
+    (MELEE_DODGE, "%s атакует. %s уклоняется."),
+    (MELEE_PARRY, "%s атакует. %s парирует атаку."),
+    (MELEE_BLOCK, "%s атакует. %s блокирует атаку."),
+];
+
+const RUSSIAN_TRAILERS: [(&str, &str); 5] = [
+    (TRAILER_RESISTED, "(сопротивление: %d)"),
+    (TRAILER_BLOCKED, "(заблокировано: %d)"),
+    (TRAILER_ABSORBED, "(поглощено: %d)"),
+    (TRAILER_GLANCING, "(вскользь)"),
+    (TRAILER_CRUSHING, "(сокрушительный удар)"),
+];
+
+const RUSSIAN_SCHOOLS: [&str; 7] = ["Физический", "Тайная магия", "Огонь", "Лед", "Тьма", "Природа", "Свет"];
+
+const CHINESE_LINES: [(&str, &str); 29] = [
+    (SPELL_CRIT_SCHOOL, "%s的%s致命一击对%s造成%d点%s伤害。"),
+    (SPELL_HIT_SCHOOL, "%s的%s击中%s造成%d点%s伤害。"),
+    (SPELL_CRIT, "%s的%s致命一击对%s造成%d点伤害。"),
+    (SPELL_HIT, "%s的%s击中%s造成%d点伤害。"),
+    (MELEE_CRIT_SCHOOL, "%s的致命一击对%s造成%d点%s伤害。"),
+    (MELEE_HIT_SCHOOL, "%s击中%s造成%d点%s伤害。"),
+    (MELEE_CRIT, "%s的致命一击对%s造成%d点伤害。"),
+    (MELEE_HIT, "%s击中%s造成%d点伤害。"),
+    (PERIODIC_DAMAGE, "%1$s受到%4$s的%5$s所造成的%2$d点%3$s伤害。"),
+    (HEAL_CRIT, "%s的%s发挥极效，为%s恢复了%d点生命值。"),
+    (HEAL, "%s的%s为%s恢复了%d点生命值。"),
+    (PERIODIC_HEAL, "%1$s从%3$s的%4$s获得%2$d点生命值。"),
+    (AURA_GAIN_HARMFUL, "%s受到了%s效果的影响（%d）。"),
+    (AURA_GAIN_HELPFUL, "%s获得了%s的效果（%d）。"),
+    (AURA_FADE, "%s效果从%s身上消失了。"),
+    (UNIT_DIES, "%s死亡了。"),
+    (UNIT_SLAIN, "%s被%s杀死了！"),
+    (SPELL_CAST_START, "%s开始施放%s。"),
+    (SPELL_CAST_ON, "%1$s对%3$s施放了%2$s。"),
+    (SPELL_CAST, "%s施放了%s。"),
+    (SPELL_PERFORM_ON, "%1$s对%3$s使用了%2$s。"),
+    (SPELL_PERFORM, "%s使用了%s。"),
+    (SPELL_MISS, "%s的%s没有击中%s。"),
+    (SPELL_RESIST, "%s的%s被%s抵抗了。"),
+    (SPELL_INTERRUPT, "%s打断了%s的%s。"),
+    (MELEE_MISS, "%s没有击中%s。"),
+    (MELEE_DODGE, "%s发起了攻击。%s闪躲开了。"),
+    (MELEE_PARRY, "%s发起了攻击。%s招架住了。"),
+    (MELEE_BLOCK, "%s发起了攻击。%s格挡住了。"),
+];
+
+const CHINESE_TRAILERS: [(&str, &str); 5] = [
+    (TRAILER_RESISTED, "（%d点被抵抗）"),
+    (TRAILER_BLOCKED, "（%d点被格挡）"),
+    (TRAILER_ABSORBED, "（%d点被吸收）"),
+    (TRAILER_GLANCING, "（偏斜）"),
+    (TRAILER_CRUSHING, "（碾压）"),
+];
+
+const CHINESE_SCHOOLS: [&str; 7] = ["物理", "奥术", "火焰", "冰霜", "暗影", "自然", "神圣"];
+
+/// The language of the client that wrote a vanilla combat log.
+/// The language id refers to data_language, whose localizations resolve the names in the log.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CombatLogLocale {
+    English,
+    German,
+    French,
+    Spanish,
+    Russian,
+    Chinese,
+}
+
+impl CombatLogLocale {
+    pub fn all() -> [CombatLogLocale; 6] {
+        [
+            CombatLogLocale::English,
+            CombatLogLocale::German,
+            CombatLogLocale::French,
+            CombatLogLocale::Spanish,
+            CombatLogLocale::Russian,
+            CombatLogLocale::Chinese,
+        ]
+    }
+
+    pub fn language_id(&self) -> u8 {
+        match self {
+            CombatLogLocale::English => 1,
+            CombatLogLocale::German => 2,
+            CombatLogLocale::French => 3,
+            CombatLogLocale::Spanish => 4,
+            CombatLogLocale::Russian => 5,
+            CombatLogLocale::Chinese => 6,
+        }
+    }
+
+    pub fn patterns(&self) -> &'static LocalePatterns {
+        lazy_static! {
+            static ref ENGLISH: LocalePatterns = LocalePatterns::new(&ENGLISH_LINES, &[], &SCHOOLS);
+            static ref GERMAN: LocalePatterns = LocalePatterns::new(&GERMAN_LINES, &GERMAN_TRAILERS, &GERMAN_SCHOOLS);
+            static ref FRENCH: LocalePatterns = LocalePatterns::new(&FRENCH_LINES, &FRENCH_TRAILERS, &FRENCH_SCHOOLS);
+            static ref SPANISH: LocalePatterns = LocalePatterns::new(&SPANISH_LINES, &SPANISH_TRAILERS, &SPANISH_SCHOOLS);
+            static ref RUSSIAN: LocalePatterns = LocalePatterns::new(&RUSSIAN_LINES, &RUSSIAN_TRAILERS, &RUSSIAN_SCHOOLS);
+            static ref CHINESE: LocalePatterns = LocalePatterns::new(&CHINESE_LINES, &CHINESE_TRAILERS, &CHINESE_SCHOOLS);
+        }
+
+        match self {
+            CombatLogLocale::English => &ENGLISH,
+            CombatLogLocale::German => &GERMAN,
+            CombatLogLocale::French => &FRENCH,
+            CombatLogLocale::Spanish => &SPANISH,
+            CombatLogLocale::Russian => &RUSSIAN,
+            CombatLogLocale::Chinese => &CHINESE,
+        }
+    }
+
+    /// Translates a line of this locale into the English line the parser understands.
+    /// Returns None for English logs and for lines that are not covered by the patterns, e.g. addon messages.
+    pub fn translate(&self, line: &str) -> Option<String> {
+        if *self == CombatLogLocale::English {
+            return None;
+        }
+        self.patterns().translate(line)
+    }
+}
+
+/// Determines the locale by the number of lines that match its patterns within the first lines of the log.
+/// Inconclusive logs are considered English.
+pub fn sniff_combat_log_locale(lines: &[(u64, &str)]) -> CombatLogLocale {
+    let mut best_locale = CombatLogLocale::English;
+    let mut best_matches = 0;
+    for locale in CombatLogLocale::all().iter() {
+        let patterns = locale.patterns();
+        let matches = lines.iter().take(SNIFF_LINES).filter(|(_, line)| patterns.matches(line)).count();
+        if matches > best_matches {
+            best_locale = *locale;
+            best_matches = matches;
+        }
+    }
+    best_locale
+}
+
+struct LocalizedTemplate {
+    regex: Regex,
+    // Argument of the English template for each capture group
+    arguments: Vec<usize>,
+    english: &'static str,
+}
+
+pub struct LocalePatterns {
+    lines: Vec<LocalizedTemplate>,
+    trailers: Vec<LocalizedTemplate>,
+    keywords: HashMap<&'static str, &'static str>,
+}
+
+impl LocalePatterns {
+    fn new(lines: &[(&'static str, &'static str)], trailers: &[(&'static str, &'static str)], schools: &[&'static str; 7]) -> Self {
+        LocalePatterns {
+            lines: lines.iter().map(|(english, localized)| LocalizedTemplate::new(english, localized, true)).collect(),
+            trailers: trailers.iter().map(|(english, localized)| LocalizedTemplate::new(english, localized, false)).collect(),
+            keywords: schools.iter().copied().zip(SCHOOLS.iter().copied()).collect(),
+        }
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        self.lines.iter().any(|template| template.regex.is_match(line))
+    }
+
+    pub fn translate(&self, line: &str) -> Option<String> {
+        self.lines.iter().find_map(|template| {
+            let captures = template.regex.captures(line)?;
+            let mut translation = template.render(&captures, &self.keywords);
+            let trailer = captures.get(template.arguments.len() + 1).map(|trailer| trailer.as_str().trim()).unwrap_or("");
+            if !trailer.is_empty() {
+                translation.push(' ');
+                translation.push_str(&self.translate_trailer(trailer));
+            }
+            Some(translation)
+        })
+    }
+
+    // Trailers are a sequence of parenthesized parts, each is translated on its own
+    fn translate_trailer(&self, trailer: &str) -> String {
+        let mut parts = Vec::new();
+        let mut remaining = trailer.trim();
+        while !remaining.is_empty() {
+            let part_end = remaining
+                .char_indices()
+                .find(|(_, character)| *character == ')' || *character == '）')
+                .map(|(index, character)| index + character.len_utf8())
+                .unwrap_or_else(|| remaining.len());
+            let part = &remaining[..part_end];
+            parts.push(
+                self.trailers
+                    .iter()
+                    .find_map(|template| template.regex.captures(part).map(|captures| template.render(&captures, &self.keywords)))
+                    .unwrap_or_else(|| part.to_string()),
+            );
+            remaining = remaining[part_end..].trim_start();
+        }
+        parts.join(" ")
+    }
+}
+
+impl LocalizedTemplate {
+    fn new(english: &'static str, localized: &str, has_trailer: bool) -> Self {
+        let mut pattern = String::from("^");
+        let mut arguments = Vec::new();
+        let mut literal = String::new();
+        let mut characters = localized.chars().peekable();
+        while let Some(character) = characters.next() {
+            if character != '%' {
+                literal.push(character);
+                continue;
+            }
+
+            // Either "%s" and "%d", which refer to the next argument, or "%n$s" and "%n$d"
+            let mut position = String::new();
+            while let Some(digit) = characters.peek().copied().filter(char::is_ascii_digit) {
+                position.push(digit);
+                characters.next();
+            }
+            if !position.is_empty() {
+                characters.next();
+            }
+            let argument = position.parse::<usize>().map(|position| position - 1).unwrap_or(arguments.len());
+            let group = match characters.next() {
+                Some('d') => r"(\d+)",
+                _ => "(.+?)",
+            };
+
+            pattern.push_str(&regex::escape(&literal));
+            literal.clear();
+            pattern.push_str(group);
+            arguments.push(argument);
+        }
+        pattern.push_str(&regex::escape(&literal));
+        if has_trailer {
+            pattern.push_str(r"\s?(.*)");
+        }
+        pattern.push('$');
+
+        LocalizedTemplate {
+            regex: Regex::new(&pattern).unwrap(),
+            arguments,
+            english,
+        }
+    }
+
+    // Substitutes the arguments in the order of the English template
+    fn render(&self, captures: &Captures, keywords: &HashMap<&'static str, &'static str>) -> String {
+        let mut values = vec![""; self.arguments.len()];
+        for (group, argument) in self.arguments.iter().enumerate() {
+            if let (Some(value), Some(capture)) = (values.get_mut(*argument), captures.get(group + 1)) {
+                *value = keywords.get(capture.as_str()).copied().unwrap_or_else(|| capture.as_str());
+            }
+        }
+
+        let mut rendered = String::with_capacity(self.english.len() + 32);
+        let mut values = values.into_iter();
+        let mut parts = self.english.split('%');
+        rendered.push_str(parts.next().unwrap_or(""));
+        for part in parts {
+            rendered.push_str(values.next().unwrap_or(""));
+            rendered.push_str(&part[1..]);
+        }
+        rendered
+    }
+}
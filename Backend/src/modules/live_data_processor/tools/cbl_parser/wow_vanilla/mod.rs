@@ -1,5 +1,6 @@
 pub(crate) mod hashed_unit_id;
+pub(crate) mod locale;
 mod parse_spell_args;
 mod parse_trailer;
-mod parse_unit;
+pub(crate) mod parse_unit;
 mod parser;
@@ -1,21 +1,23 @@
 use crate::modules::data::tools::RetrieveSpell;
 use crate::modules::data::Data;
+use crate::modules::live_data_processor::tools::cbl_parser::CblParseFailure;
 use std::collections::HashMap;
 
 pub fn parse_spell_args_periodic(
     cache: &mut HashMap<String, Option<u32>>,
     data: &Data,
+    language_id: u8,
     spell_name: &str,
-) -> Option<u32> {
+) -> Result<u32, CblParseFailure> {
     if spell_name == "Unknown" {
-        return None;
+        return Err(CblParseFailure::UnknownSpell(spell_name.to_string()));
     }
 
     let mut lookup = |name: &str| -> Option<u32> {
         if let Some(spell_id) = cache.get(name) {
             *spell_id
         } else {
-            let spell_id = data.get_spell_by_name(1, &name.to_string()).map(|spell| spell.id);
+            let spell_id = data.get_spell_by_localized_name(1, language_id, &name.to_string()).map(|spell| spell.id);
             cache.insert(name.to_string(), spell_id);
             spell_id
         }
@@ -23,23 +25,23 @@ pub fn parse_spell_args_periodic(
 
     let periodic_spell_name = format!("{} (dot)", spell_name);
     if let Some(id) = lookup(&periodic_spell_name) {
-        return Some(id);
+        return Ok(id);
     }
 
-    lookup(spell_name)
+    lookup(spell_name).ok_or_else(|| CblParseFailure::UnknownSpell(spell_name.to_string()))
 }
 
-pub fn parse_spell_args(cache: &mut HashMap<String, Option<u32>>, data: &Data, spell_name: &str) -> Option<u32> {
+pub fn parse_spell_args(cache: &mut HashMap<String, Option<u32>>, data: &Data, language_id: u8, spell_name: &str) -> Result<u32, CblParseFailure> {
     if spell_name == "Unknown" {
-        return None;
+        return Err(CblParseFailure::UnknownSpell(spell_name.to_string()));
     }
 
     let spell_name = spell_name.to_string();
     if let Some(spell_id) = cache.get(&spell_name) {
-        return *spell_id;
+        return spell_id.ok_or_else(|| CblParseFailure::UnknownSpell(spell_name.clone()));
     }
 
-    let spell_id = data.get_spell_by_name(1, &spell_name).map(|spell| spell.id);
-    cache.insert(spell_name, spell_id);
-    spell_id
-}
\ No newline at end of file
+    let spell_id = data.get_spell_by_localized_name(1, language_id, &spell_name).map(|spell| spell.id);
+    cache.insert(spell_name.clone(), spell_id);
+    spell_id.ok_or(CblParseFailure::UnknownSpell(spell_name))
+}
@@ -1,6 +1,7 @@
 use crate::modules::live_data_processor::domain_value::HitType;
+use crate::modules::live_data_processor::tools::cbl_parser::CblParseFailure;
 
-pub fn parse_trailer(trailer: &str) -> Vec<(Option<u32>, HitType)> {
+pub fn parse_trailer(trailer: &str) -> Result<Vec<(Option<u32>, HitType)>, CblParseFailure> {
     let mut result = Vec::new();
     for ind_trailer in trailer.split(") (") {
         let ind_trailer = ind_trailer.replace("(", "").replace(")", "");
@@ -10,6 +11,9 @@ pub fn parse_trailer(trailer: &str) -> Vec<(Option<u32>, HitType)> {
             result.push((None, HitType::Crushing));
         } else if !ind_trailer.is_empty() {
             let parts = ind_trailer.split(' ').collect::<Vec<&str>>();
+            if parts.len() < 2 {
+                return Err(CblParseFailure::MalformedTrailer(ind_trailer));
+            }
 
             // Some private servers seems to have implemented "Vulnerability Bonus" which was removed on 1.9
             // It is decided to ignore this vulnerability trailer.
@@ -22,11 +26,11 @@ pub fn parse_trailer(trailer: &str) -> Vec<(Option<u32>, HitType)> {
                     "resisted" => HitType::PartialResist,
                     "blocked" => HitType::PartialBlock,
                     "absorbed" => HitType::PartialAbsorb,
-                    _ => unreachable!(),
+                    _ => return Err(CblParseFailure::MalformedTrailer(ind_trailer)),
                 };
                 result.push((Some(amount), hit_type));
             }
         }
     }
-    result
+    Ok(result)
 }
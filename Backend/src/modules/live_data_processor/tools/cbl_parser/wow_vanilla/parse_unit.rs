@@ -1,37 +1,49 @@
 use crate::modules::data::Data;
 use crate::modules::live_data_processor::dto::Unit;
-use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::hashed_unit_id::{get_hashed_player_unit_id, get_npc_unit_id};
+use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::hashed_unit_id::{get_hashed_player_unit_id, get_localized_npc_unit_id};
+use crate::modules::live_data_processor::tools::cbl_parser::CblParseFailure;
 use std::collections::HashMap;
 
-pub fn parse_unit(cache: &mut HashMap<String, Unit>, data: &Data, unit_name: &str) -> Option<Unit> {
+#[derive(Debug, Clone)]
+pub struct CachedUnit {
+    pub unit: Unit,
+    // Did not resolve to an NPC, but can not be a player either
+    pub is_unknown_npc: bool,
+}
+
+pub fn parse_unit(cache: &mut HashMap<String, CachedUnit>, data: &Data, language_id: u8, unit_name: &str) -> Result<Unit, CblParseFailure> {
     if unit_name == "Unknown" {
-        return None;
+        return Err(CblParseFailure::UnknownUnit(unit_name.to_string()));
     }
 
     let unit_name = unit_name.to_string();
-    if let Some(unit) = cache.get(&unit_name) {
-        return Some(unit.clone());
+    if let Some(cached_unit) = cache.get(&unit_name) {
+        return Ok(cached_unit.unit.clone());
     }
 
     let unit;
-    if let Some(unit_id) = get_npc_unit_id(data, &unit_name) {
+    let mut is_unknown_npc = false;
+    if let Some(unit_id) = get_localized_npc_unit_id(data, language_id, &unit_name) {
         let self_damage = unit_name.contains("self damage");
 
         unit = Unit { is_player: false, unit_id, is_self_damage: self_damage, is_mind_control:false };
     } else {
         // This indicates that something went terribly wrong during parsing
         if unit_name.contains("'s ") {
-            return None;
+            return Err(CblParseFailure::UnknownUnit(unit_name));
         }
         let self_damage = unit_name.contains("self damage");
+        let mind_control = !self_damage && unit_name.contains("(") && unit_name.contains(")");
+        // Player names never contain spaces, these are most likely NPCs that are not known
+        is_unknown_npc = !self_damage && !mind_control && unit_name.contains(' ');
         unit = Unit {
             is_player: true,
             unit_id: get_hashed_player_unit_id(unit_name.as_str()),
             is_self_damage: self_damage,
-            is_mind_control: !self_damage && unit_name.contains("(") && unit_name.contains(")"),
+            is_mind_control: mind_control,
         }
     }
 
-    cache.insert(unit_name, unit.clone());
-    Some(unit)
+    cache.insert(unit_name, CachedUnit { unit: unit.clone(), is_unknown_npc });
+    Ok(unit)
 }
@@ -1,14 +1,16 @@
 use chrono::NaiveDateTime;
-use regex::Regex;
+use regex::{Captures, Match, Regex};
 use crate::modules::armory::domain_value::GuildRank;
 use crate::modules::armory::dto::{CharacterDto, CharacterGearDto, CharacterGuildDto, CharacterHistoryDto, CharacterInfoDto, CharacterItemDto, GuildDto};
 use crate::modules::data::Data;
-use crate::modules::data::tools::{RetrieveMap};
+use crate::modules::data::domain_value::SpecRuleSource;
+use crate::modules::data::tools::{ReloadData, RetrieveMap, RetrieveSpecRule};
 use crate::modules::live_data_processor::domain_value::{HitType, School};
-use crate::modules::live_data_processor::dto::{AuraApplication, DamageComponent, DamageDone, Death, HealDone, InstanceMap, Interrupt, Loot, Message, MessageType, PlayersInCombat, SpellCast, Summon, UnAura, Unit};
+use crate::modules::live_data_processor::dto::{AuraApplication, DamageComponent, DamageDone, Death, HealDone, InstanceMap, Interrupt, Loot, Message, MessageType, ParseReport, PlayersInCombat, SpellCast, Summon, UnAura, Unit};
 use crate::modules::live_data_processor::material::{ActiveMapVec, Participant, WoWVanillaParser};
-use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
+use crate::modules::live_data_processor::tools::cbl_parser::{CblParseFailure, CombatLogParser};
 use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::hashed_unit_id::{get_hashed_player_unit_id, get_npc_unit_id};
+use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::locale::sniff_combat_log_locale;
 use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::parse_spell_args::{parse_spell_args, parse_spell_args_periodic};
 use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::parse_trailer::parse_trailer;
 use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::parse_unit::parse_unit;
@@ -85,151 +87,37 @@ SPELLHAPPINESSDRAINOTHER
 POWERGAINOTHEROTHER
 
  */
-pub const WARRIOR_ARMS_SPEC: &str = "51|0|0";
-pub const WARRIOR_FURY_SPEC: &str = "0|51|0";
-pub const WARRIOR_PROTECTION_SPEC: &str = "0|0|51";
-pub const PALADIN_HOLY_SPEC: &str = "51|0|0";
-pub const PALADIN_PROTECTION_SPEC: &str = "0|51|0";
-pub const PALADIN_RETRIBUTION_SPEC: &str = "0|0|51";
-pub const HUNTER_BEAST_MASTERY_SPEC: &str = "51|0|0";
-pub const HUNTER_MARKSMANSHIP_SPEC: &str = "0|51|0";
-pub const HUNTER_SURVIVAL_SPEC: &str = "0|0|51";
-pub const ROGUE_ASSASSINATION_SPEC: &str = "51|0|0";
-pub const ROGUE_COMBAT_SPEC: &str = "0|51|0";
-pub const ROGUE_SUBTLETY_SPEC: &str = "0|0|51";
-pub const PRIEST_DISCIPLINE_SPEC: &str = "51|0|0";
-pub const PRIEST_HOLY_SPEC: &str = "0|51|0";
-pub const PRIEST_SHADOW_SPEC: &str = "0|0|51";
-pub const SHAMAN_ELEMENTAL_SPEC: &str = "51|0|0";
-pub const SHAMAN_ENHANCEMENT_SPEC: &str = "0|51|0";
-pub const SHAMAN_RESTORATION_SPEC: &str = "0|0|51";
-pub const MAGE_ARCANE_SPEC: &str = "51|0|0";
-pub const MAGE_FIRE_SPEC: &str = "0|51|0";
-pub const MAGE_FROST_SPEC: &str = "0|0|51";
-pub const WARLOCK_AFFLICTION_SPEC: &str = "51|0|0";
-pub const WARLOCK_DEMONOLOGY_SPEC: &str = "0|51|0";
-pub const WARLOCK_DESTRUCTION_SPEC: &str = "0|0|51";
-pub const DRUID_BALANCE_SPEC: &str = "51|0|0";
-pub const DRUID_FERAL_COMBAT_SPEC: &str = "0|51|0";
-pub const DRUID_RESTORATION_SPEC: &str = "0|0|51";
-
-fn assign_spec_from_aura_gain(receiver: Option<&mut Participant>, spell: &str, timestamp: u64) {
-    if receiver.is_none() {
-        return;
-    }
+// Talent strings stand in for the actual talents, as combat logs only reveal the talent tree
+fn spec_talent_string(talent_tree: u8) -> String {
+    (0..3).map(|tree| if tree == talent_tree { "51" } else { "0" }).collect::<Vec<&str>>().join("|")
+}
+
+fn assign_spec(data: &Data, participant: Option<&mut Participant>, source: SpecRuleSource, spell: &str, timestamp: u64) {
+    let p = match participant {
+        Some(p) => p,
+        None => return,
+    };
 
-    if spell == "Scrambled Brain" {
+    if source == SpecRuleSource::AuraGain && spell == "Scrambled Brain" {
         // track when brainwasher used for spec changes
-        let p = receiver.unwrap();
         p.last_brainwash = timestamp;
-    } else if spell == "Arcane Eclipse" || spell == "Nature Eclipse" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, DRUID_BALANCE_SPEC);
-    } else if spell == "Blood Frenzy" || spell == "Berserk" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, DRUID_FERAL_COMBAT_SPEC);
-    } else if spell == "Tidal Surge" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, SHAMAN_RESTORATION_SPEC);
-    } else if spell == "Holy Might" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, PALADIN_RETRIBUTION_SPEC);
-    } else if spell == "Tree of Life Form" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, DRUID_RESTORATION_SPEC);
-    } else if spell == "Arcane Power" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, MAGE_ARCANE_SPEC);
-    } else if spell == "Combustion" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, MAGE_FIRE_SPEC);
-    } else if spell == "Ice Barrier" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, MAGE_FROST_SPEC);
-    } else if spell == "Seal of Command" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, PALADIN_RETRIBUTION_SPEC);
-    } else if spell == "Elemental Mastery" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, SHAMAN_ELEMENTAL_SPEC);
-    } else if spell == "Stormstrike" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, SHAMAN_ENHANCEMENT_SPEC);
-    } else if spell == "Envenom" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, ROGUE_ASSASSINATION_SPEC);
-    } else if spell == "Adrenaline Rush" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, ROGUE_COMBAT_SPEC);
-    } else if spell == "Frenzy Effect" {
-        let p = receiver.unwrap();
-        p.record_talents(timestamp, HUNTER_BEAST_MASTERY_SPEC);
-    }
-}
-
-fn assign_spec_from_heal(caster: Option<&mut Participant>, spell: &str, timestamp: u64) {
-    if caster.is_none() {
+        p.reset_spec_evidence(timestamp);
         return;
     }
 
-    if spell == "Holy Shock" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, PALADIN_HOLY_SPEC);
-    }
-}
-
-fn assign_spec_from_cast(caster: Option<&mut Participant>, spell: &str, timestamp: u64) {
-    if caster.is_none() {
+    let spec_rules = data.get_spec_rules(source, spell);
+    if spec_rules.is_empty() {
         return;
     }
 
-    if spell == "Mortal Strike" || spell == "Sweeping Strikes" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, WARRIOR_ARMS_SPEC);
-    } else if spell == "Bloodthirst" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, WARRIOR_FURY_SPEC);
-    } else if spell == "Shield Slam" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, WARRIOR_PROTECTION_SPEC);
-    } else if spell == "Bulwark of the Righteous" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, PALADIN_PROTECTION_SPEC);
-    } else if spell == "Bestial Wrath" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, HUNTER_BEAST_MASTERY_SPEC);
-    } else if spell == "Piercing Shots" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, HUNTER_MARKSMANSHIP_SPEC);
-    } else if spell == "Carve" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, HUNTER_SURVIVAL_SPEC);
-    } else if spell == "Enlighten" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, PRIEST_DISCIPLINE_SPEC);
-    } else if spell == "Proclaim Champion" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, PRIEST_HOLY_SPEC);
-    } else if spell == "Vampiric Embrace" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, PRIEST_SHADOW_SPEC);
-    } else if spell == "Dark Harvest" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, WARLOCK_AFFLICTION_SPEC);
-    } else if spell == "Power Overwhelming" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, WARLOCK_DEMONOLOGY_SPEC);
-    } else if spell == "Conflagrate" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, WARLOCK_DESTRUCTION_SPEC);
-    } else if spell == "Mark for Death" {
-        let p = caster.unwrap();
-        p.record_talents(timestamp, ROGUE_SUBTLETY_SPEC);
+    spec_rules.iter().for_each(|spec_rule| p.add_spec_evidence(spec_rule, timestamp));
+    if let Some(spec) = p.get_detected_spec() {
+        p.record_talents(timestamp, &spec_talent_string(spec.talent_tree));
     }
 }
 
-impl CombatLogParser for WoWVanillaParser {
-    fn parse_cbl_line(&mut self, data: &Data, event_ts: u64, content: &str) -> Option<Vec<MessageType>> {
+impl WoWVanillaParser {
+    fn parse_line(&mut self, data: &Data, event_ts: u64, content: &str) -> Result<Vec<MessageType>, CblParseFailure> {
         lazy_static! {
             static ref RE_DAMAGE_HIT_OR_CRIT: Regex = Regex::new(r"(.+[^\s]) (cr|h)its (.+[^\s]) for (\d+)\.\s?(.*)").unwrap();
             static ref RE_DAMAGE_HIT_OR_CRIT_SCHOOL: Regex = Regex::new(r"(.+[^\s]) (cr|h)its (.+[^\s]) for (\d+) ([a-zA-Z]+) damage\.\s?(.*)").unwrap();
@@ -282,18 +170,20 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if RE_BUG_DAMAGE_SPELL_HIT_OR_CRIT.captures(&content).is_some() {
-            return None;
+            self.line_family = "known_bug";
+            return Ok(Vec::new());
         }
 
         if let Some(captures) = RE_SPELL_CAST_ATTEMPT.captures(&content) {
-            let caster = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
+            self.line_family = "spell_cast";
+            let caster = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
 
-            let spell_name = captures.get(2)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
+            let spell_name = capture(&captures, 2)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
 
-            assign_spec_from_cast(self.participants.get_mut(&caster.unit_id), spell_name, event_ts);
+            assign_spec(data, self.participants.get_mut(&caster.unit_id), SpecRuleSource::Cast, spell_name, event_ts);
 
-            return Some(vec![MessageType::SpellCastAttempt(SpellCast {
+            return Ok(vec![MessageType::SpellCastAttempt(SpellCast {
                 caster,
                 target: None,
                 spell_id,
@@ -302,23 +192,25 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_GAIN.captures(&content) {
-            if !captures.get(3)?.as_str().contains("ealth") {
-                return None;
+            self.line_family = "heal";
+            if !capture(&captures, 3)?.as_str().contains("ealth") {
+                // Power gains are not tracked
+                return Ok(Vec::new());
             }
 
-            let target = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let amount = u32::from_str_radix(captures.get(2)?.as_str(), 10).ok()?;
-            let caster = parse_unit(&mut self.cache_unit, data, captures.get(4)?.as_str())?;
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, captures.get(5)?.as_str())?;
-            self.collect_participant(&caster, captures.get(4)?.as_str(), event_ts);
-            self.collect_participant(&target, captures.get(1)?.as_str(), event_ts);
+            let target = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let amount = u32::from_str_radix(capture(&captures, 2)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+            let caster = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 4)?.as_str())?;
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), capture(&captures, 5)?.as_str())?;
+            self.collect_participant(&caster, capture(&captures, 4)?.as_str(), event_ts);
+            self.collect_participant(&target, capture(&captures, 1)?.as_str(), event_ts);
             self.collect_active_map(data, &caster, event_ts);
             self.collect_active_map(data, &target, event_ts);
-            let effective_heal = self.participants.get_mut(&target.unit_id).unwrap().attribute_heal(amount);
+            let (effective_heal, overheal) = self.participants.get_mut(&target.unit_id).unwrap().attribute_heal(amount, 0);
 
-            assign_spec_from_aura_gain(self.participants.get_mut(&caster.unit_id), captures.get(5)?.as_str(), event_ts);
+            assign_spec(data, self.participants.get_mut(&caster.unit_id), SpecRuleSource::AuraGain, capture(&captures, 5)?.as_str(), event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: caster.clone(),
                     target: Some(target.clone()),
@@ -331,6 +223,7 @@ impl CombatLogParser for WoWVanillaParser {
                     spell_id,
                     total_heal: amount,
                     effective_heal,
+                    overheal,
                     absorb: 0,
                     hit_mask: HitType::Hit as u32,
                 }),
@@ -341,23 +234,24 @@ impl CombatLogParser for WoWVanillaParser {
          * Spell Damage
          */
         if let Some(captures) = RE_DAMAGE_SPELL_HIT_OR_CRIT.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_name = captures.get(2)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
-            let mut hit_mask = if captures.get(3)?.as_str() == "cr" { HitType::Crit as u32 } else { HitType::Hit as u32 };
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(4)?.as_str())?;
-            let damage = u32::from_str_radix(captures.get(5)?.as_str(), 10).ok()?;
-            let trailer = parse_trailer(captures.get(6)?.as_str());
+            self.line_family = "spell_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_name = capture(&captures, 2)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
+            let mut hit_mask = if capture(&captures, 3)?.as_str() == "cr" { HitType::Crit as u32 } else { HitType::Hit as u32 };
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 4)?.as_str())?;
+            let damage = u32::from_str_radix(capture(&captures, 5)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+            let trailer = parse_trailer(capture(&captures, 6)?.as_str())?;
             trailer.iter().for_each(|(_, hit_type)| hit_mask |= hit_type.clone() as u32);
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(4)?.as_str(), event_ts);
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 4)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
             self.participants.get_mut(&victim.unit_id).unwrap().attribute_damage(damage);
 
-            assign_spec_from_cast(self.participants.get_mut(&attacker.unit_id), spell_name, event_ts);
+            assign_spec(data, self.participants.get_mut(&attacker.unit_id), SpecRuleSource::Cast, spell_name, event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -383,13 +277,14 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_SPELL_HIT_OR_CRIT_SCHOOL.captures(&content) {
-            let mut attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_name = captures.get(2)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
-            let mut hit_mask = if captures.get(3)?.as_str() == "cr" { HitType::Crit as u32 } else { HitType::Hit as u32 };
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(4)?.as_str())?;
-            let damage = u32::from_str_radix(captures.get(5)?.as_str(), 10).ok()?;
-            let school = match captures.get(6)?.as_str() {
+            self.line_family = "spell_damage";
+            let mut attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_name = capture(&captures, 2)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
+            let mut hit_mask = if capture(&captures, 3)?.as_str() == "cr" { HitType::Crit as u32 } else { HitType::Hit as u32 };
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 4)?.as_str())?;
+            let damage = u32::from_str_radix(capture(&captures, 5)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+            let school = match capture(&captures, 6)?.as_str() {
                 "Physical" => School::Physical,
                 "Arcane" => School::Arcane,
                 "Fire" => School::Fire,
@@ -397,21 +292,21 @@ impl CombatLogParser for WoWVanillaParser {
                 "Shadow" => School::Shadow,
                 "Nature" => School::Nature,
                 "Holy" => School::Holy,
-                _ => unreachable!(),
+                unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
             };
-            let trailer = parse_trailer(captures.get(7)?.as_str());
+            let trailer = parse_trailer(capture(&captures, 7)?.as_str())?;
             trailer.iter().for_each(|(_, hit_type)| hit_mask |= hit_type.clone() as u32);
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(4)?.as_str(), event_ts);
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 4)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
             self.participants.get_mut(&victim.unit_id).unwrap().attribute_damage(damage);
 
-            assign_spec_from_cast(self.participants.get_mut(&attacker.unit_id), spell_name, event_ts);
+            assign_spec(data, self.participants.get_mut(&attacker.unit_id), SpecRuleSource::Cast, spell_name, event_ts);
 
             if spell_name == "Shackle Shatter" {
                 // change attacker to Mephistroth
-                attacker = Unit { is_player: false, unit_id: get_npc_unit_id(data, "Mephistroth").unwrap(), is_self_damage: false, is_mind_control:false };
+                attacker = Unit { is_player: false, unit_id: get_npc_unit_id(data, "Mephistroth").ok_or_else(|| CblParseFailure::UnknownNpc("Mephistroth".to_string()))?, is_self_damage: false, is_mind_control:false };
             }
 
             // Check if damage is 0 and handle as absorb-only
@@ -420,7 +315,7 @@ impl CombatLogParser for WoWVanillaParser {
                     .map(|(amount, _)| amount.unwrap())
                     .unwrap_or(0);
 
-                return Some(vec![MessageType::SpellDamage(DamageDone {
+                return Ok(vec![MessageType::SpellDamage(DamageDone {
                     attacker,
                     victim,
                     spell_id: Some(spell_id),
@@ -437,7 +332,7 @@ impl CombatLogParser for WoWVanillaParser {
                 })]);
             }
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -463,9 +358,10 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_PERIODIC.captures(&content) {
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let damage = u32::from_str_radix(captures.get(2)?.as_str(), 10).ok()?;
-            let school = match captures.get(3)?.as_str() {
+            self.line_family = "spell_damage";
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let damage = u32::from_str_radix(capture(&captures, 2)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+            let school = match capture(&captures, 3)?.as_str() {
                 "Physical" => School::Physical,
                 "Arcane" => School::Arcane,
                 "Fire" => School::Fire,
@@ -473,36 +369,36 @@ impl CombatLogParser for WoWVanillaParser {
                 "Shadow" => School::Shadow,
                 "Nature" => School::Nature,
                 "Holy" => School::Holy,
-                _ => unreachable!(),
+                unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
             };
 
-            let spell_name = captures.get(5)?.as_str();
-            let spell_id = parse_spell_args_periodic(&mut self.cache_spell_id, data, spell_name)?;
+            let spell_name = capture(&captures, 5)?.as_str();
+            let spell_id = parse_spell_args_periodic(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
 
-            let mut attacker_capture = captures.get(4)?.as_str().to_string();
+            let mut attacker_capture = capture(&captures, 4)?.as_str().to_string();
 
             if (spell_name == "Power Overwhelming") && !attacker_capture.contains("self damage") {
                 // assign demo spec to the original attacker
-                let original_attacker = parse_unit(&mut self.cache_unit, data, attacker_capture.as_str())?;
-                assign_spec_from_cast(self.participants.get_mut(&original_attacker.unit_id), spell_name, event_ts);
+                let original_attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), attacker_capture.as_str())?;
+                assign_spec(data, self.participants.get_mut(&original_attacker.unit_id), SpecRuleSource::Cast, spell_name, event_ts);
 
                 // append (self damage) to the attacker name
                 attacker_capture = format!("{} (self damage)", attacker_capture);
             }
 
-            let attacker = parse_unit(&mut self.cache_unit, data, attacker_capture.as_str())?;
-            assign_spec_from_cast(self.participants.get_mut(&attacker.unit_id), spell_name, event_ts);
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), attacker_capture.as_str())?;
+            assign_spec(data, self.participants.get_mut(&attacker.unit_id), SpecRuleSource::Cast, spell_name, event_ts);
 
             let mut hit_mask = HitType::Hit as u32;
-            let trailer = parse_trailer(captures.get(6)?.as_str());
+            let trailer = parse_trailer(capture(&captures, 6)?.as_str())?;
             trailer.iter().for_each(|(_, hit_type)| hit_mask |= hit_type.clone() as u32);
-            self.collect_participant(&victim, captures.get(1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 1)?.as_str(), event_ts);
             self.collect_participant(&attacker, attacker_capture.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
             self.participants.get_mut(&victim.unit_id).unwrap().attribute_damage(damage);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -528,9 +424,10 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_SHIELD.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let damage = u32::from_str_radix(captures.get(2)?.as_str(), 10).ok()?;
-            let school = match captures.get(3)?.as_str() {
+            self.line_family = "spell_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let damage = u32::from_str_radix(capture(&captures, 2)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+            let school = match capture(&captures, 3)?.as_str() {
                 "Physical" => School::Physical,
                 "Arcane" => School::Arcane,
                 "Fire" => School::Fire,
@@ -538,17 +435,17 @@ impl CombatLogParser for WoWVanillaParser {
                 "Shadow" => School::Shadow,
                 "Nature" => School::Nature,
                 "Holy" => School::Holy,
-                _ => unreachable!(),
+                unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
             };
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(4)?.as_str())?;
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 4)?.as_str())?;
             let spell_id = 9906; // Spell named 'Reflection'
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(4)?.as_str(), event_ts);
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 4)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
             self.participants.get_mut(&victim.unit_id).unwrap().attribute_damage(damage);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -577,19 +474,20 @@ impl CombatLogParser for WoWVanillaParser {
          * Melee Damage
          */
         if let Some(captures) = RE_DAMAGE_HIT_OR_CRIT.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let mut hit_mask = if captures.get(2)?.as_str() == "cr" { HitType::Crit as u32 } else { HitType::Hit as u32 };
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(3)?.as_str())?;
-            let damage = u32::from_str_radix(captures.get(4)?.as_str(), 10).ok()?;
-            let trailer = parse_trailer(captures.get(5)?.as_str());
+            self.line_family = "melee_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let mut hit_mask = if capture(&captures, 2)?.as_str() == "cr" { HitType::Crit as u32 } else { HitType::Hit as u32 };
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            let damage = u32::from_str_radix(capture(&captures, 4)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+            let trailer = parse_trailer(capture(&captures, 5)?.as_str())?;
             trailer.iter().for_each(|(_, hit_type)| hit_mask |= hit_type.clone() as u32);
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(3)?.as_str(), event_ts);
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 3)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
             self.participants.get_mut(&victim.unit_id).unwrap().attribute_damage(damage);
 
-            return Some(vec![MessageType::MeleeDamage(DamageDone {
+            return Ok(vec![MessageType::MeleeDamage(DamageDone {
                 attacker,
                 victim,
                 spell_id: None,
@@ -607,11 +505,12 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_HIT_OR_CRIT_SCHOOL.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let mut hit_mask = if captures.get(2)?.as_str() == "cr" { HitType::Crit as u32 } else { HitType::Hit as u32 };
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(3)?.as_str())?;
-            let damage = u32::from_str_radix(captures.get(4)?.as_str(), 10).ok()?;
-            let school = match captures.get(5)?.as_str() {
+            self.line_family = "melee_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let mut hit_mask = if capture(&captures, 2)?.as_str() == "cr" { HitType::Crit as u32 } else { HitType::Hit as u32 };
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            let damage = u32::from_str_radix(capture(&captures, 4)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+            let school = match capture(&captures, 5)?.as_str() {
                 "Physical" => School::Physical,
                 "Arcane" => School::Arcane,
                 "Fire" => School::Fire,
@@ -619,17 +518,17 @@ impl CombatLogParser for WoWVanillaParser {
                 "Shadow" => School::Shadow,
                 "Nature" => School::Nature,
                 "Holy" => School::Holy,
-                _ => unreachable!(),
+                unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
             };
-            let trailer = parse_trailer(captures.get(6)?.as_str());
+            let trailer = parse_trailer(capture(&captures, 6)?.as_str())?;
             trailer.iter().for_each(|(_, hit_type)| hit_mask |= hit_type.clone() as u32);
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(3)?.as_str(), event_ts);
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 3)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
             self.participants.get_mut(&victim.unit_id).unwrap().attribute_damage(damage);
 
-            return Some(vec![MessageType::MeleeDamage(DamageDone {
+            return Ok(vec![MessageType::MeleeDamage(DamageDone {
                 attacker,
                 victim,
                 spell_id: None,
@@ -651,20 +550,21 @@ impl CombatLogParser for WoWVanillaParser {
          */
 
         if let Some(captures) = RE_HEAL_CRIT.captures(&content) {
-            let caster = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, captures.get(2)?.as_str())?;
+            self.line_family = "heal";
+            let caster = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
             let hit_mask = HitType::Crit as u32;
-            let target = parse_unit(&mut self.cache_unit, data, captures.get(3)?.as_str())?;
-            let amount = u32::from_str_radix(captures.get(4)?.as_str(), 10).ok()?;
-            self.collect_participant(&caster, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&target, captures.get(3)?.as_str(), event_ts);
+            let target = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            let amount = u32::from_str_radix(capture(&captures, 4)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+            self.collect_participant(&caster, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&target, capture(&captures, 3)?.as_str(), event_ts);
             self.collect_active_map(data, &caster, event_ts);
             self.collect_active_map(data, &target, event_ts);
-            let effective_heal = self.participants.get_mut(&target.unit_id).unwrap().attribute_heal(amount);
+            let (effective_heal, overheal) = self.participants.get_mut(&target.unit_id).unwrap().attribute_heal(amount, 0);
 
-            assign_spec_from_heal(self.participants.get_mut(&caster.unit_id), captures.get(2)?.as_str(), event_ts);
+            assign_spec(data, self.participants.get_mut(&caster.unit_id), SpecRuleSource::Heal, capture(&captures, 2)?.as_str(), event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: caster.clone(),
                     target: Some(target.clone()),
@@ -677,6 +577,7 @@ impl CombatLogParser for WoWVanillaParser {
                     spell_id,
                     total_heal: amount,
                     effective_heal,
+                    overheal,
                     absorb: 0,
                     hit_mask,
                 }),
@@ -684,20 +585,21 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_HEAL_HIT.captures(&content) {
-            let caster = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, captures.get(2)?.as_str())?;
+            self.line_family = "heal";
+            let caster = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
             let hit_mask = HitType::Hit as u32;
-            let target = parse_unit(&mut self.cache_unit, data, captures.get(3)?.as_str())?;
-            let amount = u32::from_str_radix(captures.get(4)?.as_str(), 10).ok()?;
-            self.collect_participant(&caster, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&target, captures.get(3)?.as_str(), event_ts);
+            let target = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            let amount = u32::from_str_radix(capture(&captures, 4)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+            self.collect_participant(&caster, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&target, capture(&captures, 3)?.as_str(), event_ts);
             self.collect_active_map(data, &caster, event_ts);
             self.collect_active_map(data, &target, event_ts);
-            let effective_heal = self.participants.get_mut(&target.unit_id).unwrap().attribute_heal(amount);
+            let (effective_heal, overheal) = self.participants.get_mut(&target.unit_id).unwrap().attribute_heal(amount, 0);
 
-            assign_spec_from_heal(self.participants.get_mut(&caster.unit_id), captures.get(2)?.as_str(), event_ts);
+            assign_spec(data, self.participants.get_mut(&caster.unit_id), SpecRuleSource::Heal, capture(&captures, 2)?.as_str(), event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: caster.clone(),
                     target: Some(target.clone()),
@@ -710,6 +612,7 @@ impl CombatLogParser for WoWVanillaParser {
                     spell_id,
                     total_heal: amount,
                     effective_heal,
+                    overheal,
                     absorb: 0,
                     hit_mask,
                 }),
@@ -720,19 +623,20 @@ impl CombatLogParser for WoWVanillaParser {
          * Aura Application
          */
         if let Some(captures) = RE_AURA_GAIN_HARMFUL_HELPFUL.captures(&content) {
-            let target = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let helpful_harmful = captures.get(2)?.as_str();
-            let spell_name = captures.get(3)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
-            let stack_amount = u8::from_str_radix(captures.get(4)?.as_str(), 10).ok()?;
+            self.line_family = "aura";
+            let target = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let helpful_harmful = capture(&captures, 2)?.as_str();
+            let spell_name = capture(&captures, 3)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
+            let stack_amount = u8::from_str_radix(capture(&captures, 4)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
             let caster = Unit { is_player: true, unit_id: 0, is_self_damage: false, is_mind_control: false };
-            self.collect_participant(&target, captures.get(1)?.as_str(), event_ts);
+            self.collect_participant(&target, capture(&captures, 1)?.as_str(), event_ts);
             self.collect_active_map(data, &target, event_ts);
 
-            assign_spec_from_aura_gain(self.participants.get_mut(&target.unit_id), spell_name, event_ts);
+            assign_spec(data, self.participants.get_mut(&target.unit_id), SpecRuleSource::AuraGain, spell_name, event_ts);
 
             return if helpful_harmful == "gains" {
-                Some(vec![MessageType::AuraApplication(AuraApplication {
+                Ok(vec![MessageType::AuraApplication(AuraApplication {
                     caster,
                     target,
                     spell_id,
@@ -740,7 +644,7 @@ impl CombatLogParser for WoWVanillaParser {
                     delta: stack_amount as i8,
                 })])
             } else {
-                Some(vec![
+                Ok(vec![
                     MessageType::AuraApplication(AuraApplication {
                         caster,
                         target,
@@ -753,13 +657,14 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_AURA_FADE.captures(&content) {
-            let target = parse_unit(&mut self.cache_unit, data, captures.get(2)?.as_str())?;
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, captures.get(1)?.as_str())?;
+            self.line_family = "aura";
+            let target = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
             let caster = Unit { is_player: true, unit_id: 0, is_self_damage: false, is_mind_control: false };
-            self.collect_participant(&target, captures.get(2)?.as_str(), event_ts);
+            self.collect_participant(&target, capture(&captures, 2)?.as_str(), event_ts);
             self.collect_active_map(data, &target, event_ts);
 
-            return Some(vec![MessageType::AuraApplication(AuraApplication {
+            return Ok(vec![MessageType::AuraApplication(AuraApplication {
                 caster,
                 target,
                 spell_id,
@@ -772,30 +677,31 @@ impl CombatLogParser for WoWVanillaParser {
          * Spell damage continued
          */
         if let Some(captures) = RE_DAMAGE_SPELL_SPLIT.captures(&content) {
-            let spell_name = captures.get(2)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(3)?.as_str())?;
-            let damage = u32::from_str_radix(captures.get(4)?.as_str(), 10).ok()?;
+            self.line_family = "spell_damage";
+            let spell_name = capture(&captures, 2)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            let damage = u32::from_str_radix(capture(&captures, 4)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
 
-            let mut attacker_capture = captures.get(1)?.as_str().to_string();
+            let mut attacker_capture = capture(&captures, 1)?.as_str().to_string();
 
             if (spell_name == "Soul Link") && !attacker_capture.contains("self damage") {
                 // append (self damage) to the attacker name
                 attacker_capture = format!("{} (self damage)", attacker_capture);
             }
 
-            let attacker = parse_unit(&mut self.cache_unit, data, attacker_capture.as_str())?;
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), attacker_capture.as_str())?;
 
             let mut hit_mask = HitType::Hit as u32;
-            let trailer = parse_trailer(captures.get(5)?.as_str());
+            let trailer = parse_trailer(capture(&captures, 5)?.as_str())?;
             trailer.iter().for_each(|(_, hit_type)| hit_mask |= hit_type.clone() as u32);
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(3)?.as_str(), event_ts);
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 3)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
             self.participants.get_mut(&victim.unit_id).unwrap().attribute_damage(damage);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -821,18 +727,19 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_SPELL_MISS.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_name = captures.get(2)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(4)?.as_str())?;
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(4)?.as_str(), event_ts);
+            self.line_family = "spell_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_name = capture(&captures, 2)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 4)?.as_str())?;
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 4)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            assign_spec_from_cast(self.participants.get_mut(&attacker.unit_id), spell_name, event_ts);
+            assign_spec(data, self.participants.get_mut(&attacker.unit_id), SpecRuleSource::Cast, spell_name, event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -853,27 +760,28 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_SPELL_BLOCK_PARRY_DODGE_RESIST_DEFLECT.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_name = captures.get(2)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
-            let hit_type = match captures.get(3)?.as_str() {
+            self.line_family = "spell_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_name = capture(&captures, 2)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
+            let hit_type = match capture(&captures, 3)?.as_str() {
                 "blocked" => HitType::FullBlock,
                 "parried" => HitType::Parry,
                 "evaded" => HitType::Evade,
                 "dodged" => HitType::Dodge,
                 "deflected" => HitType::Deflect,
                 "resisted" => HitType::FullResist,
-                _ => unreachable!(),
+                unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
             };
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(4)?.as_str())?;
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(4)?.as_str(), event_ts);
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 4)?.as_str())?;
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 4)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            assign_spec_from_cast(self.participants.get_mut(&attacker.unit_id), spell_name, event_ts);
+            assign_spec(data, self.participants.get_mut(&attacker.unit_id), SpecRuleSource::Cast, spell_name, event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -894,18 +802,19 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_SPELL_ABSORB.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_name = captures.get(2)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(3)?.as_str())?;
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(3)?.as_str(), event_ts);
+            self.line_family = "spell_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_name = capture(&captures, 2)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 3)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            assign_spec_from_cast(self.participants.get_mut(&attacker.unit_id), spell_name, event_ts);
+            assign_spec(data, self.participants.get_mut(&attacker.unit_id), SpecRuleSource::Cast, spell_name, event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -926,18 +835,19 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_SPELL_ABSORB_SELF.captures(&content) {
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(2)?.as_str())?;
-            let spell_name = captures.get(3)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
-            self.collect_participant(&victim, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&attacker, captures.get(2)?.as_str(), event_ts);
+            self.line_family = "spell_damage";
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            let spell_name = capture(&captures, 3)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
+            self.collect_participant(&victim, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&attacker, capture(&captures, 2)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            assign_spec_from_cast(self.participants.get_mut(&attacker.unit_id), captures.get(3)?.as_str(), event_ts);
+            assign_spec(data, self.participants.get_mut(&attacker.unit_id), SpecRuleSource::Cast, capture(&captures, 3)?.as_str(), event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -958,18 +868,19 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_REFLECT.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_name = captures.get(2)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, captures.get(2)?.as_str())?;
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(3)?.as_str())?;
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(3)?.as_str(), event_ts);
+            self.line_family = "spell_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_name = capture(&captures, 2)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 3)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            assign_spec_from_cast(self.participants.get_mut(&attacker.unit_id), captures.get(2)?.as_str(), event_ts);
+            assign_spec(data, self.participants.get_mut(&attacker.unit_id), SpecRuleSource::Cast, capture(&captures, 2)?.as_str(), event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -990,18 +901,19 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_PROC_RESIST.captures(&content) {
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(2)?.as_str())?;
-            let spell_name = captures.get(3)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(2)?.as_str(), event_ts);
+            self.line_family = "spell_damage";
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            let spell_name = capture(&captures, 3)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 2)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            assign_spec_from_cast(self.participants.get_mut(&attacker.unit_id), spell_name, event_ts);
+            assign_spec(data, self.participants.get_mut(&attacker.unit_id), SpecRuleSource::Cast, spell_name, event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -1022,18 +934,19 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_SPELL_IMMUNE.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_name = captures.get(2)?.as_str();
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, spell_name)?;
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(3)?.as_str())?;
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(3)?.as_str(), event_ts);
+            self.line_family = "spell_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_name = capture(&captures, 2)?.as_str();
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), spell_name)?;
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 3)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            assign_spec_from_cast(self.participants.get_mut(&attacker.unit_id), captures.get(2)?.as_str(), event_ts);
+            assign_spec(data, self.participants.get_mut(&attacker.unit_id), SpecRuleSource::Cast, capture(&captures, 2)?.as_str(), event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: attacker.clone(),
                     target: Some(victim.clone()),
@@ -1057,14 +970,15 @@ impl CombatLogParser for WoWVanillaParser {
          * Melee Damage continued
          */
         if let Some(captures) = RE_DAMAGE_MISS.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(2)?.as_str())?;
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(2)?.as_str(), event_ts);
+            self.line_family = "melee_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 2)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            return Some(vec![MessageType::MeleeDamage(DamageDone {
+            return Ok(vec![MessageType::MeleeDamage(DamageDone {
                 attacker,
                 victim,
                 spell_id: None,
@@ -1077,22 +991,23 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_BLOCK_PARRY_DODGE_DEFLECT.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(2)?.as_str())?;
-            let hit_type = match captures.get(3)?.as_str() {
+            self.line_family = "melee_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            let hit_type = match capture(&captures, 3)?.as_str() {
                 "blocks" => HitType::FullBlock,
                 "parries" => HitType::Parry,
                 "evades" => HitType::Evade,
                 "dodges" => HitType::Dodge,
                 "deflects" => HitType::Deflect,
-                _ => unreachable!(),
+                unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
             };
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(2)?.as_str(), event_ts);
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 2)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            return Some(vec![MessageType::MeleeDamage(DamageDone {
+            return Ok(vec![MessageType::MeleeDamage(DamageDone {
                 attacker,
                 victim,
                 spell_id: None,
@@ -1105,19 +1020,20 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_ABSORB_RESIST.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(2)?.as_str())?;
-            let hit_type = match captures.get(3)?.as_str() {
+            self.line_family = "melee_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            let hit_type = match capture(&captures, 3)?.as_str() {
                 "absorbs" => HitType::FullAbsorb,
                 "resists" => HitType::FullResist,
-                _ => unreachable!(),
+                unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
             };
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(2)?.as_str(), event_ts);
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 2)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            return Some(vec![MessageType::MeleeDamage(DamageDone {
+            return Ok(vec![MessageType::MeleeDamage(DamageDone {
                 attacker,
                 victim,
                 spell_id: None,
@@ -1130,14 +1046,15 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_DAMAGE_IMMUNE.captures(&content) {
-            let attacker = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(2)?.as_str())?;
-            self.collect_participant(&attacker, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&victim, captures.get(2)?.as_str(), event_ts);
+            self.line_family = "melee_damage";
+            let attacker = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            self.collect_participant(&attacker, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&victim, capture(&captures, 2)?.as_str(), event_ts);
             self.collect_active_map(data, &attacker, event_ts);
             self.collect_active_map(data, &victim, event_ts);
 
-            return Some(vec![MessageType::MeleeDamage(DamageDone {
+            return Ok(vec![MessageType::MeleeDamage(DamageDone {
                 attacker,
                 victim,
                 spell_id: None,
@@ -1153,17 +1070,18 @@ impl CombatLogParser for WoWVanillaParser {
          * Spell casts
          */
         if let Some(captures) = RE_SPELL_CAST_PERFORM_DURABILITY.captures(&content) {
-            let caster = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, captures.get(3)?.as_str())?;
-            let target = parse_unit(&mut self.cache_unit, data, captures.get(4)?.as_str())?;
-            self.collect_participant(&caster, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&target, captures.get(4)?.as_str(), event_ts);
+            self.line_family = "spell_cast";
+            let caster = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            let target = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 4)?.as_str())?;
+            self.collect_participant(&caster, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&target, capture(&captures, 4)?.as_str(), event_ts);
             self.collect_active_map(data, &caster, event_ts);
             self.collect_active_map(data, &target, event_ts);
 
-            assign_spec_from_cast(self.participants.get_mut(&caster.unit_id), captures.get(3)?.as_str(), event_ts);
+            assign_spec(data, self.participants.get_mut(&caster.unit_id), SpecRuleSource::Cast, capture(&captures, 3)?.as_str(), event_ts);
 
-            return Some(vec![MessageType::SpellCast(SpellCast {
+            return Ok(vec![MessageType::SpellCast(SpellCast {
                 caster,
                 target: Some(target),
                 spell_id,
@@ -1172,17 +1090,18 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_SPELL_CAST_PERFORM.captures(&content) {
-            let caster = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, captures.get(3)?.as_str())?;
-            let target = parse_unit(&mut self.cache_unit, data, captures.get(4)?.as_str())?;
-            self.collect_participant(&caster, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&target, captures.get(4)?.as_str(), event_ts);
+            self.line_family = "spell_cast";
+            let caster = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            let target = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 4)?.as_str())?;
+            self.collect_participant(&caster, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&target, capture(&captures, 4)?.as_str(), event_ts);
             self.collect_active_map(data, &caster, event_ts);
             self.collect_active_map(data, &target, event_ts);
 
-            assign_spec_from_cast(self.participants.get_mut(&caster.unit_id), captures.get(3)?.as_str(), event_ts);
+            assign_spec(data, self.participants.get_mut(&caster.unit_id), SpecRuleSource::Cast, capture(&captures, 3)?.as_str(), event_ts);
 
-            return Some(vec![MessageType::SpellCast(SpellCast {
+            return Ok(vec![MessageType::SpellCast(SpellCast {
                 caster,
                 target: Some(target),
                 spell_id,
@@ -1191,14 +1110,15 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_SPELL_CAST_PERFORM_UNKNOWN.captures(&content) {
-            let caster = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, captures.get(3)?.as_str())?;
-            self.collect_participant(&caster, captures.get(1)?.as_str(), event_ts);
+            self.line_family = "spell_cast";
+            let caster = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            self.collect_participant(&caster, capture(&captures, 1)?.as_str(), event_ts);
             self.collect_active_map(data, &caster, event_ts);
 
-            assign_spec_from_cast(self.participants.get_mut(&caster.unit_id), captures.get(3)?.as_str(), event_ts);
+            assign_spec(data, self.participants.get_mut(&caster.unit_id), SpecRuleSource::Cast, capture(&captures, 3)?.as_str(), event_ts);
 
-            return Some(vec![MessageType::SpellCast(SpellCast {
+            return Ok(vec![MessageType::SpellCast(SpellCast {
                 caster,
                 target: None,
                 spell_id,
@@ -1210,29 +1130,34 @@ impl CombatLogParser for WoWVanillaParser {
          * Unit Death
          */
         if let Some(captures) = RE_UNIT_DIE_DESTROYED.captures(&content) {
-            let unit_name = captures.get(1)?.as_str();
-            let victim = parse_unit(&mut self.cache_unit, data, unit_name)?;
-            self.collect_participant(&victim, captures.get(1)?.as_str(), event_ts);
+            self.line_family = "death";
+            let unit_name = capture(&captures, 1)?.as_str();
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), unit_name)?;
+            self.collect_participant(&victim, capture(&captures, 1)?.as_str(), event_ts);
             self.collect_active_map(data, &victim, event_ts);
-            return Some(vec![MessageType::Death(Death { cause: None, victim })]);
+            self.participants.get_mut(&victim.unit_id).unwrap().attribute_death();
+            return Ok(vec![MessageType::Death(Death { cause: None, victim })]);
         }
 
         if let Some(captures) = RE_UNIT_SLAY.captures(&content) {
-            let victim = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let cause = parse_unit(&mut self.cache_unit, data, captures.get(2)?.as_str())?;
-            self.collect_participant(&victim, captures.get(1)?.as_str(), event_ts);
-            self.collect_participant(&cause, captures.get(2)?.as_str(), event_ts);
+            self.line_family = "death";
+            let victim = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let cause = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            self.collect_participant(&victim, capture(&captures, 1)?.as_str(), event_ts);
+            self.collect_participant(&cause, capture(&captures, 2)?.as_str(), event_ts);
             self.collect_active_map(data, &victim, event_ts);
             self.collect_active_map(data, &cause, event_ts);
-            return Some(vec![MessageType::Death(Death { cause: Some(cause), victim })]);
+            self.participants.get_mut(&victim.unit_id).unwrap().attribute_death();
+            return Ok(vec![MessageType::Death(Death { cause: Some(cause), victim })]);
         }
 
         if let Some(captures) = PLAYERS_IN_COMBAT.captures(&content) {
-            let in_combat = u32::from_str_radix(captures.get(1)?.as_str(), 10).ok()?;
-            let total = u32::from_str_radix(captures.get(2)?.as_str(), 10).ok()?;
+            self.line_family = "players_in_combat";
+            let in_combat = u32::from_str_radix(capture(&captures, 1)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+            let total = u32::from_str_radix(capture(&captures, 2)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
             if total > 0 {
                 let percent = (in_combat * 100) / total;
-                return Some(vec![MessageType::PercentPlayersInCombat(PlayersInCombat {
+                return Ok(vec![MessageType::PercentPlayersInCombat(PlayersInCombat {
                     unit: Default::default(),
                     percentage: percent,
                 })]);
@@ -1248,22 +1173,26 @@ impl CombatLogParser for WoWVanillaParser {
             vec![content]
         };
 
+        let mut consumed = false;
         for i_content in content_vec {
             if let Some(captures) = RE_LOOT.captures(&i_content) {
-                let timestamp = NaiveDateTime::parse_from_str(captures.get(1)?.as_str(), "%d.%m.%y %H:%M:%S").ok()?.timestamp_millis();
-                let receiver = parse_unit(&mut self.cache_unit, data, captures.get(2)?.as_str())?;
-                self.collect_participant(&receiver, captures.get(2)?.as_str(), event_ts);
+                self.line_family = "loot";
+                let timestamp = NaiveDateTime::parse_from_str(capture(&captures, 1)?.as_str(), "%d.%m.%y %H:%M:%S").map_err(|_| CblParseFailure::InvalidTimestamp)?.timestamp_millis();
+                let receiver = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+                self.collect_participant(&receiver, capture(&captures, 2)?.as_str(), event_ts);
                 self.collect_active_map(data, &receiver, event_ts);
-                let item_id = u32::from_str_radix(captures.get(4)?.as_str(), 10).ok()?;
-                let count = u32::from_str_radix(captures.get(9)?.as_str(), 10).ok()?;
+                let item_id = u32::from_str_radix(capture(&captures, 4)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+                let count = u32::from_str_radix(capture(&captures, 9)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
                 self.bonus_messages.push(Message::new_parsed(timestamp as u64, 0, MessageType::Loot(Loot { unit: receiver, item_id, count })));
+                consumed = true;
                 continue;
             }
 
             if let Some(captures) = RE_ZONE_INFO.captures(&i_content) {
-                let timestamp = NaiveDateTime::parse_from_str(captures.get(1)?.as_str(), "%d.%m.%y %H:%M:%S").ok()?.timestamp_millis();
-                let map_name = captures.get(2)?.as_str().to_string();
-                let instance_id = u32::from_str_radix(captures.get(3)?.as_str(), 10).ok()?;
+                self.line_family = "zone_info";
+                let timestamp = NaiveDateTime::parse_from_str(capture(&captures, 1)?.as_str(), "%d.%m.%y %H:%M:%S").map_err(|_| CblParseFailure::InvalidTimestamp)?.timestamp_millis();
+                let map_name = capture(&captures, 2)?.as_str().to_string();
+                let instance_id = u32::from_str_radix(capture(&captures, 3)?.as_str(), 10).map_err(|_| CblParseFailure::InvalidNumber)?;
                 if let Some(map) = data.get_map_by_name(&map_name) {
                     self.bonus_messages.push(Message::new_parsed(
                         timestamp as u64,
@@ -1276,31 +1205,42 @@ impl CombatLogParser for WoWVanillaParser {
                         }),
                     ));
                 }
+                consumed = true;
                 continue;
             }
 
             if i_content.starts_with("PET: ") {
-                let message_args = content.trim_start_matches("PET: ").split('&').collect::<Vec<&str>>();
-                let _timestamp = NaiveDateTime::parse_from_str(message_args[0], "%d.%m.%y %H:%M:%S").ok()?.timestamp_millis();
+                self.line_family = "pet";
+                let message_args = i_content.trim_start_matches("PET: ").split('&').collect::<Vec<&str>>();
+                if message_args.len() < 3 {
+                    return Err(CblParseFailure::MalformedPet(message_args.len()));
+                }
+                let _timestamp = NaiveDateTime::parse_from_str(message_args[0], "%d.%m.%y %H:%M:%S").map_err(|_| CblParseFailure::InvalidTimestamp)?.timestamp_millis();
                 let player_name = message_args[1];
                 let pet_name = message_args[2];
 
                 let unit_id = get_hashed_player_unit_id(player_name);
                 if pet_name != "nil" && !pet_name.is_empty() {
-                    let pet_unit = parse_unit(&mut self.cache_unit, data, pet_name)?;
+                    let pet_unit = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), pet_name)?;
                     self.pet_owner.insert(pet_unit.unit_id, unit_id);
                 }
+                consumed = true;
                 continue;
             }
         }
 
+        if consumed {
+            return Ok(Vec::new());
+        }
+
         if content.starts_with("COMBATANT_INFO:") {
+            self.line_family = "combatant_info";
             let message_args = content.trim_start_matches("COMBATANT_INFO: ").split('&').collect::<Vec<&str>>();
             if message_args.len() <= 27 {
-                return None;
+                return Err(CblParseFailure::MalformedCombatantInfo(message_args.len()));
             }
 
-            let timestamp = NaiveDateTime::parse_from_str(message_args[0], "%d.%m.%y %H:%M:%S").ok()?.timestamp_millis();
+            let timestamp = NaiveDateTime::parse_from_str(message_args[0], "%d.%m.%y %H:%M:%S").map_err(|_| CblParseFailure::InvalidTimestamp)?.timestamp_millis();
             let player_name = message_args[1];
             let hero_class_local = message_args[2].to_lowercase();
             let race_local = message_args[3].to_lowercase();
@@ -1310,22 +1250,35 @@ impl CombatLogParser for WoWVanillaParser {
             let guild_rank_name = message_args[7];
             let guild_rank_index = message_args[8];
 
+            let hero_class_id = match hero_class_local.as_str() {
+                "warrior" => 1,
+                "paladin" => 2,
+                "hunter" => 3,
+                "rogue" => 4,
+                "priest" => 5,
+                "shaman" => 7,
+                "mage" => 8,
+                "warlock" => 9,
+                "druid" => 11,
+                unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
+            };
+            let race_id = match race_local.as_str() {
+                "human" => 1,
+                "orc" => 2,
+                "dwarf" => 3,
+                "night elf" => 4,
+                "nightelf" => 4,
+                "undead" => 5,
+                "scourge" => 5,
+                "tauren" => 6,
+                "gnome" => 7,
+                "troll" => 8,
+                unknown => return Err(CblParseFailure::UnknownKeyword(unknown.to_string())),
+            };
+
             let unit_id = get_hashed_player_unit_id(player_name);
             let participant = self.participants.entry(unit_id).or_insert_with(|| Participant::new(unit_id, true, false, false, player_name.to_string(), event_ts));
-            if participant.hero_class_id.is_none() {
-                participant.hero_class_id = Some(match hero_class_local.as_str() {
-                    "warrior" => 1,
-                    "paladin" => 2,
-                    "hunter" => 3,
-                    "rogue" => 4,
-                    "priest" => 5,
-                    "shaman" => 7,
-                    "mage" => 8,
-                    "warlock" => 9,
-                    "druid" => 11,
-                    _ => return None,
-                });
-            }
+            participant.record_identity(hero_class_id, race_id);
 
             if participant.gender_id.is_none() {
                 if gender_local == "2" {
@@ -1335,29 +1288,13 @@ impl CombatLogParser for WoWVanillaParser {
                 }
             }
 
-            if participant.race_id.is_none() {
-                participant.race_id = Some(match race_local.as_str() {
-                    "human" => 1,
-                    "orc" => 2,
-                    "dwarf" => 3,
-                    "night elf" => 4,
-                    "nightelf" => 4,
-                    "undead" => 5,
-                    "scourge" => 5,
-                    "tauren" => 6,
-                    "gnome" => 7,
-                    "troll" => 8,
-                    _ => return None,
-                });
-            }
-
             if participant.guild_args.is_none() && guild_name != "nil" && guild_rank_name != "nil" {
-                let guild_rank_index = u8::from_str_radix(guild_rank_index, 10).ok()?;
+                let guild_rank_index = u8::from_str_radix(guild_rank_index, 10).map_err(|_| CblParseFailure::InvalidNumber)?;
                 participant.guild_args = Some((guild_name.to_string(), guild_rank_name.to_string(), guild_rank_index));
             }
 
             if pet_name != "nil" && !pet_name.is_empty() {
-                let pet_unit = parse_unit(&mut self.cache_unit, data, pet_name)?;
+                let pet_unit = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), pet_name)?;
                 self.pet_owner.insert(pet_unit.unit_id, unit_id);
             }
 
@@ -1375,8 +1312,8 @@ impl CombatLogParser for WoWVanillaParser {
                         gear.push(None);
                         continue;
                     }
-                    let item_id = u32::from_str_radix(item_args[0], 10).ok()?;
-                    let enchant_id = u32::from_str_radix(item_args[1], 10).ok()?;
+                    let item_id = u32::from_str_radix(item_args[0], 10).map_err(|_| CblParseFailure::InvalidNumber)?;
+                    let enchant_id = u32::from_str_radix(item_args[1], 10).map_err(|_| CblParseFailure::InvalidNumber)?;
                     if item_id == 0 || item_id > 25818 {
                         gear.push(None);
                     } else if enchant_id == 0 || enchant_id > 3000 {
@@ -1388,25 +1325,31 @@ impl CombatLogParser for WoWVanillaParser {
                 gear_setups.push((timestamp as u64, gear));
             }
 
-            if message_args[28] != "nil" && message_args[28].contains("}") {
-                participant.talents.push((event_ts, strip_talent_specialization(&Some(message_args[28].replace("}", "|")))));
+            if let Some(talents) = message_args.get(28).filter(|talents| **talents != "nil" && talents.contains('}')) {
+                participant.talents.push((event_ts, strip_talent_specialization(&Some(talents.replace("}", "|")))));
             }
 
-            return None;
+            // Later versions of the addon append the maximum health
+            if let Some(max_health) = message_args.get(29).and_then(|max_health| u32::from_str_radix(max_health, 10).ok()) {
+                participant.health.observe_max_health(max_health);
+            }
+
+            return Ok(Vec::new());
         }
 
         /*
          * Dispel, Steal and Interrupt
          */
         if let Some(captures) = RE_AURA_DISPEL.captures(&content) {
+            self.line_family = "dispel_interrupt";
             let un_aura_caster = Unit { is_player: true, unit_id: 0, is_self_damage: false, is_mind_control: false };
             let un_aura_spell_id = 42;
-            let target = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let target_spell_id = parse_spell_args(&mut self.cache_spell_id, data, captures.get(2)?.as_str())?;
-            self.collect_participant(&target, captures.get(1)?.as_str(), event_ts);
+            let target = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let target_spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            self.collect_participant(&target, capture(&captures, 1)?.as_str(), event_ts);
             self.collect_active_map(data, &target, event_ts);
 
-            return Some(vec![MessageType::Dispel(UnAura {
+            return Ok(vec![MessageType::Dispel(UnAura {
                 un_aura_caster,
                 target,
                 aura_caster: None,
@@ -1417,13 +1360,14 @@ impl CombatLogParser for WoWVanillaParser {
         }
 
         if let Some(captures) = RE_AURA_INTERRUPT.captures(&content) {
-            let un_aura_caster = parse_unit(&mut self.cache_unit, data, captures.get(1)?.as_str())?;
-            let target = parse_unit(&mut self.cache_unit, data, captures.get(2)?.as_str())?;
-            let interrupted_spell_id = parse_spell_args(&mut self.cache_spell_id, data, captures.get(3)?.as_str())?;
-            self.collect_participant(&target, captures.get(2)?.as_str(), event_ts);
+            self.line_family = "dispel_interrupt";
+            let un_aura_caster = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 1)?.as_str())?;
+            let target = parse_unit(&mut self.cache_unit, data, self.locale.language_id(), capture(&captures, 2)?.as_str())?;
+            let interrupted_spell_id = parse_spell_args(&mut self.cache_spell_id, data, self.locale.language_id(), capture(&captures, 3)?.as_str())?;
+            self.collect_participant(&target, capture(&captures, 2)?.as_str(), event_ts);
             self.collect_active_map(data, &target, event_ts);
 
-            return Some(vec![
+            return Ok(vec![
                 MessageType::SpellCast(SpellCast {
                     caster: un_aura_caster,
                     target: Some(target.clone()),
@@ -1434,10 +1378,42 @@ impl CombatLogParser for WoWVanillaParser {
             ]);
         }
 
-        None
+        Err(CblParseFailure::UnrecognizedLine)
+    }
+}
+
+impl CombatLogParser for WoWVanillaParser {
+    fn parse_cbl_line(&mut self, data: &Data, event_ts: u64, content: &str) -> Result<Vec<MessageType>, CblParseFailure> {
+        self.line_family = "unmatched";
+        // Names may resolve to other spells and NPCs once the data was reloaded
+        let data_generation = data.get_generation();
+        if data_generation != self.data_generation {
+            self.cache_unit.clear();
+            self.cache_spell_id.clear();
+            self.data_generation = data_generation;
+        }
+        let translated_content = self.locale.translate(content);
+        let message_types = self.parse_line(data, event_ts, translated_content.as_deref().unwrap_or(content));
+        if let Err(failure) = &message_types {
+            self.parse_report.record_failure(failure.kind());
+        }
+        self.parse_report.record_line(self.line_family, content, message_types.is_ok());
+        message_types
     }
 
     fn do_message_post_processing(&mut self, _data: &Data, messages: &mut Vec<Message>) {
+        // Names that resolved to nothing are reported, so missing data can be added
+        for (spell_name, spell_id) in self.cache_spell_id.iter() {
+            if spell_id.is_none() && !spell_name.ends_with(" (dot)") {
+                self.parse_report.record_unknown_spell_name(spell_name);
+            }
+        }
+        for (unit_name, cached_unit) in self.cache_unit.iter() {
+            if cached_unit.is_unknown_npc {
+                self.parse_report.record_unknown_npc_name(unit_name);
+            }
+        }
+
         // And create pet summon events
         let mut summon_events: Vec<Message> = Vec::with_capacity(40);
         for (pet_unit_id, owner_unit_id) in self.pet_owner.iter() {
@@ -1522,7 +1498,8 @@ impl CombatLogParser for WoWVanillaParser {
             let hero_class_id = participant.hero_class_id.unwrap_or(12);
 
             // don't save player characters with hero class 12 (unknown)
-            if !participant.is_player || hero_class_id != 12 || participant.is_mind_control || participant.is_self_damage {
+            // nor players of different realms that share a name, as their histories cannot be told apart
+            if (!participant.is_player || hero_class_id != 12 || participant.is_mind_control || participant.is_self_damage) && !participant.has_name_collision {
                 let mut gear = CharacterGearDto {
                     head: None,
                     neck: None,
@@ -1706,9 +1683,68 @@ impl CombatLogParser for WoWVanillaParser {
         None
     }
 
+    fn get_parse_report(&mut self) -> &mut ParseReport {
+        &mut self.parse_report
+    }
+
+    fn detect_language(&mut self, lines: &[(u64, &str)]) -> Option<u8> {
+        self.locale = sniff_combat_log_locale(lines);
+        Some(self.locale.language_id())
+    }
+
     fn get_ability_caster(&self, _ability_id: u32) -> Option<u32> {
         None
     }
+
+    fn estimates_overheal(&self) -> bool {
+        true
+    }
+
+    fn fork(&self) -> Option<Self> {
+        let mut fork = WoWVanillaParser::new(self.server_id);
+        fork.locale = self.locale;
+        Some(fork)
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (unit_id, participant) in other.participants {
+            match self.participants.get_mut(&unit_id) {
+                Some(known_participant) => known_participant.merge(participant),
+                None => {
+                    self.participants.insert(unit_id, participant);
+                },
+            }
+        }
+        for (map_id, active_map) in other.active_map {
+            match self.active_map.get_mut(&map_id) {
+                Some(known_active_map) => known_active_map.merge(active_map),
+                None => {
+                    self.active_map.insert(map_id, active_map);
+                },
+            }
+        }
+        self.pet_owner.extend(other.pet_owner);
+        // Only the caches of the most recent data are kept
+        if other.data_generation > self.data_generation {
+            self.cache_unit.clear();
+            self.cache_spell_id.clear();
+            self.data_generation = other.data_generation;
+        }
+        if other.data_generation == self.data_generation {
+            for (unit_name, cached_unit) in other.cache_unit {
+                self.cache_unit.entry(unit_name).or_insert(cached_unit);
+            }
+            for (spell_name, spell_id) in other.cache_spell_id {
+                self.cache_spell_id.entry(spell_name).or_insert(spell_id);
+            }
+        }
+        self.bonus_messages.extend(other.bonus_messages);
+        self.parse_report.merge(other.parse_report);
+    }
+}
+
+fn capture<'t>(captures: &Captures<'t>, index: usize) -> Result<Match<'t>, CblParseFailure> {
+    captures.get(index).ok_or(CblParseFailure::MissingCapture(index))
 }
 
 fn create_character_item_dto(item: &Option<(u32, Option<u32>, Option<Vec<Option<u32>>>)>) -> Option<CharacterItemDto> {
@@ -0,0 +1,20 @@
+use crate::modules::live_data_processor::material::{CleuParserState, WoWWotLKParser};
+use crate::modules::live_data_processor::tools::cbl_parser::wow_cleu::{CleuLayout, CleuParser, WOTLK_CLEU_LAYOUT};
+
+impl CleuParser for WoWWotLKParser {
+    fn layout(&self) -> &'static CleuLayout {
+        &WOTLK_CLEU_LAYOUT
+    }
+
+    fn server_id(&self) -> u32 {
+        self.server_id
+    }
+
+    fn state(&self) -> &CleuParserState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut CleuParserState {
+        &mut self.state
+    }
+}
@@ -154,7 +154,7 @@ impl EventTypeDeserializer for EventType {
                     }
 
                     format!(
-                        "{},{},{},{},{},{},{},{},{}",
+                        "{},{},{},{},{},{},{},{},{},{}",
                         spell_cause.id,
                         spell_cause.subject.deserialize(),
                         heal.target.deserialize(),
@@ -163,11 +163,12 @@ impl EventTypeDeserializer for EventType {
                         school_mask_to_u8(spell_cast.school_mask.clone()),
                         heal.total,
                         heal.effective,
-                        heal.mitigation.deserialize()
+                        heal.mitigation.deserialize(),
+                        heal.overheal
                     )
                 },
                 EventType::AuraApplication(aura_app) => format!(
-                    "{},{},{},{},{},{},{},{},{}",
+                    "{},{},{},{},{},{},{},{},{},{}",
                     spell_cause.id,
                     aura_app.caster.deserialize(),
                     heal.target.deserialize(),
@@ -176,7 +177,8 @@ impl EventTypeDeserializer for EventType {
                     school_mask_to_u8(aura_app.school_mask.clone()),
                     heal.total,
                     heal.effective,
-                    heal.mitigation.deserialize()
+                    heal.mitigation.deserialize(),
+                    heal.overheal
                 ),
                 _ => unreachable!(),
             },
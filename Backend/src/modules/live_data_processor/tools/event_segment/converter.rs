@@ -0,0 +1,68 @@
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use crate::modules::live_data_processor::tools::event_segment::{EventSegmentReader, EventSegmentWriter};
+use std::fs::File;
+use std::io::Read;
+
+/// Converts the per event type text files of an instance directory into a segment.
+/// Returns the amount of converted events. Fails if any line can not be converted, such that the source is kept.
+pub fn convert_directory_to_event_segment(src_dir: &str, segment_path: &str) -> Result<usize, LiveDataProcessorFailure> {
+    let mut writer = EventSegmentWriter::default();
+    let mut line_count = 0;
+    let entries = std::fs::read_dir(src_dir).map_err(|_| LiveDataProcessorFailure::InvalidInput)?;
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        if let Some(event_type) = entry.file_name().to_str().and_then(|file_name| file_name.parse::<u8>().ok()) {
+            let content = std::fs::read_to_string(entry.path()).map_err(|_| LiveDataProcessorFailure::FileIsNotUTF8)?;
+            line_count += push_event_lines(&mut writer, event_type, &content)?;
+        }
+    }
+    write_and_verify(writer, line_count, segment_path)
+}
+
+/// Converts a legacy instance zip into a segment.
+/// Fails if any line can not be converted, such that the zip is kept.
+pub fn convert_zip_to_event_segment(zip_path: &str, segment_path: &str) -> Result<usize, LiveDataProcessorFailure> {
+    let file = File::open(zip_path).map_err(|_| LiveDataProcessorFailure::InvalidZipFile)?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|_| LiveDataProcessorFailure::InvalidZipFile)?;
+
+    let mut writer = EventSegmentWriter::default();
+    let mut line_count = 0;
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i).map_err(|_| LiveDataProcessorFailure::InvalidZipFile)?;
+        if let Ok(event_type) = file.name().parse::<u8>() {
+            let mut content = String::with_capacity(file.size() as usize);
+            file.read_to_string(&mut content).map_err(|_| LiveDataProcessorFailure::FileIsNotUTF8)?;
+            line_count += push_event_lines(&mut writer, event_type, &content)?;
+        }
+    }
+    write_and_verify(writer, line_count, segment_path)
+}
+
+// Returns the amount of lines, every one of them has to be an event
+fn push_event_lines(writer: &mut EventSegmentWriter, event_type: u8, content: &str) -> Result<usize, LiveDataProcessorFailure> {
+    let mut line_count = 0;
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        writer.push_line(event_type, line)?;
+        line_count += 1;
+    }
+    Ok(line_count)
+}
+
+fn write_and_verify(writer: EventSegmentWriter, line_count: usize, segment_path: &str) -> Result<usize, LiveDataProcessorFailure> {
+    let event_count = writer.len();
+    if event_count != line_count {
+        return Err(LiveDataProcessorFailure::InvalidEventSegment);
+    }
+    writer.write_to_file(segment_path)?;
+
+    // Inflates every block once, such that a corrupt block is noticed before the source is removed
+    let mut reader = EventSegmentReader::open(segment_path)?;
+    let mut written_event_count = 0;
+    for event_type in reader.event_types() {
+        written_event_count += reader.read_events(event_type).map(|events| events.len()).unwrap_or(0);
+    }
+    if written_event_count != line_count {
+        let _ = std::fs::remove_file(segment_path);
+        return Err(LiveDataProcessorFailure::InvalidEventSegment);
+    }
+    Ok(event_count)
+}
@@ -0,0 +1,28 @@
+pub use self::converter::*;
+pub use self::reader::EventSegmentReader;
+pub use self::writer::EventSegmentWriter;
+
+mod converter;
+mod reader;
+mod value_codec;
+mod writer;
+
+/*
+ * Event segment, version 2. All numbers are little endian.
+ *
+ * Header:    "LPES" | version: u16 | block count: u16
+ * Directory: block count * (event type: u8 | event count: u32 | block offset: u64 | block size: u64)
+ * Block:     gzip(ids: u32 * n | timestamps: u64 * n | payload offsets: u64 * (n + 1) | payloads)
+ *
+ * Every block is compressed on its own, so reading an event type only inflates its block.
+ * Events of a block are sorted by timestamp, so a time range can be found by a binary search
+ * over the timestamp column. The payload is the deserialized event without its id and timestamp.
+ */
+const SEGMENT_MAGIC: &[u8; 4] = b"LPES";
+const SEGMENT_VERSION: u16 = 2;
+const SEGMENT_HEADER_SIZE: u64 = 8;
+const SEGMENT_DIRECTORY_ENTRY_SIZE: u64 = 21;
+
+pub fn get_event_segment_path(storage_path: &str, server_id: u32, instance_meta_id: u32) -> String {
+    format!("{}/{}/{}.lpes", storage_path, server_id, instance_meta_id)
+}
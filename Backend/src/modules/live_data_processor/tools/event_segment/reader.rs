@@ -0,0 +1,141 @@
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use crate::modules::live_data_processor::tools::event_segment::value_codec::decode_value;
+use crate::modules::live_data_processor::tools::event_segment::{SEGMENT_DIRECTORY_ENTRY_SIZE, SEGMENT_HEADER_SIZE, SEGMENT_MAGIC, SEGMENT_VERSION};
+use byteorder::{ByteOrder, LittleEndian, ReadBytesExt};
+use flate2::read::GzDecoder;
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+
+struct EventSegmentBlock {
+    event_type: u8,
+    event_count: u64,
+    offset: u64,
+    size: u64,
+}
+
+pub struct EventSegmentReader<R: Read + Seek> {
+    reader: R,
+    segment_size: u64,
+    blocks: Vec<EventSegmentBlock>,
+}
+
+impl EventSegmentReader<BufReader<File>> {
+    pub fn open(path: &str) -> Result<Self, LiveDataProcessorFailure> {
+        let file = File::open(path).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+        EventSegmentReader::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read + Seek> EventSegmentReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, LiveDataProcessorFailure> {
+        let segment_size = reader.seek(SeekFrom::End(0)).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+        reader.seek(SeekFrom::Start(0)).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+        let version = reader.read_u16::<LittleEndian>().map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+        if &magic != SEGMENT_MAGIC || version != SEGMENT_VERSION {
+            return Err(LiveDataProcessorFailure::InvalidEventSegment);
+        }
+
+        let block_count = reader.read_u16::<LittleEndian>().map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)? as u64;
+        let mut directory = vec![0; (SEGMENT_DIRECTORY_ENTRY_SIZE * block_count) as usize];
+        reader.read_exact(&mut directory).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+
+        let mut blocks = Vec::with_capacity(block_count as usize);
+        for entry in directory.chunks(SEGMENT_DIRECTORY_ENTRY_SIZE as usize) {
+            let block = EventSegmentBlock {
+                event_type: entry[0],
+                event_count: LittleEndian::read_u32(&entry[1..5]) as u64,
+                offset: LittleEndian::read_u64(&entry[5..13]),
+                size: LittleEndian::read_u64(&entry[13..21]),
+            };
+            if block.offset < SEGMENT_HEADER_SIZE || block.offset.saturating_add(block.size) > segment_size {
+                return Err(LiveDataProcessorFailure::InvalidEventSegment);
+            }
+            blocks.push(block);
+        }
+
+        Ok(EventSegmentReader { reader, segment_size, blocks })
+    }
+
+    pub fn event_types(&self) -> Vec<u8> {
+        self.blocks.iter().map(|block| block.event_type).collect()
+    }
+
+    pub fn event_count(&self, event_type: u8) -> usize {
+        self.blocks.iter().find(|block| block.event_type == event_type).map(|block| block.event_count as usize).unwrap_or(0)
+    }
+
+    pub fn read_events(&mut self, event_type: u8) -> Result<Vec<Value>, LiveDataProcessorFailure> {
+        self.read_time_range(event_type, 0, u64::MAX)
+    }
+
+    /// Returns the events of the given type whose timestamp lies within [start_ts, end_ts].
+    pub fn read_time_range(&mut self, event_type: u8, start_ts: u64, end_ts: u64) -> Result<Vec<Value>, LiveDataProcessorFailure> {
+        let (event_count, offset, size) = match self.blocks.iter().find(|block| block.event_type == event_type) {
+            Some(block) => (block.event_count, block.offset, block.size),
+            None => return Ok(Vec::new()),
+        };
+        if event_count == 0 || start_ts > end_ts {
+            return Ok(Vec::new());
+        }
+
+        let block = self.read_block(offset, size)?;
+        let columns_size = 20 * event_count + 8;
+        if (block.len() as u64) < columns_size {
+            return Err(LiveDataProcessorFailure::InvalidEventSegment);
+        }
+        let (ids, rest) = block.split_at(4 * event_count as usize);
+        let (timestamps, rest) = rest.split_at(8 * event_count as usize);
+        let (payload_offsets, payloads) = rest.split_at(8 * (event_count as usize + 1));
+
+        let first = lower_bound(timestamps, start_ts);
+        let last = if end_ts == u64::MAX { event_count } else { lower_bound(timestamps, end_ts + 1) };
+
+        let mut events = Vec::with_capacity(last.saturating_sub(first) as usize);
+        for index in first as usize..last as usize {
+            let event_start = LittleEndian::read_u64(&payload_offsets[8 * index..8 * index + 8]);
+            let event_end = LittleEndian::read_u64(&payload_offsets[8 * index + 8..8 * index + 16]);
+            if event_end < event_start || event_end > payloads.len() as u64 {
+                return Err(LiveDataProcessorFailure::InvalidEventSegment);
+            }
+
+            let mut event = vec![
+                Value::Number(LittleEndian::read_u32(&ids[4 * index..4 * index + 4]).into()),
+                Value::Number(LittleEndian::read_u64(&timestamps[8 * index..8 * index + 8]).into()),
+            ];
+            match decode_value(&mut &payloads[event_start as usize..event_end as usize])? {
+                Value::Array(mut payload) => event.append(&mut payload),
+                _ => return Err(LiveDataProcessorFailure::InvalidEventSegment),
+            }
+            events.push(Value::Array(event));
+        }
+        Ok(events)
+    }
+
+    fn read_block(&mut self, offset: u64, size: u64) -> Result<Vec<u8>, LiveDataProcessorFailure> {
+        if offset.saturating_add(size) > self.segment_size {
+            return Err(LiveDataProcessorFailure::InvalidEventSegment);
+        }
+        self.reader.seek(SeekFrom::Start(offset)).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+        let mut block = Vec::new();
+        GzDecoder::new((&mut self.reader).take(size)).read_to_end(&mut block).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+        Ok(block)
+    }
+}
+
+fn lower_bound(timestamps: &[u8], timestamp: u64) -> u64 {
+    let mut low = 0;
+    let mut high = timestamps.len() / 8;
+    while low < high {
+        let mid = (low + high) / 2;
+        if LittleEndian::read_u64(&timestamps[8 * mid..8 * mid + 8]) < timestamp {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low as u64
+}
@@ -0,0 +1,122 @@
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use serde_json::{Map, Number, Value};
+use std::io::Read;
+
+const TAG_NULL: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_UNSIGNED: u8 = 3;
+const TAG_NEGATIVE: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_ARRAY: u8 = 6;
+const TAG_STRING: u8 = 7;
+const TAG_OBJECT: u8 = 8;
+
+pub fn encode_value(value: &Value, buffer: &mut Vec<u8>) {
+    match value {
+        Value::Null => buffer.push(TAG_NULL),
+        Value::Bool(false) => buffer.push(TAG_FALSE),
+        Value::Bool(true) => buffer.push(TAG_TRUE),
+        Value::Number(number) => {
+            if let Some(unsigned) = number.as_u64() {
+                buffer.push(TAG_UNSIGNED);
+                encode_varint(unsigned, buffer);
+            } else if let Some(signed) = number.as_i64() {
+                buffer.push(TAG_NEGATIVE);
+                encode_varint(!signed as u64, buffer);
+            } else {
+                buffer.push(TAG_FLOAT);
+                let _ = buffer.write_f64::<LittleEndian>(number.as_f64().unwrap_or(0.0));
+            }
+        },
+        Value::String(string) => {
+            buffer.push(TAG_STRING);
+            encode_varint(string.len() as u64, buffer);
+            buffer.extend_from_slice(string.as_bytes());
+        },
+        Value::Array(values) => encode_array(values, buffer),
+        Value::Object(map) => {
+            buffer.push(TAG_OBJECT);
+            encode_varint(map.len() as u64, buffer);
+            for (key, value) in map.iter() {
+                encode_varint(key.len() as u64, buffer);
+                buffer.extend_from_slice(key.as_bytes());
+                encode_value(value, buffer);
+            }
+        },
+    }
+}
+
+pub fn encode_array(values: &[Value], buffer: &mut Vec<u8>) {
+    buffer.push(TAG_ARRAY);
+    encode_varint(values.len() as u64, buffer);
+    for value in values.iter() {
+        encode_value(value, buffer);
+    }
+}
+
+pub fn decode_value(content: &mut &[u8]) -> Result<Value, LiveDataProcessorFailure> {
+    let tag = content.read_u8().map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+    match tag {
+        TAG_NULL => Ok(Value::Null),
+        TAG_FALSE => Ok(Value::Bool(false)),
+        TAG_TRUE => Ok(Value::Bool(true)),
+        TAG_UNSIGNED => decode_varint(content).map(|unsigned| Value::Number(unsigned.into())),
+        TAG_NEGATIVE => decode_varint(content).map(|magnitude| Value::Number((!(magnitude as i64)).into())),
+        TAG_FLOAT => {
+            let float = content.read_f64::<LittleEndian>().map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+            Ok(Number::from_f64(float).map(Value::Number).unwrap_or(Value::Null))
+        },
+        TAG_STRING => decode_string(content).map(Value::String),
+        TAG_ARRAY => {
+            let len = decode_varint(content)? as usize;
+            // Each value takes at least one byte, which bounds the allocation for corrupt lengths
+            let mut values = Vec::with_capacity(len.min(content.len()));
+            for _ in 0..len {
+                values.push(decode_value(content)?);
+            }
+            Ok(Value::Array(values))
+        },
+        TAG_OBJECT => {
+            let len = decode_varint(content)? as usize;
+            let mut map = Map::new();
+            for _ in 0..len {
+                let key = decode_string(content)?;
+                map.insert(key, decode_value(content)?);
+            }
+            Ok(Value::Object(map))
+        },
+        _ => Err(LiveDataProcessorFailure::InvalidEventSegment),
+    }
+}
+
+fn decode_string(content: &mut &[u8]) -> Result<String, LiveDataProcessorFailure> {
+    let len = decode_varint(content)? as usize;
+    if len > content.len() {
+        return Err(LiveDataProcessorFailure::InvalidEventSegment);
+    }
+    let mut bytes = vec![0; len];
+    content.read_exact(&mut bytes).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+    String::from_utf8(bytes).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)
+}
+
+fn encode_varint(mut number: u64, buffer: &mut Vec<u8>) {
+    while number >= 0x80 {
+        buffer.push((number as u8 & 0x7F) | 0x80);
+        number >>= 7;
+    }
+    buffer.push(number as u8);
+}
+
+fn decode_varint(content: &mut &[u8]) -> Result<u64, LiveDataProcessorFailure> {
+    let mut number = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = content.read_u8().map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+        number |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(number);
+        }
+    }
+    Err(LiveDataProcessorFailure::InvalidEventSegment)
+}
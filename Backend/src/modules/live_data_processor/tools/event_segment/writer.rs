@@ -0,0 +1,102 @@
+use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use crate::modules::live_data_processor::tools::event_segment::value_codec::encode_array;
+use crate::modules::live_data_processor::tools::event_segment::{SEGMENT_DIRECTORY_ENTRY_SIZE, SEGMENT_HEADER_SIZE, SEGMENT_MAGIC, SEGMENT_VERSION};
+use byteorder::{LittleEndian, WriteBytesExt};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::{from_str, Value};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Write};
+
+#[derive(Debug, Default)]
+pub struct EventSegmentWriter {
+    blocks: BTreeMap<u8, Vec<(u32, u64, Vec<u8>)>>,
+}
+
+impl EventSegmentWriter {
+    pub fn push_event(&mut self, event_type: u8, event: &Value) -> Result<(), LiveDataProcessorFailure> {
+        let event = event.as_array().ok_or(LiveDataProcessorFailure::InvalidInput)?;
+        let id = event.get(0).and_then(Value::as_u64).ok_or(LiveDataProcessorFailure::InvalidInput)? as u32;
+        let timestamp = event.get(1).and_then(Value::as_u64).ok_or(LiveDataProcessorFailure::InvalidInput)?;
+
+        let mut payload = Vec::new();
+        encode_array(&event[2..], &mut payload);
+        self.blocks.entry(event_type).or_insert_with(Vec::new).push((id, timestamp, payload));
+        Ok(())
+    }
+
+    pub fn push_line(&mut self, event_type: u8, line: &str) -> Result<(), LiveDataProcessorFailure> {
+        let event = from_str::<Value>(line).map_err(|_| LiveDataProcessorFailure::InvalidInput)?;
+        self.push_event(event_type, &event)
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.values().map(|events| events.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn write(mut self, writer: &mut impl Write) -> std::io::Result<()> {
+        let mut blocks = Vec::with_capacity(self.blocks.len());
+        for (event_type, events) in self.blocks.iter_mut() {
+            events.sort_by(|left, right| left.1.cmp(&right.1).then_with(|| left.0.cmp(&right.0)));
+            let event_count = u32::try_from(events.len()).map_err(|_| Error::new(ErrorKind::InvalidInput, "Too many events in a block"))?;
+            blocks.push((*event_type, event_count, compress_block(events)?));
+        }
+
+        writer.write_all(SEGMENT_MAGIC)?;
+        writer.write_u16::<LittleEndian>(SEGMENT_VERSION)?;
+        writer.write_u16::<LittleEndian>(blocks.len() as u16)?;
+
+        let mut block_offset = SEGMENT_HEADER_SIZE + SEGMENT_DIRECTORY_ENTRY_SIZE * blocks.len() as u64;
+        for (event_type, event_count, block) in blocks.iter() {
+            writer.write_u8(*event_type)?;
+            writer.write_u32::<LittleEndian>(*event_count)?;
+            writer.write_u64::<LittleEndian>(block_offset)?;
+            writer.write_u64::<LittleEndian>(block.len() as u64)?;
+            block_offset += block.len() as u64;
+        }
+
+        for (_, _, block) in blocks.iter() {
+            writer.write_all(block)?;
+        }
+        writer.flush()
+    }
+
+    /// Writes into a temporary file first, so readers never observe a partially written segment.
+    pub fn write_to_file(self, path: &str) -> Result<(), LiveDataProcessorFailure> {
+        let tmp_path = format!("{}.tmp", path);
+        let file = File::create(&tmp_path).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)?;
+        let mut writer = BufWriter::new(file);
+        if self.write(&mut writer).is_err() {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(LiveDataProcessorFailure::InvalidEventSegment);
+        }
+        drop(writer);
+        std::fs::rename(&tmp_path, path).map_err(|_| LiveDataProcessorFailure::InvalidEventSegment)
+    }
+}
+
+fn compress_block(events: &[(u32, u64, Vec<u8>)]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    for (id, _, _) in events.iter() {
+        encoder.write_u32::<LittleEndian>(*id)?;
+    }
+    for (_, timestamp, _) in events.iter() {
+        encoder.write_u64::<LittleEndian>(*timestamp)?;
+    }
+    let mut payload_offset: u64 = 0;
+    encoder.write_u64::<LittleEndian>(payload_offset)?;
+    for (_, _, payload) in events.iter() {
+        payload_offset = payload_offset.checked_add(payload.len() as u64).ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Payload offset overflow"))?;
+        encoder.write_u64::<LittleEndian>(payload_offset)?;
+    }
+    for (_, _, payload) in events.iter() {
+        encoder.write_all(payload)?;
+    }
+    encoder.finish()
+}
@@ -8,15 +8,21 @@ use crate::modules::armory::Armory;
 use crate::modules::data::tools::{RetrieveNPC, RetrieveServer, RetrieveSpell};
 use crate::modules::data::Data;
 use crate::modules::live_data_processor::dto::{get_damage_components_total, CombatState, InstanceMap, Interrupt, Message, MessageType, SpellCast, Unit};
-use crate::modules::live_data_processor::material::{IntervalBucket, Participant, RetrieveActiveMap};
+use crate::modules::live_data_processor::material::{HealthModel, IntervalBucket, Participant, RetrieveActiveMap};
 use crate::modules::live_data_processor::tools::cbl_parser::wow_vanilla::hashed_unit_id::{get_hashed_player_unit_id, get_npc_unit_id};
-use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
+use crate::modules::live_data_processor::tools::cbl_parser::{CblParseFailure, CombatLogParser};
+use crate::modules::live_data_processor::tools::upload_job::{set_upload_language, set_upload_progress};
+use crate::modules::live_data_processor::tools::upload_spec::set_upload_specs;
 use crate::modules::live_data_processor::tools::GUID;
-use crate::modules::live_data_processor::LiveDataProcessor;
 use crate::util::database::{Execute, Select};
 
+// Lines may be written slightly out of order, anything beyond that is reported
+const TIMESTAMP_BACKWARDS_TOLERANCE: u64 = 60000;
+// Smaller logs are not worth the overhead of spawning threads
+pub const PARSE_MIN_CHUNK_SIZE: usize = 50000;
+
 pub fn parse_cbl(
-    parser: &mut impl CombatLogParser, live_data_processor: &LiveDataProcessor, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, file_content: &str, start_parse: u64, _end_parse: u64, member_id: u32, only_parse_characters: bool,
+    parser: &mut (impl CombatLogParser + Send), db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, file_content: &str, start_parse: u64, _end_parse: u64, _member_id: u32, upload_id: u32, only_parse_characters: bool,
 ) -> Option<(u32, Vec<Message>)> {
     let mut messages = Vec::with_capacity(1000000);
 
@@ -26,21 +32,23 @@ pub fn parse_cbl(
 
     let combat_ignore_spells = ["Distract", "Hunter's Mark", "Calm Elements", "Mind Soothe", "Mind Control", "Mind Vision", "Enlighten", "Bloodrage", "Acid Spit"];
 
-    let combat_ignore_units = [
-        get_npc_unit_id(data, "Rat").unwrap(),
-        get_npc_unit_id(data, "Maggot").unwrap(),
-        get_npc_unit_id(data, "Larva").unwrap(),
-        get_npc_unit_id(data, "Roach").unwrap(),
-        get_npc_unit_id(data, "Spider").unwrap(), // critter
-        get_npc_unit_id(data, "Mr. Bigglesworth").unwrap(),
-    ];
+    // Critters that are missing in the data are just not ignored
+    let combat_ignore_units = ["Rat", "Maggot", "Larva", "Roach", "Spider", "Mr. Bigglesworth"]
+        .iter()
+        .filter_map(|critter| get_npc_unit_id(data, critter))
+        .collect::<Vec<u64>>();
 
     // Pre processing
     // TODO: Handle 31/12 => 01/01 raids
     let current_year = NaiveDateTime::from_timestamp((start_parse / 1000) as i64, 0).year();
+    let mut last_event_timestamp = 0;
+    let mut lines = Vec::new();
     for line in file_content.split('\n').into_iter() {
         let meta = line.split("  ").collect::<Vec<&str>>();
         if meta.len() != 2 {
+            if !line.trim().is_empty() {
+                parser.get_parse_report().record_line("malformed", line, false);
+            }
             continue;
         }
         if let Ok(timestamp) = NaiveDateTime::parse_from_str(&format!("{}/{}", current_year, meta[0]), "%Y/%m/%d %H:%M:%S%.3f") {
@@ -49,79 +57,97 @@ pub fn parse_cbl(
             // wer're gonna have a 24 hour grace period for logs in the future to avoid timezone issues
             let future_timestamp = current_timestamp + Duration::hours(24);
             if timestamp > future_timestamp {
+                parser.get_parse_report().record_timestamp_anomaly("shifted_to_last_year", line);
                 let last_year = timestamp - Duration::days(365);
                 event_timestamp = last_year.timestamp_millis() as u64;
             } else {
                 event_timestamp = timestamp.timestamp_millis() as u64;
             }
+
+            if event_timestamp + TIMESTAMP_BACKWARDS_TOLERANCE < last_event_timestamp {
+                parser.get_parse_report().record_timestamp_anomaly("out_of_order", line);
+            }
+            last_event_timestamp = last_event_timestamp.max(event_timestamp);
             /*
             if event_timestamp < start_parse || event_timestamp > end_parse {
                 continue;
             }
              */
 
-            if let Some(message_types) = parser.parse_cbl_line(data, event_timestamp, meta[1].trim_end_matches('\r')) {
-                let mut message_count = (messages.len() + message_types.len()) as u64;
-                let mut msg_type_len = message_types.len() as u64;
-                for message_type in message_types {
-                    // if combat started, we can start adding messages
-                    if !combat_started_once {
-                        match &message_type {
-                            MessageType::MeleeDamage(dmg) | MessageType::SpellDamage(dmg) => {
-                                let mut ignore = dmg.victim.unit_id == 0 || dmg.attacker.unit_id == 0 || dmg.attacker.is_self_damage || dmg.victim.unit_id == dmg.attacker.unit_id;
-
-                                if !ignore {
-                                    // ignore dmg against immune enemy
-                                    ignore = dmg.hit_mask & 0x00002000 != 0;
-                                }
+            lines.push((event_timestamp, meta[1].trim_end_matches('\r')));
+        } else {
+            parser.get_parse_report().record_timestamp_anomaly("unparseable", line);
+            parser.get_parse_report().record_line("malformed", line, false);
+        }
+    }
 
-                                if !ignore {
-                                    if let Some(spell_name) = dmg.spell_name.as_ref() {
-                                        ignore = combat_ignore_spells.contains(&spell_name.as_str());
-                                    }
-                                }
+    if let Some(language_id) = parser.detect_language(&lines) {
+        set_upload_language(db_main, upload_id, language_id);
+    }
 
-                                if !ignore {
-                                    ignore = combat_ignore_units.contains(&dmg.victim.unit_id);
-                                }
+    let parsed_lines = parse_lines(parser, data, &lines);
+    for (&(event_timestamp, _), parsed_line) in lines.iter().zip(parsed_lines) {
+        if let Ok(message_types) = parsed_line {
+            let mut message_count = (messages.len() + message_types.len()) as u64;
+            let mut msg_type_len = message_types.len() as u64;
+            for message_type in message_types {
+                // if combat started, we can start adding messages
+                if !combat_started_once {
+                    match &message_type {
+                        MessageType::MeleeDamage(dmg) | MessageType::SpellDamage(dmg) => {
+                            let mut ignore = dmg.victim.unit_id == 0 || dmg.attacker.unit_id == 0 || dmg.attacker.is_self_damage || dmg.victim.unit_id == dmg.attacker.unit_id;
+
+                            if !ignore {
+                                // ignore dmg against immune enemy
+                                ignore = dmg.hit_mask & 0x00002000 != 0;
+                            }
 
-                                if !ignore {
-                                    println!("First combat started by {}", dmg.attacker.unit_id);
-                                    combat_started_once = true;
+                            if !ignore {
+                                if let Some(spell_name) = dmg.spell_name.as_ref() {
+                                    ignore = combat_ignore_spells.contains(&spell_name.as_str());
                                 }
-                            },
-                            _ => {},
-                        }
-                    } else {
-                        let mut ts_offset = 0;
-                        if let MessageType::Summon(summon) = &message_type {
-                            if let Some(entry) = summon.unit.unit_id.get_entry() {
-                                match entry {
-                                    // Order for Shaman Totems
-                                    15439 | 15430 => ts_offset = 50,
-                                    _ => {},
-                                };
                             }
-                        }
 
-                        // check if players in combat messages exist
-                        if !has_players_in_combat_info {
-                            if let MessageType::PercentPlayersInCombat(_) = &message_type {
-                                has_players_in_combat_info = true;
+                            if !ignore {
+                                ignore = combat_ignore_units.contains(&dmg.victim.unit_id);
                             }
-                        }
 
-                        message_count -= 1;
-                        msg_type_len -= 1;
+                            if !ignore {
+                                println!("First combat started by {}", dmg.attacker.unit_id);
+                                combat_started_once = true;
+                            }
+                        },
+                        _ => {},
+                    }
+                } else {
+                    let mut ts_offset = 0;
+                    if let MessageType::Summon(summon) = &message_type {
+                        if let Some(entry) = summon.unit.unit_id.get_entry() {
+                            match entry {
+                                // Order for Shaman Totems
+                                15439 | 15430 => ts_offset = 50,
+                                _ => {},
+                            };
+                        }
+                    }
 
-                        messages.push(Message {
-                            api_version: 0,
-                            message_length: 0,
-                            timestamp: event_timestamp - msg_type_len - ts_offset,
-                            message_count,
-                            message_type,
-                        });
+                    // check if players in combat messages exist
+                    if !has_players_in_combat_info {
+                        if let MessageType::PercentPlayersInCombat(_) = &message_type {
+                            has_players_in_combat_info = true;
+                        }
                     }
+
+                    message_count -= 1;
+                    msg_type_len -= 1;
+
+                    messages.push(Message {
+                        api_version: 0,
+                        message_length: 0,
+                        timestamp: event_timestamp - msg_type_len - ts_offset,
+                        message_count,
+                        message_type,
+                    });
                 }
             }
         }
@@ -136,18 +162,12 @@ pub fn parse_cbl(
         res
     });
 
-    {
-        let mut upload_progress = live_data_processor.upload_progress.write().unwrap();
-        upload_progress.insert(member_id, 10);
-    }
+    set_upload_progress(db_main, upload_id, 10);
 
     // Post processing step
     parser.do_message_post_processing(data, &mut messages);
 
-    {
-        let mut upload_progress = live_data_processor.upload_progress.write().unwrap();
-        upload_progress.insert(member_id, 20);
-    }
+    set_upload_progress(db_main, upload_id, 20);
 
     let expansion_id = parser.get_expansion_id();
     let mut server_id = parser.get_server_id();
@@ -162,10 +182,7 @@ pub fn parse_cbl(
         }
     }
 
-    {
-        let mut upload_progress = live_data_processor.upload_progress.write().unwrap();
-        upload_progress.insert(member_id, 25);
-    }
+    set_upload_progress(db_main, upload_id, 25);
 
     let server_id = server_id?;
 
@@ -174,11 +191,9 @@ pub fn parse_cbl(
         let server_id = retail_server_id.map(|id| data.get_internal_server_by_retail_id(id).unwrap().id).unwrap_or(server_id);
         let _result = armory.set_character(db_main, server_id, character_dto, timestamp);
     }
+    set_upload_specs(db_main, armory, server_id, upload_id, &parser.get_participants());
 
-    {
-        let mut upload_progress = live_data_processor.upload_progress.write().unwrap();
-        upload_progress.insert(member_id, 50);
-    }
+    set_upload_progress(db_main, upload_id, 50);
     println!("Stop Char processing");
 
     if only_parse_characters {
@@ -596,10 +611,7 @@ pub fn parse_cbl(
         };
     }
 
-    {
-        let mut upload_progress = live_data_processor.upload_progress.write().unwrap();
-        upload_progress.insert(member_id, 80);
-    }
+    set_upload_progress(db_main, upload_id, 80);
 
     // Artificially set in combat to false at the end for each in combat npc
     for (entry, last_update_ts) in last_combat_update.iter() {
@@ -639,10 +651,7 @@ pub fn parse_cbl(
     messages.append(&mut additional_messages);
     messages.sort_by(|left, right| left.timestamp.cmp(&right.timestamp));
 
-    {
-        let mut upload_progress = live_data_processor.upload_progress.write().unwrap();
-        upload_progress.insert(member_id, 99);
-    }
+    set_upload_progress(db_main, upload_id, 95);
 
     Some((server_id, messages))
 }
@@ -719,6 +728,79 @@ fn is_in_remove_list(remove_unit: &BTreeSet<u64>, message_type: &MessageType) ->
     }
 }
 
+/// Parses the lines in order. If the parser supports it, the log is split into chunks that are parsed in parallel
+/// by forks of the parser, which are merged back in order.
+pub fn parse_lines<P: CombatLogParser + Send>(parser: &mut P, data: &Data, lines: &[(u64, &str)]) -> Vec<Result<Vec<MessageType>, CblParseFailure>> {
+    let threads = std::thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1);
+    parse_lines_in_chunks(parser, data, lines, PARSE_MIN_CHUNK_SIZE.max((lines.len() + threads - 1) / threads))
+}
+
+/// Parses the lines in chunks of the given size. The result does not depend on the chunk size.
+pub fn parse_lines_in_chunks<P: CombatLogParser + Send>(parser: &mut P, data: &Data, lines: &[(u64, &str)], chunk_size: usize) -> Vec<Result<Vec<MessageType>, CblParseFailure>> {
+    let forks = lines.chunks(chunk_size).skip(1).map(|_| parser.fork()).collect::<Option<Vec<P>>>();
+    let mut parsed_lines = match forks {
+        Some(forks) if !forks.is_empty() => std::thread::scope(|scope| {
+            let handles = forks
+                .into_iter()
+                .zip(lines.chunks(chunk_size).skip(1))
+                .map(|(mut fork, chunk)| {
+                    scope.spawn(move || {
+                        let parsed_chunk = parse_chunk(&mut fork, data, chunk);
+                        (fork, parsed_chunk)
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let mut parsed_lines = parse_chunk(parser, data, &lines[..chunk_size]);
+            for handle in handles {
+                let (fork, parsed_chunk) = handle.join().expect("Parsing a chunk of the log panicked");
+                parser.merge(fork);
+                parsed_lines.extend(parsed_chunk);
+            }
+            parsed_lines
+        }),
+        _ => parse_chunk(parser, data, lines),
+    };
+
+    if parser.estimates_overheal() {
+        let max_health: HashMap<u64, u32> = parser.get_participants().into_iter().filter_map(|participant| participant.health.max_health.map(|max_health| (participant.id, max_health))).collect();
+        reattribute_effective_heal(&mut parsed_lines, data, parser.get_expansion_id(), &max_health);
+    }
+    parsed_lines
+}
+
+fn parse_chunk(parser: &mut impl CombatLogParser, data: &Data, lines: &[(u64, &str)]) -> Vec<Result<Vec<MessageType>, CblParseFailure>> {
+    lines.iter().map(|(event_timestamp, content)| parser.parse_cbl_line(data, *event_timestamp, content)).collect()
+}
+
+// While parsing, the maximum health of a unit is only known after it was observed and forks did not know the health
+// of the units when their chunk started. Hence effective heal is attributed again in order, once all is known.
+fn reattribute_effective_heal(parsed_lines: &mut [Result<Vec<MessageType>, CblParseFailure>], data: &Data, expansion_id: u8, max_health: &HashMap<u64, u32>) {
+    let mut health_models: HashMap<u64, HealthModel> = HashMap::new();
+    for message_type in parsed_lines.iter_mut().filter_map(|parsed_line| parsed_line.as_mut().ok()).flatten() {
+        match message_type {
+            MessageType::MeleeDamage(damage_done) | MessageType::SpellDamage(damage_done) => {
+                get_health_model(&mut health_models, data, expansion_id, max_health, damage_done.victim.unit_id).take_damage(get_damage_components_total(&damage_done.damage_components));
+            },
+            MessageType::Heal(heal_done) => {
+                let (effective_heal, overheal) = get_health_model(&mut health_models, data, expansion_id, max_health, heal_done.target.unit_id).take_heal(heal_done.total_heal, heal_done.absorb);
+                heal_done.effective_heal = effective_heal;
+                heal_done.overheal = overheal;
+            },
+            MessageType::Death(death) => get_health_model(&mut health_models, data, expansion_id, max_health, death.victim.unit_id).die(),
+            _ => {},
+        }
+    }
+}
+
+// The observed maximum health takes precedence, NPCs that were not observed fall back to their data
+fn get_health_model<'a>(health_models: &'a mut HashMap<u64, HealthModel>, data: &Data, expansion_id: u8, max_health: &HashMap<u64, u32>, unit_id: u64) -> &'a mut HealthModel {
+    health_models.entry(unit_id).or_insert_with(|| {
+        let max_health = max_health.get(&unit_id).cloned().or_else(|| unit_id.get_entry().and_then(|entry| data.get_npc(expansion_id, entry)).and_then(|npc| npc.max_health));
+        HealthModel::new(max_health)
+    })
+}
+
 fn add_combat_event(parser: &impl CombatLogParser, data: &Data, expansion_id: u8, additional_messages: &mut Vec<Message>, last_combat_update: &mut HashMap<u64, u64>, current_timestamp: u64, current_message_count: u64, unit: &Unit) {
     let mut ts_offset: i64 = -1;
     let mut timeout = 60000;
@@ -1,17 +1,23 @@
 pub use self::deserializer::*;
 pub use self::guid::GUID;
 pub use self::message::*;
+pub use self::parse_report::UploadParseReport;
 pub use self::process::*;
 pub use self::unit::*;
+pub use self::upload_job::UploadJobQueue;
 
 pub mod byte_reader;
 mod deserializer;
+pub mod event_segment;
 mod guid;
 pub mod log_parser;
 mod message;
+mod parse_report;
 pub mod payload_mapper;
 mod process;
 pub mod server;
 mod unit;
+pub mod upload_job;
+pub mod upload_spec;
 
 pub mod cbl_parser;
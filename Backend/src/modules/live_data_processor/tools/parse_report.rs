@@ -0,0 +1,34 @@
+use crate::modules::live_data_processor::dto::{LiveDataProcessorFailure, ParseReport};
+use crate::modules::live_data_processor::LiveDataProcessor;
+use crate::params;
+use crate::util::database::{Execute, Select};
+
+pub trait UploadParseReport {
+    fn set_upload_parse_report(&self, db_main: &mut impl Execute, upload_id: u32, parse_report: &ParseReport);
+    fn get_upload_parse_report(&self, db_main: &mut impl Select, member_id: u32, upload_id: u32) -> Result<ParseReport, LiveDataProcessorFailure>;
+}
+
+impl UploadParseReport for LiveDataProcessor {
+    fn set_upload_parse_report(&self, db_main: &mut impl Execute, upload_id: u32, parse_report: &ParseReport) {
+        db_main.execute_wparams(
+            "UPDATE `instance_uploads` SET `parse_report`=:parse_report WHERE id=:upload_id",
+            params!(
+                "parse_report" => serde_json::to_string(parse_report).unwrap(),
+                "upload_id" => upload_id
+            ),
+        );
+    }
+
+    fn get_upload_parse_report(&self, db_main: &mut impl Select, member_id: u32, upload_id: u32) -> Result<ParseReport, LiveDataProcessorFailure> {
+        let parse_report = db_main
+            .select_wparams_value(
+                "SELECT parse_report FROM `instance_uploads` WHERE id=:upload_id AND member_id=:member_id",
+                |mut row| row.take_opt::<String, usize>(0).unwrap().ok(),
+                params!("upload_id" => upload_id, "member_id" => member_id),
+            )
+            .ok_or(LiveDataProcessorFailure::UploadNotFound)?;
+
+        // The report is written once the log was parsed
+        parse_report.and_then(|parse_report| serde_json::from_str(&parse_report).ok()).ok_or(LiveDataProcessorFailure::InvalidUploadJobState)
+    }
+}
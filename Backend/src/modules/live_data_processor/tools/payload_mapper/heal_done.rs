@@ -11,13 +11,18 @@ impl MapHealDone for [u8] {
         if self.len() != 38 {
             return Err(LiveDataProcessorFailure::InvalidInput);
         }
+        let total_heal = byte_reader::read_u32(&self[22..26])?;
+        let effective_heal = byte_reader::read_u32(&self[26..30])?;
+        let absorb = byte_reader::read_u32(&self[30..34])?;
         Ok(HealDone {
             caster: self[0..9].to_unit()?,
             target: self[9..18].to_unit()?,
             spell_id: byte_reader::read_u32(&self[18..22])?,
-            total_heal: byte_reader::read_u32(&self[22..26])?,
-            effective_heal: byte_reader::read_u32(&self[26..30])?,
-            absorb: byte_reader::read_u32(&self[30..34])?,
+            total_heal,
+            effective_heal,
+            // The server reports the effective heal, the remainder that was not absorbed is overheal
+            overheal: total_heal.saturating_sub(effective_heal).saturating_sub(absorb),
+            absorb,
             hit_mask: byte_reader::read_u32(&self[34..38])?,
         })
     }
@@ -1,6 +1,7 @@
 use crate::modules::armory::Armory;
 use crate::modules::data::Data;
 use crate::modules::live_data_processor::dto::{LiveDataProcessorFailure, Message};
+use crate::modules::live_data_processor::tools::server::write_server_snapshot;
 use crate::modules::live_data_processor::tools::MessageParser;
 use crate::modules::live_data_processor::LiveDataProcessor;
 use crate::util::database::{Execute, Select};
@@ -19,9 +20,18 @@ impl ProcessMessages for LiveDataProcessor {
     fn process_messages(&self, db_main: &mut (impl Select + Execute), server_id: u32, armory: &Armory, data: &Data, msg_vec: Vec<Message>, member_id: u32, upload_id: u32) -> Result<(), LiveDataProcessorFailure> {
         if !msg_vec.is_empty() {
             self.create_server_if_not_exist(db_main, server_id);
-            let servers = self.servers.read().unwrap();
-            let mut server = servers.get(&server_id).expect("Server Id must exist!").write().unwrap();
-            return server.parse_events(db_main, armory, data, msg_vec, member_id, upload_id);
+            let (result, snapshot) = {
+                let servers = self.servers.read().unwrap();
+                let mut server = servers.get(&server_id).expect("Server Id must exist!").write().unwrap();
+                let result = server.parse_events(db_main, armory, data, msg_vec, member_id, upload_id);
+                let now = time_util::now();
+                server.evict(&self.eviction_policy, now);
+                (result, server.take_snapshot(&self.snapshot_policy, now))
+            };
+            if let Some((snapshot_sequence, snapshot)) = snapshot {
+                write_server_snapshot(&self.written_snapshots, snapshot_sequence, snapshot);
+            }
+            return result;
         }
 
         Ok(())
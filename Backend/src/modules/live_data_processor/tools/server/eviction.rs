@@ -0,0 +1,120 @@
+use crate::modules::live_data_processor::material::{EvictionPolicy, Server};
+use crate::util::metrics::{LIVE_DATA_EVICTED_ENTRIES, LIVE_DATA_STATE_ENTRIES};
+use std::collections::BTreeSet;
+
+impl Server {
+    /// Drops the processing state of instances that did not commit any event for longer than the policy allows
+    /// and bounds the unit caches. Age is measured in wall clock seconds, as uploads of old logs replay historical event timestamps.
+    /// Instances with events that were not saved yet or with running attempts are kept regardless.
+    pub fn evict(&mut self, policy: &EvictionPolicy, current_ts: u64) {
+        // An instance is active since it was first seen and as long as its committed event count keeps increasing
+        for key in self.active_instances.keys() {
+            self.state_last_activity.entry(*key).or_insert((0, current_ts));
+        }
+        for (key, committed_events_count) in self.committed_events_count.iter() {
+            let last_activity = self.state_last_activity.entry(*key).or_insert((*committed_events_count, current_ts));
+            if last_activity.0 != *committed_events_count {
+                *last_activity = (*committed_events_count, current_ts);
+            }
+        }
+        for key in self.active_attempts.keys() {
+            self.state_last_activity.entry(*key).or_insert((0, current_ts));
+        }
+
+        let has_pending_state = |key: &(u32, u32)| self.committed_events.get(key).map_or(false, |committed_events| !committed_events.is_empty()) || self.active_attempts.get(key).map_or(false, |active_attempts| !active_attempts.is_empty());
+        let mut evicted_keys = self
+            .state_last_activity
+            .iter()
+            .filter(|(key, (_, last_activity_ts))| last_activity_ts.saturating_add(policy.max_instance_age) < current_ts && !has_pending_state(key))
+            .map(|(key, _)| *key)
+            .collect::<BTreeSet<(u32, u32)>>();
+        let remaining_keys = self.state_last_activity.len() - evicted_keys.len();
+        if remaining_keys > policy.max_active_instances {
+            let mut by_age = self
+                .state_last_activity
+                .iter()
+                .filter(|(key, _)| !evicted_keys.contains(key) && !has_pending_state(key))
+                .map(|(key, (_, last_activity_ts))| (*last_activity_ts, *key))
+                .collect::<Vec<(u64, (u32, u32))>>();
+            by_age.sort();
+            let evicted_amount = (remaining_keys - policy.max_active_instances).min(by_age.len());
+            evicted_keys.extend(by_age.into_iter().take(evicted_amount).map(|(_, key)| key));
+        }
+
+        for key in evicted_keys.iter() {
+            self.state_last_activity.remove(key);
+            count_evicted(self.server_id, "active_instances", self.active_instances.remove(key).is_some() as u64);
+            count_evicted(self.server_id, "active_attempts", self.active_attempts.remove(key).is_some() as u64);
+            count_evicted(self.server_id, "completed_attempts", self.completed_attempts.remove(key).is_some() as u64);
+            count_evicted(self.server_id, "committed_events", self.committed_events.remove(key).is_some() as u64);
+            count_evicted(self.server_id, "committed_events_count", self.committed_events_count.remove(key).is_some() as u64);
+            count_evicted(
+                self.server_id,
+                "recently_committed_spell_cast_and_aura_applications",
+                self.recently_committed_spell_cast_and_aura_applications.remove(key).is_some() as u64,
+            );
+        }
+
+        if !evicted_keys.is_empty() {
+            let remaining_instance_ids = self.state_last_activity.keys().map(|(instance_id, _)| *instance_id).collect::<BTreeSet<u32>>();
+            let evicted_instance_ids = evicted_keys
+                .iter()
+                .map(|(instance_id, _)| *instance_id)
+                .filter(|instance_id| !remaining_instance_ids.contains(instance_id))
+                .collect::<BTreeSet<u32>>();
+
+            let unit_instance_id_len = self.unit_instance_id.len();
+            self.unit_instance_id.retain(|_, instance_id| !evicted_instance_ids.contains(instance_id));
+            count_evicted(self.server_id, "unit_instance_id", (unit_instance_id_len - self.unit_instance_id.len()) as u64);
+
+            let last_processed_len = self.post_processing_last_precessed_event_id.len();
+            self.post_processing_last_precessed_event_id.retain(|instance_id, _| !evicted_instance_ids.contains(instance_id));
+            count_evicted(self.server_id, "post_processing_last_precessed_event_id", (last_processed_len - self.post_processing_last_precessed_event_id.len()) as u64);
+        }
+
+        let active_instance_meta_ids = self.active_instances.values().map(|unit_instance| unit_instance.instance_meta_id).collect::<BTreeSet<u32>>();
+        let instance_participants_len = self.instance_participants.len();
+        self.instance_participants.retain(|instance_meta_id, _| active_instance_meta_ids.contains(instance_meta_id));
+        count_evicted(self.server_id, "instance_participants", (instance_participants_len - self.instance_participants.len()) as u64);
+
+        // Both caches are rebuilt on demand, the summons from the next summon events of the owners
+        if self.cache_unit.len() > policy.max_cache_entries {
+            count_evicted(self.server_id, "cache_unit", self.cache_unit.len() as u64);
+            self.cache_unit.clear();
+        }
+        if self.summons.len() > policy.max_cache_entries {
+            count_evicted(self.server_id, "summons", self.summons.len() as u64);
+            self.summons.clear();
+        }
+
+        self.update_state_metrics();
+    }
+
+    fn update_state_metrics(&self) {
+        let server_id = self.server_id.to_string();
+        let entries = [
+            ("summons", self.summons.len()),
+            ("active_instances", self.active_instances.len()),
+            ("unit_instance_id", self.unit_instance_id.len()),
+            ("instance_participants", self.instance_participants.len()),
+            ("active_attempts", self.active_attempts.len()),
+            ("completed_attempts", self.completed_attempts.len()),
+            ("subject_prepend_mode_set", self.subject_prepend_mode_set.len()),
+            ("post_processing_last_precessed_event_id", self.post_processing_last_precessed_event_id.len()),
+            ("non_committed_events", self.non_committed_events.len()),
+            ("committed_events", self.committed_events.len()),
+            ("committed_events_count", self.committed_events_count.len()),
+            ("recently_committed_spell_cast_and_aura_applications", self.recently_committed_spell_cast_and_aura_applications.len()),
+            ("cache_unit", self.cache_unit.len()),
+        ];
+        for (map, amount) in entries.iter() {
+            LIVE_DATA_STATE_ENTRIES.with_label_values(&[&server_id, map]).set(*amount as i64);
+        }
+    }
+}
+
+fn count_evicted(server_id: u32, map: &str, amount: u64) {
+    if amount > 0 {
+        LIVE_DATA_EVICTED_ENTRIES.with_label_values(&[&server_id.to_string(), map]).inc_by(amount);
+    }
+}
@@ -1,11 +1,14 @@
 pub use self::dispel::try_parse_dispel;
 pub use self::instance_reset::HandleInstanceReset;
 pub use self::interrupt::*;
+pub use self::snapshot::write_server_snapshot;
 pub use self::spell_steal::try_parse_spell_steal;
 
 mod dispel;
+mod eviction;
 mod instance_reset;
 mod interrupt;
 pub mod server;
 pub mod server_post_processing;
+mod snapshot;
 mod spell_steal;
@@ -40,7 +40,7 @@ impl Server {
         self.test_for_committable_events(db_main, data, armory, member_id);
         self.cleanup(last_ts);
         self.reset_instances(db_main, last_ts);
-        self.perform_post_processing(db_main, data);
+        self.perform_post_processing(db_main, armory, data);
         println!("Done");
         Ok(())
     }
@@ -439,6 +439,7 @@ impl Server {
                         heal: domain_value::Heal {
                             total: heal_done.total_heal,
                             effective: heal_done.effective_heal,
+                            overheal: heal_done.overheal,
                             mitigation: if heal_done.absorb > 0 { vec![Mitigation::Absorb(heal_done.absorb)] } else { Vec::new() },
                             target,
                             hit_mask: hit_mask_from_u32(heal_done.hit_mask),
@@ -1,30 +1,30 @@
 #![allow(clippy::if_same_then_else)]
 
-use crate::modules::data::tools::{RetrieveEncounterNpc, RetrieveItem, RetrieveLocalization, RetrieveNPC};
+use crate::modules::armory::Armory;
+use crate::modules::data::tools::{RetrieveEncounterNpc, RetrieveEncounterRule, RetrieveItem, RetrieveLocalization, RetrieveNPC};
 use crate::modules::data::Data;
+use crate::modules::instance::tools::{update_ranking_results, RankingScope};
 use crate::modules::live_data_processor::domain_value::get_spell_components_total;
 use crate::modules::live_data_processor::domain_value::{Creature, Event, EventType, Player, Power, PowerType, Unit, UnitInstance};
 use crate::modules::live_data_processor::material::{Attempt, Server};
+use crate::modules::live_data_processor::tools::event_segment::{convert_directory_to_event_segment, get_event_segment_path};
 use crate::modules::live_data_processor::tools::LiveDataDeserializer;
 use crate::params;
 use crate::util::database::{Execute, Select};
 use time_util::format_ts_ms;
 use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::fs::File;
-use std::io::{Read, Write};
+use std::io::Write;
 use std::ops::Div;
 use std::path::Path;
-use walkdir::WalkDir;
-use zip::write::FileOptions;
 
 impl Server {
-    pub fn perform_post_processing(&mut self, db_main: &mut (impl Execute + Select), data: &Data) {
-        self.extract_attempts_and_collect_ranking(db_main, data);
+    pub fn perform_post_processing(&mut self, db_main: &mut (impl Execute + Select), armory: &Armory, data: &Data) {
+        self.extract_attempts_and_collect_ranking(db_main, armory, data);
         self.extract_loot(db_main, data);
         self.save_current_event_id_and_end_ts(db_main);
         self.save_committed_events_to_disk();
-        self.zip_instances();
+        self.write_instance_segments();
     }
 
     fn extract_loot(&self, db_main: &mut (impl Execute + Select), data: &Data) {
@@ -58,7 +58,7 @@ impl Server {
      *    2. Or if pivot NPC dies
      *    3. Or if pivot NPC goes below a certain threshold
      */
-    fn extract_attempts_and_collect_ranking(&mut self, db_main: &mut (impl Execute + Select), data: &Data) {
+    fn extract_attempts_and_collect_ranking(&mut self, db_main: &mut (impl Execute + Select), armory: &Armory, data: &Data) {
         static KILL_MIN_INFIGHT_UNITS: usize = 5;
 
         let mut has_percent_players_in_combat_events = false;
@@ -78,7 +78,7 @@ impl Server {
                             for (_, mut attempt) in active_attempts.drain() {
                                 println!("{}: committing attempt for encounter {} due to PercentPlayersInCombat=0", format_ts_ms(event.timestamp), attempt.encounter_id);
                                 attempt.end_ts = event.timestamp;
-                                commit_attempt(db_main, *instance_meta_id, attempt, completed_attempts);
+                                commit_attempt(db_main, armory, data, *instance_meta_id, attempt, completed_attempts);
                             }
                         }
                         previous_percent_players_in_combat = *percentage;
@@ -87,6 +87,14 @@ impl Server {
 
                     match &event.subject {
                         Unit::Creature(Creature { creature_id, encounter_npc_id, owner: _ }) => {
+                            if let EventType::Death { .. } = &event.event {
+                                for (encounter_id, attempt) in active_attempts.iter_mut() {
+                                    if data.is_hard_mode_npc(*encounter_id, *encounter_npc_id) {
+                                        attempt.hard_mode_npcs_died.insert(*encounter_npc_id);
+                                    }
+                                }
+                            }
+
                             if let Some(encounter_npc) = data.get_encounter_npc(*encounter_npc_id) {
                                 match &event.event {
                                     EventType::CombatState { in_combat } => {
@@ -100,16 +108,7 @@ impl Server {
                                                 println!("{}: starting combat with {} for encounter {} attempt enc {}", format_ts_ms(event.timestamp), encounter_npc.npc_id, encounter_npc.encounter_id, attempt.encounter_id);
 
                                                 // add main boss to fights with starting add phases
-                                                // C'Thun(42), Nefarian(29), Razorgore(22), Thekal(17), Kel'Thuzad(57), Gothik(54), Thaddius(46), Ley-Watcher Incantagos(207)
-                                                if encounter_npc.encounter_id == 42
-                                                    || encounter_npc.encounter_id == 29
-                                                    || encounter_npc.encounter_id == 22
-                                                    || encounter_npc.encounter_id == 17
-                                                    || encounter_npc.encounter_id == 57
-                                                    || encounter_npc.encounter_id == 54
-                                                    || encounter_npc.encounter_id == 46
-                                                    || encounter_npc.encounter_id == 207
-                                                {
+                                                if data.encounter_has_add_phase(encounter_npc.encounter_id) {
                                                     // add required death creatures
                                                     let required_death_creature_ids = data.get_required_death_creature_ids(encounter_npc.encounter_id);
                                                     for required_death_creature_id in required_death_creature_ids {
@@ -152,7 +151,7 @@ impl Server {
                                                     );
 
                                                     attempt.end_ts = event.timestamp;
-                                                    commit_attempt(db_main, *instance_meta_id, attempt, completed_attempts);
+                                                    commit_attempt(db_main, armory, data, *instance_meta_id, attempt, completed_attempts);
                                                 }
                                             }
                                         }
@@ -198,7 +197,7 @@ impl Server {
                                             if let Some(mut attempt) = active_attempts.remove(&encounter_npc.encounter_id) {
                                                 attempt.end_ts = event.timestamp;
                                                 println!("{}: combat death for {} for encounter {} attempt enc {}", format_ts_ms(event.timestamp), encounter_npc.npc_id, encounter_npc.encounter_id, attempt.encounter_id);
-                                                commit_attempt(db_main, *instance_meta_id, attempt, completed_attempts);
+                                                commit_attempt(db_main, armory, data, *instance_meta_id, attempt, completed_attempts);
                                             }
                                         }
                                     },
@@ -221,7 +220,7 @@ impl Server {
                                                         "{}: pivot creature {} health threshold reached for encounter {} attempt enc {}",
                                                         format_ts_ms(event.timestamp), creature_id, encounter_npc.encounter_id, attempt.encounter_id
                                                     );
-                                                    commit_attempt(db_main, *instance_meta_id, attempt, completed_attempts);
+                                                    commit_attempt(db_main, armory, data, *instance_meta_id, attempt, completed_attempts);
                                                 }
                                             }
                                         }
@@ -249,10 +248,9 @@ impl Server {
 
                                         // If enough player are OOC and Kill requirements are fulfilled
                                         for (encounter_id, attempt) in active_attempts.clone() {
-                                            // C'Thun(42), Nefarian(29), Razorgore(22), Thekal(17), Kel'Thuzad(57), Gothik(54), Thaddius(46)
-                                            if encounter_id == 42 || encounter_id == 29 || encounter_id == 22 || encounter_id == 17 || encounter_id == 57 || encounter_id == 54 || encounter_id == 46 {
-                                                // if it has been less than 30 seconds since the last creature death ignore this event
-                                                if event.timestamp - attempt.last_creature_death < 30000 {
+                                            // if the adds of the encounter died within the grace period ignore this event
+                                            if let Some(grace_period) = data.get_add_phase_grace_period(encounter_id) {
+                                                if event.timestamp - attempt.last_creature_death < grace_period {
                                                     continue;
                                                 }
                                             }
@@ -263,7 +261,7 @@ impl Server {
                                                     if let Some(mut attempt) = active_attempts.remove(&encounter_id) {
                                                         attempt.end_ts = event.timestamp;
                                                         println!("{}: < 5 infight units committing attempt for encounter {} as kill", format_ts_ms(event.timestamp), encounter_id);
-                                                        commit_attempt(db_main, *instance_meta_id, attempt, completed_attempts);
+                                                        commit_attempt(db_main, armory, data, *instance_meta_id, attempt, completed_attempts);
                                                     }
                                                 }
                                                 // Commit As Attempt
@@ -271,7 +269,7 @@ impl Server {
                                                     if let Some(mut attempt) = active_attempts.remove(&encounter_id) {
                                                         attempt.end_ts = event.timestamp;
                                                         println!("{}: < 5 infight units committing attempt for encounter {} as attempt", format_ts_ms(event.timestamp), encounter_id);
-                                                        commit_attempt(db_main, *instance_meta_id, attempt, completed_attempts);
+                                                        commit_attempt(db_main, armory, data, *instance_meta_id, attempt, completed_attempts);
                                                     }
                                                 }
                                             }
@@ -279,8 +277,8 @@ impl Server {
                                     }
                                 },
                                 EventType::AuraApplication(aura_app) => {
-                                    if aura_app.spell_id == 62670 || aura_app.spell_id == 62650 || aura_app.spell_id == 62671 || aura_app.spell_id == 62702 {
-                                        if let Some((_, attempt)) = active_attempts.iter_mut().find(|(encounter_id, _)| **encounter_id == 126) {
+                                    for (encounter_id, attempt) in active_attempts.iter_mut() {
+                                        if data.is_hard_mode_aura(*encounter_id, aura_app.spell_id) {
                                             attempt.hard_mode_found_buffs.insert(aura_app.spell_id);
                                         }
                                     }
@@ -354,12 +352,12 @@ impl Server {
         }
     }
 
-    fn zip_instances(&mut self) {
+    fn write_instance_segments(&mut self) {
         let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
         for (key, instance) in self.active_instances.clone() {
-            let dst_file = format!("{}/{}/{}.zip", storage_path, self.server_id, instance.instance_meta_id);
+            let segment_path = get_event_segment_path(&storage_path, self.server_id, instance.instance_meta_id);
             let src_dir = format!("{}/{}/{}", storage_path, self.server_id, instance.instance_meta_id);
-            if Path::new(&src_dir).exists() && zip_directory(src_dir.clone(), dst_file).is_ok() {
+            if Path::new(&src_dir).exists() && convert_directory_to_event_segment(&src_dir, &segment_path).is_ok() {
                 let _ = fs::remove_dir_all(&src_dir);
             }
             self.instance_participants.remove(&instance.instance_meta_id);
@@ -369,37 +367,6 @@ impl Server {
     }
 }
 
-fn zip_directory(src_dir: String, dst_file: String) -> zip::result::ZipResult<()> {
-    let path = Path::new(&dst_file);
-    let file = File::create(&path)?;
-
-    let walkdir = WalkDir::new(src_dir.clone());
-    let it = walkdir.into_iter();
-
-    let mut zip = zip::ZipWriter::new(file);
-    let options = FileOptions::default().compression_method(zip::CompressionMethod::Bzip2).unix_permissions(0o755);
-
-    let mut buffer = Vec::new();
-    for entry in it {
-        let path = entry.as_ref().unwrap().path();
-        let name = path.strip_prefix(Path::new(&src_dir)).unwrap();
-
-        if path.is_file() {
-            #[allow(deprecated)]
-            zip.start_file_from_path(name, options)?;
-            let mut f = File::open(path)?;
-            f.read_to_end(&mut buffer)?;
-            zip.write_all(&*buffer)?;
-            buffer.clear();
-        } else if !name.as_os_str().is_empty() {
-            #[allow(deprecated)]
-            zip.add_directory_from_path(name, options)?;
-        }
-    }
-    zip.finish()?;
-    Result::Ok(())
-}
-
 fn process_ranking(unit: &Unit, event: &Event, data: &Data, active_attempts: &mut HashMap<u32, Attempt>) {
     if let Unit::Player(Player { character_id, .. }) = unit.get_owner_or_self() {
         match &event.event {
@@ -407,11 +374,15 @@ fn process_ranking(unit: &Unit, event: &Event, data: &Data, active_attempts: &mu
                 if let Unit::Creature(Creature { encounter_npc_id: entry, .. }) = damage.victim {
                     if let Some(encounter_npc) = data.get_encounter_npc(entry) {
                         if let Some(attempt) = active_attempts.get_mut(&encounter_npc.encounter_id) {
+                            let hit = get_spell_components_total(&damage.components);
                             if let Some(player_damage) = attempt.ranking_damage.get_mut(&character_id) {
-                                *player_damage += get_spell_components_total(&damage.components);
+                                *player_damage += hit;
                             } else {
-                                attempt.ranking_damage.insert(character_id, get_spell_components_total(&damage.components));
+                                attempt.ranking_damage.insert(character_id, hit);
                             }
+                            let (hits, max_hit) = attempt.ranking_damage_hits.entry(character_id).or_insert((0, 0));
+                            *hits += 1;
+                            *max_hit = (*max_hit).max(hit);
                         }
                     }
                 }
@@ -446,104 +417,14 @@ fn process_ranking(unit: &Unit, event: &Event, data: &Data, active_attempts: &mu
     }
 }
 
-fn commit_attempt(db_main: &mut (impl Execute + Select), instance_meta_id: u32, mut attempt: Attempt, completed_attempts: &mut HashMap<u32, Attempt>) {
+fn commit_attempt(db_main: &mut (impl Execute + Select), armory: &Armory, data: &Data, instance_meta_id: u32, mut attempt: Attempt, completed_attempts: &mut HashMap<u32, Attempt>) {
     // Likely a false positive
     if attempt.end_ts - attempt.start_ts <= 5000 {
         println!("{}: ignoring attempt for encounter {} as it is too short", format_ts_ms(attempt.start_ts), attempt.encounter_id);
         return;
     }
 
-    // switch encounter from 42 -> 60 for C'Thun < 2 min (AQ40)
-    if attempt.encounter_id == 42 && attempt.end_ts - attempt.start_ts < 120000 {
-        println!("switching encounter from 42 to 60 for C'Thun < 2 min {}", attempt.end_ts - attempt.start_ts);
-        attempt.encounter_id = 60;
-    }
-
-    // switch encounter from 54 -> 61 for Gothik < 3 min (Gothik)
-    if attempt.encounter_id == 54 && attempt.end_ts - attempt.start_ts < 180000 {
-        println!("switching encounter from 54 to 61 for Gothik < 3 min {}", attempt.end_ts - attempt.start_ts);
-        attempt.encounter_id = 61;
-    }
-
-    // switch encounter from 57 -> 62 for Kel'Thuzad < 3.5 min (Kel'Thuzad)
-    if attempt.encounter_id == 57 && attempt.end_ts - attempt.start_ts < 210000 {
-        println!("switching encounter from 57 to 62 for Kel'Thuzad < 3.5 min {}", attempt.end_ts - attempt.start_ts);
-        attempt.encounter_id = 62;
-    }
-
-    // switch encounter from 57 -> 63 for Kel'Thuzad > 6 min (Kel'Thuzad)
-    if attempt.encounter_id == 57 && attempt.end_ts - attempt.start_ts > 360000 {
-        println!("switching encounter from 57 to 63 for Kel'Thuzad > 6 min {}", attempt.end_ts - attempt.start_ts);
-        attempt.encounter_id = 63;
-    }
-
-    // switch encounter from 29 -> 64 for Nefarian < 3 min (Nefarian)
-    if attempt.encounter_id == 29 && attempt.end_ts - attempt.start_ts < 180000 {
-        println!("switching encounter from 29 to 64 for Nefarian < 3 min {}", attempt.end_ts - attempt.start_ts);
-        attempt.encounter_id = 64;
-    }
-
-
-    // Ulduar hard modes
-    // Flame Leviathan
-    if attempt.encounter_id == 114 {
-        if attempt.hard_mode_found_buffs.len() == 1 {
-            attempt.hard_mode_encounter_id = Some(146);
-        } else if attempt.hard_mode_found_buffs.len() == 2 {
-            attempt.hard_mode_encounter_id = Some(147);
-        } else if attempt.hard_mode_found_buffs.len() == 3 {
-            attempt.hard_mode_encounter_id = Some(148);
-        } else if attempt.hard_mode_found_buffs.len() == 4 {
-            attempt.hard_mode_encounter_id = Some(149);
-        }
-    }
-    // Freya
-    else if attempt.encounter_id == 122 {
-        let knocks = attempt.hard_mode_found_buffs.len() + attempt.hard_mode_npcs_died.len();
-        if knocks == 1 {
-            attempt.hard_mode_encounter_id = Some(152);
-        } else if knocks == 2 {
-            attempt.hard_mode_encounter_id = Some(153);
-        } else if knocks == 3 {
-            attempt.hard_mode_encounter_id = Some(154);
-        }
-    }
-    // Mimiron
-    else if attempt.encounter_id == 121 {
-        if !attempt.hard_mode_found_buffs.is_empty() {
-            attempt.hard_mode_encounter_id = Some(156);
-        }
-    }
-    // Thorim
-    else if attempt.encounter_id == 123 {
-        if !attempt.hard_mode_found_buffs.is_empty() {
-            attempt.hard_mode_encounter_id = Some(155);
-        }
-    }
-    // Hodir
-    else if attempt.encounter_id == 124 {
-        if attempt.end_ts - attempt.start_ts - 2000 <= 120000 {
-            attempt.hard_mode_encounter_id = Some(162);
-        }
-    }
-    // General Vezax
-    else if attempt.encounter_id == 125 {
-        if !attempt.hard_mode_found_buffs.is_empty() {
-            attempt.hard_mode_encounter_id = Some(157);
-        }
-    }
-    // Yogg-Saron
-    else if attempt.encounter_id == 126 {
-        if attempt.hard_mode_found_buffs.len() == 3 {
-            attempt.hard_mode_encounter_id = Some(158);
-        } else if attempt.hard_mode_found_buffs.len() == 2 {
-            attempt.hard_mode_encounter_id = Some(159);
-        } else if attempt.hard_mode_found_buffs.len() == 1 {
-            attempt.hard_mode_encounter_id = Some(160);
-        } else if attempt.hard_mode_found_buffs.is_empty() {
-            attempt.hard_mode_encounter_id = Some(161);
-        }
-    }
+    apply_encounter_rules(data, &mut attempt);
 
     if attempt.creatures_required_to_die.is_empty() {
         println!("{}: committing attempt for encounter {} as kill", format_ts_ms(attempt.end_ts), attempt.encounter_id);
@@ -576,18 +457,27 @@ fn commit_attempt(db_main: &mut (impl Execute + Select), instance_meta_id: u32,
 
     if let Some(attempt_id) = db_main.select_wparams_value(
         "SELECT id FROM `instance_attempt` WHERE instance_meta_id=:instance_meta_id AND encounter_id=:encounter_id AND start_ts=:start_ts AND end_ts=:end_ts AND is_kill=:is_kill",
-        |mut row| row.take::<u32, usize>(0),
+        |mut row| row.take::<u32, usize>(0).unwrap(),
         params,
     ) {
         let ranking_damage = std::mem::replace(&mut attempt.ranking_damage, HashMap::new());
+        let ranking_damage_hits = std::mem::replace(&mut attempt.ranking_damage_hits, HashMap::new());
         db_main.execute_batch_wparams(
-            "INSERT INTO `instance_ranking_damage` (`character_id`, `attempt_id`, `damage`) VALUES (:character_id, :attempt_id, :damage)",
-            ranking_damage.into_iter().collect(),
-            move |(character_id, damage)| {
+            "INSERT INTO `instance_ranking_damage` (`character_id`, `attempt_id`, `damage`, `hits`, `max_hit`) VALUES (:character_id, :attempt_id, :damage, :hits, :max_hit)",
+            ranking_damage
+                .into_iter()
+                .map(|(character_id, damage)| {
+                    let (hits, max_hit) = ranking_damage_hits.get(&character_id).cloned().unwrap_or((0, 0));
+                    (character_id, damage, hits, max_hit)
+                })
+                .collect(),
+            move |(character_id, damage, hits, max_hit)| {
                 params! {
                     "character_id" => character_id,
                     "attempt_id" => attempt_id,
-                    "damage" => damage
+                    "damage" => damage,
+                    "hits" => hits,
+                    "max_hit" => max_hit
                 }
             },
         );
@@ -617,6 +507,23 @@ fn commit_attempt(db_main: &mut (impl Execute + Select), instance_meta_id: u32,
                 }
             },
         );
+
+        // Anomalies are detected by the instance update loop
+        update_ranking_results(db_main, armory, data, RankingScope::Attempt(attempt_id));
+    }
+}
+
+/// Attributes the attempt to the duration variant of its encounter and, if applicable, to its hard mode.
+pub fn apply_encounter_rules(data: &Data, attempt: &mut Attempt) {
+    let duration = attempt.end_ts - attempt.start_ts;
+    if let Some(variant_encounter_id) = data.get_encounter_duration_variant(attempt.encounter_id, duration) {
+        println!("switching encounter from {} to {} for duration {}", attempt.encounter_id, variant_encounter_id, duration);
+        attempt.encounter_id = variant_encounter_id;
+    }
+
+    let found_triggers = attempt.hard_mode_found_buffs.len() + attempt.hard_mode_npcs_died.len();
+    if let Some(hard_mode_encounter_id) = data.get_hard_mode_encounter_id(attempt.encounter_id, found_triggers, duration) {
+        attempt.hard_mode_encounter_id = Some(hard_mode_encounter_id);
     }
 }
 
@@ -0,0 +1,128 @@
+use crate::modules::live_data_processor::material::{Server, ServerSnapshot, SnapshotPolicy, SERVER_SNAPSHOT_VERSION};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+
+impl Server {
+    /// Takes a snapshot of the in-flight processing state, so that a restart does not split instances or attempts.
+    /// Expected to be called after post processing and eviction, taking one after every package is too expensive though.
+    pub fn take_snapshot(&mut self, policy: &SnapshotPolicy, now: u64) -> Option<(u64, ServerSnapshot)> {
+        self.packages_since_snapshot += 1;
+        if self.packages_since_snapshot < policy.max_packages && self.last_snapshot_ts.saturating_add(policy.interval_secs) > now {
+            return None;
+        }
+
+        self.packages_since_snapshot = 0;
+        self.last_snapshot_ts = now;
+        self.snapshot_sequence += 1;
+        Some((self.snapshot_sequence, self.create_snapshot()))
+    }
+
+    pub fn create_snapshot(&self) -> ServerSnapshot {
+        ServerSnapshot {
+            version: SERVER_SNAPSHOT_VERSION,
+            server_id: self.server_id,
+            summons: self.summons.clone(),
+            active_instances: self.active_instances.iter().map(|(key, value)| (*key, value.clone())).collect(),
+            last_raid_instance_id: self.last_raid_instance_id,
+            unit_instance_id: self.unit_instance_id.clone(),
+            instance_participants: self.instance_participants.clone(),
+            active_attempts: self.active_attempts.iter().map(|(key, value)| (*key, value.clone())).collect(),
+            completed_attempts: self.completed_attempts.iter().map(|(key, value)| (*key, value.clone())).collect(),
+            subject_prepend_mode_set: self.subject_prepend_mode_set.clone(),
+            post_processing_last_precessed_event_id: self.post_processing_last_precessed_event_id.clone(),
+            non_committed_events: self.non_committed_events.clone(),
+            committed_events: self.committed_events.iter().map(|(key, value)| (*key, value.clone())).collect(),
+            committed_events_count: self.committed_events_count.iter().map(|(key, value)| (*key, *value)).collect(),
+            recently_committed_spell_cast_and_aura_applications: self.recently_committed_spell_cast_and_aura_applications.iter().map(|(key, value)| (*key, value.clone())).collect(),
+        }
+    }
+
+    /// Restores the state of the last snapshot on top of the state that was loaded from the database.
+    pub fn restore_snapshot(&mut self) {
+        if let Some(snapshot) = std::fs::read(get_server_snapshot_path(self.server_id)).ok().and_then(|content| serde_json::from_slice::<ServerSnapshot>(&content).ok()) {
+            self.apply_snapshot(snapshot);
+        }
+    }
+
+    /// Snapshots of another version or server are ignored.
+    /// Instances that expired or were deleted in the meantime are not restored.
+    pub fn apply_snapshot(&mut self, snapshot: ServerSnapshot) {
+        if snapshot.version != SERVER_SNAPSHOT_VERSION || snapshot.server_id != self.server_id {
+            return;
+        }
+
+        let known_instances = self.active_instances.values().map(|unit_instance| unit_instance.instance_meta_id).collect::<BTreeSet<u32>>();
+        let restored_instances = snapshot.active_instances.into_iter().filter(|(_, unit_instance)| known_instances.contains(&unit_instance.instance_meta_id)).collect::<Vec<_>>();
+        let restored_keys = restored_instances.iter().map(|(key, _)| *key).collect::<BTreeSet<(u32, u32)>>();
+        let restored_instance_meta_ids = restored_instances.iter().map(|(_, unit_instance)| unit_instance.instance_meta_id).collect::<BTreeSet<u32>>();
+
+        for (key, unit_instance) in restored_instances {
+            self.active_instances.insert(key, unit_instance);
+        }
+        for (instance_meta_id, participants) in snapshot.instance_participants {
+            if restored_instance_meta_ids.contains(&instance_meta_id) {
+                self.instance_participants.entry(instance_meta_id).or_insert_with(BTreeSet::new).extend(participants);
+            }
+        }
+        for (key, event_count) in snapshot.committed_events_count {
+            if restored_keys.contains(&key) {
+                let current_event_count = self.committed_events_count.entry(key).or_insert(0);
+                *current_event_count = (*current_event_count).max(event_count);
+            }
+        }
+        self.active_attempts.extend(snapshot.active_attempts.into_iter().filter(|(key, _)| restored_keys.contains(key)));
+        self.completed_attempts.extend(snapshot.completed_attempts.into_iter().filter(|(key, _)| restored_keys.contains(key)));
+        self.committed_events.extend(snapshot.committed_events.into_iter().filter(|(key, _)| restored_keys.contains(key)));
+        self.recently_committed_spell_cast_and_aura_applications
+            .extend(snapshot.recently_committed_spell_cast_and_aura_applications.into_iter().filter(|(key, _)| restored_keys.contains(key)));
+        self.unit_instance_id.extend(
+            snapshot
+                .unit_instance_id
+                .into_iter()
+                .filter(|(_, instance_id)| restored_keys.iter().any(|(restored_instance_id, _)| restored_instance_id == instance_id)),
+        );
+
+        self.summons = snapshot.summons;
+        self.last_raid_instance_id = snapshot.last_raid_instance_id;
+        self.subject_prepend_mode_set = snapshot.subject_prepend_mode_set;
+        self.post_processing_last_precessed_event_id = snapshot.post_processing_last_precessed_event_id;
+        self.non_committed_events = snapshot.non_committed_events;
+    }
+}
+
+/// Writes the snapshot outside of the server lock. Workers may finish their writes out of order,
+/// hence snapshots that were overtaken by a newer one of the same server are dropped.
+pub fn write_server_snapshot(written_snapshots: &Mutex<HashMap<u32, u64>>, snapshot_sequence: u64, snapshot: ServerSnapshot) {
+    let server_id = snapshot.server_id;
+    let content = match serde_json::to_vec(&snapshot) {
+        Ok(content) => content,
+        Err(error) => {
+            println!("Failed to serialize the snapshot of server {}: {}", server_id, error);
+            return;
+        },
+    };
+
+    let mut written_snapshots = written_snapshots.lock().unwrap();
+    if written_snapshots.get(&server_id).map_or(false, |written_sequence| *written_sequence >= snapshot_sequence) {
+        return;
+    }
+    let snapshot_path = get_server_snapshot_path(server_id);
+    let tmp_path = format!("{}.tmp", snapshot_path);
+    if let Err(error) = std::fs::create_dir_all(get_server_snapshot_dir())
+        .and_then(|_| std::fs::write(&tmp_path, content))
+        .and_then(|_| std::fs::rename(&tmp_path, &snapshot_path))
+    {
+        println!("Failed to write the snapshot of server {}: {}", server_id, error);
+        return;
+    }
+    written_snapshots.insert(server_id, snapshot_sequence);
+}
+
+fn get_server_snapshot_dir() -> String {
+    let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
+    format!("{}/snapshots", storage_path)
+}
+
+fn get_server_snapshot_path(server_id: u32) -> String {
+    format!("{}/server_{}.json", get_server_snapshot_dir(), server_id)
+}
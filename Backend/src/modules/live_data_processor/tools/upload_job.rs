@@ -0,0 +1,297 @@
+use std::fs::File;
+use std::io::Read;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::modules::armory::Armory;
+use crate::modules::data::tools::RetrieveServer;
+use crate::modules::data::Data;
+use crate::modules::live_data_processor::domain_value::UploadJobStatus;
+use crate::modules::live_data_processor::dto::{LiveDataProcessorFailure, UploadJob};
+use crate::modules::live_data_processor::dto::Message;
+use crate::modules::live_data_processor::material::{WoWTBCParser, WoWVanillaParser, WoWWotLKParser};
+use crate::modules::live_data_processor::tools::cbl_parser::{select_combat_log_format, CombatLogFormat, CombatLogParser};
+use crate::modules::live_data_processor::tools::log_parser::parse_cbl;
+use crate::modules::live_data_processor::tools::{ProcessMessages, UploadParseReport};
+use crate::modules::live_data_processor::LiveDataProcessor;
+use crate::params;
+use crate::util::database::{Execute, Select};
+
+pub const UPLOAD_JOB_MAX_ATTEMPTS: u8 = 3;
+// Once a job reached this progress its messages are handed to the server and become visible.
+// Repeating it from there would create duplicate instances, hence it is not retried anymore.
+pub const UPLOAD_JOB_COMMIT_PROGRESS: u8 = 99;
+
+pub struct QueuedUploadJob {
+    pub upload_id: u32,
+    pub member_id: u32,
+    pub server_id: u32,
+}
+
+pub trait UploadJobQueue {
+    fn enqueue_upload_job(&self, db_main: &mut impl Execute, upload_id: u32, member_id: u32, server_id: u32) -> Result<(), LiveDataProcessorFailure>;
+    fn get_upload_job(&self, db_main: &mut impl Select, member_id: u32, upload_id: u32) -> Result<UploadJob, LiveDataProcessorFailure>;
+    fn get_upload_jobs(&self, db_main: &mut impl Select, member_id: u32) -> Vec<UploadJob>;
+    fn cancel_upload_job(&self, db_main: &mut (impl Select + Execute), member_id: u32, upload_id: u32) -> Result<(), LiveDataProcessorFailure>;
+    fn retry_upload_job(&self, db_main: &mut (impl Select + Execute), member_id: u32, upload_id: u32) -> Result<(), LiveDataProcessorFailure>;
+    fn recover_upload_jobs(&self, db_main: &mut impl Execute);
+    fn claim_upload_job(&self, db_main: &mut (impl Select + Execute)) -> Option<QueuedUploadJob>;
+    fn run_upload_job(&self, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, job: QueuedUploadJob);
+}
+
+impl UploadJobQueue for LiveDataProcessor {
+    fn enqueue_upload_job(&self, db_main: &mut impl Execute, upload_id: u32, member_id: u32, server_id: u32) -> Result<(), LiveDataProcessorFailure> {
+        let now = time_util::now();
+        if db_main.execute_wparams(
+            "INSERT INTO `instance_upload_jobs` (`upload_id`, `member_id`, `server_id`, `status`, `created`, `updated`) VALUES (:upload_id, :member_id, :server_id, :status, :now, :now)",
+            params!(
+                "upload_id" => upload_id,
+                "member_id" => member_id,
+                "server_id" => server_id,
+                "status" => UploadJobStatus::Queued.to_u8(),
+                "now" => now
+            ),
+        ) {
+            return Ok(());
+        }
+        Err(LiveDataProcessorFailure::DatabaseFailure(String::from("enqueue_upload_job")))
+    }
+
+    fn get_upload_job(&self, db_main: &mut impl Select, member_id: u32, upload_id: u32) -> Result<UploadJob, LiveDataProcessorFailure> {
+        db_main
+            .select_wparams_value(
+                "SELECT upload_id, server_id, status, progress, attempts, error, created, updated FROM `instance_upload_jobs` WHERE upload_id=:upload_id AND member_id=:member_id",
+                map_upload_job,
+                params!("upload_id" => upload_id, "member_id" => member_id),
+            )
+            .ok_or(LiveDataProcessorFailure::UploadNotFound)
+    }
+
+    fn get_upload_jobs(&self, db_main: &mut impl Select, member_id: u32) -> Vec<UploadJob> {
+        db_main.select_wparams(
+            "SELECT upload_id, server_id, status, progress, attempts, error, created, updated FROM `instance_upload_jobs` WHERE member_id=:member_id ORDER BY id DESC LIMIT 50",
+            map_upload_job,
+            params!("member_id" => member_id),
+        )
+    }
+
+    fn cancel_upload_job(&self, db_main: &mut (impl Select + Execute), member_id: u32, upload_id: u32) -> Result<(), LiveDataProcessorFailure> {
+        let job = self.get_upload_job(db_main, member_id, upload_id)?;
+        if job.status != UploadJobStatus::Queued && job.status != UploadJobStatus::Processing {
+            return Err(LiveDataProcessorFailure::InvalidUploadJobState);
+        }
+
+        // The worker checks the status again before committing, so a job that is currently processed stops there
+        if db_main.execute_wparams(
+            "UPDATE `instance_upload_jobs` SET `status`=:cancelled, `updated`=:now WHERE upload_id=:upload_id AND progress < :commit_progress AND status IN (:queued, :processing)",
+            params!(
+                "cancelled" => UploadJobStatus::Cancelled.to_u8(),
+                "now" => time_util::now(),
+                "upload_id" => upload_id,
+                "commit_progress" => UPLOAD_JOB_COMMIT_PROGRESS,
+                "queued" => UploadJobStatus::Queued.to_u8(),
+                "processing" => UploadJobStatus::Processing.to_u8()
+            ),
+        ) && self.get_upload_job(db_main, member_id, upload_id)?.status == UploadJobStatus::Cancelled
+        {
+            return Ok(());
+        }
+        Err(LiveDataProcessorFailure::InvalidUploadJobState)
+    }
+
+    fn retry_upload_job(&self, db_main: &mut (impl Select + Execute), member_id: u32, upload_id: u32) -> Result<(), LiveDataProcessorFailure> {
+        let job = self.get_upload_job(db_main, member_id, upload_id)?;
+        if (job.status != UploadJobStatus::Failed && job.status != UploadJobStatus::Cancelled) || job.progress >= UPLOAD_JOB_COMMIT_PROGRESS {
+            return Err(LiveDataProcessorFailure::InvalidUploadJobState);
+        }
+
+        if db_main.execute_wparams(
+            "UPDATE `instance_upload_jobs` SET `status`=:queued, `progress`=0, `attempts`=0, `error`=NULL, `updated`=:now WHERE upload_id=:upload_id",
+            params!(
+                "queued" => UploadJobStatus::Queued.to_u8(),
+                "now" => time_util::now(),
+                "upload_id" => upload_id
+            ),
+        ) {
+            return Ok(());
+        }
+        Err(LiveDataProcessorFailure::DatabaseFailure(String::from("retry_upload_job")))
+    }
+
+    fn recover_upload_jobs(&self, db_main: &mut impl Execute) {
+        // Jobs that were interrupted before they were committed start over,
+        // the other ones may have been partially committed and can't be repeated safely.
+        db_main.execute_wparams(
+            "UPDATE `instance_upload_jobs` SET `status`=:failed, `error`='Interrupted by a restart', `updated`=:now WHERE status=:processing AND (progress >= :commit_progress OR attempts >= :max_attempts)",
+            params!(
+                "failed" => UploadJobStatus::Failed.to_u8(),
+                "now" => time_util::now(),
+                "processing" => UploadJobStatus::Processing.to_u8(),
+                "commit_progress" => UPLOAD_JOB_COMMIT_PROGRESS,
+                "max_attempts" => UPLOAD_JOB_MAX_ATTEMPTS
+            ),
+        );
+        db_main.execute_wparams(
+            "UPDATE `instance_upload_jobs` SET `status`=:queued, `progress`=0, `updated`=:now WHERE status=:processing",
+            params!(
+                "queued" => UploadJobStatus::Queued.to_u8(),
+                "now" => time_util::now(),
+                "processing" => UploadJobStatus::Processing.to_u8()
+            ),
+        );
+    }
+
+    fn claim_upload_job(&self, db_main: &mut (impl Select + Execute)) -> Option<QueuedUploadJob> {
+        let _claim_guard = self.upload_job_claim.lock().unwrap();
+        let job = db_main.select_wparams_value(
+            "SELECT upload_id, member_id, server_id FROM `instance_upload_jobs` WHERE status=:queued ORDER BY id LIMIT 1",
+            |mut row| QueuedUploadJob {
+                upload_id: row.take(0).unwrap(),
+                member_id: row.take(1).unwrap(),
+                server_id: row.take(2).unwrap(),
+            },
+            params!("queued" => UploadJobStatus::Queued.to_u8()),
+        )?;
+
+        if db_main.execute_wparams(
+            "UPDATE `instance_upload_jobs` SET `status`=:processing, `progress`=0, `attempts`=attempts+1, `updated`=:now WHERE upload_id=:upload_id AND status=:queued",
+            params!(
+                "processing" => UploadJobStatus::Processing.to_u8(),
+                "now" => time_util::now(),
+                "upload_id" => job.upload_id,
+                "queued" => UploadJobStatus::Queued.to_u8()
+            ),
+        ) {
+            return Some(job);
+        }
+        None
+    }
+
+    fn run_upload_job(&self, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, job: QueuedUploadJob) {
+        println!("[Upload worker] processing upload {}", job.upload_id);
+        let error = match catch_unwind(AssertUnwindSafe(|| process_upload_job(self, db_main, data, armory, &job))) {
+            Ok(Ok(())) => None,
+            Ok(Err(failure)) => Some(format!("{:?}", failure)),
+            Err(_) => Some(String::from("Processing panicked")),
+        };
+
+        // Rows that were cancelled in the meantime are left untouched
+        if let Some(error) = error {
+            println!("[Upload worker] upload {} failed: {}", job.upload_id, error);
+            db_main.execute_wparams(
+                "UPDATE `instance_upload_jobs` SET `status`=IF(attempts < :max_attempts AND progress < :commit_progress, :queued, :failed), `error`=:error, `updated`=:now WHERE upload_id=:upload_id AND status=:processing",
+                params!(
+                    "max_attempts" => UPLOAD_JOB_MAX_ATTEMPTS,
+                    "commit_progress" => UPLOAD_JOB_COMMIT_PROGRESS,
+                    "queued" => UploadJobStatus::Queued.to_u8(),
+                    "failed" => UploadJobStatus::Failed.to_u8(),
+                    "error" => error.chars().take(255).collect::<String>(),
+                    "now" => time_util::now(),
+                    "upload_id" => job.upload_id,
+                    "processing" => UploadJobStatus::Processing.to_u8()
+                ),
+            );
+        } else {
+            db_main.execute_wparams(
+                "UPDATE `instance_upload_jobs` SET `status`=:finished, `progress`=100, `error`=NULL, `updated`=:now WHERE upload_id=:upload_id AND status=:processing",
+                params!(
+                    "finished" => UploadJobStatus::Finished.to_u8(),
+                    "now" => time_util::now(),
+                    "upload_id" => job.upload_id,
+                    "processing" => UploadJobStatus::Processing.to_u8()
+                ),
+            );
+        }
+    }
+}
+
+pub fn set_upload_progress(db_main: &mut impl Execute, upload_id: u32, progress: u8) {
+    db_main.execute_wparams(
+        "UPDATE `instance_upload_jobs` SET `progress`=:progress, `updated`=:now WHERE upload_id=:upload_id AND status=:processing",
+        params!(
+            "progress" => progress,
+            "now" => time_util::now(),
+            "upload_id" => upload_id,
+            "processing" => UploadJobStatus::Processing.to_u8()
+        ),
+    );
+}
+
+// The language of the client that wrote the log, it resolves the names of the log
+pub fn set_upload_language(db_main: &mut impl Execute, upload_id: u32, language_id: u8) {
+    db_main.execute_wparams(
+        "UPDATE `instance_uploads` SET `language_id`=:language_id WHERE id=:upload_id",
+        params!(
+            "language_id" => language_id,
+            "upload_id" => upload_id
+        ),
+    );
+}
+
+pub fn read_upload_log(upload_id: u32) -> Result<String, LiveDataProcessorFailure> {
+    let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
+    let file = File::open(format!("{}/zips/upload_{}.zip", storage_path, upload_id)).map_err(|_| LiveDataProcessorFailure::UploadNotFound)?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|_| LiveDataProcessorFailure::InvalidZipFile)?;
+
+    // There should only be the combat log in there
+    let file = zip.by_index(0).map_err(|_| LiveDataProcessorFailure::InvalidZipFile)?;
+    let bytes = file.bytes().filter_map(|byte| byte.ok()).collect::<Vec<u8>>();
+    let mut content = Vec::new();
+    for slice in bytes.split(|c| *c == 10) {
+        if let Ok(parsed_str) = std::str::from_utf8(slice) {
+            content.push(parsed_str);
+        }
+    }
+    Ok(content.join("\n"))
+}
+
+fn process_upload_job(me: &LiveDataProcessor, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, job: &QueuedUploadJob) -> Result<(), LiveDataProcessorFailure> {
+    let content = read_upload_log(job.upload_id)?;
+
+    // The filtering functionality was removed
+    let start_time_in_ms: u64 = time_util::now() * 1000;
+    let end_time_in_ms: u64 = time_util::now() * 1000;
+
+    let expansion_id = data.get_server(job.server_id).map(|server| server.expansion_id);
+    let parse_result = match select_combat_log_format(expansion_id, &content) {
+        CombatLogFormat::Vanilla => parse_upload_log(me, &mut *db_main, data, armory, job, &content, &mut WoWVanillaParser::new(job.server_id), start_time_in_ms, end_time_in_ms),
+        CombatLogFormat::TBC => parse_upload_log(me, &mut *db_main, data, armory, job, &content, &mut WoWTBCParser::new(job.server_id), start_time_in_ms, end_time_in_ms),
+        CombatLogFormat::WotLK => parse_upload_log(me, &mut *db_main, data, armory, job, &content, &mut WoWWotLKParser::new(job.server_id), start_time_in_ms, end_time_in_ms),
+    };
+    let (server_id, messages) = parse_result.ok_or(LiveDataProcessorFailure::InvalidInput)?;
+
+    if is_upload_job_cancelled(db_main, job.upload_id) {
+        println!("[Upload worker] upload {} was cancelled", job.upload_id);
+        return Ok(());
+    }
+
+    set_upload_progress(db_main, job.upload_id, UPLOAD_JOB_COMMIT_PROGRESS);
+    me.process_messages(&mut *db_main, server_id, armory, data, messages, job.member_id, job.upload_id)
+}
+
+fn parse_upload_log(
+    me: &LiveDataProcessor, db_main: &mut (impl Select + Execute), data: &Data, armory: &Armory, job: &QueuedUploadJob, content: &str, parser: &mut (impl CombatLogParser + Send), start_time_in_ms: u64, end_time_in_ms: u64,
+) -> Option<(u32, Vec<Message>)> {
+    let parse_result = parse_cbl(parser, &mut *db_main, data, armory, content, start_time_in_ms, end_time_in_ms, job.member_id, job.upload_id, false);
+    me.set_upload_parse_report(&mut *db_main, job.upload_id, parser.get_parse_report());
+    parse_result
+}
+
+fn is_upload_job_cancelled(db_main: &mut impl Select, upload_id: u32) -> bool {
+    db_main
+        .select_wparams_value("SELECT status FROM `instance_upload_jobs` WHERE upload_id=:upload_id", |mut row| row.take::<u8, usize>(0).unwrap(), params!("upload_id" => upload_id))
+        .map(|status| UploadJobStatus::new(status) == UploadJobStatus::Cancelled)
+        .unwrap_or(true)
+}
+
+fn map_upload_job(mut row: crate::mysql::Row) -> UploadJob {
+    UploadJob {
+        upload_id: row.take(0).unwrap(),
+        server_id: row.take(1).unwrap(),
+        status: UploadJobStatus::new(row.take(2).unwrap()),
+        progress: row.take(3).unwrap(),
+        attempts: row.take(4).unwrap(),
+        error: row.take_opt(5).unwrap().ok(),
+        created: row.take(6).unwrap(),
+        updated: row.take(7).unwrap(),
+    }
+}
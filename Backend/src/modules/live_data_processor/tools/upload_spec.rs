@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use crate::modules::armory::tools::GetCharacter;
+use crate::modules::armory::Armory;
+use crate::modules::live_data_processor::dto::{SpecDetection, SpecEvidence};
+use crate::modules::live_data_processor::material::Participant;
+use crate::params;
+use crate::util::database::{Execute, Select};
+
+// Participants whose spec changed within the upload keep the spec they were detected with last
+pub fn set_upload_specs(db_main: &mut impl Execute, armory: &Armory, server_id: u32, upload_id: u32, participants: &[Participant]) {
+    for participant in participants.iter().filter(|participant| participant.is_player) {
+        if let Some(spec) = participant.get_detected_spec() {
+            if let Some(character_id) = armory.get_character_id_by_uid(server_id, participant.id) {
+                let evidence: Vec<&SpecEvidence> = participant.spec_evidence.iter().filter(|evidence| evidence.hero_class_id == spec.hero_class_id).collect();
+                db_main.execute_wparams(
+                    "REPLACE INTO `instance_upload_spec` (`upload_id`, `character_id`, `hero_class_id`, `talent_tree`, `role`, `confidence`, `evidence`) VALUES (:upload_id, :character_id, :hero_class_id, :talent_tree, :role, :confidence, :evidence)",
+                    params!(
+                        "upload_id" => upload_id,
+                        "character_id" => character_id,
+                        "hero_class_id" => spec.hero_class_id,
+                        "talent_tree" => spec.talent_tree,
+                        "role" => spec.role,
+                        "confidence" => spec.confidence,
+                        "evidence" => serde_json::to_string(&evidence).unwrap()
+                    ),
+                );
+            }
+        }
+    }
+}
+
+pub fn get_upload_specs(db_main: &mut impl Select, upload_id: u32) -> HashMap<u32, (SpecDetection, Vec<SpecEvidence>)> {
+    db_main
+        .select_wparams(
+            "SELECT character_id, hero_class_id, talent_tree, role, confidence, evidence FROM `instance_upload_spec` WHERE upload_id=:upload_id",
+            |mut row| {
+                let character_id: u32 = row.take(0).unwrap();
+                let spec = SpecDetection {
+                    hero_class_id: row.take(1).unwrap(),
+                    talent_tree: row.take(2).unwrap(),
+                    role: row.take_opt(3).unwrap().ok(),
+                    confidence: row.take(4).unwrap(),
+                };
+                let evidence: String = row.take(5).unwrap();
+                (character_id, (spec, serde_json::from_str(&evidence).unwrap_or_default()))
+            },
+            params!("upload_id" => upload_id),
+        )
+        .into_iter()
+        .collect()
+}
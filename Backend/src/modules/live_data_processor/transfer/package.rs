@@ -1,4 +1,4 @@
-use crate::modules::account::guard::ServerOwner;
+use crate::modules::account::guard::{HasPermission, ManageServer};
 use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
 use crate::modules::live_data_processor::tools::ProcessMessages;
 use crate::modules::live_data_processor::LiveDataProcessor;
@@ -12,7 +12,7 @@ use rocket_multipart_form_data::{MultipartFormData, MultipartFormDataField, Mult
 
 #[openapi(skip)]
 #[post("/package", format = "multipart/form-data", data = "<data>")]
-pub fn get_package(mut db_main: MainDb, me: State<LiveDataProcessor>, armory: State<Armory>, domain_data: State<DomainData>, owner: ServerOwner, content_type: &ContentType, data: Data) -> Result<(), LiveDataProcessorFailure> {
+pub fn get_package(mut db_main: MainDb, me: State<LiveDataProcessor>, armory: State<Armory>, domain_data: State<DomainData>, owner: HasPermission<ManageServer>, content_type: &ContentType, data: Data) -> Result<(), LiveDataProcessorFailure> {
     let mut options = MultipartFormDataOptions::new();
     options.allowed_fields.push(MultipartFormDataField::bytes("payload").size_limit(2 * 1024 * 1024));
 
@@ -34,7 +34,7 @@ pub fn get_package(mut db_main: MainDb, me: State<LiveDataProcessor>, armory: St
                 }
                 messages.push(raw.drain(..(raw[2] as usize)).collect());
             }
-            return me.parse_messages(&mut *db_main, owner.0, &armory, &domain_data, messages, owner.0);
+            return me.parse_messages(&mut *db_main, owner.scope_id, &armory, &domain_data, messages, owner.scope_id);
         }
     }
     Err(LiveDataProcessorFailure::InvalidInput)
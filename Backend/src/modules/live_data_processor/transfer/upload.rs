@@ -1,5 +1,5 @@
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::Write;
 use sha2::{Sha256, Digest};
 
 use rocket::{Data, State};
@@ -8,21 +8,16 @@ use rocket_multipart_form_data::{MultipartFormData, MultipartFormDataField, Mult
 
 use crate::MainDb;
 use crate::modules::account::guard::Authenticate;
-use crate::modules::armory::Armory;
-use crate::modules::data::Data as DataMaterial;
-use crate::modules::live_data_processor::dto::LiveDataProcessorFailure;
+use crate::modules::live_data_processor::dto::{LiveDataProcessorFailure, ParseReport, UploadJob};
 use crate::modules::live_data_processor::LiveDataProcessor;
-use crate::modules::live_data_processor::material::{WoWVanillaParser};
-use crate::modules::live_data_processor::tools::cbl_parser::CombatLogParser;
-use crate::modules::live_data_processor::tools::log_parser::parse_cbl;
-use crate::modules::live_data_processor::tools::ProcessMessages;
+use crate::modules::live_data_processor::tools::{UploadJobQueue, UploadParseReport};
 use crate::params;
 use crate::util::database::{Execute, Select};
 use rocket_contrib::json::Json;
 
 #[openapi(skip)]
 #[post("/upload", format = "multipart/form-data", data = "<form_data>")]
-pub fn upload_log(mut db_main: MainDb, auth: Authenticate, me: State<LiveDataProcessor>, data: State<DataMaterial>, armory: State<Armory>, content_type: &ContentType, form_data: Data) -> Result<(), LiveDataProcessorFailure> {
+pub fn upload_log(mut db_main: MainDb, auth: Authenticate, me: State<LiveDataProcessor>, content_type: &ContentType, form_data: Data) -> Result<Json<u32>, LiveDataProcessorFailure> {
     let mut options = MultipartFormDataOptions::new();
     options.allowed_fields.push(MultipartFormDataField::bytes("payload").size_limit(40 * 1024 * 1024 * 1024));
     options.allowed_fields.push(MultipartFormDataField::bytes("server_id").size_limit(1024));
@@ -43,15 +38,6 @@ pub fn upload_log(mut db_main: MainDb, auth: Authenticate, me: State<LiveDataPro
     let end_time = NaiveDateTime::parse_from_str(&end_time_raw, "%d.%m.%y %I:%M %p").ok().ok_or(LiveDataProcessorFailure::InvalidEndTime)?;
      */
 
-    {
-        let mut upload_progress = me.upload_progress.write().unwrap();
-        upload_progress.insert(auth.0, 0);
-    }
-
-    // The filtering functionality was removed
-    let start_time_in_ms: u64 = time_util::now() * 1000;
-    let end_time_in_ms: u64 = time_util::now() * 1000;
-
     let mut server_id_raw_fields = multipart_form_data.raw.remove("server_id").ok_or(LiveDataProcessorFailure::InvalidInput)?;
     let RawField { raw: server_id_raw, .. } = server_id_raw_fields.remove(0);
     let server_id = i32::from_str_radix(std::str::from_utf8(&server_id_raw).map_err(|_| LiveDataProcessorFailure::InvalidInput)?, 10).map_err(|_| LiveDataProcessorFailure::InvalidInput)?;
@@ -79,8 +65,7 @@ pub fn upload_log(mut db_main: MainDb, auth: Authenticate, me: State<LiveDataPro
     if existing_upload.is_some() {
         return Err(LiveDataProcessorFailure::DuplicateUpload);
     }
-    let reader = std::io::Cursor::new(raw.as_slice());
-    let mut zip = zip::ZipArchive::new(reader).map_err(|_| LiveDataProcessorFailure::InvalidZipFile)?;
+    zip::ZipArchive::new(std::io::Cursor::new(raw.as_slice())).map_err(|_| LiveDataProcessorFailure::InvalidZipFile)?;
 
     // Create Upload Id
     let upload_time = time_util::now();
@@ -90,47 +75,47 @@ pub fn upload_log(mut db_main: MainDb, auth: Authenticate, me: State<LiveDataPro
                                                         |mut row| row.take::<u32, usize>(0).unwrap(), upload_params).unwrap();
 
     let storage_path = std::env::var("INSTANCE_STORAGE_PATH").expect("storage path must be set");
-    if std::fs::create_dir_all(&format!("{}/zips", storage_path)).is_ok() {
-        if let Ok(mut saved_zip) = File::create(&format!("{}/zips/upload_{}.zip", storage_path, upload_id)) {
-            let _ = saved_zip.write_all(&raw);
-        }
-    }
+    std::fs::create_dir_all(&format!("{}/zips", storage_path)).map_err(|error| LiveDataProcessorFailure::IoFailure(format!("upload_log: {}", error)))?;
+    let mut saved_zip = File::create(&format!("{}/zips/upload_{}.zip", storage_path, upload_id)).map_err(|error| LiveDataProcessorFailure::IoFailure(format!("upload_log: {}", error)))?;
+    saved_zip.write_all(&raw).map_err(|error| LiveDataProcessorFailure::IoFailure(format!("upload_log: {}", error)))?;
 
-    // There should only be the combat log in there
-    let file = zip.by_index(0).map_err(|_| LiveDataProcessorFailure::InvalidZipFile)?;
-    let bytes = file.bytes().filter_map(|byte| byte.ok()).collect::<Vec<u8>>();
-    let mut content = Vec::new();
-    for slice in bytes.split(|c| *c == 10) {
-        if let Ok(parsed_str) = std::str::from_utf8(slice) {
-            content.push(parsed_str);
-        }
-    }
-    let content = content.join("\n");
-
-    return parse(
-        &me,
-        WoWVanillaParser::new(server_id as u32),
-        &mut *db_main,
-        &data,
-        &armory,
-        &content,
-        start_time_in_ms,
-        end_time_in_ms,
-        auth.0,
-        upload_id,
-    );
+    // The upload workers pick it up from here
+    me.enqueue_upload_job(&mut *db_main, upload_id, auth.0, server_id as u32)?;
+    Ok(Json(upload_id))
 }
 
 #[openapi]
 #[get("/upload/progress")]
-pub fn get_upload_progress(me: State<LiveDataProcessor>, auth: Authenticate) -> Json<u8> {
-    let upload_progress = me.upload_progress.read().unwrap();
-    Json(*upload_progress.get(&auth.0).unwrap_or(&0))
+pub fn get_upload_progress(mut db_main: MainDb, me: State<LiveDataProcessor>, auth: Authenticate) -> Json<u8> {
+    Json(me.get_upload_jobs(&mut *db_main, auth.0).first().map(|job| job.progress).unwrap_or(0))
 }
 
-fn parse(me: &LiveDataProcessor, mut parser: impl CombatLogParser, db_main: &mut (impl Select + Execute), data: &DataMaterial, armory: &Armory, content: &str, start_time: u64, end_time: u64, member_id: u32, upload_id: u32) -> Result<(), LiveDataProcessorFailure> {
-    if let Some((server_id, messages)) = parse_cbl(&mut parser, &me, &mut *db_main, data, armory, content, start_time, end_time, member_id, false) {
-        return me.process_messages(&mut *db_main, server_id as u32, &armory, &data, messages, member_id, upload_id);
-    }
-    Err(LiveDataProcessorFailure::InvalidInput)
+#[openapi]
+#[get("/upload/jobs")]
+pub fn get_upload_jobs(mut db_main: MainDb, me: State<LiveDataProcessor>, auth: Authenticate) -> Json<Vec<UploadJob>> {
+    Json(me.get_upload_jobs(&mut *db_main, auth.0))
+}
+
+#[openapi]
+#[get("/upload/<upload_id>/status")]
+pub fn get_upload_job(mut db_main: MainDb, me: State<LiveDataProcessor>, auth: Authenticate, upload_id: u32) -> Result<Json<UploadJob>, LiveDataProcessorFailure> {
+    me.get_upload_job(&mut *db_main, auth.0, upload_id).map(Json)
+}
+
+#[openapi]
+#[post("/upload/<upload_id>/cancel")]
+pub fn cancel_upload_job(mut db_main: MainDb, me: State<LiveDataProcessor>, auth: Authenticate, upload_id: u32) -> Result<(), LiveDataProcessorFailure> {
+    me.cancel_upload_job(&mut *db_main, auth.0, upload_id)
+}
+
+#[openapi]
+#[post("/upload/<upload_id>/retry")]
+pub fn retry_upload_job(mut db_main: MainDb, me: State<LiveDataProcessor>, auth: Authenticate, upload_id: u32) -> Result<(), LiveDataProcessorFailure> {
+    me.retry_upload_job(&mut *db_main, auth.0, upload_id)
+}
+
+#[openapi]
+#[get("/upload/<upload_id>/report")]
+pub fn get_upload_report(mut db_main: MainDb, me: State<LiveDataProcessor>, auth: Authenticate, upload_id: u32) -> Result<Json<ParseReport>, LiveDataProcessorFailure> {
+    me.get_upload_parse_report(&mut *db_main, auth.0, upload_id).map(Json)
 }
@@ -1,3 +1,5 @@
+use crate::modules::account::domain_value::Permission;
+use crate::modules::account::tools::Permissions;
 use crate::modules::account::Account;
 use crate::modules::utility::domain_value::Paste;
 use crate::modules::utility::dto::{PasteDto, UtilityFailure};
@@ -33,9 +35,7 @@ impl UpdateAddonPaste for Utility {
             let mut addon_pastes = self.addon_pastes.write().unwrap();
             let mut i_paste = addon_pastes.get_mut(&id).ok_or(UtilityFailure::InvalidInput)?;
             if i_paste.member_id != member_id {
-                let members = account.member.read().unwrap();
-                let member = members.get(&member_id).unwrap();
-                if (member.access_rights & 1) != 1 {
+                if !account.has_permission(member_id, Permission::Moderate) {
                     return Err(UtilityFailure::InvalidInput);
                 }
             }
@@ -82,9 +82,7 @@ impl UpdateAddonPaste for Utility {
         } else {
             let paste = addon_pastes.get(&paste_id).unwrap();
             if paste.member_id != member_id {
-                let members = account.member.read().unwrap();
-                let member = members.get(&member_id).unwrap();
-                if (member.access_rights & 1) != 1 {
+                if !account.has_permission(member_id, Permission::Moderate) {
                     return Err(UtilityFailure::InvalidInput);
                 }
             }
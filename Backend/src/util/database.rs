@@ -26,6 +26,13 @@ pub trait Exists {
     fn exists_wparams(&mut self, query_str: &str, params: std::vec::Vec<(std::string::String, Value)>) -> bool;
 }
 
+#[cfg_attr(test, automock)]
+pub trait Transaction {
+    fn start_transaction(&mut self) -> bool;
+    fn commit(&mut self) -> bool;
+    fn rollback(&mut self) -> bool;
+}
+
 #[cfg_attr(test, automock)]
 pub trait Select {
     fn select<T: 'static, F: 'static + (Fn(Row) -> T)>(&mut self, query_str: &str, process_row: F) -> Vec<T>;
@@ -63,6 +70,21 @@ impl Exists for crate::mysql::Conn {
     }
 }
 
+// Transaction statements are not supported by the prepared statement protocol
+impl Transaction for crate::mysql::Conn {
+    fn start_transaction(&mut self) -> bool {
+        self.query("START TRANSACTION").is_ok()
+    }
+
+    fn commit(&mut self) -> bool {
+        self.query("COMMIT").is_ok()
+    }
+
+    fn rollback(&mut self) -> bool {
+        self.query("ROLLBACK").is_ok()
+    }
+}
+
 impl Select for crate::mysql::Conn {
     fn select<T: 'static, F: 'static + (Fn(Row) -> T)>(&mut self, query_str: &str, process_row: F) -> Vec<T> {
         self.prep_exec(query_str, ()).map(|result| result.map(|x| x.unwrap()).map(|row| process_row(crate::mysql::from_row(row))).collect()).unwrap()
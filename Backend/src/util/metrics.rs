@@ -0,0 +1,17 @@
+use rocket_prometheus::prometheus::{IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry};
+
+lazy_static! {
+    pub static ref LIVE_DATA_STATE_ENTRIES: IntGaugeVec =
+        IntGaugeVec::new(Opts::new("live_data_state_entries", "Entries held in memory by the live data processor per server and map"), &["server_id", "map"]).unwrap();
+    pub static ref LIVE_DATA_EVICTED_ENTRIES: IntCounterVec =
+        IntCounterVec::new(Opts::new("live_data_evicted_entries_total", "Entries evicted from the live data processor per server and map"), &["server_id", "map"]).unwrap();
+    pub static ref ARMORY_NAME_CACHE_ENTRIES: IntGauge = IntGauge::new("armory_name_cache_entries", "Entries of the armory character name cache").unwrap();
+    pub static ref ARMORY_NAME_CACHE_EVICTED_ENTRIES: IntCounter = IntCounter::new("armory_name_cache_evicted_entries_total", "Entries evicted from the armory character name cache").unwrap();
+}
+
+pub fn register_metrics(registry: &Registry) {
+    registry.register(Box::new(LIVE_DATA_STATE_ENTRIES.clone())).unwrap();
+    registry.register(Box::new(LIVE_DATA_EVICTED_ENTRIES.clone())).unwrap();
+    registry.register(Box::new(ARMORY_NAME_CACHE_ENTRIES.clone())).unwrap();
+    registry.register(Box::new(ARMORY_NAME_CACHE_EVICTED_ENTRIES.clone())).unwrap();
+}
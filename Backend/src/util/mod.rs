@@ -1,4 +1,5 @@
 pub mod database;
 pub mod hash_str;
+pub mod metrics;
 pub mod ordering;
 pub mod rpll_table;
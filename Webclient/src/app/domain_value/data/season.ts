@@ -0,0 +1,8 @@
+export interface Season {
+    id: number;
+    server_id: number;
+    name: string;
+    start_ts: number;
+    end_ts?: number;
+    map_ids: Array<number>;
+}
@@ -213,7 +213,7 @@ export class RankingComponent implements OnInit, OnDestroy {
         },
     ];
 
-    private finished_loading: [boolean, boolean, boolean] = [false, false, false];
+    private finished_loading: [boolean, boolean, boolean, boolean] = [false, false, false, false];
 
     constructor(
         private settingsService: SettingsService,
@@ -253,9 +253,6 @@ export class RankingComponent implements OnInit, OnDestroy {
     }
 
     ngOnInit(): void {
-        this.seasons = this.dataService.ranking_seasons.map(item => {
-            return {id: item.value, label: item.label_key};
-        }).reverse();
         this.classes = this.dataService.specs.map(item => {
             return {id: item.value, label: item.label_key};
         });
@@ -283,6 +280,13 @@ export class RankingComponent implements OnInit, OnDestroy {
             this.finished_loading[2] = encounters.length > 0;
             if (this.finished_loading.every(item => item)) this.init_ranking();
         }));
+        this.subscription.add(this.dataService.ranking_seasons.subscribe(seasons => {
+            this.seasons = seasons.map(item => {
+                return {id: item.value, label: item.label_key};
+            }).reverse();
+            this.finished_loading[3] = seasons.length > 0;
+            if (this.finished_loading.every(item => item)) this.init_ranking();
+        }));
     }
 
     private init_ranking(): void {
@@ -5,5 +5,6 @@ export interface RankingResult {
     duration: number;
     difficulty_id: number;
     character_spec: number;
-    season_index: number;
+    season_id: number;
+    percentile: number;
 }
@@ -5,6 +5,7 @@ import {RankingResult} from "../domain_value/ranking_result";
 import {RankingCharacterMeta} from "../domain_value/ranking_character_meta";
 import {RankingRow} from "../domain_value/ranking_row";
 import {DataService} from "../../../../../service/data";
+import {filter, take} from "rxjs/operators";
 
 @Injectable({
     providedIn: "root",
@@ -47,20 +48,17 @@ export class RankingService {
     }
 
     get all_dps_rankings(): Observable<Array<[number, Array<[number, RankingCharacterMeta, Array<RankingResult>]>]>> {
-        this.current_season_ids$ = this.dataService.ranking_seasons.map(item => item.value as number);
-        this.load(RankingService.URL_INSTANCE_RANKING_DPS, this.season_loaded_dps, this.dps_rankings, this.dps_rankings$);
+        this.load_all_seasons(RankingService.URL_INSTANCE_RANKING_DPS, this.season_loaded_dps, this.dps_rankings, this.dps_rankings$);
         return this.dps_rankings$.asObservable();
     }
 
     get all_hps_rankings(): Observable<Array<[number, Array<[number, RankingCharacterMeta, Array<RankingResult>]>]>> {
-        this.current_season_ids$ = this.dataService.ranking_seasons.map(item => item.value as number);
-        this.load(RankingService.URL_INSTANCE_RANKING_HPS, this.season_loaded_hps, this.hps_rankings, this.hps_rankings$);
+        this.load_all_seasons(RankingService.URL_INSTANCE_RANKING_HPS, this.season_loaded_hps, this.hps_rankings, this.hps_rankings$);
         return this.hps_rankings$.asObservable();
     }
 
     get all_tps_rankings(): Observable<Array<[number, Array<[number, RankingCharacterMeta, Array<RankingResult>]>]>> {
-        this.current_season_ids$ = this.dataService.ranking_seasons.map(item => item.value as number);
-        this.load(RankingService.URL_INSTANCE_RANKING_TPS, this.season_loaded_tps, this.tps_rankings, this.tps_rankings$);
+        this.load_all_seasons(RankingService.URL_INSTANCE_RANKING_TPS, this.season_loaded_tps, this.tps_rankings, this.tps_rankings$);
         return this.tps_rankings$.asObservable();
     }
 
@@ -75,6 +73,14 @@ export class RankingService {
         this.load_current_mode();
     }
 
+    private load_all_seasons(url: string, loaded_arr: Array<number>, container: Map<number, Map<number, [RankingCharacterMeta, Array<RankingResult>]>>,
+                             observable: Subject<Array<[number, Array<[number, RankingCharacterMeta, Array<RankingResult>]>]>>): void {
+        this.dataService.ranking_seasons.pipe(filter(seasons => seasons.length > 0), take(1)).subscribe(seasons => {
+            this.current_season_ids$ = seasons.map(item => item.value as number);
+            this.load(url, loaded_arr, container, observable);
+        });
+    }
+
     private commit() {
         const new_rankings = new Map<number, [RankingCharacterMeta, Array<Array<number>>]>();
         if (this.current_selection$ === 1) {
@@ -86,7 +92,7 @@ export class RankingService {
                             && !!this.current_specs$.find(spec => spec[0] === meta.hero_class_id))
                         .map(([character_id, meta, rankings]) => {
                             const best_result = rankings
-                                .filter(ranking => this.current_difficulty_ids$.includes(ranking.difficulty_id) && this.current_season_ids$.includes(ranking.season_index)
+                                .filter(ranking => this.current_difficulty_ids$.includes(ranking.difficulty_id) && this.current_season_ids$.includes(ranking.season_id)
                                     && !!this.current_specs$.find(spec => spec[0] === meta.hero_class_id && spec[1] === ranking.character_spec))
                                 .reduce((best, ranking) => {
                                     const ranking_result = (ranking.amount * 1000) / ranking.duration;
@@ -153,14 +159,15 @@ export class RankingService {
                     if (container.has(encounter_id)) {
                         const encounter_map = container.get(encounter_id);
                         for (const [character_id, meta, tiny_rr] of char_results) {
-                            const rr = tiny_rr.map(({a, b, c, d, e, f, g}) => ({
+                            const rr = tiny_rr.map(({a, b, c, d, e, f, g, h}) => ({
                                 instance_meta_id: a,
                                 attempt_id: b,
                                 amount: c,
                                 duration: d,
                                 difficulty_id: e,
                                 character_spec: f,
-                                season_index: g
+                                season_id: g,
+                                percentile: h
                             }));
 
                             if (encounter_map.has(character_id)) {
@@ -176,14 +183,15 @@ export class RankingService {
                             new Map(
                                 char_results.map(([id, meta, tiny_rr]) => [
                                     id,
-                                    [meta, tiny_rr.map(({a, b, c, d, e, f, g}) => ({
+                                    [meta, tiny_rr.map(({a, b, c, d, e, f, g, h}) => ({
                                         instance_meta_id: a,
                                         attempt_id: b,
                                         amount: c,
                                         duration: d,
                                         difficulty_id: e,
                                         character_spec: f,
-                                        season_index: g,
+                                        season_id: g,
+                                        percentile: h,
                                     }))],
                                 ])
                             )
@@ -22,7 +22,7 @@ import {Meta, Title} from "@angular/platform-browser";
 export class SpeedKillComponent implements OnInit, OnDestroy {
 
     private subscriptions: Subscription;
-    private finished_loading: [boolean, boolean, boolean] = [false, false, false];
+    private finished_loading: [boolean, boolean, boolean, boolean] = [false, false, false, false];
 
     selections_current_selection: number = 1;
     selections: Array<SelectOption> = [
@@ -74,9 +74,6 @@ export class SpeedKillComponent implements OnInit, OnDestroy {
     }
 
     ngOnInit(): void {
-        this.seasons = this.dataService.ranking_seasons.map(item => {
-            return {id: item.value, label: item.label_key};
-        }).reverse();
         this.subscriptions = this.dataService.servers.subscribe(servers => {
             this.servers = servers.sort((left, right) => left.expansion_id - right.expansion_id)
                 .map(server => {
@@ -101,6 +98,13 @@ export class SpeedKillComponent implements OnInit, OnDestroy {
             this.finished_loading[2] = difficulties.length > 0;
             if (this.finished_loading.every(item => item)) this.init_ranking();
         }));
+        this.subscriptions.add(this.dataService.ranking_seasons.subscribe(seasons => {
+            this.seasons = seasons.map(item => {
+                return {id: item.value, label: item.label_key};
+            }).reverse();
+            this.finished_loading[3] = seasons.length > 0;
+            if (this.finished_loading.every(item => item)) this.init_ranking();
+        }));
     }
 
     ngOnDestroy(): void {
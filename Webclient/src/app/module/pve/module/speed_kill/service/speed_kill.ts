@@ -3,6 +3,7 @@ import {BehaviorSubject, Observable, Subject} from "rxjs";
 import {APIService} from "../../../../../service/api";
 import {SpeedKill} from "../domain_value/speed_kill";
 import {DataService} from "../../../../../service/data";
+import {filter, take} from "rxjs/operators";
 
 @Injectable({
     providedIn: "root",
@@ -34,8 +35,10 @@ export class SpeedKillService {
     }
 
     get all_speed_kills(): Observable<Array<SpeedKill>> {
-        this.current_season_ids$ = this.dataService.ranking_seasons.map(item => item.value as number);
-        this.load_data();
+        this.dataService.ranking_seasons.pipe(filter(seasons => seasons.length > 0), take(1)).subscribe(seasons => {
+            this.current_season_ids$ = seasons.map(item => item.value as number);
+            this.load_data();
+        });
         return this.all_speed_kills$.asObservable();
     }
 
@@ -65,7 +68,7 @@ export class SpeedKillService {
     commit(): void {
         const result = this.speed_kills_internal.filter(speed_kill => speed_kill.encounter_id === this.current_encounter_id$
             && this.current_server_ids$.includes(speed_kill.server_id) && this.current_difficulty_ids$.includes(speed_kill.difficulty_id)
-            && this.current_season_ids$.includes(speed_kill.season_index))
+            && this.current_season_ids$.includes(speed_kill.season_id))
             .sort((left, right) => left.duration - right.duration);
         this.speed_kills$.next(result);
     }
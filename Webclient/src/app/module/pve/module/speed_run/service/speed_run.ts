@@ -3,6 +3,7 @@ import {APIService} from "../../../../../service/api";
 import {BehaviorSubject, Observable, Subject} from "rxjs";
 import {SpeedRun} from "../domain_value/speed_run";
 import {DataService} from "../../../../../service/data";
+import {filter, take} from "rxjs/operators";
 
 @Injectable({
     providedIn: "root",
@@ -32,8 +33,10 @@ export class SpeedRunService {
     }
 
     get all_speed_runs(): Observable<Array<SpeedRun>> {
-        this.current_season_ids$ = this.dataService.ranking_seasons.map(item => item.value as number);
-        this.load_data();
+        this.dataService.ranking_seasons.pipe(filter(seasons => seasons.length > 0), take(1)).subscribe(seasons => {
+            this.current_season_ids$ = seasons.map(item => item.value as number);
+            this.load_data();
+        });
         return this.all_speed_runs$.asObservable();
     }
 
@@ -63,7 +66,7 @@ export class SpeedRunService {
     commit(): void {
         const result = this.speed_runs_internal.filter(speed_run => speed_run.map_id === this.current_map_id$
             && this.current_server_ids$.includes(speed_run.server_id) && this.current_difficulty_ids$.includes(speed_run.difficulty_id)
-            && this.current_season_ids$.includes(speed_run.season_index))
+            && this.current_season_ids$.includes(speed_run.season_id))
             .sort((left, right) => left.duration - right.duration);
         this.speed_runs$.next(result);
     }
@@ -21,8 +21,8 @@ export type Summon = [number, number, Unit, Unit];
 export type MeleeDamage = [number, number, Unit, Unit, number, Array<SpellComponent>];
 // Spell_cause_id, source, target, spell_id, hit_mask, spell_components
 export type SpellDamage = [number, number, number, Unit, Unit, number, number, Array<SpellComponent>];
-// Spell_cause_id, source, target, spell_id, hit_mask, school_mask, total, effective, mitigation
-export type Heal = [number, number, number, Unit, Unit, number, number, number, number, number, number, number, number];
+// Spell_cause_id, source, target, spell_id, hit_mask, school_mask, total, effective, mitigation, overheal
+export type Heal = [number, number, number, Unit, Unit, number, number, number, number, number, number, number, number, number];
 // cause_event_id, source, target, spell_id, hit_mask, school_mask, amount
 export type Threat = [number, number, number, Unit, Unit, number, number, number, number];
 export type PercentPlayersInCombat = [number, number, Unit, number];
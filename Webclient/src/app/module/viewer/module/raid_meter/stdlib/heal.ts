@@ -20,7 +20,8 @@ function commit_heal(current_mode: HealMode, heal: Array<Heal>, heal_unit_extrac
             let healing;
             if (current_mode === HealMode.Total) healing = event[8];
             else if (current_mode === HealMode.Effective) healing = event[9];
-            else healing = event[8] - event[9];
+            // Events of earlier uploads do not tell overheal and absorbed heal apart
+            else healing = event[13] !== undefined ? event[13] : event[8] - event[9];
             if (current_mode !== HealMode.Overheal || healing > 0) {
                 if (abilities_data.has(spell_id)) abilities_data.set(spell_id, abilities_data.get(spell_id) + healing);
                 else abilities_data.set(spell_id, healing);
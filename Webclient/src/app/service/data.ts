@@ -13,6 +13,7 @@ import {Difficulty} from "../domain_value/difficulty";
 import {BasicItem} from "../domain_value/data/basic_item";
 import {BasicSpell} from "../domain_value/data/basic_spell";
 import {Encounter} from "../domain_value/data/encounter";
+import {Season} from "../domain_value/data/season";
 import {CONST_AUTO_ATTACK_LABEL, CONST_UNKNOWN_LABEL} from "../module/viewer/constant/viewer";
 import {SelectOption} from "../template/input/select_input/domain_value/select_option";
 
@@ -31,6 +32,7 @@ export class DataService implements OnDestroy {
     private static readonly URL_DATA_BASIC_SPELL_LOCALIZED: string = '/data/spell/localized/basic_spell/:expansion_id/:spell_id';
     private static readonly URL_DATA_BASIC_SPELLS_LOCALIZED: string = '/data/spells/localized/basic_spell';
     private static readonly URL_DATA_ENCOUNTER_LOCALIZED: string = '/data/encounter/localized';
+    private static readonly URL_DATA_SEASON: string = '/data/season';
 
     private subscriptions: Subscription = new Subscription();
 
@@ -39,6 +41,7 @@ export class DataService implements OnDestroy {
     private races$: BehaviorSubject<Array<Localized<Race>>>;
     private hero_classes$: BehaviorSubject<Array<Localized<HeroClass>>>;
     private difficulties$: BehaviorSubject<Array<Localized<Difficulty>>>;
+    private seasons$: BehaviorSubject<Array<Season>>;
 
     private cache_basic_spell: Map<number, Map<number, Localized<BasicSpell>>> = new Map();
     private cache_basic_item: Map<number, Map<number, BehaviorSubject<Localized<BasicItem>>>> = new Map();
@@ -167,35 +170,26 @@ export class DataService implements OnDestroy {
         });
     }
 
-    get ranking_seasons(): Array<SelectOption> {
-        const starting_unix_time = 1731470400000; // Wed Nov 13 2024 04:00:00 GMT+0000 1st instance reset after CC2
-        const one_week_in_ms = 604800000;
-        const current_unix_time = Date.now();
-
-        const results = [];
-
-        let prev_t = starting_unix_time;
-        let t = starting_unix_time + one_week_in_ms;
-
-        // loop through weeks until past the current time
-        for (let i = 1; true; ++i) {
-            const prev_date = new Date(prev_t);
-            const date = new Date(t);
-
-            // format each entry like "Dec 31 - Jan 1 2024"
-            results.push({
-                value: i,
-                label_key: `Week ${i} ${this.months[prev_date.getMonth()]} ${prev_date.getDate()} - ${this.months[date.getMonth()]} ${date.getDate()} ${date.getFullYear()}`
-            });
+    get seasons(): Observable<Array<Season>> {
+        this.seasons$ = this.settingsService.init_or_load_behavior_subject("data_service_seasons", 1, this.seasons$, [],
+            (callback) => this.apiService.get(DataService.URL_DATA_SEASON, callback));
+        return this.seasons$.asObservable().pipe(map(result => result.sort((left, right) => left.start_ts - right.start_ts || left.id - right.id)));
+    }
 
-            if (t > current_unix_time) {
-                break;
-            }
-            prev_t = t;
-            t += one_week_in_ms;
-        }
+    get ranking_seasons(): Observable<Array<SelectOption>> {
+        return this.seasons.pipe(map(seasons => seasons.map(season => {
+            const start_date = new Date(season.start_ts);
+            // format each entry like "Week 1 Dec 31 - Jan 1 2024", the current season is open-ended
+            const end_label = !season.end_ts ? "today" : this.format_date(new Date(season.end_ts));
+            return {
+                value: season.id,
+                label_key: `${season.name} ${this.months[start_date.getMonth()]} ${start_date.getDate()} - ${end_label}`
+            };
+        })));
+    }
 
-        return results;
+    private format_date(date: Date): string {
+        return `${this.months[date.getMonth()]} ${date.getDate()} ${date.getFullYear()}`;
     }
 
     get servers(): Observable<Array<AvailableServer>> {